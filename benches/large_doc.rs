@@ -0,0 +1,267 @@
+//! Parses a generated 1 MB document and reports where the time goes.
+//!
+//! Run with `cargo bench --bench large_doc`. The document is built from a fixed seed, so runs
+//! are comparable: deeply nested elements, elements with many attributes and character
+//! references, and text mixed with inline markup. Each phase is timed separately:
+//!
+//! - tokenization alone, pulling every token out of the tokenizer
+//! - tree construction alone, feeding the tokens collected beforehand
+//! - the full `parse_document`
+//!
+//! Peak memory is measured by a counting global allocator, relative to the allocations live
+//! when the phase starts.
+
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::dom::parser::tree_constructor::TreeConstructor;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const TARGET_SIZE: usize = 1024 * 1024;
+const SEED: u64 = 0x5eed_b005_7e12;
+const ITERATIONS: u32 = 5;
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// xorshift64*, enough for reproducible markup without pulling in a rand crate
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const BLOCK_TAGS: &[&str] = &["div", "section", "article", "ul", "table", "blockquote"];
+const INLINE_TAGS: &[&str] = &["span", "a", "b", "em", "code", "small"];
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "&amp;",
+    "&lt;tag&gt;",
+    "caf&eacute;",
+    "&#x1F600;",
+    "parser",
+    "tree",
+    "token",
+];
+
+/// A well-formed document of at least `target_size` bytes: every element is closed and nested
+/// where the tree builder keeps it, so the tree mirrors the markup
+fn generate_document(target_size: usize, seed: u64) -> String {
+    let mut rng = Rng(seed);
+    let mut html = String::with_capacity(target_size + 4096);
+    html.push_str("<!DOCTYPE html><html><head><title>Large document</title></head><body>");
+    while html.len() < target_size {
+        generate_block(&mut rng, &mut html, 0);
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+fn generate_block(rng: &mut Rng, html: &mut String, depth: usize) {
+    let tag = rng.pick(BLOCK_TAGS);
+    let (tag, child) = match tag {
+        "ul" => ("ul", "li"),
+        "table" => ("table", "tbody"),
+        _ => (tag, ""),
+    };
+    open_tag(rng, html, tag);
+    match child {
+        "li" => {
+            for _ in 0..1 + rng.below(4) {
+                html.push_str("<li>");
+                generate_inline(rng, html, 0);
+                html.push_str("</li>");
+            }
+        }
+        "tbody" => {
+            html.push_str("<tbody>");
+            for _ in 0..1 + rng.below(3) {
+                html.push_str("<tr>");
+                for _ in 0..1 + rng.below(4) {
+                    html.push_str("<td>");
+                    generate_inline(rng, html, 0);
+                    html.push_str("</td>");
+                }
+                html.push_str("</tr>");
+            }
+            html.push_str("</tbody>");
+        }
+        _ => {
+            for _ in 0..1 + rng.below(3) {
+                // deep chains every now and then, shallow siblings otherwise
+                if depth < 40 && rng.below(3) != 0 {
+                    generate_block(rng, html, depth + 1);
+                } else {
+                    html.push_str("<p>");
+                    generate_inline(rng, html, 0);
+                    html.push_str("</p>");
+                }
+            }
+        }
+    }
+    html.push_str("</");
+    html.push_str(tag);
+    html.push('>');
+}
+
+fn generate_inline(rng: &mut Rng, html: &mut String, depth: usize) {
+    for _ in 0..1 + rng.below(6) {
+        if depth < 4 && rng.below(4) == 0 {
+            // links cannot nest, the adoption agency would restructure them
+            let tag = match rng.pick(INLINE_TAGS) {
+                "a" if depth > 0 => "span",
+                tag => tag,
+            };
+            open_tag(rng, html, tag);
+            generate_inline(rng, html, depth + 1);
+            html.push_str("</");
+            html.push_str(tag);
+            html.push('>');
+        } else {
+            html.push_str(rng.pick(WORDS));
+            html.push(' ');
+        }
+    }
+}
+
+fn open_tag(rng: &mut Rng, html: &mut String, tag: &str) {
+    html.push('<');
+    html.push_str(tag);
+    // mostly a few attributes, sometimes a large set
+    let count = if rng.below(20) == 0 {
+        20 + rng.below(30)
+    } else {
+        rng.below(4)
+    };
+    for index in 0..count {
+        html.push_str(&format!(" data-a{index}=\"{}\"", rng.pick(WORDS)));
+    }
+    if tag == "a" {
+        html.push_str(" href=\"/page?x=1&amp;y=2\"");
+    }
+    html.push('>');
+}
+
+struct Measurement {
+    best: Duration,
+    peak_bytes: usize,
+}
+
+/// Best time over `ITERATIONS` runs of `run` and the peak memory of the first one. `setup`
+/// prepares the input of each run outside of the measurement.
+fn measure<S, T>(mut setup: impl FnMut() -> S, mut run: impl FnMut(S) -> T) -> Measurement {
+    let mut best = Duration::MAX;
+    let mut peak_bytes = 0;
+    for iteration in 0..ITERATIONS {
+        let input = setup();
+        let baseline = ALLOCATED.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        let start = Instant::now();
+        let result = run(input);
+        let elapsed = start.elapsed();
+        if iteration == 0 {
+            peak_bytes = PEAK.load(Ordering::Relaxed) - baseline;
+        }
+        drop(black_box(result));
+        best = best.min(elapsed);
+    }
+    Measurement { best, peak_bytes }
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(html.as_bytes());
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.next_token();
+        let is_eof = token == Token::EOF;
+        tokens.push(token);
+        if is_eof {
+            return tokens;
+        }
+    }
+}
+
+fn construct_tree(tokens: Vec<Token>) -> TreeConstructor {
+    let mut tree_constructor = TreeConstructor::new();
+    for token in tokens {
+        tree_constructor.process_token(token);
+    }
+    tree_constructor
+}
+
+fn report(phase: &str, measurement: &Measurement, input_size: usize) {
+    let seconds = measurement.best.as_secs_f64();
+    println!(
+        "{phase:<20} {:>9.2} ms {:>8.1} MB/s   peak {:>8.1} KB",
+        seconds * 1000.0,
+        input_size as f64 / seconds / (1024.0 * 1024.0),
+        measurement.peak_bytes as f64 / 1024.0,
+    );
+}
+
+fn main() {
+    let html = generate_document(TARGET_SIZE, SEED);
+    println!(
+        "document: {} bytes, seed {SEED:#x}, best of {ITERATIONS}",
+        html.len()
+    );
+
+    let tokenization = measure(|| (), |()| tokenize(&html));
+    report("tokenization", &tokenization, html.len());
+
+    // the generated markup has no script, style or title past the head, so collecting tokens
+    // without the tree constructor driving tokenizer states gives the same token stream
+    let tokens = tokenize(&html);
+    let tree_construction = measure(|| tokens.clone(), construct_tree);
+    report("tree construction", &tree_construction, html.len());
+
+    let full = measure(|| (), |()| parse_document(&html));
+    report("parse_document", &full, html.len());
+
+    let bottleneck = if tokenization.best > tree_construction.best {
+        "tokenization"
+    } else {
+        "tree construction"
+    };
+    println!("primary bottleneck: {bottleneck}");
+}
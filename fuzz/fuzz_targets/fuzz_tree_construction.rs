@@ -0,0 +1,19 @@
+//! Parses arbitrary bytes as HTML and checks the structural invariants of the result.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_tree_construction -- -runs=10000000` from the
+//! repository root.
+
+#![no_main]
+
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::testing::check_tree_invariants;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // the parser takes text, invalid UTF-8 is replaced the way a decoder would
+    let html = String::from_utf8_lossy(data);
+    let document = parse_document(&html);
+    if let Err(error) = check_tree_invariants(&document) {
+        panic!("{error}\ninput: {html:?}");
+    }
+});
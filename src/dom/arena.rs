@@ -0,0 +1,518 @@
+use crate::dom::mutation_observer::{MutationObserver, MutationObserverId, MutationObservers};
+use crate::dom::node::{Node, NodeData, NodeId};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Errors raised by tree mutations, named after the matching DOM exceptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomError {
+    /// The insertion would produce an invalid tree, e.g. a node inside its own descendant
+    HierarchyRequest,
+    /// The reference node is not a child of the given parent
+    NotFound,
+    /// The node cannot be modified this way, e.g. replacing the document element with markup
+    NoModificationAllowed,
+    /// An offset is out of range, or not on a character boundary
+    IndexSize,
+    /// The operation does not apply to this kind of node, e.g. splitting an element
+    InvalidNodeType,
+    /// A string argument is malformed, e.g. an empty token
+    Syntax,
+    /// A string argument holds a character it may not, e.g. whitespace in a token
+    InvalidCharacter,
+    /// The object is not in a state allowing the operation, e.g. showing an open dialog
+    InvalidState,
+    /// The operation is valid but not implemented, e.g. a canvas rendering context
+    NotSupported,
+}
+
+/// Owns every node of a tree, nodes refer to each other by `NodeId`
+#[derive(Debug, Clone, Default)]
+pub struct NodeArena {
+    nodes: Vec<Node>,
+    /// Bumped by every compaction, ids of older generations are rejected
+    generation: u32,
+    observers: MutationObservers,
+    /// Form controls the parser associated with the form element pointer, kept until the
+    /// control is moved or its form attribute changes
+    parser_form_owners: HashMap<NodeId, NodeId>,
+}
+
+/// Translates the ids handed out before a compaction into the ids of the compacted arena
+#[derive(Debug, Clone)]
+pub struct NodeIdMap {
+    indices: Vec<Option<usize>>,
+    old_generation: u32,
+    generation: u32,
+}
+
+impl NodeIdMap {
+    /// The id of `old` after the compaction, None if the node was dropped or `old` does not
+    /// belong to the generation that was compacted
+    pub fn get(&self, old: NodeId) -> Option<NodeId> {
+        if old.generation != self.old_generation {
+            return None;
+        }
+        let index = (*self.indices.get(old.index)?)?;
+        Some(NodeId {
+            index,
+            generation: self.generation,
+        })
+    }
+}
+
+impl NodeArena {
+    pub fn new() -> Self {
+        NodeArena::default()
+    }
+
+    /// Empty arena with room for `elements` elements and the text in between before it
+    /// reallocates. Text is owned by each text node, so `chars` only bounds the number of text
+    /// nodes: at most one between two elements, and never more than there are characters.
+    pub fn with_capacity(elements: usize, chars: usize) -> Self {
+        NodeArena {
+            nodes: Vec::with_capacity(elements + chars.min(elements + 1)),
+            ..NodeArena::default()
+        }
+    }
+
+    pub fn new_node(&mut self, data: NodeData) -> NodeId {
+        self.nodes.push(Node::new(data));
+        NodeId {
+            index: self.nodes.len() - 1,
+            generation: self.generation,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of nodes the arena can hold before it reallocates
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// The node behind `id`. Panics when the id was handed out before the last compaction.
+    pub fn get(&self, id: NodeId) -> &Node {
+        self.check_generation(id);
+        &self.nodes[id.index]
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> &mut Node {
+        self.check_generation(id);
+        &mut self.nodes[id.index]
+    }
+
+    fn check_generation(&self, id: NodeId) {
+        assert!(
+            id.generation == self.generation,
+            "{id:?} was handed out before the arena was compacted"
+        );
+    }
+
+    /// Drops every node whose index is not marked in `keep` and moves the others down,
+    /// keeping their relative order. Starts a new generation: the returned map translates
+    /// the ids from before, which the arena no longer accepts.
+    pub(crate) fn compact(&mut self, keep: &[bool]) -> NodeIdMap {
+        let mut indices = Vec::with_capacity(self.nodes.len());
+        let mut kept = 0;
+        for index in 0..self.nodes.len() {
+            if keep.get(index).copied().unwrap_or(false) {
+                indices.push(Some(kept));
+                kept += 1;
+            } else {
+                indices.push(None);
+            }
+        }
+        let map = NodeIdMap {
+            indices,
+            old_generation: self.generation,
+            generation: self.generation.wrapping_add(1),
+        };
+        let nodes = std::mem::take(&mut self.nodes);
+        self.nodes = nodes
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| map.indices[*index].is_some())
+            .map(|(_, mut node)| {
+                for link in [
+                    &mut node.parent,
+                    &mut node.prev_sibling,
+                    &mut node.next_sibling,
+                    &mut node.first_child,
+                    &mut node.last_child,
+                ] {
+                    *link = link.and_then(|id| map.get(id));
+                }
+                node
+            })
+            .collect();
+        self.generation = map.generation;
+        self.observers.remap(&map);
+        self.parser_form_owners = std::mem::take(&mut self.parser_form_owners)
+            .into_iter()
+            .filter_map(|(control, form)| Some((map.get(control)?, map.get(form)?)))
+            .collect();
+        map
+    }
+
+    /// The form the parser associated `control` with, see `form_owner`
+    pub(crate) fn parser_form_owner(&self, control: NodeId) -> Option<NodeId> {
+        self.parser_form_owners.get(&control).copied()
+    }
+
+    pub(crate) fn set_parser_form_owner(&mut self, control: NodeId, form: NodeId) {
+        self.parser_form_owners.insert(control, form);
+    }
+
+    pub(crate) fn clear_parser_form_owner(&mut self, control: NodeId) {
+        self.parser_form_owners.remove(&control);
+    }
+
+    /// Adds an observer of the changes made to this tree, see `MutationObserver`
+    pub fn add_mutation_observer(&mut self, observer: MutationObserver) -> MutationObserverId {
+        self.observers.add(observer)
+    }
+
+    pub fn mutation_observer(&self, id: MutationObserverId) -> &MutationObserver {
+        self.observers.get(id)
+    }
+
+    pub fn mutation_observer_mut(&mut self, id: MutationObserverId) -> &mut MutationObserver {
+        self.observers.get_mut(id)
+    }
+
+    /// Calls the callback of every observer with the records queued since the last delivery.
+    /// This stands in for the microtask of the DOM standard: call it once a batch of changes
+    /// is done.
+    pub fn deliver_mutation_records(&mut self) {
+        self.observers.deliver();
+    }
+
+    /// Queues an attribute record for `target`, made before the attribute changes
+    pub(crate) fn queue_attribute_mutation(
+        &mut self,
+        target: NodeId,
+        name: &str,
+        old_value: Option<&str>,
+    ) {
+        if self.observers.is_empty() {
+            return;
+        }
+        let ancestors: Vec<NodeId> = self.ancestors(target).collect();
+        self.observers
+            .queue_attribute(target, &ancestors, name, old_value);
+    }
+
+    fn queue_child_list_mutation(
+        &mut self,
+        parent: NodeId,
+        added: Option<NodeId>,
+        removed: Option<NodeId>,
+    ) {
+        if self.observers.is_empty() {
+            return;
+        }
+        let ancestors: Vec<NodeId> = self.ancestors(parent).collect();
+        self.observers
+            .queue_child_list(parent, &ancestors, added, removed);
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.get(id).parent
+    }
+
+    /// Children of a node in tree order
+    pub fn children(&self, id: NodeId) -> Children<'_> {
+        Children {
+            arena: self,
+            next: self.get(id).first_child,
+        }
+    }
+
+    /// Ancestors of a node, closest first, not including the node itself
+    pub fn ancestors(&self, id: NodeId) -> Ancestors<'_> {
+        Ancestors {
+            arena: self,
+            next: self.get(id).parent,
+        }
+    }
+
+    /// Descendants of a node in tree order, not including the node itself
+    pub fn descendants(&self, id: NodeId) -> Descendants<'_> {
+        Descendants {
+            arena: self,
+            root: id,
+            next: self.get(id).first_child,
+        }
+    }
+
+    /// Checks whether `id` is `ancestor` or one of its descendants
+    pub fn is_inclusive_descendant(&self, id: NodeId, ancestor: NodeId) -> bool {
+        id == ancestor || self.ancestors(id).any(|a| a == ancestor)
+    }
+
+    /// Compares two nodes by tree order: an ancestor comes before its descendants, and
+    /// otherwise the nodes are ordered by the children of their closest common ancestor they
+    /// descend from. Nodes in disconnected trees are ordered by their roots, arbitrarily but
+    /// consistently.
+    pub fn document_order(&self, a: NodeId, b: NodeId) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        // inclusive ancestor chains, root first
+        let chain = |id: NodeId| {
+            let mut chain: Vec<NodeId> = self.ancestors(id).collect();
+            chain.reverse();
+            chain.push(id);
+            chain
+        };
+        let (a_chain, b_chain) = (chain(a), chain(b));
+        if a_chain[0] != b_chain[0] {
+            return a_chain[0].index.cmp(&b_chain[0].index);
+        }
+        let common = a_chain
+            .iter()
+            .zip(&b_chain)
+            .take_while(|(a, b)| a == b)
+            .count();
+        match (a_chain.get(common), b_chain.get(common)) {
+            (None, _) => Ordering::Less,
+            (_, None) => Ordering::Greater,
+            (Some(&a_branch), Some(&b_branch)) => {
+                let mut sibling = self.get(a_branch).next_sibling;
+                while let Some(next) = sibling {
+                    if next == b_branch {
+                        return Ordering::Less;
+                    }
+                    sibling = self.get(next).next_sibling;
+                }
+                Ordering::Greater
+            }
+        }
+    }
+
+    /// Sorts nodes into tree order, see `document_order`
+    pub fn sort_by_document_order(&self, nodes: &mut [NodeId]) {
+        nodes.sort_by(|&a, &b| self.document_order(a, b));
+    }
+
+    /// The next node in tree order that is not a descendant of `id`, bounded by `root`
+    pub(crate) fn following_skipping_children(&self, id: NodeId, root: NodeId) -> Option<NodeId> {
+        let mut current = id;
+        loop {
+            if current == root {
+                return None;
+            }
+            if let Some(next) = self.get(current).next_sibling {
+                return Some(next);
+            }
+            current = self.get(current).parent?;
+        }
+    }
+
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) -> Result<NodeId, DomError> {
+        self.insert_before(parent, child, None)
+    }
+
+    /// Inserts `child` into `parent` before `reference`, or last when `reference` is `None`.
+    /// The child is removed from its previous parent first. When `child` is a document
+    /// fragment its children are moved in, in order, and the fragment is left empty.
+    pub fn insert_before(
+        &mut self,
+        parent: NodeId,
+        child: NodeId,
+        reference: Option<NodeId>,
+    ) -> Result<NodeId, DomError> {
+        self.ensure_pre_insertion_validity(parent, child, reference)?;
+        if self.get(child).is_document_fragment() {
+            self.move_fragment_children(child, parent, reference);
+            return Ok(child);
+        }
+        // inserting a node before itself leaves it where it is
+        let reference = if reference == Some(child) {
+            self.get(child).next_sibling
+        } else {
+            reference
+        };
+        self.detach(child);
+        self.link_before(parent, child, reference);
+        Ok(child)
+    }
+
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> Result<NodeId, DomError> {
+        if self.get(child).parent != Some(parent) {
+            return Err(DomError::NotFound);
+        }
+        self.detach(child);
+        Ok(child)
+    }
+
+    /// Replaces `old_child` of `parent` with `new_child`, returns the removed node
+    pub fn replace_child(
+        &mut self,
+        parent: NodeId,
+        new_child: NodeId,
+        old_child: NodeId,
+    ) -> Result<NodeId, DomError> {
+        if self.get(old_child).parent != Some(parent) {
+            return Err(DomError::NotFound);
+        }
+        if new_child == old_child {
+            return Ok(old_child);
+        }
+        self.ensure_pre_insertion_validity(parent, new_child, None)?;
+        let mut reference = self.get(old_child).next_sibling;
+        if reference == Some(new_child) {
+            reference = self.get(new_child).next_sibling;
+        }
+        self.detach(old_child);
+        if self.get(new_child).is_document_fragment() {
+            self.move_fragment_children(new_child, parent, reference);
+            return Ok(old_child);
+        }
+        self.detach(new_child);
+        self.link_before(parent, new_child, reference);
+        Ok(old_child)
+    }
+
+    /// Removes a node from its parent, keeping its own subtree intact
+    pub fn detach(&mut self, id: NodeId) {
+        let (parent, prev, next) = {
+            let node = self.get(id);
+            (node.parent, node.prev_sibling, node.next_sibling)
+        };
+        let Some(parent) = parent else {
+            return;
+        };
+        self.queue_child_list_mutation(parent, None, Some(id));
+        if !self.parser_form_owners.is_empty() {
+            // a control moved away from where the parser put it is owned by its ancestors
+            let owners = std::mem::take(&mut self.parser_form_owners);
+            self.parser_form_owners = owners
+                .into_iter()
+                .filter(|&(control, _)| !self.is_inclusive_descendant(control, id))
+                .collect();
+        }
+        match prev {
+            Some(prev) => self.get_mut(prev).next_sibling = next,
+            None => self.get_mut(parent).first_child = next,
+        }
+        match next {
+            Some(next) => self.get_mut(next).prev_sibling = prev,
+            None => self.get_mut(parent).last_child = prev,
+        }
+        let node = self.get_mut(id);
+        node.parent = None;
+        node.prev_sibling = None;
+        node.next_sibling = None;
+    }
+
+    fn ensure_pre_insertion_validity(
+        &self,
+        parent: NodeId,
+        child: NodeId,
+        reference: Option<NodeId>,
+    ) -> Result<(), DomError> {
+        if !self.get(parent).can_have_children()
+            || matches!(self.get(child).data, NodeData::Document)
+            || self.is_inclusive_descendant(parent, child)
+        {
+            return Err(DomError::HierarchyRequest);
+        }
+        if let Some(reference) = reference {
+            if self.get(reference).parent != Some(parent) {
+                return Err(DomError::NotFound);
+            }
+        }
+        Ok(())
+    }
+
+    fn move_fragment_children(
+        &mut self,
+        fragment: NodeId,
+        parent: NodeId,
+        reference: Option<NodeId>,
+    ) {
+        while let Some(child) = self.get(fragment).first_child {
+            self.detach(child);
+            self.link_before(parent, child, reference);
+        }
+    }
+
+    fn link_before(&mut self, parent: NodeId, child: NodeId, reference: Option<NodeId>) {
+        let prev = match reference {
+            Some(reference) => self.get(reference).prev_sibling,
+            None => self.get(parent).last_child,
+        };
+        {
+            let node = self.get_mut(child);
+            node.parent = Some(parent);
+            node.prev_sibling = prev;
+            node.next_sibling = reference;
+        }
+        match prev {
+            Some(prev) => self.get_mut(prev).next_sibling = Some(child),
+            None => self.get_mut(parent).first_child = Some(child),
+        }
+        match reference {
+            Some(reference) => self.get_mut(reference).prev_sibling = Some(child),
+            None => self.get_mut(parent).last_child = Some(child),
+        }
+        self.queue_child_list_mutation(parent, Some(child), None);
+    }
+}
+
+pub struct Children<'a> {
+    arena: &'a NodeArena,
+    next: Option<NodeId>,
+}
+
+impl Iterator for Children<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let current = self.next?;
+        self.next = self.arena.get(current).next_sibling;
+        Some(current)
+    }
+}
+
+pub struct Ancestors<'a> {
+    arena: &'a NodeArena,
+    next: Option<NodeId>,
+}
+
+impl Iterator for Ancestors<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let current = self.next?;
+        self.next = self.arena.get(current).parent;
+        Some(current)
+    }
+}
+
+pub struct Descendants<'a> {
+    arena: &'a NodeArena,
+    root: NodeId,
+    next: Option<NodeId>,
+}
+
+impl Iterator for Descendants<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let current = self.next?;
+        self.next = match self.arena.get(current).first_child {
+            Some(child) => Some(child),
+            None => self.arena.following_skipping_children(current, self.root),
+        };
+        Some(current)
+    }
+}
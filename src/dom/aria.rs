@@ -0,0 +1,125 @@
+use crate::dom::arena::NodeArena;
+use crate::dom::node::{Element, Node};
+
+/// The commonly used WAI-ARIA roles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AriaRole {
+    Button,
+    Checkbox,
+    Dialog,
+    Grid,
+    Heading,
+    Link,
+    Listbox,
+    Menuitem,
+    Menuitemcheckbox,
+    Navigation,
+    Region,
+    Tab,
+    Tablist,
+    Textbox,
+    Tooltip,
+    Treeitem,
+}
+
+impl AriaRole {
+    /// Role named by a single token of a role attribute, matched ASCII case-insensitively
+    pub fn from_token(token: &str) -> Option<AriaRole> {
+        let role = match token.to_ascii_lowercase().as_str() {
+            "button" => AriaRole::Button,
+            "checkbox" => AriaRole::Checkbox,
+            "dialog" => AriaRole::Dialog,
+            "grid" => AriaRole::Grid,
+            "heading" => AriaRole::Heading,
+            "link" => AriaRole::Link,
+            "listbox" => AriaRole::Listbox,
+            "menuitem" => AriaRole::Menuitem,
+            "menuitemcheckbox" => AriaRole::Menuitemcheckbox,
+            "navigation" => AriaRole::Navigation,
+            "region" => AriaRole::Region,
+            "tab" => AriaRole::Tab,
+            "tablist" => AriaRole::Tablist,
+            "textbox" => AriaRole::Textbox,
+            "tooltip" => AriaRole::Tooltip,
+            "treeitem" => AriaRole::Treeitem,
+            _ => return None,
+        };
+        Some(role)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AriaRole::Button => "button",
+            AriaRole::Checkbox => "checkbox",
+            AriaRole::Dialog => "dialog",
+            AriaRole::Grid => "grid",
+            AriaRole::Heading => "heading",
+            AriaRole::Link => "link",
+            AriaRole::Listbox => "listbox",
+            AriaRole::Menuitem => "menuitem",
+            AriaRole::Menuitemcheckbox => "menuitemcheckbox",
+            AriaRole::Navigation => "navigation",
+            AriaRole::Region => "region",
+            AriaRole::Tab => "tab",
+            AriaRole::Tablist => "tablist",
+            AriaRole::Textbox => "textbox",
+            AriaRole::Tooltip => "tooltip",
+            AriaRole::Treeitem => "treeitem",
+        }
+    }
+}
+
+impl Element {
+    /// The explicit role: the first token of the role attribute naming a known role, the
+    /// later tokens being fallbacks
+    pub fn aria_role(&self) -> Option<AriaRole> {
+        self.get_attribute("role")?
+            .split_ascii_whitespace()
+            .find_map(AriaRole::from_token)
+    }
+
+    pub fn aria_label(&self) -> Option<&str> {
+        self.get_attribute("aria-label")
+    }
+
+    /// `Some(true)` or `Some(false)` for the literal values, None when absent or undefined
+    pub fn aria_hidden(&self) -> Option<bool> {
+        let value = self
+            .get_attribute("aria-hidden")?
+            .trim_matches(|c: char| c.is_ascii_whitespace());
+        if value.eq_ignore_ascii_case("true") {
+            Some(true)
+        } else if value.eq_ignore_ascii_case("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// The raw id reference list of aria-describedby
+    pub fn aria_describedby(&self) -> Option<&str> {
+        self.get_attribute("aria-describedby")
+    }
+}
+
+impl Node {
+    /// Whether this node or one of its ancestors has `aria-hidden="true"`
+    pub fn is_aria_hidden_subtree(&self, arena: &NodeArena) -> bool {
+        let is_hidden = |node: &Node| {
+            node.as_element()
+                .is_some_and(|element| element.aria_hidden() == Some(true))
+        };
+        if is_hidden(self) {
+            return true;
+        }
+        let mut parent = self.parent();
+        while let Some(id) = parent {
+            let node = arena.get(id);
+            if is_hidden(node) {
+                return true;
+            }
+            parent = node.parent();
+        }
+        false
+    }
+}
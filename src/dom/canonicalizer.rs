@@ -0,0 +1,78 @@
+use crate::dom::document::Document;
+use crate::dom::node::{NodeData, NodeId};
+use crate::dom::escape::{escape_attribute_value, escape_text};
+use crate::dom::serializer::{is_void_element, serialized_attribute_name};
+
+const INDENT: &str = "  ";
+
+/// Canonical serialization of a document: one tag, text run or comment per line, indented by
+/// depth, with attributes sorted by name and whitespace collapsed outside pre and textarea.
+/// Two documents that only differ in insignificant formatting produce the same output.
+pub fn canonicalize(document: &Document) -> String {
+    let mut lines = Vec::new();
+    for child in document.arena().children(document.root()) {
+        canonicalize_node(document, child, 0, false, &mut lines);
+    }
+    let mut output = lines.join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+fn canonicalize_node(
+    document: &Document,
+    node: NodeId,
+    depth: usize,
+    preserve_whitespace: bool,
+    lines: &mut Vec<String>,
+) {
+    let indent = INDENT.repeat(depth);
+    match &document.arena().get(node).data {
+        NodeData::Document => {}
+        NodeData::DocumentFragment => {
+            for child in document.arena().children(node) {
+                canonicalize_node(document, child, depth, preserve_whitespace, lines);
+            }
+        }
+        NodeData::Doctype { name, .. } => lines.push(format!("{indent}<!DOCTYPE {name}>")),
+        NodeData::Element(element) => {
+            let mut attributes: Vec<(String, &str)> = element
+                .attributes
+                .iter()
+                .map(|attr| (serialized_attribute_name(attr), attr.value.as_str()))
+                .collect();
+            attributes.sort();
+            let mut open_tag = format!("{indent}<{}", element.name);
+            for (name, value) in attributes {
+                open_tag.push_str(&format!(" {name}=\"{}\"", escape_attribute_value(value)));
+            }
+            open_tag.push('>');
+            lines.push(open_tag);
+            if element.is_html() && is_void_element(&element.name) {
+                return;
+            }
+            let preserve_whitespace = preserve_whitespace
+                || (element.is_html()
+                    && matches!(element.name.as_str(), "pre" | "textarea" | "listing"));
+            let children_of = document.template_content(node).unwrap_or(node);
+            for child in document.arena().children(children_of) {
+                canonicalize_node(document, child, depth + 1, preserve_whitespace, lines);
+            }
+            lines.push(format!("{indent}</{}>", element.name));
+        }
+        NodeData::Text(text) => {
+            if preserve_whitespace {
+                for line in escape_text(text).split('\n') {
+                    lines.push(format!("{indent}{line}"));
+                }
+                return;
+            }
+            let collapsed = text.split_ascii_whitespace().collect::<Vec<_>>().join(" ");
+            if !collapsed.is_empty() {
+                lines.push(format!("{indent}{}", escape_text(&collapsed)));
+            }
+        }
+        NodeData::Comment(data) => lines.push(format!("{indent}<!--{data}-->")),
+    }
+}
@@ -0,0 +1,354 @@
+// src/dom/datetime.rs
+
+//! The date and time microsyntaxes of the HTML standard, as accepted by the datetime attribute
+//! of the time element. Only valid strings are parsed, there is no error recovery.
+
+use std::time::Duration;
+
+/// A calendar date of the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// A time of day, seconds and milliseconds are 0 when the string leaves them out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Time {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub millisecond: u32,
+}
+
+/// A value of one of the date and time microsyntaxes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeValue {
+    /// `2024-05`
+    Month { year: u32, month: u32 },
+    /// `2024-05-17`
+    Date(Date),
+    /// `05-17` or `--05-17`
+    YearlessDate { month: u32, day: u32 },
+    /// `14:54`, `14:54:39` or `14:54:39.929`
+    Time(Time),
+    /// `2024-05-17T14:54`, a space may stand for the T
+    LocalDateTime(Date, Time),
+    /// `Z`, `+05:30` or `-0800`, in minutes east of UTC
+    TimeZoneOffset(i32),
+    /// A local date and time followed by a time-zone offset in minutes
+    GlobalDateTime(Date, Time, i32),
+    /// `2024-W20`
+    Week { year: u32, week: u32 },
+    /// `2024`, four digits or more
+    Year(u32),
+    /// `PT4H18M3S` or `4h 18m 3s`
+    Duration(Duration),
+}
+
+/// Parses `input` as the first of the microsyntaxes it is valid for, in the order the time
+/// element lists them. Returns None for strings valid for none.
+pub fn parse_date_time_value(input: &str) -> Option<DateTimeValue> {
+    let parsers: [fn(&mut Cursor) -> Option<DateTimeValue>; 10] = [
+        |cursor| month(cursor).map(|(year, month)| DateTimeValue::Month { year, month }),
+        |cursor| date(cursor).map(DateTimeValue::Date),
+        |cursor| {
+            yearless_date(cursor).map(|(month, day)| DateTimeValue::YearlessDate { month, day })
+        },
+        |cursor| time(cursor).map(DateTimeValue::Time),
+        |cursor| {
+            let (date, time) = local_date_time(cursor)?;
+            Some(DateTimeValue::LocalDateTime(date, time))
+        },
+        |cursor| time_zone_offset(cursor).map(DateTimeValue::TimeZoneOffset),
+        |cursor| {
+            let (date, time) = local_date_time(cursor)?;
+            let offset = time_zone_offset(cursor)?;
+            Some(DateTimeValue::GlobalDateTime(date, time, offset))
+        },
+        |cursor| week(cursor).map(|(year, week)| DateTimeValue::Week { year, week }),
+        |cursor| year(cursor).map(DateTimeValue::Year),
+        |cursor| duration(cursor).map(DateTimeValue::Duration),
+    ];
+    parsers.iter().find_map(|parser| {
+        let mut cursor = Cursor { input, position: 0 };
+        parser(&mut cursor).filter(|_| cursor.at_end())
+    })
+}
+
+struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Cursor<'_> {
+    fn at_end(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    /// Consumes `byte` when it comes next
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Consumes at least `min` and at most `max` ASCII digits
+    fn digits(&mut self, min: usize, max: usize) -> Option<u32> {
+        let start = self.position;
+        let rest = &self.input.as_bytes()[start..];
+        let len = rest
+            .iter()
+            .take(max)
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if len < min {
+            return None;
+        }
+        self.position += len;
+        self.input[start..start + len].parse().ok()
+    }
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(400) || (year.is_multiple_of(4) && !year.is_multiple_of(100))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day of the week of 1 January of `year`, 0 being Sunday
+fn new_year_weekday(year: u32) -> u32 {
+    let y = u64::from(year - 1);
+    // 1 January of year 1 was a Monday
+    ((1 + y * 365 + y / 4 - y / 100 + y / 400) % 7) as u32
+}
+
+/// Years starting on a Thursday, and leap years starting on a Wednesday, have 53 weeks
+fn weeks_in_year(year: u32) -> u32 {
+    match new_year_weekday(year) {
+        4 => 53,
+        3 if is_leap_year(year) => 53,
+        _ => 52,
+    }
+}
+
+fn year(cursor: &mut Cursor) -> Option<u32> {
+    cursor.digits(4, usize::MAX).filter(|&year| year > 0)
+}
+
+fn month(cursor: &mut Cursor) -> Option<(u32, u32)> {
+    let year = year(cursor)?;
+    if !cursor.eat(b'-') {
+        return None;
+    }
+    let month = cursor
+        .digits(2, 2)
+        .filter(|month| (1..=12).contains(month))?;
+    Some((year, month))
+}
+
+fn date(cursor: &mut Cursor) -> Option<Date> {
+    let (year, month) = month(cursor)?;
+    if !cursor.eat(b'-') {
+        return None;
+    }
+    let day = cursor
+        .digits(2, 2)
+        .filter(|&day| day >= 1 && day <= days_in_month(year, month))?;
+    Some(Date { year, month, day })
+}
+
+fn yearless_date(cursor: &mut Cursor) -> Option<(u32, u32)> {
+    if cursor.eat(b'-') && !cursor.eat(b'-') {
+        return None;
+    }
+    let month = cursor
+        .digits(2, 2)
+        .filter(|month| (1..=12).contains(month))?;
+    if !cursor.eat(b'-') {
+        return None;
+    }
+    // any leap year allows 29 February
+    let day = cursor
+        .digits(2, 2)
+        .filter(|&day| day >= 1 && day <= days_in_month(4, month))?;
+    Some((month, day))
+}
+
+fn time(cursor: &mut Cursor) -> Option<Time> {
+    let hour = cursor.digits(2, 2).filter(|&hour| hour <= 23)?;
+    if !cursor.eat(b':') {
+        return None;
+    }
+    let minute = cursor.digits(2, 2).filter(|&minute| minute <= 59)?;
+    let mut time = Time {
+        hour,
+        minute,
+        second: 0,
+        millisecond: 0,
+    };
+    if cursor.eat(b':') {
+        time.second = cursor.digits(2, 2).filter(|&second| second <= 59)?;
+        if cursor.eat(b'.') {
+            time.millisecond = milliseconds(cursor)?;
+        }
+    }
+    Some(time)
+}
+
+/// The one to three digits of a fraction of a second, as milliseconds
+fn milliseconds(cursor: &mut Cursor) -> Option<u32> {
+    let start = cursor.position;
+    let fraction = cursor.digits(1, 3)?;
+    let scale = [100, 10, 1][cursor.position - start - 1];
+    Some(fraction * scale)
+}
+
+fn local_date_time(cursor: &mut Cursor) -> Option<(Date, Time)> {
+    let date = date(cursor)?;
+    if !cursor.eat(b'T') && !cursor.eat(b' ') {
+        return None;
+    }
+    Some((date, time(cursor)?))
+}
+
+fn time_zone_offset(cursor: &mut Cursor) -> Option<i32> {
+    if cursor.eat(b'Z') {
+        return Some(0);
+    }
+    let sign = if cursor.eat(b'+') {
+        1
+    } else if cursor.eat(b'-') {
+        -1
+    } else {
+        return None;
+    };
+    let hours = cursor.digits(2, 2).filter(|&hours| hours <= 23)?;
+    cursor.eat(b':');
+    let minutes = cursor.digits(2, 2).filter(|&minutes| minutes <= 59)?;
+    Some(sign * (hours * 60 + minutes) as i32)
+}
+
+fn week(cursor: &mut Cursor) -> Option<(u32, u32)> {
+    let year = year(cursor)?;
+    if !cursor.eat(b'-') || !cursor.eat(b'W') {
+        return None;
+    }
+    let week = cursor
+        .digits(2, 2)
+        .filter(|&week| week >= 1 && week <= weeks_in_year(year))?;
+    Some((year, week))
+}
+
+fn duration(cursor: &mut Cursor) -> Option<Duration> {
+    if cursor.peek() == Some(b'P') {
+        iso_duration(cursor)
+    } else {
+        component_duration(cursor)
+    }
+}
+
+/// `P[nD][T[nH][nM][n[.fff]S]]` with at least one component, and one after a T
+fn iso_duration(cursor: &mut Cursor) -> Option<Duration> {
+    cursor.eat(b'P');
+    let mut duration = Duration::ZERO;
+    let mut components = 0;
+    let checkpoint = cursor.position;
+    if let Some(days) = cursor.digits(1, usize::MAX) {
+        if !cursor.eat(b'D') {
+            // the digits belong to no day component, e.g. `P5`
+            cursor.position = checkpoint;
+            return None;
+        }
+        duration += Duration::from_secs(u64::from(days) * 86_400);
+        components += 1;
+    }
+    if cursor.eat(b'T') {
+        let mut time_components = 0;
+        for (unit, seconds) in [(b'H', 3_600), (b'M', 60)] {
+            let checkpoint = cursor.position;
+            if let Some(count) = cursor.digits(1, usize::MAX) {
+                if cursor.eat(unit) {
+                    duration += Duration::from_secs(u64::from(count) * seconds);
+                    time_components += 1;
+                } else {
+                    cursor.position = checkpoint;
+                }
+            }
+        }
+        if cursor.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            duration += seconds_component(cursor)?;
+            if !cursor.eat(b'S') {
+                return None;
+            }
+            time_components += 1;
+        }
+        if time_components == 0 {
+            return None;
+        }
+        components += time_components;
+    }
+    (components > 0).then_some(duration)
+}
+
+/// One or more of `nW`, `nD`, `nH`, `nM` and `n[.fff]S`, each unit at most once, any
+/// whitespace between them and units in either case
+fn component_duration(cursor: &mut Cursor) -> Option<Duration> {
+    let mut duration = Duration::ZERO;
+    let mut seen = Vec::new();
+    loop {
+        while cursor.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            cursor.position += 1;
+        }
+        if cursor.at_end() {
+            break;
+        }
+        let start = cursor.position;
+        let amount = seconds_component(cursor)?;
+        let fractional = cursor.input[start..cursor.position].contains('.');
+        while cursor.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            cursor.position += 1;
+        }
+        let unit = cursor.peek()?.to_ascii_uppercase();
+        cursor.position += 1;
+        let scale = match unit {
+            b'W' => 604_800,
+            b'D' => 86_400,
+            b'H' => 3_600,
+            b'M' => 60,
+            b'S' => 1,
+            _ => return None,
+        };
+        if seen.contains(&unit) || (fractional && unit != b'S') {
+            return None;
+        }
+        seen.push(unit);
+        duration += amount * scale;
+    }
+    (!seen.is_empty()).then_some(duration)
+}
+
+/// Digits with an optional fraction of one to three digits, as that many seconds
+fn seconds_component(cursor: &mut Cursor) -> Option<Duration> {
+    let seconds = cursor.digits(1, usize::MAX)?;
+    let millis = if cursor.eat(b'.') {
+        milliseconds(cursor)?
+    } else {
+        0
+    };
+    Some(Duration::from_secs(u64::from(seconds)) + Duration::from_millis(u64::from(millis)))
+}
@@ -0,0 +1,64 @@
+use crate::dom::document::{Document, QuirksMode};
+
+/// A departure from the HTML5 doctype, `<!DOCTYPE html>` without identifiers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DoctypeIssue {
+    /// The document has no doctype
+    MissingDoctype,
+    /// The document was parsed in quirks mode
+    QuirksMode,
+    /// The document was parsed in limited-quirks mode
+    LimitedQuirksMode,
+    /// A public identifier not belonging to an older HTML or XHTML version
+    UnknownPublicIdentifier(String),
+    /// A system identifier, `about:legacy-compat` included
+    SystemIdentifierPresent,
+    /// An HTML 2.0 to 4.01 or XHTML 1.x doctype
+    ObsoleteDoctype,
+}
+
+/// Public identifier prefixes of the HTML and XHTML versions before HTML5, lowercase
+const OBSOLETE_PUBLIC_PREFIXES: &[&str] = &[
+    "-//ietf//dtd html",
+    "-//w3c//dtd html",
+    "-//w3c//dtd xhtml",
+    "-//w3o//dtd w3 html",
+];
+
+/// Lints the doctype of `doc` against the HTML5 form. The document mode set by the parser
+/// is reported as well, so a parsed document without doctype yields both `MissingDoctype`
+/// and `QuirksMode`. An obsolete doctype is reported once, its system identifier aside.
+/// Returns an empty vec for a compliant document.
+pub fn check_doctype_compliance(doc: &Document) -> Vec<DoctypeIssue> {
+    let mut issues = Vec::new();
+    match doc
+        .doctype()
+        .and_then(|id| doc.arena().get(id).as_doctype())
+    {
+        None => issues.push(DoctypeIssue::MissingDoctype),
+        Some(doctype) => {
+            let public_id = doctype.public_id.to_ascii_lowercase();
+            if OBSOLETE_PUBLIC_PREFIXES
+                .iter()
+                .any(|prefix| public_id.starts_with(prefix))
+            {
+                issues.push(DoctypeIssue::ObsoleteDoctype);
+            } else {
+                if !doctype.public_id.is_empty() {
+                    issues.push(DoctypeIssue::UnknownPublicIdentifier(
+                        doctype.public_id.to_string(),
+                    ));
+                }
+                if !doctype.system_id.is_empty() {
+                    issues.push(DoctypeIssue::SystemIdentifierPresent);
+                }
+            }
+        }
+    }
+    match doc.quirks_mode() {
+        QuirksMode::NoQuirks => {}
+        QuirksMode::Quirks => issues.push(DoctypeIssue::QuirksMode),
+        QuirksMode::LimitedQuirks => issues.push(DoctypeIssue::LimitedQuirksMode),
+    }
+    issues
+}
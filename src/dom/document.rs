@@ -0,0 +1,1106 @@
+use crate::dom::arena::{DomError, NodeArena, NodeIdMap};
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_heading_element::{HTMLHeadingElement, OutlineEntry};
+use crate::dom::elements::html_meta_element::HTMLMetaElement;
+use crate::dom::elements::node_list::NodeList;
+use crate::dom::event::{Event, EventListenerId, EventListeners};
+use crate::dom::node::{Element, Node, NodeData, NodeId};
+use crate::dom::parser::html_parser::parse_fragment;
+use crate::dom::url::resolve_url;
+use std::collections::{HashMap, HashSet};
+use std::panic::RefUnwindSafe;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuirksMode {
+    #[default]
+    NoQuirks,
+    Quirks,
+    LimitedQuirks,
+}
+
+/// An item of the DOM convenience mutators (`append`, `before`, ...): a node, or a string
+/// inserted as a new text node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeOrText {
+    Node(NodeId),
+    Text(String),
+}
+
+impl From<NodeId> for NodeOrText {
+    fn from(node: NodeId) -> Self {
+        NodeOrText::Node(node)
+    }
+}
+
+impl From<&NodeId> for NodeOrText {
+    fn from(node: &NodeId) -> Self {
+        NodeOrText::Node(*node)
+    }
+}
+
+impl From<&str> for NodeOrText {
+    fn from(text: &str) -> Self {
+        NodeOrText::Text(text.to_string())
+    }
+}
+
+impl From<String> for NodeOrText {
+    fn from(text: String) -> Self {
+        NodeOrText::Text(text)
+    }
+}
+
+fn contains_node(items: &[NodeOrText], node: NodeId) -> bool {
+    items.contains(&NodeOrText::Node(node))
+}
+
+/// The attribute name `set_attribute` and `remove_attribute` act on: lowercased on HTML
+/// elements, kept as given elsewhere
+fn attribute_name_for(element: &Element, name: &str) -> String {
+    if element.is_html() {
+        name.to_ascii_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Where `insert_adjacent_*` inserts, relative to the element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdjacentPosition {
+    /// Before the element, as its previous sibling
+    BeforeBegin,
+    /// Inside the element, before its first child
+    AfterBegin,
+    /// Inside the element, after its last child
+    BeforeEnd,
+    /// After the element, as its next sibling
+    AfterEnd,
+}
+
+/// A document tree: the arena holding the nodes plus the id of the document node
+///
+/// Nodes are plain values in the arena and `NodeId`s are indices, so a parsed document can be
+/// moved to or shared with other threads as is.
+#[derive(Debug, Clone)]
+pub struct Document {
+    arena: NodeArena,
+    root: NodeId,
+    quirks_mode: QuirksMode,
+    url: Option<String>,
+    /// The href of the first base element inserted by the parser, and that href resolved
+    /// against the document URL
+    base_href: Option<String>,
+    base_url: Option<String>,
+    /// The contents fragment of each template element
+    template_contents: HashMap<NodeId, NodeId>,
+    /// Selectedness of the option elements it was set for, the others follow their selected
+    /// attribute
+    option_selectedness: HashMap<NodeId, bool>,
+    /// Values set through the APIs of input and textarea elements, the others show their
+    /// default value. For output elements, which keep their value in their text, the default
+    /// value saved by the first value change.
+    control_values: HashMap<NodeId, String>,
+    /// Style elements whose style sheet was disabled through `HTMLStyleElement::set_disabled`
+    disabled_style_sheets: HashSet<NodeId>,
+    /// Return values of the dialog elements closed with one
+    dialog_return_values: HashMap<NodeId, String>,
+    event_listeners: EventListeners,
+}
+
+// documents are handed between threads by crawlers, keep them free of Rc and Cell
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Document>();
+    assert_send_sync::<NodeId>();
+    assert_send_sync::<HTMLCollection>();
+    assert_send_sync::<NodeList>();
+};
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    /// Empty document whose arena has room for `elements` elements, the document node
+    /// included, and `chars` characters of text, see `NodeArena::with_capacity`
+    pub fn with_capacity(elements: usize, chars: usize) -> Self {
+        let mut arena = NodeArena::with_capacity(elements, chars);
+        let root = arena.new_node(NodeData::Document);
+        Document {
+            arena,
+            root,
+            quirks_mode: QuirksMode::NoQuirks,
+            url: None,
+            base_href: None,
+            base_url: None,
+            template_contents: HashMap::new(),
+            option_selectedness: HashMap::new(),
+            control_values: HashMap::new(),
+            disabled_style_sheets: HashSet::new(),
+            dialog_return_values: HashMap::new(),
+            event_listeners: EventListeners::default(),
+        }
+    }
+
+    /// Empty document loaded from `url`, relative references resolve against it unless the
+    /// document has a base element
+    pub fn with_url(url: &str) -> Self {
+        let mut document = Self::new();
+        document.set_url(url);
+        document
+    }
+
+    /// The address of the document, if known
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn set_url(&mut self, url: &str) {
+        self.url = Some(url.to_string());
+        self.update_base_url();
+    }
+
+    /// The document node itself
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn quirks_mode(&self) -> QuirksMode {
+        self.quirks_mode
+    }
+
+    pub fn set_quirks_mode(&mut self, mode: QuirksMode) {
+        self.quirks_mode = mode;
+    }
+
+    pub fn arena(&self) -> &NodeArena {
+        &self.arena
+    }
+
+    pub fn arena_mut(&mut self) -> &mut NodeArena {
+        &mut self.arena
+    }
+
+    /// The doctype child of the document node, see `Node::as_doctype` for its fields
+    pub fn doctype(&self) -> Option<NodeId> {
+        self.arena
+            .children(self.root)
+            .find(|&child| self.arena.get(child).as_doctype().is_some())
+    }
+
+    /// The root element, i.e. the first element child of the document
+    pub fn document_element(&self) -> Option<NodeId> {
+        self.arena
+            .children(self.root)
+            .find(|&node| self.arena.get(node).is_element())
+    }
+
+    /// First head child of the html document element
+    pub fn head(&self) -> Option<NodeId> {
+        self.html_element_child(|node| node.is_element_named("head"))
+    }
+
+    /// First body or frameset child of the html document element
+    pub fn body(&self) -> Option<NodeId> {
+        self.html_element_child(|node| {
+            node.is_element_named("body") || node.is_element_named("frameset")
+        })
+    }
+
+    fn html_element_child(&self, predicate: impl Fn(&Node) -> bool) -> Option<NodeId> {
+        let html = self
+            .document_element()
+            .filter(|&node| self.arena.get(node).is_element_named("html"))?;
+        self.arena
+            .children(html)
+            .find(|&node| predicate(self.arena.get(node)))
+    }
+
+    /// The first title element of the head, if any
+    fn title_element(&self) -> Option<NodeId> {
+        let head = self.head()?;
+        self.arena
+            .descendants(head)
+            .find(|&node| self.arena.get(node).is_element_named("title"))
+    }
+
+    /// The document title: the text children of the title element, with whitespace stripped
+    /// and collapsed. Empty when there is no title element.
+    pub fn title(&self) -> String {
+        let Some(title) = self.title_element() else {
+            return String::new();
+        };
+        let text: String = self
+            .arena
+            .children(title)
+            .filter_map(|node| match &self.arena.get(node).data {
+                NodeData::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        text.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Replaces the children of the title element with a single text node. Missing title and
+    /// head elements are created under the html element, without one (e.g. a fragment-like
+    /// tree) nothing happens.
+    pub fn set_title(&mut self, title: &str) {
+        let title_element = match self.title_element() {
+            Some(element) => element,
+            None => {
+                let head = match self.head() {
+                    Some(head) => head,
+                    None => {
+                        let Some(html) = self
+                            .document_element()
+                            .filter(|&node| self.arena.get(node).is_element_named("html"))
+                        else {
+                            return;
+                        };
+                        let head = self.create_element("head");
+                        let first_child = self.arena.get(html).first_child();
+                        self.arena.insert_before(html, head, first_child).ok();
+                        head
+                    }
+                };
+                let element = self.create_element("title");
+                self.arena.append_child(head, element).ok();
+                element
+            }
+        };
+        self.set_text_content(title_element, title);
+    }
+
+    /// The URL relative references resolve against: the href of the first base element with
+    /// an href attribute seen by the parser, resolved against the document URL, or the
+    /// document URL when there is no such base element. Later base elements are ignored.
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref().or(self.url())
+    }
+
+    /// Records `href` as the base of the document, unless a base element already set one
+    pub(crate) fn set_base_href(&mut self, href: &str) {
+        if self.base_href.is_none() {
+            self.base_href = Some(href.to_string());
+            self.update_base_url();
+        }
+    }
+
+    fn update_base_url(&mut self) {
+        self.base_url = self.base_href.as_ref().map(|href| match self.url() {
+            Some(url) => resolve_url(url, href).unwrap_or(href.clone()),
+            None => href.clone(),
+        });
+    }
+
+    /// The URL attribute `name` of `node` (href, src, ...) resolved against the base URL. The
+    /// value is returned as-is when there is no usable base URL, and None when the attribute
+    /// is missing.
+    pub fn resolved_url(&self, node: NodeId, name: &str) -> Option<String> {
+        let value = self.get_attribute(node, name)?;
+        let resolved = self
+            .base_url()
+            .and_then(|base| resolve_url(base, value).ok());
+        Some(resolved.unwrap_or_else(|| value.to_string()))
+    }
+
+    /// Number of nodes in the document: the document node, its descendants and the contents
+    /// of its templates
+    pub fn node_count(&self) -> usize {
+        self.reachable_nodes()
+            .into_iter()
+            .filter(|&reachable| reachable)
+            .count()
+    }
+
+    /// Number of nodes held by the arena, including removed subtrees and nodes never
+    /// inserted, until the next `compact`
+    pub fn allocated_count(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// Frees the nodes that are no longer part of the document (see `node_count`). The kept
+    /// nodes are renumbered, so every `NodeId` obtained before, including the roots of
+    /// collections and the nodes of element wrappers, is invalid afterwards and panics when
+    /// used. The returned map translates the old ids into new ones.
+    pub fn compact(&mut self) -> NodeIdMap {
+        let map = self.arena.compact(&self.reachable_nodes());
+        self.root = map
+            .get(self.root)
+            .expect("the document node is always kept");
+        self.template_contents = self
+            .template_contents
+            .iter()
+            .filter_map(|(&template, &content)| Some((map.get(template)?, map.get(content)?)))
+            .collect();
+        self.option_selectedness = self
+            .option_selectedness
+            .iter()
+            .filter_map(|(&option, &selected)| Some((map.get(option)?, selected)))
+            .collect();
+        self.control_values = std::mem::take(&mut self.control_values)
+            .into_iter()
+            .filter_map(|(control, value)| Some((map.get(control)?, value)))
+            .collect();
+        self.disabled_style_sheets = self
+            .disabled_style_sheets
+            .iter()
+            .filter_map(|&style| map.get(style))
+            .collect();
+        self.dialog_return_values = std::mem::take(&mut self.dialog_return_values)
+            .into_iter()
+            .filter_map(|(dialog, value)| Some((map.get(dialog)?, value)))
+            .collect();
+        self.event_listeners.remap(&map);
+        map
+    }
+
+    /// Which arena indices hold nodes of the document
+    fn reachable_nodes(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.arena.len()];
+        let mut pending = vec![self.root];
+        while let Some(root) = pending.pop() {
+            for node in std::iter::once(root).chain(self.arena.descendants(root)) {
+                reachable[node.index()] = true;
+                if let Some(content) = self.template_content(node) {
+                    pending.push(content);
+                }
+            }
+        }
+        reachable
+    }
+
+    pub fn create_element(&mut self, name: &str) -> NodeId {
+        let element = self.arena.new_node(NodeData::Element(Element::new(name)));
+        self.attach_template_content(element);
+        element
+    }
+
+    /// Gives `element` its contents fragment if it is an HTML template element without one
+    pub(crate) fn attach_template_content(&mut self, element: NodeId) {
+        if self.arena.get(element).is_element_named("template")
+            && !self.template_contents.contains_key(&element)
+        {
+            let content = self.create_document_fragment();
+            self.template_contents.insert(element, content);
+        }
+    }
+
+    /// The document fragment holding the contents of a template element. Template contents
+    /// are not children of the template, so they are not visited by tree traversals and
+    /// collections of the document.
+    pub fn template_content(&self, template: NodeId) -> Option<NodeId> {
+        self.template_contents.get(&template).copied()
+    }
+
+    /// Whether the option element `option` is selected: the selectedness set by the select
+    /// element APIs, or failing that the presence of its selected attribute
+    pub fn option_selectedness(&self, option: NodeId) -> bool {
+        match self.option_selectedness.get(&option) {
+            Some(&selected) => selected,
+            None => self
+                .element(option)
+                .is_some_and(|element| element.has_attribute("selected")),
+        }
+    }
+
+    pub(crate) fn set_option_selectedness(&mut self, option: NodeId, selected: bool) {
+        self.option_selectedness.insert(option, selected);
+    }
+
+    /// Makes the option follow its selected attribute again
+    pub(crate) fn clear_option_selectedness(&mut self, option: NodeId) {
+        self.option_selectedness.remove(&option);
+    }
+
+    /// The value set through the APIs of an input or textarea element, None while the
+    /// control shows its default value
+    pub(crate) fn control_value(&self, control: NodeId) -> Option<&str> {
+        self.control_values.get(&control).map(String::as_str)
+    }
+
+    pub(crate) fn set_control_value(&mut self, control: NodeId, value: String) {
+        self.control_values.insert(control, value);
+    }
+
+    /// Makes the control show its default value again
+    pub(crate) fn clear_control_value(&mut self, control: NodeId) {
+        self.control_values.remove(&control);
+    }
+
+    pub(crate) fn style_sheet_disabled(&self, style: NodeId) -> bool {
+        self.disabled_style_sheets.contains(&style)
+    }
+
+    pub(crate) fn set_style_sheet_disabled(&mut self, style: NodeId, disabled: bool) {
+        if disabled {
+            self.disabled_style_sheets.insert(style);
+        } else {
+            self.disabled_style_sheets.remove(&style);
+        }
+    }
+
+    pub(crate) fn dialog_return_value(&self, dialog: NodeId) -> Option<&str> {
+        self.dialog_return_values.get(&dialog).map(String::as_str)
+    }
+
+    pub(crate) fn set_dialog_return_value(&mut self, dialog: NodeId, value: String) {
+        self.dialog_return_values.insert(dialog, value);
+    }
+
+    /// Calls `listener` for the events of type `type_` reaching `target`: in the capture
+    /// phase when `capture` is set, otherwise at the target and in the bubble phase
+    pub fn add_event_listener(
+        &mut self,
+        target: NodeId,
+        type_: &str,
+        capture: bool,
+        listener: impl Fn(&mut Event) + Send + Sync + RefUnwindSafe + 'static,
+    ) -> EventListenerId {
+        self.event_listeners.add(target, type_, capture, listener)
+    }
+
+    pub fn remove_event_listener(&mut self, listener: EventListenerId) {
+        self.event_listeners.remove(listener);
+    }
+
+    /// Dispatches `event` to `target`: the capture listeners of its ancestors from the root
+    /// down, the listeners of the target, then for bubbling events the bubble listeners of
+    /// its ancestors back up. Returns false when the event was canceled.
+    pub fn dispatch_event(&self, target: NodeId, event: &mut Event) -> bool {
+        let path: Vec<NodeId> = std::iter::once(target)
+            .chain(self.arena.ancestors(target))
+            .collect();
+        self.event_listeners.dispatch(event, &path)
+    }
+
+    /// A new doctype node, to be inserted before the document element
+    pub fn create_doctype(&mut self, name: &str, public_id: &str, system_id: &str) -> NodeId {
+        self.arena.new_node(NodeData::Doctype {
+            name: name.to_string(),
+            public_id: public_id.to_string(),
+            system_id: system_id.to_string(),
+        })
+    }
+
+    pub fn create_text_node(&mut self, data: &str) -> NodeId {
+        self.arena.new_node(NodeData::Text(data.to_string()))
+    }
+
+    pub fn create_document_fragment(&mut self) -> NodeId {
+        self.arena.new_node(NodeData::DocumentFragment)
+    }
+
+    pub fn create_comment(&mut self, data: &str) -> NodeId {
+        self.arena.new_node(NodeData::Comment(data.to_string()))
+    }
+
+    pub fn element(&self, id: NodeId) -> Option<&Element> {
+        self.arena.get(id).as_element()
+    }
+
+    pub fn element_mut(&mut self, id: NodeId) -> Option<&mut Element> {
+        self.arena.get_mut(id).as_element_mut()
+    }
+
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) -> Result<NodeId, DomError> {
+        self.arena.append_child(parent, child)
+    }
+
+    pub fn insert_before(
+        &mut self,
+        parent: NodeId,
+        child: NodeId,
+        reference: Option<NodeId>,
+    ) -> Result<NodeId, DomError> {
+        self.arena.insert_before(parent, child, reference)
+    }
+
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> Result<NodeId, DomError> {
+        self.arena.remove_child(parent, child)
+    }
+
+    pub fn replace_child(
+        &mut self,
+        parent: NodeId,
+        new_child: NodeId,
+        old_child: NodeId,
+    ) -> Result<NodeId, DomError> {
+        self.arena.replace_child(parent, new_child, old_child)
+    }
+
+    /// Copies `node` of `source` and its descendants into this document, template contents
+    /// included. The copy is not attached anywhere yet.
+    pub fn import_node(&mut self, source: &Document, node: NodeId) -> NodeId {
+        let copy = self.arena.new_node(source.arena.get(node).data.clone());
+        self.attach_template_content(copy);
+        if let (Some(content), Some(copy_content)) =
+            (source.template_content(node), self.template_content(copy))
+        {
+            for child in source.arena.children(content) {
+                let child_copy = self.import_node(source, child);
+                self.link_child(copy_content, child_copy);
+            }
+        }
+        for child in source.arena.children(node) {
+            let child_copy = self.import_node(source, child);
+            self.link_child(copy, child_copy);
+        }
+        copy
+    }
+
+    fn link_child(&mut self, parent: NodeId, child: NodeId) {
+        self.arena
+            .append_child(parent, child)
+            .expect("a fresh copy can be appended to its copied parent");
+    }
+
+    /// Replaces the children of `node` with `html` parsed in its context, or the contents of
+    /// a template element (the innerHTML setter)
+    pub fn set_inner_html(&mut self, node: NodeId, html: &str) -> Result<(), DomError> {
+        let context = match &self.arena.get(node).data {
+            NodeData::Element(element) => element.clone(),
+            NodeData::Document => return Err(DomError::NoModificationAllowed),
+            _ => Element::new("body"),
+        };
+        let fragment = self.parse_fragment_into(html, &context);
+        let target = self.template_content(node).unwrap_or(node);
+        while let Some(child) = self.arena.get(target).first_child {
+            self.arena.detach(child);
+        }
+        self.arena.append_child(target, fragment)?;
+        Ok(())
+    }
+
+    /// Replaces `node` with `html` parsed in the context of its parent (the outerHTML
+    /// setter). Fails with `NotFound` for a node without parent and `NoModificationAllowed`
+    /// for a child of the document node.
+    pub fn set_outer_html(&mut self, node: NodeId, html: &str) -> Result<(), DomError> {
+        let parent = self.arena.parent(node).ok_or(DomError::NotFound)?;
+        let context = match &self.arena.get(parent).data {
+            NodeData::Element(element) => element.clone(),
+            NodeData::Document => return Err(DomError::NoModificationAllowed),
+            _ => Element::new("body"),
+        };
+        let fragment = self.parse_fragment_into(html, &context);
+        self.arena.replace_child(parent, fragment, node)?;
+        Ok(())
+    }
+
+    /// Parses `html` in `context` into a new document fragment of this document
+    fn parse_fragment_into(&mut self, html: &str, context: &Element) -> NodeId {
+        let parsed = parse_fragment(html, context);
+        let fragment = self.create_document_fragment();
+        let Some(parsed_root) = parsed.document_element() else {
+            return fragment;
+        };
+        for child in parsed.arena.children(parsed_root) {
+            let copy = self.import_node(&parsed, child);
+            self.link_child(fragment, copy);
+        }
+        fragment
+    }
+
+    /// Inserts `items` in order just before `node` (ChildNode.before). Nothing happens when
+    /// `node` has no parent.
+    pub fn before<I: Into<NodeOrText>>(
+        &mut self,
+        node: NodeId,
+        items: impl IntoIterator<Item = I>,
+    ) -> Result<(), DomError> {
+        let Some(parent) = self.arena.parent(node) else {
+            return Ok(());
+        };
+        let items: Vec<NodeOrText> = items.into_iter().map(Into::into).collect();
+        // the closest previous sibling that is not being moved, `node` itself may be
+        let mut previous = self.arena.get(node).prev_sibling;
+        while let Some(sibling) = previous.filter(|&sibling| contains_node(&items, sibling)) {
+            previous = self.arena.get(sibling).prev_sibling;
+        }
+        let fragment = self.fragment_of(parent, items)?;
+        let reference = match previous {
+            Some(previous) => self.arena.get(previous).next_sibling,
+            None => self.arena.get(parent).first_child,
+        };
+        self.arena.insert_before(parent, fragment, reference)?;
+        Ok(())
+    }
+
+    /// Inserts `items` in order just after `node` (ChildNode.after). Nothing happens when
+    /// `node` has no parent.
+    pub fn after<I: Into<NodeOrText>>(
+        &mut self,
+        node: NodeId,
+        items: impl IntoIterator<Item = I>,
+    ) -> Result<(), DomError> {
+        let Some(parent) = self.arena.parent(node) else {
+            return Ok(());
+        };
+        let items: Vec<NodeOrText> = items.into_iter().map(Into::into).collect();
+        let next = self.next_sibling_not_in(node, &items);
+        let fragment = self.fragment_of(parent, items)?;
+        self.arena.insert_before(parent, fragment, next)?;
+        Ok(())
+    }
+
+    /// Replaces `node` with `items` (ChildNode.replaceWith). Nothing happens when `node` has
+    /// no parent.
+    pub fn replace_with<I: Into<NodeOrText>>(
+        &mut self,
+        node: NodeId,
+        items: impl IntoIterator<Item = I>,
+    ) -> Result<(), DomError> {
+        let Some(parent) = self.arena.parent(node) else {
+            return Ok(());
+        };
+        let items: Vec<NodeOrText> = items.into_iter().map(Into::into).collect();
+        let next = self.next_sibling_not_in(node, &items);
+        let fragment = self.fragment_of(parent, items)?;
+        if self.arena.parent(node) == Some(parent) {
+            self.arena.replace_child(parent, fragment, node)?;
+        } else {
+            // `node` was one of the items and has moved into the fragment
+            self.arena.insert_before(parent, fragment, next)?;
+        }
+        Ok(())
+    }
+
+    /// Detaches `node` from its parent (ChildNode.remove)
+    pub fn remove(&mut self, node: NodeId) {
+        self.arena.detach(node);
+    }
+
+    /// Splits the text node `node` at the byte `offset`, which must be on a character
+    /// boundary: `node` keeps the text before it and a new text node holding the rest is
+    /// inserted as its next sibling, when `node` has a parent. Returns the new node.
+    pub fn split_text(&mut self, node: NodeId, offset: usize) -> Result<NodeId, DomError> {
+        let NodeData::Text(text) = &mut self.arena.get_mut(node).data else {
+            return Err(DomError::InvalidNodeType);
+        };
+        if !text.is_char_boundary(offset) {
+            return Err(DomError::IndexSize);
+        }
+        let rest = text.split_off(offset);
+        let new_node = self.create_text_node(&rest);
+        if let Some(parent) = self.arena.parent(node) {
+            let next = self.arena.get(node).next_sibling;
+            self.arena.insert_before(parent, new_node, next)?;
+        }
+        Ok(new_node)
+    }
+
+    /// The data of `node` and of the text nodes contiguous to it, in tree order. `None` when
+    /// `node` is not a text node.
+    pub fn whole_text(&self, node: NodeId) -> Option<String> {
+        self.arena.get(node).as_text()?;
+        let mut first = node;
+        while let Some(previous) = self
+            .arena
+            .get(first)
+            .prev_sibling
+            .filter(|&previous| self.arena.get(previous).as_text().is_some())
+        {
+            first = previous;
+        }
+        let mut whole_text = String::new();
+        let mut current = Some(first);
+        while let Some(text_node) = current {
+            let Some(text) = self.arena.get(text_node).as_text() else {
+                break;
+            };
+            whole_text.push_str(text);
+            current = self.arena.get(text_node).next_sibling;
+        }
+        Some(whole_text)
+    }
+
+    /// The text of the text node descendants of `node` in tree order (Node.textContent for
+    /// elements and fragments)
+    pub fn text_content(&self, node: NodeId) -> String {
+        self.arena
+            .descendants(node)
+            .filter_map(|descendant| self.arena.get(descendant).as_text())
+            .collect()
+    }
+
+    /// Replaces the children of `node` with a single text node holding `text`, or with
+    /// nothing when `text` is empty
+    pub fn set_text_content(&mut self, node: NodeId, text: &str) {
+        while let Some(child) = self.arena.get(node).first_child() {
+            self.arena.detach(child);
+        }
+        if !text.is_empty() {
+            let text = self.create_text_node(text);
+            self.arena.append_child(node, text).ok();
+        }
+    }
+
+    /// Removes the empty text nodes under `node` and merges runs of contiguous text nodes
+    /// into the first of each run (Node.normalize)
+    pub fn normalize(&mut self, node: NodeId) {
+        let text_nodes: Vec<NodeId> = self
+            .arena
+            .descendants(node)
+            .filter(|&descendant| self.arena.get(descendant).as_text().is_some())
+            .collect();
+        for text_node in text_nodes {
+            // already merged into a previous sibling
+            if self.arena.parent(text_node).is_none() {
+                continue;
+            }
+            let mut merged = String::new();
+            while let Some(next) = self
+                .arena
+                .get(text_node)
+                .next_sibling
+                .filter(|&next| self.arena.get(next).as_text().is_some())
+            {
+                merged.push_str(self.arena.get(next).as_text().unwrap_or_default());
+                self.arena.detach(next);
+            }
+            let NodeData::Text(text) = &mut self.arena.get_mut(text_node).data else {
+                continue;
+            };
+            text.push_str(&merged);
+            if text.is_empty() {
+                self.arena.detach(text_node);
+            }
+        }
+    }
+
+    /// Inserts `items` in order after the last child of `parent` (ParentNode.append). Nodes
+    /// already in a tree are moved.
+    pub fn append<I: Into<NodeOrText>>(
+        &mut self,
+        parent: NodeId,
+        items: impl IntoIterator<Item = I>,
+    ) -> Result<(), DomError> {
+        let items = items.into_iter().map(Into::into).collect();
+        let fragment = self.fragment_of(parent, items)?;
+        self.arena.append_child(parent, fragment)?;
+        Ok(())
+    }
+
+    /// Inserts `items` in order before the first child of `parent` (ParentNode.prepend)
+    pub fn prepend<I: Into<NodeOrText>>(
+        &mut self,
+        parent: NodeId,
+        items: impl IntoIterator<Item = I>,
+    ) -> Result<(), DomError> {
+        let items = items.into_iter().map(Into::into).collect();
+        let fragment = self.fragment_of(parent, items)?;
+        let first_child = self.arena.get(parent).first_child;
+        self.arena.insert_before(parent, fragment, first_child)?;
+        Ok(())
+    }
+
+    /// Inserts a text node at `position` relative to `element`. Nothing happens when the
+    /// position is outside `element` and it has no parent.
+    pub fn insert_adjacent_text(
+        &mut self,
+        element: NodeId,
+        position: AdjacentPosition,
+        text: &str,
+    ) -> Result<(), DomError> {
+        let text = [NodeOrText::Text(text.to_string())];
+        match position {
+            AdjacentPosition::BeforeBegin => self.before(element, text),
+            AdjacentPosition::AfterBegin => self.prepend(element, text),
+            AdjacentPosition::BeforeEnd => self.append(element, text),
+            AdjacentPosition::AfterEnd => self.after(element, text),
+        }
+    }
+
+    /// Inserts `node` at `position` relative to `element` and returns it, or None when the
+    /// position is outside `element` and it has no parent
+    pub fn insert_adjacent_element(
+        &mut self,
+        element: NodeId,
+        position: AdjacentPosition,
+        node: NodeId,
+    ) -> Result<Option<NodeId>, DomError> {
+        let Some((parent, reference)) = self.adjacent_location(element, position) else {
+            return Ok(None);
+        };
+        self.arena.insert_before(parent, node, reference)?;
+        Ok(Some(node))
+    }
+
+    /// Parses `html` and inserts the resulting nodes at `position` relative to `element`. The
+    /// markup is parsed in the context of `element` for the inner positions and of its parent
+    /// for the outer ones, with body standing in for an html element or a fragment. Fails
+    /// with `NoModificationAllowed` for an outer position when the parent is missing or is
+    /// the document node.
+    pub fn insert_adjacent_html(
+        &mut self,
+        element: NodeId,
+        position: AdjacentPosition,
+        html: &str,
+    ) -> Result<(), DomError> {
+        let context_node = match position {
+            AdjacentPosition::BeforeBegin | AdjacentPosition::AfterEnd => self
+                .arena
+                .parent(element)
+                .ok_or(DomError::NoModificationAllowed)?,
+            AdjacentPosition::AfterBegin | AdjacentPosition::BeforeEnd => element,
+        };
+        let context = match &self.arena.get(context_node).data {
+            NodeData::Element(context) if !context.is_html_named("html") => context.clone(),
+            NodeData::Document => return Err(DomError::NoModificationAllowed),
+            _ => Element::new("body"),
+        };
+        let fragment = self.parse_fragment_into(html, &context);
+        let (parent, reference) = self
+            .adjacent_location(element, position)
+            .ok_or(DomError::NoModificationAllowed)?;
+        self.arena.insert_before(parent, fragment, reference)?;
+        Ok(())
+    }
+
+    /// The parent and the reference child `position` designates next to `element`
+    fn adjacent_location(
+        &self,
+        element: NodeId,
+        position: AdjacentPosition,
+    ) -> Option<(NodeId, Option<NodeId>)> {
+        let node = self.arena.get(element);
+        match position {
+            AdjacentPosition::BeforeBegin => Some((node.parent?, Some(element))),
+            AdjacentPosition::AfterBegin => Some((element, node.first_child)),
+            AdjacentPosition::BeforeEnd => Some((element, None)),
+            AdjacentPosition::AfterEnd => Some((node.parent?, node.next_sibling)),
+        }
+    }
+
+    fn next_sibling_not_in(&self, node: NodeId, items: &[NodeOrText]) -> Option<NodeId> {
+        let mut next = self.arena.get(node).next_sibling;
+        while let Some(sibling) = next.filter(|&sibling| contains_node(items, sibling)) {
+            next = self.arena.get(sibling).next_sibling;
+        }
+        next
+    }
+
+    /// A new fragment holding `items` in order, strings as new text nodes. Every node is
+    /// checked before anything moves, so a node that cannot go into `parent` (an inclusive
+    /// ancestor of it, or a document) fails the whole insertion without side effects.
+    fn fragment_of(&mut self, parent: NodeId, items: Vec<NodeOrText>) -> Result<NodeId, DomError> {
+        for item in &items {
+            if let NodeOrText::Node(node) = *item {
+                if self.arena.is_inclusive_descendant(parent, node)
+                    || matches!(self.arena.get(node).data, NodeData::Document)
+                {
+                    return Err(DomError::HierarchyRequest);
+                }
+            }
+        }
+        let fragment = self.create_document_fragment();
+        for item in items {
+            let node = match item {
+                NodeOrText::Node(node) => node,
+                NodeOrText::Text(text) => self.create_text_node(&text),
+            };
+            self.arena.append_child(fragment, node)?;
+        }
+        Ok(fragment)
+    }
+
+    /// Live list of the children of `node`
+    pub fn child_nodes(&self, node: NodeId) -> NodeList {
+        NodeList::child_nodes(node)
+    }
+
+    pub fn get_attribute(&self, id: NodeId, name: &str) -> Option<&str> {
+        self.element(id).and_then(|e| e.get_attribute(name))
+    }
+
+    /// Sets an attribute on the element `id`. The name is lowercased on HTML elements before
+    /// the mutation record is queued, so observers see the name that was actually changed.
+    pub fn set_attribute(&mut self, id: NodeId, name: &str, value: &str) {
+        let Some(element) = self.arena.get(id).as_element() else {
+            return;
+        };
+        let name = attribute_name_for(element, name);
+        let old_value = element.get_attribute(&name).map(str::to_string);
+        self.arena
+            .queue_attribute_mutation(id, &name, old_value.as_deref());
+        if name == "form" {
+            self.arena.clear_parser_form_owner(id);
+        }
+        if let Some(element) = self.element_mut(id) {
+            element.set_attribute(&name, value);
+        }
+    }
+
+    /// Removes an attribute from the element `id`, the name is lowercased on HTML elements
+    pub fn remove_attribute(&mut self, id: NodeId, name: &str) {
+        let Some(element) = self.arena.get(id).as_element() else {
+            return;
+        };
+        let name = attribute_name_for(element, name);
+        let Some(old_value) = element.get_attribute(&name).map(str::to_string) else {
+            return;
+        };
+        self.arena
+            .queue_attribute_mutation(id, &name, Some(&old_value));
+        if name == "form" {
+            self.arena.clear_parser_form_owner(id);
+        }
+        if let Some(element) = self.element_mut(id) {
+            element.remove_attribute(&name);
+        }
+    }
+
+    /// First element in tree order whose id attribute equals `id`
+    pub fn get_element_by_id(&self, id: &str) -> Option<NodeId> {
+        if id.is_empty() {
+            return None;
+        }
+        self.arena
+            .descendants(self.root)
+            .find(|&node| self.get_attribute(node, "id") == Some(id))
+    }
+
+    /// Live collection of the elements with the given qualified name, `"*"` matches every
+    /// element. The name is case-insensitive for HTML elements only.
+    pub fn get_elements_by_tag_name(&self, name: &str) -> HTMLCollection {
+        HTMLCollection::new(self.root, CollectionFilter::TagName(name.to_string()))
+    }
+
+    /// Live collection of the form elements
+    pub fn forms(&self) -> HTMLCollection {
+        self.html_elements_named("form")
+    }
+
+    /// Live collection of the img elements
+    pub fn images(&self) -> HTMLCollection {
+        self.html_elements_named("img")
+    }
+
+    /// Live collection of the a and area elements that have an href attribute
+    pub fn links(&self) -> HTMLCollection {
+        HTMLCollection::new(self.root, CollectionFilter::Links)
+    }
+
+    /// Live collection of the link elements that load a stylesheet, alternate ones
+    /// included, in tree order
+    pub fn stylesheet_links(&self) -> HTMLCollection {
+        HTMLCollection::new(self.root, CollectionFilter::StylesheetLinks)
+    }
+
+    /// Live collection of the script elements
+    pub fn scripts(&self) -> HTMLCollection {
+        self.html_elements_named("script")
+    }
+
+    /// Live collection of the embed elements
+    pub fn embeds(&self) -> HTMLCollection {
+        self.html_elements_named("embed")
+    }
+
+    fn html_elements_named(&self, name: &str) -> HTMLCollection {
+        HTMLCollection::new(self.root, CollectionFilter::HtmlLocalName(name.to_string()))
+    }
+
+    /// Token list over the class attribute of `element` (Element.classList)
+    pub fn class_list(&self, element: NodeId) -> DOMTokenList {
+        DOMTokenList::new(element, "class")
+    }
+
+    /// Live collection of the elements having all the given whitespace separated classes
+    pub fn get_elements_by_class_name(&self, class_names: &str) -> HTMLCollection {
+        HTMLCollection::new(
+            self.root,
+            CollectionFilter::ClassNames(
+                class_names
+                    .split_ascii_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+            ),
+        )
+    }
+
+    /// Live collection of the HTML elements whose name attribute is exactly `name`, whatever
+    /// the element (form controls, img, iframe, meta, ...)
+    pub fn get_elements_by_name(&self, name: &str) -> HTMLCollection {
+        HTMLCollection::new(self.root, CollectionFilter::Name(name.to_string()))
+    }
+
+    /// The encoding label declared by the first meta element of the head that declares one,
+    /// see `HTMLMetaElement::declared_charset`. Meta elements inside a noscript are skipped:
+    /// this parser runs with scripting disabled, but for a browser with scripting enabled
+    /// they are plain text.
+    pub fn meta_charset(&self) -> Option<String> {
+        let head = self.head()?;
+        let mut pending: Vec<NodeId> = self.arena.children(head).collect();
+        pending.reverse();
+        while let Some(node) = pending.pop() {
+            let Some(element) = self.element(node) else {
+                continue;
+            };
+            if element.is_html_named("meta") {
+                if let Some(charset) = HTMLMetaElement::new(node).declared_charset(self) {
+                    return Some(charset);
+                }
+            } else if !element.is_html_named("noscript") {
+                pending.extend(
+                    self.arena
+                        .children(node)
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                        .rev(),
+                );
+            }
+        }
+        None
+    }
+
+    /// Table of contents of the document: its h1 to h6 elements in tree order, each nested
+    /// under the closest preceding heading of higher rank. Template contents are left out.
+    pub fn generate_outline(&self) -> Vec<OutlineEntry> {
+        let mut outline = Vec::new();
+        // the open entries, each a child of the previous one
+        let mut open: Vec<OutlineEntry> = Vec::new();
+        let headings = self
+            .arena
+            .descendants(self.root)
+            .filter_map(|id| HTMLHeadingElement::new(self, id));
+        for heading in headings {
+            while open
+                .last()
+                .is_some_and(|entry| entry.level >= heading.level())
+            {
+                close_outline_entry(&mut open, &mut outline);
+            }
+            open.push(OutlineEntry {
+                level: heading.level(),
+                text: heading.text(self),
+                id: self
+                    .get_attribute(heading.node(), "id")
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string),
+                children: Vec::new(),
+            });
+        }
+        while !open.is_empty() {
+            close_outline_entry(&mut open, &mut outline);
+        }
+        outline
+    }
+}
+
+/// Moves the innermost open entry to its parent, or to the outline when it has none
+fn close_outline_entry(open: &mut Vec<OutlineEntry>, outline: &mut Vec<OutlineEntry>) {
+    if let Some(entry) = open.pop() {
+        match open.last_mut() {
+            Some(parent) => parent.children.push(entry),
+            None => outline.push(entry),
+        }
+    }
+}
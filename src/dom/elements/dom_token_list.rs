@@ -0,0 +1,169 @@
+// src/dom/elements/dom_token_list.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+/// The set of space separated tokens held by an attribute of an element, like classList.
+///
+/// The list is live: it does not store the tokens, every access reads the attribute again,
+/// and every change writes the whole set back to it. Tokens are kept in the order they
+/// first appear, without duplicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DOMTokenList {
+    element: NodeId,
+    attribute: String,
+}
+
+impl DOMTokenList {
+    /// Token list over the attribute `attribute` of `element`
+    pub fn new(element: NodeId, attribute: &str) -> Self {
+        DOMTokenList {
+            element,
+            attribute: attribute.to_string(),
+        }
+    }
+
+    /// The element whose attribute holds the tokens
+    pub fn element(&self) -> NodeId {
+        self.element
+    }
+
+    /// The tokens in order, duplicates removed
+    pub fn tokens(&self, document: &Document) -> Vec<String> {
+        let mut tokens: Vec<String> = Vec::new();
+        for token in self.value(document).split_ascii_whitespace() {
+            if !tokens.iter().any(|seen| seen == token) {
+                tokens.push(token.to_string());
+            }
+        }
+        tokens
+    }
+
+    pub fn len(&self, document: &Document) -> usize {
+        self.tokens(document).len()
+    }
+
+    pub fn is_empty(&self, document: &Document) -> bool {
+        self.tokens(document).is_empty()
+    }
+
+    pub fn item(&self, document: &Document, index: usize) -> Option<String> {
+        self.tokens(document).into_iter().nth(index)
+    }
+
+    pub fn contains(&self, document: &Document, token: &str) -> bool {
+        self.tokens(document).iter().any(|t| t == token)
+    }
+
+    /// Adds the tokens missing from the set at its end. Fails without changing anything when
+    /// a token is empty or holds whitespace.
+    pub fn add(&self, document: &mut Document, tokens: &[&str]) -> Result<(), DomError> {
+        tokens.iter().try_for_each(|token| validate_token(token))?;
+        let mut set = self.tokens(document);
+        for token in tokens {
+            if !set.iter().any(|t| t == token) {
+                set.push(token.to_string());
+            }
+        }
+        self.update(document, &set);
+        Ok(())
+    }
+
+    /// Removes the tokens from the set. Fails without changing anything when a token is empty
+    /// or holds whitespace.
+    pub fn remove(&self, document: &mut Document, tokens: &[&str]) -> Result<(), DomError> {
+        tokens.iter().try_for_each(|token| validate_token(token))?;
+        let mut set = self.tokens(document);
+        set.retain(|t| !tokens.contains(&t.as_str()));
+        self.update(document, &set);
+        Ok(())
+    }
+
+    /// Removes `token` when present and adds it otherwise, or only adds or only removes it
+    /// when `force` is given. Returns whether the token is in the set afterwards.
+    pub fn toggle(
+        &self,
+        document: &mut Document,
+        token: &str,
+        force: Option<bool>,
+    ) -> Result<bool, DomError> {
+        validate_token(token)?;
+        let mut set = self.tokens(document);
+        let present = set.iter().any(|t| t == token);
+        match (present, force) {
+            (true, Some(true)) => Ok(true),
+            (true, _) => {
+                set.retain(|t| t != token);
+                self.update(document, &set);
+                Ok(false)
+            }
+            (false, Some(false)) => Ok(false),
+            (false, _) => {
+                set.push(token.to_string());
+                self.update(document, &set);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Puts `new_token` in place of `token`, dropping later duplicates of it. Returns false,
+    /// leaving the set untouched, when `token` is not in it.
+    pub fn replace(
+        &self,
+        document: &mut Document,
+        token: &str,
+        new_token: &str,
+    ) -> Result<bool, DomError> {
+        validate_token(token)?;
+        validate_token(new_token)?;
+        let set = self.tokens(document);
+        if !set.iter().any(|t| t == token) {
+            return Ok(false);
+        }
+        let mut replaced: Vec<String> = Vec::with_capacity(set.len());
+        for t in set {
+            let t = if t == token { new_token.to_string() } else { t };
+            if !replaced.contains(&t) {
+                replaced.push(t);
+            }
+        }
+        self.update(document, &replaced);
+        Ok(true)
+    }
+
+    /// The attribute value as it is, empty when the attribute is missing
+    pub fn value(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.element, &self.attribute)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.element, &self.attribute, value);
+    }
+
+    /// The update steps: the set is written back space separated, except that an empty set
+    /// does not create a missing attribute
+    fn update(&self, document: &mut Document, set: &[String]) {
+        if set.is_empty()
+            && document
+                .get_attribute(self.element, &self.attribute)
+                .is_none()
+        {
+            return;
+        }
+        document.set_attribute(self.element, &self.attribute, &set.join(" "));
+    }
+}
+
+fn validate_token(token: &str) -> Result<(), DomError> {
+    if token.is_empty() {
+        Err(DomError::Syntax)
+    } else if token.contains(|c: char| c.is_ascii_whitespace()) {
+        Err(DomError::InvalidCharacter)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+// src/dom/elements/element_factory.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_anchor_element::HTMLAnchorElement;
+use crate::dom::elements::html_audio_element::HTMLAudioElement;
+use crate::dom::elements::html_button_element::HTMLButtonElement;
+use crate::dom::elements::html_canvas_element::HTMLCanvasElement;
+use crate::dom::elements::html_data_list_element::HTMLDataListElement;
+use crate::dom::elements::html_details_element::HTMLDetailsElement;
+use crate::dom::elements::html_dialog_element::HTMLDialogElement;
+use crate::dom::elements::html_field_set_element::HTMLFieldSetElement;
+use crate::dom::elements::html_form_element::HTMLFormElement;
+use crate::dom::elements::html_heading_element::HTMLHeadingElement;
+use crate::dom::elements::html_iframe_element::HTMLIFrameElement;
+use crate::dom::elements::html_image_element::HTMLImageElement;
+use crate::dom::elements::html_input_element::HTMLInputElement;
+use crate::dom::elements::html_label_element::HTMLLabelElement;
+use crate::dom::elements::html_li_element::HTMLLIElement;
+use crate::dom::elements::html_link_element::HTMLLinkElement;
+use crate::dom::elements::html_meta_element::HTMLMetaElement;
+use crate::dom::elements::html_meter_element::HTMLMeterElement;
+use crate::dom::elements::html_o_list_element::HTMLOListElement;
+use crate::dom::elements::html_opt_group_element::HTMLOptGroupElement;
+use crate::dom::elements::html_option_element::HTMLOptionElement;
+use crate::dom::elements::html_output_element::HTMLOutputElement;
+use crate::dom::elements::html_progress_element::HTMLProgressElement;
+use crate::dom::elements::html_script_element::HTMLScriptElement;
+use crate::dom::elements::html_select_element::HTMLSelectElement;
+use crate::dom::elements::html_style_element::HTMLStyleElement;
+use crate::dom::elements::html_table_cell_element::HTMLTableCellElement;
+use crate::dom::elements::html_table_element::HTMLTableElement;
+use crate::dom::elements::html_table_row_element::HTMLTableRowElement;
+use crate::dom::elements::html_template_element::HTMLTemplateElement;
+use crate::dom::elements::html_text_area_element::HTMLTextAreaElement;
+use crate::dom::elements::html_time_element::HTMLTimeElement;
+use crate::dom::elements::html_video_element::HTMLVideoElement;
+use crate::dom::node::NodeId;
+
+/// The interface of an element, decided from its name when the element is created. Elements
+/// with a typed wrapper in `dom::elements` get their own kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ElementKind {
+    Anchor,
+    Audio,
+    Button,
+    Canvas,
+    DataList,
+    Details,
+    Dialog,
+    FieldSet,
+    Form,
+    Heading,
+    IFrame,
+    Image,
+    Input,
+    Label,
+    LI,
+    Link,
+    Meta,
+    Meter,
+    OList,
+    OptGroup,
+    Option,
+    Output,
+    Progress,
+    Script,
+    Select,
+    Style,
+    Table,
+    TableCell,
+    TableRow,
+    Template,
+    TextArea,
+    Time,
+    Video,
+    /// An element of the HTML standard without a typed wrapper here, e.g. div or span
+    Generic,
+    /// HTMLUnknownElement: a name the HTML standard does not define, e.g. blink
+    #[default]
+    Unknown,
+    /// A valid custom element name, e.g. x-foo
+    Custom,
+    /// An SVG or MathML element
+    Foreign,
+}
+
+/// HTML elements of the standard, current and obsolete, that have no typed wrapper. Obsolete
+/// elements specified as HTMLUnknownElement (applet, blink, ...) are left out.
+const GENERIC_ELEMENTS: &[&str] = &[
+    "abbr",
+    "acronym",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "b",
+    "base",
+    "basefont",
+    "bdi",
+    "bdo",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "caption",
+    "center",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "dd",
+    "del",
+    "dfn",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "figcaption",
+    "figure",
+    "font",
+    "footer",
+    "frame",
+    "frameset",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "ins",
+    "kbd",
+    "legend",
+    "listing",
+    "main",
+    "map",
+    "mark",
+    "marquee",
+    "menu",
+    "nav",
+    "nobr",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "p",
+    "param",
+    "picture",
+    "plaintext",
+    "pre",
+    "q",
+    "rb",
+    "rp",
+    "rt",
+    "rtc",
+    "ruby",
+    "s",
+    "samp",
+    "search",
+    "section",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "tbody",
+    "tfoot",
+    "thead",
+    "title",
+    "track",
+    "tt",
+    "u",
+    "ul",
+    "var",
+    "wbr",
+    "xmp",
+];
+
+/// Names containing a hyphen that are reserved by SVG and MathML, so no custom element names
+const RESERVED_HYPHENATED_NAMES: &[&str] = &[
+    "annotation-xml",
+    "color-profile",
+    "font-face",
+    "font-face-src",
+    "font-face-uri",
+    "font-face-format",
+    "font-face-name",
+    "missing-glyph",
+];
+
+/// The kind of the HTML element with the lowercase local name `name`
+pub fn html_element_for(name: &str) -> ElementKind {
+    match name {
+        "a" => ElementKind::Anchor,
+        "audio" => ElementKind::Audio,
+        "button" => ElementKind::Button,
+        "canvas" => ElementKind::Canvas,
+        "datalist" => ElementKind::DataList,
+        "details" => ElementKind::Details,
+        "dialog" => ElementKind::Dialog,
+        "fieldset" => ElementKind::FieldSet,
+        "form" => ElementKind::Form,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => ElementKind::Heading,
+        "iframe" => ElementKind::IFrame,
+        "img" => ElementKind::Image,
+        "input" => ElementKind::Input,
+        "label" => ElementKind::Label,
+        "li" => ElementKind::LI,
+        "link" => ElementKind::Link,
+        "meta" => ElementKind::Meta,
+        "meter" => ElementKind::Meter,
+        "ol" => ElementKind::OList,
+        "optgroup" => ElementKind::OptGroup,
+        "option" => ElementKind::Option,
+        "output" => ElementKind::Output,
+        "progress" => ElementKind::Progress,
+        "script" => ElementKind::Script,
+        "select" => ElementKind::Select,
+        "style" => ElementKind::Style,
+        "table" => ElementKind::Table,
+        "td" | "th" => ElementKind::TableCell,
+        "tr" => ElementKind::TableRow,
+        "template" => ElementKind::Template,
+        "textarea" => ElementKind::TextArea,
+        "time" => ElementKind::Time,
+        "video" => ElementKind::Video,
+        _ if GENERIC_ELEMENTS.contains(&name) => ElementKind::Generic,
+        _ if is_valid_custom_element_name(name) => ElementKind::Custom,
+        _ => ElementKind::Unknown,
+    }
+}
+
+/// Whether `name` can name a custom element: a lowercase ASCII letter first, a hyphen
+/// somewhere, no uppercase ASCII letters, and not one of the reserved names
+pub fn is_valid_custom_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.contains('-')
+        && !name.contains(|c: char| c.is_ascii_uppercase())
+        && !RESERVED_HYPHENATED_NAMES.contains(&name)
+}
+
+impl NodeId {
+    /// This node as an `HTMLHeadingElement`, None when it is not one
+    pub fn as_heading(&self, document: &Document) -> Option<HTMLHeadingElement> {
+        HTMLHeadingElement::new(document, *self)
+    }
+
+    /// This node as an `HTMLTemplateElement`, None when it is not one
+    pub fn as_template(&self, document: &Document) -> Option<HTMLTemplateElement> {
+        HTMLTemplateElement::new(document, *self)
+    }
+}
+
+/// Writes a downcast on NodeId returning the typed wrapper when the node is an element of
+/// the given kind
+macro_rules! downcast {
+    ($($method:ident => $kind:ident, $wrapper:ident;)+) => {
+        impl NodeId {
+            /// The kind of the element this node is, None for other nodes
+            pub fn element_kind(&self, document: &Document) -> Option<ElementKind> {
+                document.element(*self).map(|element| element.kind())
+            }
+
+            $(
+                #[doc = concat!("This node as an `", stringify!($wrapper), "`, None when it is not one")]
+                pub fn $method(&self, document: &Document) -> Option<$wrapper> {
+                    (self.element_kind(document) == Some(ElementKind::$kind))
+                        .then(|| $wrapper::new(*self))
+                }
+            )+
+        }
+    };
+}
+
+downcast! {
+    as_anchor => Anchor, HTMLAnchorElement;
+    as_audio => Audio, HTMLAudioElement;
+    as_button => Button, HTMLButtonElement;
+    as_canvas => Canvas, HTMLCanvasElement;
+    as_data_list => DataList, HTMLDataListElement;
+    as_details => Details, HTMLDetailsElement;
+    as_dialog => Dialog, HTMLDialogElement;
+    as_field_set => FieldSet, HTMLFieldSetElement;
+    as_form => Form, HTMLFormElement;
+    as_iframe => IFrame, HTMLIFrameElement;
+    as_image => Image, HTMLImageElement;
+    as_input => Input, HTMLInputElement;
+    as_label => Label, HTMLLabelElement;
+    as_li => LI, HTMLLIElement;
+    as_link => Link, HTMLLinkElement;
+    as_meta => Meta, HTMLMetaElement;
+    as_meter => Meter, HTMLMeterElement;
+    as_o_list => OList, HTMLOListElement;
+    as_opt_group => OptGroup, HTMLOptGroupElement;
+    as_option => Option, HTMLOptionElement;
+    as_output => Output, HTMLOutputElement;
+    as_progress => Progress, HTMLProgressElement;
+    as_script => Script, HTMLScriptElement;
+    as_select => Select, HTMLSelectElement;
+    as_style => Style, HTMLStyleElement;
+    as_table => Table, HTMLTableElement;
+    as_table_cell => TableCell, HTMLTableCellElement;
+    as_table_row => TableRow, HTMLTableRowElement;
+    as_text_area => TextArea, HTMLTextAreaElement;
+    as_time => Time, HTMLTimeElement;
+    as_video => Video, HTMLVideoElement;
+}
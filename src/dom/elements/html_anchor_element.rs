@@ -0,0 +1,129 @@
+// src/dom/elements/html_anchor_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::node::NodeId;
+use crate::dom::url::UrlComponents;
+
+/// An a element. The URL decomposition getters read the resolved href: each returns the
+/// empty string when the component is missing or when the href does not resolve to an
+/// absolute URL, except `protocol` which is then ":".
+pub struct HTMLAnchorElement {
+    node: NodeId,
+}
+
+impl HTMLAnchorElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLAnchorElement { node }
+    }
+
+    /// The anchor element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The href attribute resolved against the document base URL, see
+    /// `Document::resolved_url`
+    pub fn href(&self, document: &Document) -> Option<String> {
+        document.resolved_url(self.node, "href")
+    }
+
+    pub fn set_href(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "href", value);
+    }
+
+    /// The scheme followed by a colon, e.g. "https:"
+    pub fn protocol(&self, document: &Document) -> String {
+        let scheme = self.url(document).map(|url| url.scheme).unwrap_or_default();
+        format!("{scheme}:")
+    }
+
+    /// The hostname and the port when it is not the default one, e.g. "example.com:8080"
+    pub fn host(&self, document: &Document) -> String {
+        self.url(document).map(|url| url.host()).unwrap_or_default()
+    }
+
+    pub fn hostname(&self, document: &Document) -> String {
+        self.component(document, |url| url.hostname)
+    }
+
+    /// The port, empty when it is the default port of the scheme
+    pub fn port(&self, document: &Document) -> String {
+        self.component(document, |url| url.port)
+    }
+
+    pub fn pathname(&self, document: &Document) -> String {
+        self.component(document, |url| url.path)
+    }
+
+    /// The query with its leading "?", empty when the query is missing or empty
+    pub fn search(&self, document: &Document) -> String {
+        self.component(document, |url| prefixed('?', url.query))
+    }
+
+    /// The fragment with its leading "#", empty when the fragment is missing or empty
+    pub fn hash(&self, document: &Document) -> String {
+        self.component(document, |url| prefixed('#', url.fragment))
+    }
+
+    /// The serialized origin, "null" for schemes without a tuple origin like mailto:
+    pub fn origin(&self, document: &Document) -> String {
+        self.url(document)
+            .map(|url| url.origin())
+            .unwrap_or_default()
+    }
+
+    /// Reflects the rel attribute
+    pub fn rel(&self, document: &Document) -> String {
+        self.attribute(document, "rel")
+    }
+
+    pub fn set_rel(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "rel", value);
+    }
+
+    /// Token list over the rel attribute
+    pub fn rel_list(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "rel")
+    }
+
+    /// Reflects the target attribute
+    pub fn target(&self, document: &Document) -> String {
+        self.attribute(document, "target")
+    }
+
+    pub fn set_target(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "target", value);
+    }
+
+    /// The text of the descendants of the anchor
+    pub fn text(&self, document: &Document) -> String {
+        document.text_content(self.node)
+    }
+
+    pub fn set_text(&self, document: &mut Document, value: &str) {
+        document.set_text_content(self.node, value);
+    }
+
+    fn url(&self, document: &Document) -> Option<UrlComponents> {
+        UrlComponents::parse(&self.href(document)?).ok()
+    }
+
+    fn component(&self, document: &Document, get: impl FnOnce(UrlComponents) -> String) -> String {
+        self.url(document).map(get).unwrap_or_default()
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+fn prefixed(prefix: char, component: Option<String>) -> String {
+    match component {
+        Some(component) if !component.is_empty() => format!("{prefix}{component}"),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,26 @@
+// src/dom/elements/html_audio_element.rs
+
+use crate::dom::elements::html_media_element::HTMLMediaElement;
+use crate::dom::node::NodeId;
+use std::ops::Deref;
+
+/// An audio element, all of its API is the one of `HTMLMediaElement`
+pub struct HTMLAudioElement {
+    media: HTMLMediaElement,
+}
+
+impl HTMLAudioElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLAudioElement {
+            media: HTMLMediaElement::new(node),
+        }
+    }
+}
+
+impl Deref for HTMLAudioElement {
+    type Target = HTMLMediaElement;
+
+    fn deref(&self) -> &HTMLMediaElement {
+        &self.media
+    }
+}
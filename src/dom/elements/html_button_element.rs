@@ -0,0 +1,61 @@
+// src/dom/elements/html_button_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_form_element::{
+    form_owner, is_disabled_form_control, HTMLFormElement,
+};
+use crate::dom::elements::reflect::{reflect_bool, reflect_enum, reflect_string};
+use crate::dom::node::NodeId;
+
+pub struct HTMLButtonElement {
+    node: NodeId,
+}
+
+impl HTMLButtonElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLButtonElement { node }
+    }
+
+    /// The button element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The form owner of the button, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
+    }
+
+    reflect_enum!(
+        /// The type attribute as one of "submit", "reset" and "button", "submit" when it is
+        /// missing or invalid
+        r#type, set_type, "type", ["submit", "reset", "button"], "submit"
+    );
+
+    reflect_bool!(
+        /// Reflects the disabled attribute, see `is_disabled` for the state inherited from
+        /// fieldsets
+        disabled, set_disabled, "disabled"
+    );
+
+    /// Whether the button is disabled, by its own attribute or a disabled fieldset ancestor
+    pub fn is_disabled(&self, document: &Document) -> bool {
+        is_disabled_form_control(document, self.node)
+    }
+
+    reflect_string!(
+        /// Reflects the value attribute
+        value, set_value, "value"
+    );
+
+    reflect_string!(
+        /// Reflects the name attribute
+        name, set_name, "name"
+    );
+
+    /// Whether the button is a candidate for constraint validation: only enabled submit
+    /// buttons are
+    pub fn will_validate(&self, document: &Document) -> bool {
+        self.r#type(document) == "submit" && !self.is_disabled(document)
+    }
+}
@@ -0,0 +1,79 @@
+// src/dom/elements/html_canvas_element.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::elements::reflect::reflect_u32;
+use crate::dom::node::NodeId;
+
+/// The context types `get_context` knows the names of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextKind {
+    /// `2d`
+    TwoD,
+    /// `bitmaprenderer`
+    BitmapRenderer,
+    /// `webgl`
+    WebGl,
+    /// `webgl2`
+    WebGl2,
+    /// `webgpu`
+    WebGpu,
+}
+
+impl ContextKind {
+    /// The kind named `context_id`, the names are case-sensitive
+    pub fn from_id(context_id: &str) -> Option<Self> {
+        match context_id {
+            "2d" => Some(ContextKind::TwoD),
+            "bitmaprenderer" => Some(ContextKind::BitmapRenderer),
+            "webgl" => Some(ContextKind::WebGl),
+            "webgl2" => Some(ContextKind::WebGl2),
+            "webgpu" => Some(ContextKind::WebGpu),
+            _ => None,
+        }
+    }
+}
+
+/// A rendering context of a canvas. There is no rendering, so no context can be created yet.
+#[derive(Debug)]
+pub enum RenderingContext {}
+
+pub struct HTMLCanvasElement {
+    node: NodeId,
+}
+
+impl HTMLCanvasElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLCanvasElement { node }
+    }
+
+    /// The canvas element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    reflect_u32!(
+        /// The width attribute, 300 when it is missing or not a valid non-negative integer. 0
+        /// is valid and kept, as in browsers, giving an empty bitmap.
+        width, set_width, "width", 300
+    );
+
+    reflect_u32!(
+        /// The height attribute, 150 when it is missing or not a valid non-negative integer. 0
+        /// is kept as for `width`.
+        height, set_height, "height", 150
+    );
+
+    /// Returns the rendering context of type `context_id`. Unknown types give Ok(None), as
+    /// the null of browsers; known ones fail with NotSupported until there is rendering.
+    pub fn get_context(
+        &self,
+        _document: &mut Document,
+        context_id: &str,
+    ) -> Result<Option<RenderingContext>, DomError> {
+        match ContextKind::from_id(context_id) {
+            Some(_) => Err(DomError::NotSupported),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// src/dom/elements/html_collection.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_form_element::{form_owner, is_listed_element};
+use crate::dom::elements::html_link_element::has_rel_keyword;
+use crate::dom::node::{Element, NodeId};
+
+/// Decides which descendants of the collection root belong to an `HTMLCollection`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollectionFilter {
+    /// Every element
+    All,
+    /// Elements with the given qualified name, `"*"` matches every element. HTML elements are
+    /// matched ASCII case-insensitively, elements in other namespaces exactly.
+    TagName(String),
+    /// Elements carrying every one of the classes, an empty list matches nothing
+    ClassNames(Vec<String>),
+    /// HTML elements whose name attribute is exactly the given value
+    Name(String),
+    /// The options of a select root whose selectedness is set. Only the element part is
+    /// checked by `matches`, the collection checks the position and the selectedness.
+    SelectedOptions,
+    /// The list of options of a select root: its option children and the option children of
+    /// its optgroup children. Only the element part is checked by `matches`, the collection
+    /// checks the position.
+    SelectOptions,
+    /// Listed form-associated elements owned by the form root, except image buttons. Only
+    /// the element part is checked by `matches`, the collection checks the form owner and
+    /// looks for the controls in the whole tree of the form.
+    FormElements,
+    /// Listed form-associated elements, image buttons included
+    ListedElements,
+    /// HTML elements with the given local name
+    HtmlLocalName(String),
+    /// HTML element children of the root with one of the given local names. Only the
+    /// element part is checked by `matches`, the collection checks the parent.
+    HtmlChildren(Vec<String>),
+    /// The rows of a table root: its tr children and the tr children of its thead, tbody and
+    /// tfoot children. Only the element part is checked by `matches`, the collection checks
+    /// the position and puts the thead rows first and the tfoot rows last.
+    TableRows,
+    /// a and area elements with an href attribute
+    Links,
+    /// link elements whose rel attribute has the stylesheet keyword
+    StylesheetLinks,
+}
+
+impl CollectionFilter {
+    pub fn matches(&self, element: &Element) -> bool {
+        match self {
+            CollectionFilter::All => true,
+            CollectionFilter::TagName(name) => {
+                name == "*"
+                    || if element.is_html() {
+                        element.name.eq_ignore_ascii_case(name)
+                    } else {
+                        element.name == *name
+                    }
+            }
+            CollectionFilter::ClassNames(classes) => {
+                !classes.is_empty() && classes.iter().all(|c| element.has_class(c))
+            }
+            CollectionFilter::Name(name) => {
+                element.is_html() && element.get_attribute("name") == Some(name.as_str())
+            }
+            CollectionFilter::SelectedOptions | CollectionFilter::SelectOptions => {
+                element.is_html_named("option")
+            }
+            CollectionFilter::FormElements => {
+                is_listed_element(element)
+                    && !(element.name == "input"
+                        && element
+                            .get_attribute("type")
+                            .is_some_and(|t| t.eq_ignore_ascii_case("image")))
+            }
+            CollectionFilter::ListedElements => is_listed_element(element),
+            CollectionFilter::HtmlLocalName(name) => element.is_html_named(name),
+            CollectionFilter::HtmlChildren(names) => {
+                element.is_html() && names.contains(&element.name)
+            }
+            CollectionFilter::TableRows => element.is_html_named("tr"),
+            CollectionFilter::StylesheetLinks => {
+                element.is_html_named("link") && has_rel_keyword(element, "stylesheet")
+            }
+            CollectionFilter::Links => {
+                (element.is_html_named("a") || element.is_html_named("area"))
+                    && element.has_attribute("href")
+            }
+        }
+    }
+}
+
+/// A live list of elements: the descendants of `root` accepted by `filter`, in tree order.
+/// The collection does not borrow the document, it is handed in on every access and the
+/// members are recomputed each time, so mutations made in between are always reflected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HTMLCollection {
+    root: NodeId,
+    filter: CollectionFilter,
+}
+
+impl HTMLCollection {
+    pub fn new(root: NodeId, filter: CollectionFilter) -> Self {
+        HTMLCollection { root, filter }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn filter(&self) -> &CollectionFilter {
+        &self.filter
+    }
+
+    /// Members of the collection in tree order, table rows in their table order
+    pub fn iter<'a>(&'a self, document: &'a Document) -> impl Iterator<Item = NodeId> + 'a {
+        let scope = match self.filter {
+            CollectionFilter::FormElements => document
+                .arena()
+                .ancestors(self.root)
+                .last()
+                .unwrap_or(self.root),
+            _ => self.root,
+        };
+        let members = move || {
+            document.arena().descendants(scope).filter(move |&id| {
+                document
+                    .element(id)
+                    .is_some_and(|element| self.filter.matches(element))
+                    && self.accepts(document, id)
+            })
+        };
+        if self.filter != CollectionFilter::TableRows {
+            return Box::new(members()) as Box<dyn Iterator<Item = NodeId>>;
+        }
+        let section = move |row: NodeId| {
+            let parent = document.arena().parent(row);
+            match parent.and_then(|parent| document.element(parent)) {
+                Some(parent) if parent.is_html_named("thead") => 0,
+                Some(parent) if parent.is_html_named("tfoot") => 2,
+                _ => 1,
+            }
+        };
+        Box::new((0..3).flat_map(move |rank| members().filter(move |&row| section(row) == rank)))
+    }
+
+    fn accepts(&self, document: &Document, id: NodeId) -> bool {
+        let arena = document.arena();
+        match self.filter {
+            CollectionFilter::FormElements => return form_owner(document, id) == Some(self.root),
+            CollectionFilter::HtmlChildren(_) => return arena.parent(id) == Some(self.root),
+            CollectionFilter::TableRows => {
+                return match arena.parent(id) {
+                    Some(parent) if parent == self.root => true,
+                    Some(parent) => {
+                        arena.parent(parent) == Some(self.root)
+                            && document.element(parent).is_some_and(|parent| {
+                                ["thead", "tbody", "tfoot"]
+                                    .iter()
+                                    .any(|name| parent.is_html_named(name))
+                            })
+                    }
+                    None => false,
+                };
+            }
+            CollectionFilter::SelectOptions => {}
+            CollectionFilter::SelectedOptions if document.option_selectedness(id) => {}
+            CollectionFilter::SelectedOptions => return false,
+            _ => return true,
+        }
+        match arena.parent(id) {
+            Some(parent) if parent == self.root => true,
+            Some(parent) => {
+                arena.parent(parent) == Some(self.root)
+                    && document
+                        .element(parent)
+                        .is_some_and(|parent| parent.is_html_named("optgroup"))
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self, document: &Document) -> usize {
+        self.iter(document).count()
+    }
+
+    pub fn is_empty(&self, document: &Document) -> bool {
+        self.iter(document).next().is_none()
+    }
+
+    pub fn item(&self, document: &Document, index: usize) -> Option<NodeId> {
+        self.iter(document).nth(index)
+    }
+
+    /// First member whose id is `name`, or failing that, the first whose name attribute is `name`
+    pub fn named_item(&self, document: &Document, name: &str) -> Option<NodeId> {
+        if name.is_empty() {
+            return None;
+        }
+        self.iter(document)
+            .find(|&id| document.get_attribute(id, "id") == Some(name))
+            .or_else(|| {
+                self.iter(document)
+                    .find(|&id| document.get_attribute(id, "name") == Some(name))
+            })
+    }
+}
@@ -0,0 +1,28 @@
+// src/dom/elements/html_data_list_element.rs
+
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::node::NodeId;
+
+pub struct HTMLDataListElement {
+    node: NodeId,
+}
+
+impl HTMLDataListElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLDataListElement { node }
+    }
+
+    /// The datalist element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Live collection of the option elements inside the datalist, at any depth, in tree
+    /// order
+    pub fn options(&self) -> HTMLCollection {
+        HTMLCollection::new(
+            self.node,
+            CollectionFilter::HtmlLocalName("option".to_string()),
+        )
+    }
+}
@@ -0,0 +1,60 @@
+// src/dom/elements/html_details_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::event::{Event, EventInit};
+use crate::dom::node::NodeId;
+
+pub struct HTMLDetailsElement {
+    node: NodeId,
+}
+
+impl HTMLDetailsElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLDetailsElement { node }
+    }
+
+    /// The details element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the open attribute, whether the contents beyond the summary are shown
+    pub fn open(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("open"))
+    }
+
+    pub fn set_open(&self, document: &mut Document, value: bool) {
+        if value == self.open(document) {
+            return;
+        }
+        if value {
+            document.set_attribute(self.node, "open", "");
+        } else {
+            document.remove_attribute(self.node, "open");
+        }
+        // dispatched right away, browsers queue it
+        let mut event = Event::new("toggle", EventInit::default());
+        document.dispatch_event(self.node, &mut event);
+    }
+
+    /// Flips the open state, as activating the summary does. Returns the new state.
+    pub fn toggle(&self, document: &mut Document) -> bool {
+        let open = !self.open(document);
+        self.set_open(document, open);
+        open
+    }
+
+    /// Reflects the name attribute, which groups exclusive accordions
+    pub fn name(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "name")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+}
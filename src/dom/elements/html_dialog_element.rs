@@ -0,0 +1,86 @@
+// src/dom/elements/html_dialog_element.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::event::{Event, EventInit};
+use crate::dom::node::NodeId;
+
+/// A dialog element. Only the open state and the return value are modelled, there is no top
+/// layer or focus handling.
+pub struct HTMLDialogElement {
+    node: NodeId,
+}
+
+impl HTMLDialogElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLDialogElement { node }
+    }
+
+    /// The dialog element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the open attribute
+    pub fn open(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("open"))
+    }
+
+    pub fn set_open(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "open", "");
+        } else {
+            document.remove_attribute(self.node, "open");
+        }
+    }
+
+    /// The value given to the last `close` that had one, empty until then
+    pub fn return_value(&self, document: &Document) -> String {
+        document
+            .dialog_return_value(self.node)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_return_value(&self, document: &mut Document, value: &str) {
+        document.set_dialog_return_value(self.node, value.to_string());
+    }
+
+    /// Shows the dialog, a dialog already open stays as it is
+    pub fn show(&self, document: &mut Document) {
+        if !self.open(document) {
+            self.set_open(document, true);
+        }
+    }
+
+    /// Shows the dialog as a modal. Fails with InvalidState when it is already open or not in
+    /// the document.
+    pub fn show_modal(&self, document: &mut Document) -> Result<(), DomError> {
+        let connected = document
+            .arena()
+            .ancestors(self.node)
+            .any(|ancestor| ancestor == document.root());
+        if self.open(document) || !connected {
+            return Err(DomError::InvalidState);
+        }
+        self.set_open(document, true);
+        Ok(())
+    }
+
+    /// Closes an open dialog, recording `return_value` when given, and fires a close event
+    /// at it. Closing a closed dialog does nothing.
+    pub fn close(&self, document: &mut Document, return_value: Option<&str>) {
+        if !self.open(document) {
+            return;
+        }
+        self.set_open(document, false);
+        if let Some(value) = return_value {
+            self.set_return_value(document, value);
+        }
+        // dispatched right away, browsers queue it
+        let mut event = Event::new("close", EventInit::default());
+        document.dispatch_event(self.node, &mut event);
+    }
+}
@@ -0,0 +1,76 @@
+// src/dom/elements/html_field_set_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_form_element::{
+    form_owner, is_disabled_form_control, HTMLFormElement,
+};
+use crate::dom::node::NodeId;
+
+pub struct HTMLFieldSetElement {
+    node: NodeId,
+}
+
+impl HTMLFieldSetElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLFieldSetElement { node }
+    }
+
+    /// The fieldset element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The form owner of the fieldset, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
+    }
+
+    /// Reflects the disabled attribute. A disabled fieldset disables the controls it
+    /// contains, except those in its first legend child, see `is_disabled_form_control`.
+    pub fn disabled(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("disabled"))
+    }
+
+    pub fn set_disabled(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "disabled", "");
+        } else {
+            document.remove_attribute(self.node, "disabled");
+        }
+    }
+
+    /// Whether the fieldset is disabled, by its own attribute or a disabled fieldset ancestor
+    pub fn is_disabled(&self, document: &Document) -> bool {
+        is_disabled_form_control(document, self.node)
+    }
+
+    /// Reflects the name attribute
+    pub fn name(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "name")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+
+    /// Always "fieldset"
+    pub fn r#type(&self) -> &'static str {
+        "fieldset"
+    }
+
+    /// Live collection of the listed form controls inside the fieldset, in tree order
+    pub fn elements(&self) -> HTMLCollection {
+        HTMLCollection::new(self.node, CollectionFilter::ListedElements)
+    }
+
+    /// Fieldsets are never candidates for constraint validation
+    pub fn will_validate(&self) -> bool {
+        false
+    }
+}
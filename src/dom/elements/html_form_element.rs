@@ -0,0 +1,197 @@
+// src/dom/elements/html_form_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_input_element::HTMLInputElement;
+use crate::dom::elements::html_output_element::HTMLOutputElement;
+use crate::dom::elements::html_select_element::HTMLSelectElement;
+use crate::dom::elements::html_text_area_element::HTMLTextAreaElement;
+use crate::dom::node::{Element, NodeId};
+
+pub struct HTMLFormElement {
+    node: NodeId,
+}
+
+impl HTMLFormElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLFormElement { node }
+    }
+
+    /// The form element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Live collection of the listed form controls owned by this form, in tree order. Controls
+    /// outside the form that name it in their form attribute are included, see `form_owner`.
+    pub fn elements(&self) -> HTMLCollection {
+        HTMLCollection::new(self.node, CollectionFilter::FormElements)
+    }
+
+    pub fn length(&self, document: &Document) -> u32 {
+        self.elements().len(document) as u32
+    }
+
+    pub fn item(&self, document: &Document, index: u32) -> Option<NodeId> {
+        self.elements().item(document, index as usize)
+    }
+
+    /// The form's named getter: the first listed control whose id, or failing that name, is
+    /// `name`, then the same lookup among the img elements of the form
+    pub fn named_item(&self, document: &Document, name: &str) -> Option<NodeId> {
+        self.elements().named_item(document, name).or_else(|| {
+            HTMLCollection::new(
+                self.node,
+                CollectionFilter::HtmlLocalName("img".to_string()),
+            )
+            .named_item(document, name)
+        })
+    }
+
+    /// Runs the reset algorithm of every control owned by the form: values and selectedness
+    /// go back to the defaults given by their attributes
+    pub fn reset(&self, document: &mut Document) {
+        let controls: Vec<NodeId> = self.elements().iter(document).collect();
+        for control in controls {
+            let Some(element) = document.element(control) else {
+                continue;
+            };
+            match element.name.as_str() {
+                "input" => HTMLInputElement::new(control).reset(document),
+                "output" => HTMLOutputElement::new(control).reset(document),
+                "select" => HTMLSelectElement::new(control).reset(document),
+                "textarea" => HTMLTextAreaElement::new(control).reset(document),
+                _ => {}
+            }
+        }
+    }
+
+    /// The method attribute as one of "get", "post" and "dialog", "get" when it is missing or
+    /// invalid
+    pub fn method(&self, document: &Document) -> &'static str {
+        match document.get_attribute(self.node, "method") {
+            Some(method) if method.eq_ignore_ascii_case("post") => "post",
+            Some(method) if method.eq_ignore_ascii_case("dialog") => "dialog",
+            _ => "get",
+        }
+    }
+
+    pub fn set_method(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "method", value);
+    }
+
+    /// The action attribute resolved against the base URL, or the document URL when it is
+    /// missing or empty
+    pub fn action(&self, document: &Document) -> String {
+        match document.get_attribute(self.node, "action") {
+            Some(action) if !action.is_empty() => document
+                .resolved_url(self.node, "action")
+                .unwrap_or_default(),
+            _ => document.url().unwrap_or_default().to_string(),
+        }
+    }
+
+    pub fn set_action(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "action", value);
+    }
+
+    /// Reflects the name attribute
+    pub fn name(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "name")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+
+    /// Reflects the novalidate attribute
+    pub fn no_validate(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("novalidate"))
+    }
+
+    pub fn set_no_validate(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "novalidate", "");
+        } else {
+            document.remove_attribute(self.node, "novalidate");
+        }
+    }
+}
+
+/// Whether `element` is a listed form-associated element, one that can be owned by a form
+/// and shows up in its elements
+pub fn is_listed_element(element: &Element) -> bool {
+    element.is_html()
+        && matches!(
+            element.name.as_str(),
+            "button" | "fieldset" | "input" | "object" | "output" | "select" | "textarea"
+        )
+}
+
+/// Whether the form control `control` is disabled: it has a disabled attribute, or it is a
+/// descendant of a disabled fieldset without being inside that fieldset's first legend child
+pub fn is_disabled_form_control(document: &Document, control: NodeId) -> bool {
+    let has_disabled = |node: NodeId| {
+        document
+            .element(node)
+            .is_some_and(|element| element.has_attribute("disabled"))
+    };
+    if has_disabled(control) {
+        return true;
+    }
+    let arena = document.arena();
+    let mut child = control;
+    for ancestor in arena.ancestors(control) {
+        let is_disabled_fieldset = document
+            .element(ancestor)
+            .is_some_and(|element| element.is_html_named("fieldset"))
+            && has_disabled(ancestor);
+        if is_disabled_fieldset {
+            let first_legend = arena.children(ancestor).find(|&node| {
+                document
+                    .element(node)
+                    .is_some_and(|element| element.is_html_named("legend"))
+            });
+            if first_legend != Some(child) {
+                return true;
+            }
+        }
+        child = ancestor;
+    }
+    false
+}
+
+/// The form owner of a listed control. With a form attribute, it is the form element with
+/// that id in the same tree, if any. Otherwise it is the form the parser associated the
+/// control with when it was created inside a form that is still in its tree (the form
+/// element pointer of the tree constructor, which need not be an ancestor), or failing that
+/// the nearest form ancestor.
+///
+/// The owner is worked out from the current tree on each call, so moving a control or
+/// changing its form attribute or the ids of the forms is reflected right away. Moving the
+/// control, or setting or removing its form attribute through `Document`, drops the parser
+/// association.
+pub fn form_owner(document: &Document, control: NodeId) -> Option<NodeId> {
+    let arena = document.arena();
+    let is_form = |node: NodeId| {
+        document
+            .element(node)
+            .is_some_and(|element| element.is_html_named("form"))
+    };
+    let tree_root = |node: NodeId| arena.ancestors(node).last().unwrap_or(node);
+    match document.get_attribute(control, "form") {
+        Some(id) => arena
+            .descendants(tree_root(control))
+            .find(|&node| document.get_attribute(node, "id") == Some(id))
+            .filter(|&form| !id.is_empty() && is_form(form)),
+        None => arena
+            .parser_form_owner(control)
+            .filter(|&form| tree_root(form) == tree_root(control))
+            .or_else(|| arena.ancestors(control).find(|&node| is_form(node))),
+    }
+}
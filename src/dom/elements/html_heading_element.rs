@@ -0,0 +1,64 @@
+// src/dom/elements/html_heading_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::{Element, NodeId};
+
+/// An h1 to h6 element, with its rank taken from the tag name
+pub struct HTMLHeadingElement {
+    node: NodeId,
+    level: u8,
+}
+
+impl HTMLHeadingElement {
+    /// Wraps `node`, or returns None when it is not an HTML h1 to h6 element
+    pub fn new(document: &Document, node: NodeId) -> Option<Self> {
+        let level = heading_level(document.element(node)?)?;
+        Some(HTMLHeadingElement { node, level })
+    }
+
+    /// The heading element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// 1 for h1 up to 6 for h6
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// The text of the heading with runs of ASCII whitespace collapsed to a space and trimmed
+    pub fn text(&self, document: &Document) -> String {
+        let text: String = document
+            .arena()
+            .descendants(self.node)
+            .filter_map(|id| document.arena().get(id).as_text())
+            .collect();
+        text.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// The rank of an HTML h1 to h6 element
+pub fn heading_level(element: &Element) -> Option<u8> {
+    if !element.is_html() {
+        return None;
+    }
+    match element.name.as_str() {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+/// A heading in a document outline, with the headings of lower rank that follow it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineEntry {
+    pub level: u8,
+    pub text: String,
+    /// The id attribute, for linking to the heading, None when absent or empty
+    pub id: Option<String>,
+    pub children: Vec<OutlineEntry>,
+}
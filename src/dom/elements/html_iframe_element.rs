@@ -0,0 +1,106 @@
+// src/dom/elements/html_iframe_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::node::NodeId;
+use crate::dom::parser::html_parser::parse_srcdoc_document;
+
+pub struct HTMLIFrameElement {
+    node: NodeId,
+}
+
+impl HTMLIFrameElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLIFrameElement { node }
+    }
+
+    /// The iframe element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The src attribute resolved against the document base URL, see
+    /// `Document::resolved_url`
+    pub fn src(&self, document: &Document) -> Option<String> {
+        document.resolved_url(self.node, "src")
+    }
+
+    pub fn set_src(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "src", value);
+    }
+
+    /// Reflects the srcdoc attribute, the markup of the document shown in place of src
+    pub fn srcdoc(&self, document: &Document) -> String {
+        self.attribute(document, "srcdoc")
+    }
+
+    pub fn set_srcdoc(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "srcdoc", value);
+    }
+
+    /// Parses the srcdoc attribute into its own document, see `parse_srcdoc_document`. A
+    /// missing attribute parses as the empty string.
+    pub fn parse_srcdoc(&self, document: &Document) -> Document {
+        parse_srcdoc_document(&self.srcdoc(document))
+    }
+
+    /// Reflects the name attribute
+    pub fn name(&self, document: &Document) -> String {
+        self.attribute(document, "name")
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+
+    /// Token list over the sandbox attribute, the restrictions lifted for the content
+    pub fn sandbox(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "sandbox")
+    }
+
+    /// Reflects the allow attribute, the permissions policy of the content
+    pub fn allow(&self, document: &Document) -> String {
+        self.attribute(document, "allow")
+    }
+
+    pub fn set_allow(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "allow", value);
+    }
+
+    /// Reflects the width attribute as written, dimensions of iframes are strings
+    pub fn width(&self, document: &Document) -> String {
+        self.attribute(document, "width")
+    }
+
+    pub fn set_width(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "width", value);
+    }
+
+    /// Reflects the height attribute as written
+    pub fn height(&self, document: &Document) -> String {
+        self.attribute(document, "height")
+    }
+
+    pub fn set_height(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "height", value);
+    }
+
+    /// The loading attribute as "lazy" or "eager", "eager" when it is missing or invalid
+    pub fn loading(&self, document: &Document) -> &'static str {
+        match document.get_attribute(self.node, "loading") {
+            Some(value) if value.eq_ignore_ascii_case("lazy") => "lazy",
+            _ => "eager",
+        }
+    }
+
+    pub fn set_loading(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "loading", value);
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
@@ -0,0 +1,272 @@
+// src/dom/elements/html_image_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::reflect::{reflect_enum, reflect_string, reflect_u32, reflect_url};
+use crate::dom::node::NodeId;
+use crate::dom::url::resolve_url;
+
+/// The descriptor of an image candidate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandidateDescriptor {
+    /// `<n>w`, the width of the image in CSS pixels
+    Width(u32),
+    /// `<n>x`, the pixel density the image is meant for
+    Density(f64),
+}
+
+/// An entry of a srcset attribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageCandidate {
+    /// The URL as written, not resolved
+    pub url: String,
+    /// None when the entry has no descriptor, which stands for a density of 1
+    pub descriptor: Option<CandidateDescriptor>,
+}
+
+pub struct HTMLImageElement {
+    node: NodeId,
+}
+
+impl HTMLImageElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLImageElement { node }
+    }
+
+    /// The img element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    reflect_url!(
+        /// The src attribute resolved against the document base URL, see
+        /// `Document::resolved_url`
+        src, set_src, "src"
+    );
+
+    reflect_string!(
+        /// Reflects the alt attribute
+        alt, set_alt, "alt"
+    );
+
+    reflect_u32!(
+        /// The width attribute, 0 when it is missing or not a valid non-negative integer
+        width, set_width, "width", 0
+    );
+
+    reflect_u32!(
+        /// The height attribute, 0 when it is missing or not a valid non-negative integer
+        height, set_height, "height", 0
+    );
+
+    reflect_enum!(
+        /// The loading attribute as "lazy" or "eager", "eager" when it is missing or invalid
+        loading, set_loading, "loading", ["lazy", "eager"], "eager"
+    );
+
+    reflect_enum!(
+        /// The decoding attribute as "sync", "async" or "auto", "auto" when it is missing or
+        /// invalid
+        decoding, set_decoding, "decoding", ["sync", "async", "auto"], "auto"
+    );
+
+    /// The candidates of the srcset attribute, see `parse_srcset`
+    pub fn srcset(&self, document: &Document) -> Vec<ImageCandidate> {
+        document
+            .get_attribute(self.node, "srcset")
+            .map(parse_srcset)
+            .unwrap_or_default()
+    }
+
+    /// The URL of the image that would be shown, without any knowledge of the viewport: the
+    /// first srcset candidate, or failing that the src attribute, resolved against the base
+    /// URL. Empty when there is neither.
+    pub fn current_src(&self, document: &Document) -> String {
+        let url = match self.srcset(document).into_iter().next() {
+            Some(candidate) => candidate.url,
+            None => match document.get_attribute(self.node, "src") {
+                Some(src) if !src.is_empty() => src.to_string(),
+                _ => return String::new(),
+            },
+        };
+        document
+            .base_url()
+            .and_then(|base| resolve_url(base, &url).ok())
+            .unwrap_or(url)
+    }
+}
+
+/// Parses a srcset attribute following the algorithm of the HTML standard. Entries are
+/// separated by commas, but a URL may contain commas itself: only commas at the end of a URL
+/// end it, and commas inside parentheses of the descriptors are kept. Entries with invalid
+/// or conflicting descriptors (`2w 1x`, `0w`, `-1x`, ...) are dropped.
+pub fn parse_srcset(input: &str) -> Vec<ImageCandidate> {
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let mut candidates = Vec::new();
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start_matches(|c: char| is_space(c) || c == ',');
+        if rest.is_empty() {
+            return candidates;
+        }
+        let url_end = rest.find(is_space).unwrap_or(rest.len());
+        let mut url = &rest[..url_end];
+        rest = &rest[url_end..];
+
+        let mut descriptors = Vec::new();
+        if url.ends_with(',') {
+            url = url.trim_end_matches(',');
+        } else {
+            rest = tokenize_descriptors(rest, &mut descriptors);
+        }
+        if let Some(descriptor) = parse_descriptors(&descriptors) {
+            candidates.push(ImageCandidate {
+                url: url.to_string(),
+                descriptor,
+            });
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum DescriptorState {
+    InDescriptor,
+    InParens,
+    AfterDescriptor,
+}
+
+/// Splits the descriptors of one candidate off `input`, up to the comma ending it, and
+/// returns what follows
+fn tokenize_descriptors<'a>(input: &'a str, descriptors: &mut Vec<String>) -> &'a str {
+    let input = input.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let mut state = DescriptorState::InDescriptor;
+    let mut current = String::new();
+    for (index, c) in input.char_indices() {
+        match state {
+            DescriptorState::InDescriptor => match c {
+                c if c.is_ascii_whitespace() => {
+                    if !current.is_empty() {
+                        descriptors.push(std::mem::take(&mut current));
+                        state = DescriptorState::AfterDescriptor;
+                    }
+                }
+                ',' => {
+                    if !current.is_empty() {
+                        descriptors.push(current);
+                    }
+                    return &input[index + 1..];
+                }
+                '(' => {
+                    current.push(c);
+                    state = DescriptorState::InParens;
+                }
+                _ => current.push(c),
+            },
+            DescriptorState::InParens => {
+                current.push(c);
+                if c == ')' {
+                    state = DescriptorState::InDescriptor;
+                }
+            }
+            DescriptorState::AfterDescriptor => {
+                if !c.is_ascii_whitespace() {
+                    // reconsumed in the descriptor state
+                    if c == ',' {
+                        return &input[index + 1..];
+                    }
+                    current.push(c);
+                    state = if c == '(' {
+                        DescriptorState::InParens
+                    } else {
+                        DescriptorState::InDescriptor
+                    };
+                }
+            }
+        }
+    }
+    if !current.is_empty() {
+        descriptors.push(current);
+    }
+    ""
+}
+
+/// The descriptor of a candidate from its descriptor tokens, None when they are invalid.
+/// The `h` descriptor is validated but not kept.
+fn parse_descriptors(descriptors: &[String]) -> Option<Option<CandidateDescriptor>> {
+    let mut width = None;
+    let mut density = None;
+    let mut future_height = None;
+    for descriptor in descriptors {
+        let kind = descriptor.chars().last()?;
+        let value = &descriptor[..descriptor.len() - kind.len_utf8()];
+        match kind {
+            'w' if width.is_none() && density.is_none() => {
+                width = Some(parse_valid_non_negative_integer(value).filter(|&w| w > 0)?);
+            }
+            'x' if width.is_none() && density.is_none() && future_height.is_none() => {
+                density = Some(parse_valid_floating_point_number(value).filter(|&x| x >= 0.0)?);
+            }
+            'h' if future_height.is_none() && density.is_none() => {
+                future_height = Some(parse_valid_non_negative_integer(value).filter(|&h| h > 0)?);
+            }
+            _ => return None,
+        }
+    }
+    if future_height.is_some() && width.is_none() {
+        return None;
+    }
+    Some(
+        width
+            .map(CandidateDescriptor::Width)
+            .or(density.map(CandidateDescriptor::Density)),
+    )
+}
+
+/// A valid non-negative integer: ASCII digits only
+fn parse_valid_non_negative_integer(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// A valid floating-point number: an optional minus sign, digits with an optional fraction
+/// or a fraction alone, then an optional exponent. No plus sign, no leading or trailing dot.
+fn parse_valid_floating_point_number(value: &str) -> Option<f64> {
+    let bytes = value.as_bytes();
+    let mut position = usize::from(bytes.first() == Some(&b'-'));
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let integer_digits = digits(position);
+    position += integer_digits;
+    if bytes.get(position) == Some(&b'.') {
+        let fraction_digits = digits(position + 1);
+        if fraction_digits == 0 {
+            return None;
+        }
+        position += 1 + fraction_digits;
+    } else if integer_digits == 0 {
+        return None;
+    }
+    if let Some(b'e' | b'E') = bytes.get(position) {
+        position += 1;
+        if let Some(b'-' | b'+') = bytes.get(position) {
+            position += 1;
+        }
+        let exponent_digits = digits(position);
+        if exponent_digits == 0 {
+            return None;
+        }
+        position += exponent_digits;
+    }
+    if position != bytes.len() {
+        return None;
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
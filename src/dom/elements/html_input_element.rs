@@ -0,0 +1,114 @@
+// src/dom/elements/html_input_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_data_list_element::HTMLDataListElement;
+use crate::dom::elements::html_form_element::{
+    form_owner, is_disabled_form_control, HTMLFormElement,
+};
+use crate::dom::node::NodeId;
+
+pub struct HTMLInputElement {
+    node: NodeId,
+}
+
+impl HTMLInputElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLInputElement { node }
+    }
+
+    /// The input element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The form owner of the input, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
+    }
+
+    /// The datalist of suggestions named by the list attribute: the first element in the
+    /// input's tree with that id, if it is a datalist
+    pub fn list(&self, document: &Document) -> Option<HTMLDataListElement> {
+        let id = document
+            .get_attribute(self.node, "list")
+            .filter(|id| !id.is_empty())?;
+        let arena = document.arena();
+        let tree_root = arena.ancestors(self.node).last().unwrap_or(self.node);
+        arena
+            .descendants(tree_root)
+            .find(|&node| document.get_attribute(node, "id") == Some(id))
+            .filter(|&node| {
+                document
+                    .element(node)
+                    .is_some_and(|element| element.is_html_named("datalist"))
+            })
+            .map(HTMLDataListElement::new)
+    }
+
+    /// The current value: the one set with `set_value`, or failing that the default value
+    pub fn value(&self, document: &Document) -> String {
+        match document.control_value(self.node) {
+            Some(value) => value.to_string(),
+            None => self.default_value(document),
+        }
+    }
+
+    /// Sets the current value, later changes of the value attribute no longer show through
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        document.set_control_value(self.node, value.to_string());
+    }
+
+    /// Reflects the value attribute
+    pub fn default_value(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "value")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_default_value(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "value", value);
+    }
+
+    /// Reflects the disabled attribute, see `is_disabled` for the state inherited from
+    /// fieldsets
+    pub fn disabled(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("disabled"))
+    }
+
+    pub fn set_disabled(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "disabled", "");
+        } else {
+            document.remove_attribute(self.node, "disabled");
+        }
+    }
+
+    /// Whether the input is disabled, by its own attribute or a disabled fieldset ancestor
+    pub fn is_disabled(&self, document: &Document) -> bool {
+        is_disabled_form_control(document, self.node)
+    }
+
+    /// Whether the input is a candidate for constraint validation: hidden, reset and button
+    /// inputs are not, nor are disabled or read-only ones
+    pub fn will_validate(&self, document: &Document) -> bool {
+        let barred_type = document
+            .get_attribute(self.node, "type")
+            .is_some_and(|value| {
+                ["hidden", "reset", "button"]
+                    .iter()
+                    .any(|barred| value.eq_ignore_ascii_case(barred))
+            });
+        let read_only = document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("readonly"));
+        !barred_type && !read_only && !self.is_disabled(document)
+    }
+
+    /// The reset algorithm: the value goes back to the default value
+    pub fn reset(&self, document: &mut Document) {
+        document.clear_control_value(self.node);
+    }
+}
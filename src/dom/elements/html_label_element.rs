@@ -0,0 +1,89 @@
+// src/dom/elements/html_label_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_form_element::{form_owner, HTMLFormElement};
+use crate::dom::elements::node_list::NodeList;
+use crate::dom::node::{Element, NodeId};
+
+pub struct HTMLLabelElement {
+    node: NodeId,
+}
+
+impl HTMLLabelElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLLabelElement { node }
+    }
+
+    /// The label element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the for attribute
+    pub fn html_for(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "for")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_html_for(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "for", value);
+    }
+
+    /// The labeled control. With a for attribute, it is the first element in the label's
+    /// tree whose id is the attribute value, if that element is labelable. Otherwise it is
+    /// the first labelable descendant of the label.
+    pub fn control(&self, document: &Document) -> Option<NodeId> {
+        let arena = document.arena();
+        let is_labelable = |node: NodeId| document.element(node).is_some_and(is_labelable_element);
+        match document.get_attribute(self.node, "for") {
+            Some(id) => {
+                let tree_root = arena.ancestors(self.node).last().unwrap_or(self.node);
+                arena
+                    .descendants(tree_root)
+                    .find(|&node| document.get_attribute(node, "id") == Some(id))
+                    .filter(|&node| !id.is_empty() && is_labelable(node))
+            }
+            None => arena
+                .descendants(self.node)
+                .find(|&node| is_labelable(node)),
+        }
+    }
+
+    /// The form owner of the labeled control
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        let control = self.control(document)?;
+        form_owner(document, control).map(HTMLFormElement::new)
+    }
+}
+
+/// Whether `element` is labelable, one a label can be associated with
+pub fn is_labelable_element(element: &Element) -> bool {
+    if !element.is_html() {
+        return false;
+    }
+    match element.name.as_str() {
+        "input" => !element
+            .get_attribute("type")
+            .is_some_and(|value| value.eq_ignore_ascii_case("hidden")),
+        "button" | "meter" | "output" | "progress" | "select" | "textarea" => true,
+        _ => false,
+    }
+}
+
+/// Static list of the label elements of the document whose control is `control`, in tree
+/// order
+pub fn labels_of(document: &Document, control: NodeId) -> NodeList {
+    let labels = document
+        .arena()
+        .descendants(document.root())
+        .filter(|&node| {
+            document
+                .element(node)
+                .is_some_and(|element| element.is_html_named("label"))
+                && HTMLLabelElement::new(node).control(document) == Some(control)
+        })
+        .collect();
+    NodeList::from_nodes(labels)
+}
@@ -0,0 +1,32 @@
+// src/dom/elements/html_li_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::microsyntax::parse_integer;
+use crate::dom::node::NodeId;
+
+pub struct HTMLLIElement {
+    node: NodeId,
+}
+
+impl HTMLLIElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLLIElement { node }
+    }
+
+    /// The li element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The value attribute parsed as an integer, 0 when it is missing or invalid
+    pub fn value(&self, document: &Document) -> i32 {
+        document
+            .get_attribute(self.node, "value")
+            .and_then(parse_integer)
+            .unwrap_or(0)
+    }
+
+    pub fn set_value(&self, document: &mut Document, value: i32) {
+        document.set_attribute(self.node, "value", &value.to_string());
+    }
+}
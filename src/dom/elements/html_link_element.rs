@@ -0,0 +1,122 @@
+// src/dom/elements/html_link_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::node::{Element, NodeId};
+
+pub struct HTMLLinkElement {
+    node: NodeId,
+}
+
+impl HTMLLinkElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLLinkElement { node }
+    }
+
+    /// The link element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The href attribute resolved against the document base URL, see
+    /// `Document::resolved_url`
+    pub fn href(&self, document: &Document) -> Option<String> {
+        document.resolved_url(self.node, "href")
+    }
+
+    pub fn set_href(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "href", value);
+    }
+
+    /// Reflects the rel attribute
+    pub fn rel(&self, document: &Document) -> String {
+        self.attribute(document, "rel")
+    }
+
+    pub fn set_rel(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "rel", value);
+    }
+
+    /// Token list over the rel attribute
+    pub fn rel_list(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "rel")
+    }
+
+    /// Reflects the type attribute
+    pub fn r#type(&self, document: &Document) -> String {
+        self.attribute(document, "type")
+    }
+
+    pub fn set_type(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "type", value);
+    }
+
+    /// Reflects the media attribute
+    pub fn media(&self, document: &Document) -> String {
+        self.attribute(document, "media")
+    }
+
+    pub fn set_media(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "media", value);
+    }
+
+    /// Reflects the as attribute, the destination of a preload
+    pub fn as_(&self, document: &Document) -> String {
+        self.attribute(document, "as")
+    }
+
+    pub fn set_as(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "as", value);
+    }
+
+    /// Token list over the sizes attribute, the sizes of an icon like "32x32" or "any"
+    pub fn sizes(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "sizes")
+    }
+
+    /// Reflects the hreflang attribute
+    pub fn hreflang(&self, document: &Document) -> String {
+        self.attribute(document, "hreflang")
+    }
+
+    pub fn set_hreflang(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "hreflang", value);
+    }
+
+    /// Whether the rel attribute has the stylesheet keyword, alternate stylesheets included
+    pub fn is_stylesheet(&self, document: &Document) -> bool {
+        self.has_rel(document, "stylesheet")
+    }
+
+    /// Whether the rel attribute has the icon keyword, as in "icon" or "shortcut icon"
+    pub fn is_icon(&self, document: &Document) -> bool {
+        self.has_rel(document, "icon")
+    }
+
+    /// Whether the rel attribute has the preload keyword
+    pub fn is_preload(&self, document: &Document) -> bool {
+        self.has_rel(document, "preload")
+    }
+
+    fn has_rel(&self, document: &Document, keyword: &str) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| has_rel_keyword(element, keyword))
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// Whether the rel attribute of `element` has `keyword` among its tokens, compared ASCII
+/// case-insensitively as link types are
+pub fn has_rel_keyword(element: &Element, keyword: &str) -> bool {
+    element.get_attribute("rel").is_some_and(|rel| {
+        rel.split_ascii_whitespace()
+            .any(|token| token.eq_ignore_ascii_case(keyword))
+    })
+}
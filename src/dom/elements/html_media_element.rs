@@ -0,0 +1,159 @@
+// src/dom/elements/html_media_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+use crate::dom::url::resolve_url;
+
+/// What audio and video elements share. `HTMLAudioElement` and `HTMLVideoElement` dereference
+/// to it.
+pub struct HTMLMediaElement {
+    node: NodeId,
+}
+
+impl HTMLMediaElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLMediaElement { node }
+    }
+
+    /// The audio or video element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The src attribute resolved against the base URL, None when it is missing
+    pub fn src(&self, document: &Document) -> Option<String> {
+        document.resolved_url(self.node, "src")
+    }
+
+    pub fn set_src(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "src", value);
+    }
+
+    /// Reflects the controls attribute
+    pub fn controls(&self, document: &Document) -> bool {
+        self.flag(document, "controls")
+    }
+
+    pub fn set_controls(&self, document: &mut Document, value: bool) {
+        self.set_flag(document, "controls", value);
+    }
+
+    /// Reflects the autoplay attribute
+    pub fn autoplay(&self, document: &Document) -> bool {
+        self.flag(document, "autoplay")
+    }
+
+    pub fn set_autoplay(&self, document: &mut Document, value: bool) {
+        self.set_flag(document, "autoplay", value);
+    }
+
+    /// Reflects the loop attribute
+    pub fn r#loop(&self, document: &Document) -> bool {
+        self.flag(document, "loop")
+    }
+
+    pub fn set_loop(&self, document: &mut Document, value: bool) {
+        self.set_flag(document, "loop", value);
+    }
+
+    /// Reflects the muted attribute, the default muted state
+    pub fn muted(&self, document: &Document) -> bool {
+        self.flag(document, "muted")
+    }
+
+    pub fn set_muted(&self, document: &mut Document, value: bool) {
+        self.set_flag(document, "muted", value);
+    }
+
+    /// The preload attribute as "none", "metadata" or "auto". An empty value means "auto",
+    /// a missing or invalid one "metadata".
+    pub fn preload(&self, document: &Document) -> &'static str {
+        match document.get_attribute(self.node, "preload") {
+            Some(value) if value.eq_ignore_ascii_case("none") => "none",
+            Some(value) if value.is_empty() || value.eq_ignore_ascii_case("auto") => "auto",
+            _ => "metadata",
+        }
+    }
+
+    pub fn set_preload(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "preload", value);
+    }
+
+    /// The crossorigin attribute as "anonymous" or "use-credentials". None when it is
+    /// missing, "anonymous" when it is empty or invalid.
+    pub fn cross_origin(&self, document: &Document) -> Option<&'static str> {
+        let value = document.get_attribute(self.node, "crossorigin")?;
+        if value.eq_ignore_ascii_case("use-credentials") {
+            Some("use-credentials")
+        } else {
+            Some("anonymous")
+        }
+    }
+
+    /// Sets the crossorigin attribute, None removes it
+    pub fn set_cross_origin(&self, document: &mut Document, value: Option<&str>) {
+        match value {
+            Some(value) => document.set_attribute(self.node, "crossorigin", value),
+            None => document.remove_attribute(self.node, "crossorigin"),
+        }
+    }
+
+    /// The URL the resource selection algorithm would pick, as far as it can be told without
+    /// fetching: the src attribute when there is one, otherwise the src of the first source
+    /// child with a playable type and no media query. Resolved against the base URL, empty
+    /// when nothing is picked.
+    pub fn current_src(&self, document: &Document) -> String {
+        let url = match document.get_attribute(self.node, "src") {
+            Some(src) => src,
+            None => {
+                let source = document.arena().children(self.node).find_map(|child| {
+                    let element = document.element(child)?;
+                    if !element.is_html_named("source") {
+                        return None;
+                    }
+                    let acceptable = element.get_attribute("type").is_none_or(is_playable_type)
+                        && element.get_attribute("media").is_none_or(str::is_empty);
+                    element.get_attribute("src").filter(|_| acceptable)
+                });
+                source.unwrap_or_default()
+            }
+        };
+        if url.is_empty() {
+            return String::new();
+        }
+        document
+            .base_url()
+            .and_then(|base| resolve_url(base, url).ok())
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    fn flag(&self, document: &Document, name: &str) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute(name))
+    }
+
+    fn set_flag(&self, document: &mut Document, name: &str, value: bool) {
+        if value {
+            document.set_attribute(self.node, name, "");
+        } else {
+            document.remove_attribute(self.node, name);
+        }
+    }
+}
+
+/// Whether a source type could be played, judged on the MIME type essence only: any audio or
+/// video type, or application/ogg. Codecs parameters are not looked at. An empty type passes.
+pub fn is_playable_type(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    if essence.is_empty() {
+        return true;
+    }
+    let Some((top_level, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    !subtype.is_empty()
+        && (top_level.eq_ignore_ascii_case("audio")
+            || top_level.eq_ignore_ascii_case("video")
+            || essence.eq_ignore_ascii_case("application/ogg"))
+}
@@ -0,0 +1,123 @@
+// src/dom/elements/html_meta_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+pub struct HTMLMetaElement {
+    node: NodeId,
+}
+
+impl HTMLMetaElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLMetaElement { node }
+    }
+
+    /// The meta element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the name attribute
+    pub fn name(&self, document: &Document) -> String {
+        self.attribute(document, "name")
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+
+    /// Reflects the content attribute
+    pub fn content(&self, document: &Document) -> String {
+        self.attribute(document, "content")
+    }
+
+    pub fn set_content(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "content", value);
+    }
+
+    /// Reflects the http-equiv attribute
+    pub fn http_equiv(&self, document: &Document) -> String {
+        self.attribute(document, "http-equiv")
+    }
+
+    pub fn set_http_equiv(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "http-equiv", value);
+    }
+
+    /// Reflects the charset attribute
+    pub fn charset(&self, document: &Document) -> String {
+        self.attribute(document, "charset")
+    }
+
+    pub fn set_charset(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "charset", value);
+    }
+
+    /// Reflects the media attribute
+    pub fn media(&self, document: &Document) -> String {
+        self.attribute(document, "media")
+    }
+
+    pub fn set_media(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "media", value);
+    }
+
+    /// The encoding label this meta element declares: its charset attribute, or for an
+    /// http-equiv="content-type" pragma the charset parameter of its content. Labels are not
+    /// checked against the known encodings.
+    pub fn declared_charset(&self, document: &Document) -> Option<String> {
+        let element = document.element(self.node)?;
+        if let Some(charset) = element.get_attribute("charset") {
+            let charset = charset.trim_matches(|c: char| c.is_ascii_whitespace());
+            if !charset.is_empty() {
+                return Some(charset.to_string());
+            }
+        }
+        let is_content_type = element
+            .get_attribute("http-equiv")
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("content-type"));
+        if is_content_type {
+            return element
+                .get_attribute("content")
+                .and_then(extract_charset_from_meta);
+        }
+        None
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// The algorithm for extracting a character encoding from a meta element: the value of the
+/// first `charset=` parameter of a content attribute, quoted or not. Occurrences of
+/// "charset" not followed by `=` are skipped; an unterminated quote gives None.
+pub fn extract_charset_from_meta(content: &str) -> Option<String> {
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let lowercase = content.to_ascii_lowercase();
+    let mut position = 0;
+    loop {
+        position += lowercase[position..].find("charset")? + "charset".len();
+        let rest = content[position..].trim_start_matches(is_space);
+        let Some(value) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start_matches(is_space);
+        let mut chars = value.chars();
+        return match chars.next()? {
+            quote @ ('"' | '\'') => {
+                let value = chars.as_str();
+                value.find(quote).map(|end| value[..end].to_string())
+            }
+            _ => {
+                let end = value
+                    .find(|c: char| is_space(c) || c == ';')
+                    .unwrap_or(value.len());
+                Some(value[..end].to_string())
+            }
+        };
+    }
+}
@@ -0,0 +1,102 @@
+// src/dom/elements/html_meter_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::microsyntax::parse_floating_point_number;
+use crate::dom::node::NodeId;
+
+/// A gauge. The getters return the numbers the element is rendered with: each attribute is
+/// parsed as a floating-point number and clamped so that min <= low <= high <= max and the
+/// value and optimum lie between min and max.
+pub struct HTMLMeterElement {
+    node: NodeId,
+}
+
+impl HTMLMeterElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLMeterElement { node }
+    }
+
+    /// The meter element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The min attribute, 0 when it is missing or invalid
+    pub fn min(&self, document: &Document) -> f64 {
+        self.number(document, "min").unwrap_or(0.0)
+    }
+
+    pub fn set_min(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "min", value);
+    }
+
+    /// The max attribute, 1 when it is missing or invalid, raised to `min` when below it
+    pub fn max(&self, document: &Document) -> f64 {
+        let min = self.min(document);
+        self.number(document, "max").unwrap_or(1.0).max(min)
+    }
+
+    pub fn set_max(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "max", value);
+    }
+
+    /// The value attribute clamped between `min` and `max`, 0 before clamping when it is
+    /// missing or invalid
+    pub fn value(&self, document: &Document) -> f64 {
+        let (min, max) = (self.min(document), self.max(document));
+        self.number(document, "value")
+            .unwrap_or(0.0)
+            .clamp(min, max)
+    }
+
+    pub fn set_value(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "value", value);
+    }
+
+    /// The low attribute clamped between `min` and `max`, `min` when it is missing or invalid
+    pub fn low(&self, document: &Document) -> f64 {
+        let (min, max) = (self.min(document), self.max(document));
+        self.number(document, "low").unwrap_or(min).clamp(min, max)
+    }
+
+    pub fn set_low(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "low", value);
+    }
+
+    /// The high attribute clamped between `low` and `max`, `max` when it is missing or
+    /// invalid
+    pub fn high(&self, document: &Document) -> f64 {
+        let (low, max) = (self.low(document), self.max(document));
+        self.number(document, "high").unwrap_or(max).clamp(low, max)
+    }
+
+    pub fn set_high(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "high", value);
+    }
+
+    /// The optimum attribute clamped between `min` and `max`, their midpoint when it is
+    /// missing or invalid
+    pub fn optimum(&self, document: &Document) -> f64 {
+        let (min, max) = (self.min(document), self.max(document));
+        self.number(document, "optimum")
+            .unwrap_or((min + max) / 2.0)
+            .clamp(min, max)
+    }
+
+    pub fn set_optimum(&self, document: &mut Document, value: f64) {
+        self.set_number(document, "optimum", value);
+    }
+
+    fn number(&self, document: &Document, name: &str) -> Option<f64> {
+        document
+            .get_attribute(self.node, name)
+            .and_then(parse_floating_point_number)
+    }
+
+    /// Non-finite values are ignored
+    fn set_number(&self, document: &mut Document, name: &str, value: f64) {
+        if value.is_finite() {
+            document.set_attribute(self.node, name, &value.to_string());
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// src/dom/elements/html_o_list_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::microsyntax::parse_integer;
+use crate::dom::node::NodeId;
+
+pub struct HTMLOListElement {
+    node: NodeId,
+}
+
+impl HTMLOListElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLOListElement { node }
+    }
+
+    /// The ol element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the reversed attribute, whether the items count down
+    pub fn reversed(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("reversed"))
+    }
+
+    pub fn set_reversed(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "reversed", "");
+        } else {
+            document.remove_attribute(self.node, "reversed");
+        }
+    }
+
+    /// The start attribute parsed as an integer, 1 when it is missing or invalid
+    pub fn start(&self, document: &Document) -> i32 {
+        document
+            .get_attribute(self.node, "start")
+            .and_then(parse_integer)
+            .unwrap_or(1)
+    }
+
+    pub fn set_start(&self, document: &mut Document, value: i32) {
+        document.set_attribute(self.node, "start", &value.to_string());
+    }
+
+    /// Reflects the type attribute, the kind of marker (`1`, `a`, `A`, `i` or `I`)
+    pub fn r#type(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "type")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_type(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "type", value);
+    }
+
+    /// The li elements this list owns, in tree order. Items of nested ol, ul and menu
+    /// elements belong to those.
+    pub fn items(&self, document: &Document) -> Vec<NodeId> {
+        let mut items = Vec::new();
+        collect_items(document, self.node, &mut items);
+        items
+    }
+
+    /// The ordinal value of each of `items`. A valid value attribute sets the ordinal of its
+    /// item and the following items count on from it.
+    pub fn item_numbers(&self, document: &Document) -> Vec<i64> {
+        let items = self.items(document);
+        let reversed = self.reversed(document);
+        let step = if reversed { -1 } else { 1 };
+        let start = match document
+            .get_attribute(self.node, "start")
+            .and_then(parse_integer)
+        {
+            Some(start) => i64::from(start),
+            None if reversed => items.len() as i64,
+            None => 1,
+        };
+        let mut next = start;
+        items
+            .iter()
+            .map(|&item| {
+                let ordinal = document
+                    .get_attribute(item, "value")
+                    .and_then(parse_integer)
+                    .map_or(next, i64::from);
+                next = ordinal + step;
+                ordinal
+            })
+            .collect()
+    }
+}
+
+fn collect_items(document: &Document, parent: NodeId, items: &mut Vec<NodeId>) {
+    for child in document.arena().children(parent) {
+        let Some(element) = document.element(child) else {
+            continue;
+        };
+        if element.is_html_named("li") {
+            items.push(child);
+        }
+        if !["ol", "ul", "menu"]
+            .iter()
+            .any(|name| element.is_html_named(name))
+        {
+            collect_items(document, child, items);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// src/dom/elements/html_opt_group_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+pub struct HTMLOptGroupElement {
+    node: NodeId,
+}
+
+impl HTMLOptGroupElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLOptGroupElement { node }
+    }
+
+    /// The optgroup element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the disabled attribute, which disables the option children of the group
+    pub fn disabled(&self, document: &Document) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute("disabled"))
+    }
+
+    pub fn set_disabled(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "disabled", "");
+        } else {
+            document.remove_attribute(self.node, "disabled");
+        }
+    }
+
+    /// Reflects the label attribute, the name of the group
+    pub fn label(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "label")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_label(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "label", value);
+    }
+}
@@ -0,0 +1,151 @@
+// src/dom/elements/html_option_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_opt_group_element::HTMLOptGroupElement;
+use crate::dom::elements::html_select_element::HTMLSelectElement;
+use crate::dom::microsyntax::ASCII_WHITESPACE;
+use crate::dom::node::{Namespace, NodeId};
+
+pub struct HTMLOptionElement {
+    node: NodeId,
+}
+
+impl HTMLOptionElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLOptionElement { node }
+    }
+
+    /// The option element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The descendant text, script elements excluded, with ASCII whitespace stripped and
+    /// collapsed
+    pub fn text(&self, document: &Document) -> String {
+        let arena = document.arena();
+        let mut text = String::new();
+        let mut pending: Vec<NodeId> = arena.children(self.node).collect();
+        pending.reverse();
+        while let Some(node) = pending.pop() {
+            let data = arena.get(node);
+            if let Some(data) = data.as_text() {
+                text.push_str(data);
+            } else if !data.is_element_named("script")
+                && !data.is_element_in(Namespace::Svg, "script")
+            {
+                pending.extend(arena.children(node).collect::<Vec<_>>().into_iter().rev());
+            }
+        }
+        text.split(ASCII_WHITESPACE)
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The value attribute, or failing that the text
+    pub fn value(&self, document: &Document) -> String {
+        match document.get_attribute(self.node, "value") {
+            Some(value) => value.to_string(),
+            None => self.text(document),
+        }
+    }
+
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "value", value);
+    }
+
+    /// The label attribute, or failing that the text
+    pub fn label(&self, document: &Document) -> String {
+        match document.get_attribute(self.node, "label") {
+            Some(label) => label.to_string(),
+            None => self.text(document),
+        }
+    }
+
+    pub fn set_label(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "label", value);
+    }
+
+    /// Whether the option is currently selected, see `Document::option_selectedness`
+    pub fn selected(&self, document: &Document) -> bool {
+        document.option_selectedness(self.node)
+    }
+
+    /// Sets the selectedness of the option. Selecting it in a select without the multiple
+    /// attribute deselects the other options, then the select's selectedness is reset.
+    pub fn set_selected(&self, document: &mut Document, value: bool) {
+        let select = self.owner_select(document).map(HTMLSelectElement::new);
+        match &select {
+            Some(select) if value && !select.multiple(document) => {
+                let options: Vec<NodeId> = select.options().iter(document).collect();
+                for option in options {
+                    document.set_option_selectedness(option, option == self.node);
+                }
+            }
+            _ => document.set_option_selectedness(self.node, value),
+        }
+        if let Some(select) = select {
+            select.reset_selectedness(document);
+        }
+    }
+
+    /// Reflects the selected attribute, the initial selectedness of the option
+    pub fn default_selected(&self, document: &Document) -> bool {
+        self.has_attribute(document, "selected")
+    }
+
+    pub fn set_default_selected(&self, document: &mut Document, value: bool) {
+        if value {
+            document.set_attribute(self.node, "selected", "");
+        } else {
+            document.remove_attribute(self.node, "selected");
+        }
+    }
+
+    /// Whether the option has the disabled attribute, or is a child of an optgroup that has
+    /// it. An optgroup further up does not count, optgroups do not nest.
+    pub fn disabled(&self, document: &Document) -> bool {
+        self.has_attribute(document, "disabled")
+            || document.arena().parent(self.node).is_some_and(|parent| {
+                document
+                    .element(parent)
+                    .is_some_and(|element| element.is_html_named("optgroup"))
+                    && HTMLOptGroupElement::new(parent).disabled(document)
+            })
+    }
+
+    /// Position of the option in the list of options of its select, 0 outside of one
+    pub fn index(&self, document: &Document) -> i32 {
+        self.owner_select(document)
+            .and_then(|select| {
+                HTMLSelectElement::new(select)
+                    .options()
+                    .iter(document)
+                    .position(|option| option == self.node)
+            })
+            .map_or(0, |index| index as i32)
+    }
+
+    /// The select whose list of options holds this option: its parent, or the parent of its
+    /// optgroup parent
+    fn owner_select(&self, document: &Document) -> Option<NodeId> {
+        let is_named = |node: NodeId, name: &str| {
+            document
+                .element(node)
+                .is_some_and(|element| element.is_html_named(name))
+        };
+        let parent = document.arena().parent(self.node)?;
+        if is_named(parent, "select") {
+            return Some(parent);
+        }
+        let grandparent = document.arena().parent(parent)?;
+        (is_named(parent, "optgroup") && is_named(grandparent, "select")).then_some(grandparent)
+    }
+
+    fn has_attribute(&self, document: &Document, name: &str) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute(name))
+    }
+}
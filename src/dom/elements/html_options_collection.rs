@@ -0,0 +1,174 @@
+// src/dom/elements/html_options_collection.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_select_element::AddBefore;
+use crate::dom::node::NodeId;
+
+/// The live list of options of a select: its option children and the option children of
+/// its optgroup children, in tree order. On top of the `HTMLCollection` accessors it can
+/// change the options, like the legacy setters of the DOM interface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HTMLOptionsCollection {
+    select: NodeId,
+    collection: HTMLCollection,
+}
+
+impl HTMLOptionsCollection {
+    pub fn new(select: NodeId) -> Self {
+        HTMLOptionsCollection {
+            select,
+            collection: HTMLCollection::new(select, CollectionFilter::SelectOptions),
+        }
+    }
+
+    /// The select element whose options these are
+    pub fn select(&self) -> NodeId {
+        self.select
+    }
+
+    /// Options in tree order
+    pub fn iter<'a>(&'a self, document: &'a Document) -> impl Iterator<Item = NodeId> + 'a {
+        self.collection.iter(document)
+    }
+
+    pub fn len(&self, document: &Document) -> usize {
+        self.collection.len(document)
+    }
+
+    pub fn is_empty(&self, document: &Document) -> bool {
+        self.collection.is_empty(document)
+    }
+
+    pub fn item(&self, document: &Document, index: usize) -> Option<NodeId> {
+        self.collection.item(document, index)
+    }
+
+    /// The first option whose id, or failing that name, is `name`
+    pub fn named_item(&self, document: &Document, name: &str) -> Option<NodeId> {
+        self.collection.named_item(document, name)
+    }
+
+    pub fn length(&self, document: &Document) -> u32 {
+        self.len(document) as u32
+    }
+
+    /// Appends blank options to the select, or removes the last options from their parents,
+    /// until there are `value` options. Values over 100 000 are ignored.
+    pub fn set_length(&self, document: &mut Document, value: u32) {
+        if value > 100_000 {
+            return;
+        }
+        let length = self.length(document);
+        for _ in length..value {
+            let option = document.create_element("option");
+            document
+                .append_child(self.select, option)
+                .expect("a select accepts option children");
+        }
+        for _ in value..length {
+            if let Some(last) = self.iter(document).last() {
+                document.remove(last);
+            }
+        }
+    }
+
+    /// Replaces the option at `index` with `option`, or removes it when `option` is None.
+    /// Past the last option, blank options are appended to fill the gap before `option`.
+    pub fn set(
+        &self,
+        document: &mut Document,
+        index: u32,
+        option: Option<NodeId>,
+    ) -> Result<(), DomError> {
+        let Some(option) = option else {
+            self.remove(document, index as i32);
+            return Ok(());
+        };
+        match self.item(document, index as usize) {
+            Some(current) => {
+                let parent = document.arena().parent(current).unwrap_or(self.select);
+                document.replace_child(parent, option, current)?;
+            }
+            None => {
+                for _ in self.length(document) + 1..=index {
+                    let blank = document.create_element("option");
+                    document.append_child(self.select, blank)?;
+                }
+                document.append_child(self.select, option)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Inserts `element`, an option or optgroup, before `before`: an element inside the
+    /// select or the index of an option. Without `before`, or with an index past the last
+    /// option, the element is appended to the select. Fails with `HierarchyRequest` when
+    /// `element` is an ancestor of the select and `NotFound` when `before` is an element
+    /// outside of it.
+    pub fn add(
+        &self,
+        document: &mut Document,
+        element: NodeId,
+        before: Option<AddBefore>,
+    ) -> Result<(), DomError> {
+        if document
+            .arena()
+            .is_inclusive_descendant(self.select, element)
+        {
+            return Err(DomError::HierarchyRequest);
+        }
+        let reference = match before {
+            Some(AddBefore::Element(before)) => {
+                if before == self.select
+                    || !document
+                        .arena()
+                        .is_inclusive_descendant(before, self.select)
+                {
+                    return Err(DomError::NotFound);
+                }
+                if before == element {
+                    return Ok(());
+                }
+                Some(before)
+            }
+            Some(AddBefore::Index(index)) => usize::try_from(index)
+                .ok()
+                .and_then(|index| self.item(document, index)),
+            None => None,
+        };
+        let parent = match reference {
+            Some(reference) => document.arena().parent(reference).unwrap_or(self.select),
+            None => self.select,
+        };
+        document.insert_before(parent, element, reference)?;
+        Ok(())
+    }
+
+    /// Removes the option at `index` from its parent, nothing happens when there is none
+    pub fn remove(&self, document: &mut Document, index: i32) {
+        let option = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.item(document, index));
+        if let Some(option) = option {
+            document.remove(option);
+        }
+    }
+
+    /// Index of the first selected option, or -1 when no option is selected
+    pub fn selected_index(&self, document: &Document) -> i32 {
+        self.iter(document)
+            .position(|option| document.option_selectedness(option))
+            .map_or(-1, |index| index as i32)
+    }
+
+    /// Selects the option at `index` and deselects the others, an index matching no option
+    /// deselects them all
+    pub fn set_selected_index(&self, document: &mut Document, index: i32) {
+        let options: Vec<NodeId> = self.iter(document).collect();
+        for (position, option) in options.into_iter().enumerate() {
+            document.set_option_selectedness(option, position as i64 == i64::from(index));
+        }
+    }
+}
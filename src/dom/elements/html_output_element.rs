@@ -0,0 +1,92 @@
+// src/dom/elements/html_output_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::elements::html_form_element::{form_owner, HTMLFormElement};
+use crate::dom::node::NodeId;
+
+pub struct HTMLOutputElement {
+    node: NodeId,
+}
+
+impl HTMLOutputElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLOutputElement { node }
+    }
+
+    /// The output element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Token list over the for attribute, the ids of the elements the result came from
+    pub fn html_for(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "for")
+    }
+
+    /// The form owner of the output, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
+    }
+
+    /// Reflects the name attribute
+    pub fn name(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "name")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_name(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "name", value);
+    }
+
+    /// Always "output"
+    pub fn r#type(&self) -> &'static str {
+        "output"
+    }
+
+    /// The default value: the text the output had before the first `set_value`, or its
+    /// current text content when the value was never set
+    pub fn default_value(&self, document: &Document) -> String {
+        match document.control_value(self.node) {
+            Some(default) => default.to_string(),
+            None => document.text_content(self.node),
+        }
+    }
+
+    /// Sets the default value, which replaces the text content unless the value was set
+    pub fn set_default_value(&self, document: &mut Document, value: &str) {
+        match document.control_value(self.node) {
+            Some(_) => document.set_control_value(self.node, value.to_string()),
+            None => document.set_text_content(self.node, value),
+        }
+    }
+
+    /// The text content of the output
+    pub fn value(&self, document: &Document) -> String {
+        document.text_content(self.node)
+    }
+
+    /// Replaces the text content with `value`, keeping the text it replaces as the default
+    /// value the first time
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        if document.control_value(self.node).is_none() {
+            let default = document.text_content(self.node);
+            document.set_control_value(self.node, default);
+        }
+        document.set_text_content(self.node, value);
+    }
+
+    /// The reset algorithm: the text content goes back to the default value
+    pub fn reset(&self, document: &mut Document) {
+        let default = self.default_value(document);
+        document.set_text_content(self.node, &default);
+        document.clear_control_value(self.node);
+    }
+
+    /// Outputs are never candidates for constraint validation
+    pub fn will_validate(&self) -> bool {
+        false
+    }
+}
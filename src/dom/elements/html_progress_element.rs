@@ -0,0 +1,65 @@
+// src/dom/elements/html_progress_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::microsyntax::parse_floating_point_number;
+use crate::dom::node::NodeId;
+
+pub struct HTMLProgressElement {
+    node: NodeId,
+}
+
+impl HTMLProgressElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLProgressElement { node }
+    }
+
+    /// The progress element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The current value: the value attribute clamped to `max`, 0 when it is negative,
+    /// invalid or missing
+    pub fn value(&self, document: &Document) -> f64 {
+        document
+            .get_attribute(self.node, "value")
+            .and_then(parse_floating_point_number)
+            .filter(|&value| value >= 0.0)
+            .unwrap_or(0.0)
+            .min(self.max(document))
+    }
+
+    pub fn set_value(&self, document: &mut Document, value: f64) {
+        document.set_attribute(self.node, "value", &value.to_string());
+    }
+
+    /// The maximum value: the max attribute when it is a positive number, 1 otherwise
+    pub fn max(&self, document: &Document) -> f64 {
+        document
+            .get_attribute(self.node, "max")
+            .and_then(parse_floating_point_number)
+            .filter(|&max| max > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Sets the max attribute, values that are not positive are ignored
+    pub fn set_max(&self, document: &mut Document, value: f64) {
+        if value > 0.0 && value.is_finite() {
+            document.set_attribute(self.node, "max", &value.to_string());
+        }
+    }
+
+    /// Whether the progress bar is indeterminate, which it is without a value attribute
+    pub fn is_indeterminate(&self, document: &Document) -> bool {
+        document.get_attribute(self.node, "value").is_none()
+    }
+
+    /// `value / max`, or -1 when the progress bar is indeterminate
+    pub fn position(&self, document: &Document) -> f64 {
+        if self.is_indeterminate(document) {
+            -1.0
+        } else {
+            self.value(document) / self.max(document)
+        }
+    }
+}
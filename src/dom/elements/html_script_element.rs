@@ -0,0 +1,232 @@
+// src/dom/elements/html_script_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+/// Essences of the JavaScript MIME types (MIME Sniffing, 4.6)
+const JAVASCRIPT_MIME_TYPES: &[&str] = &[
+    "application/ecmascript",
+    "application/javascript",
+    "application/x-ecmascript",
+    "application/x-javascript",
+    "text/ecmascript",
+    "text/javascript",
+    "text/javascript1.0",
+    "text/javascript1.1",
+    "text/javascript1.2",
+    "text/javascript1.3",
+    "text/javascript1.4",
+    "text/javascript1.5",
+    "text/jscript",
+    "text/livescript",
+    "text/x-ecmascript",
+    "text/x-javascript",
+];
+
+/// The referrer policies a referrerpolicy attribute may name (Referrer Policy, 3)
+const REFERRER_POLICIES: &[&str] = &[
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "same-origin",
+    "origin",
+    "strict-origin",
+    "origin-when-cross-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+/// What kind of script a script element holds, from its type and language attributes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptType {
+    Classic,
+    Module,
+    ImportMap,
+    /// A JSON data block, with the type as written
+    JsonModule(String),
+    /// A data block of any other type, which is never executed
+    Unknown(String),
+}
+
+pub struct HTMLScriptElement {
+    node: NodeId,
+}
+
+impl HTMLScriptElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLScriptElement { node }
+    }
+
+    /// The script element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The src attribute resolved against the document base URL, see
+    /// `Document::resolved_url`
+    pub fn src(&self, document: &Document) -> Option<String> {
+        document.resolved_url(self.node, "src")
+    }
+
+    pub fn set_src(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "src", value);
+    }
+
+    /// Reflects the type attribute, empty when it is missing. See `script_type` for what it
+    /// means, where a missing type and an empty one differ in the presence of a language
+    /// attribute.
+    pub fn r#type(&self, document: &Document) -> String {
+        self.attribute(document, "type")
+    }
+
+    pub fn set_type(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "type", value);
+    }
+
+    /// Reflects the nomodule attribute
+    pub fn no_module(&self, document: &Document) -> bool {
+        self.has_attribute(document, "nomodule")
+    }
+
+    pub fn set_no_module(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "nomodule", value);
+    }
+
+    /// Reflects the async attribute, a script with a src is not async without it
+    pub fn r#async(&self, document: &Document) -> bool {
+        self.has_attribute(document, "async")
+    }
+
+    pub fn set_async(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "async", value);
+    }
+
+    /// Reflects the defer attribute
+    pub fn defer(&self, document: &Document) -> bool {
+        self.has_attribute(document, "defer")
+    }
+
+    pub fn set_defer(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "defer", value);
+    }
+
+    /// The crossorigin attribute as "anonymous" or "use-credentials". None when it is
+    /// missing, "anonymous" when it is empty or invalid.
+    pub fn cross_origin(&self, document: &Document) -> Option<&'static str> {
+        let value = document.get_attribute(self.node, "crossorigin")?;
+        if value.eq_ignore_ascii_case("use-credentials") {
+            Some("use-credentials")
+        } else {
+            Some("anonymous")
+        }
+    }
+
+    /// Sets the crossorigin attribute, None removes it
+    pub fn set_cross_origin(&self, document: &mut Document, value: Option<&str>) {
+        match value {
+            Some(value) => document.set_attribute(self.node, "crossorigin", value),
+            None => document.remove_attribute(self.node, "crossorigin"),
+        }
+    }
+
+    /// The referrerpolicy attribute as one of the referrer policies, the empty string when
+    /// it is missing or invalid
+    pub fn referrer_policy(&self, document: &Document) -> &'static str {
+        let value = document
+            .get_attribute(self.node, "referrerpolicy")
+            .unwrap_or_default();
+        REFERRER_POLICIES
+            .iter()
+            .find(|policy| value.eq_ignore_ascii_case(policy))
+            .copied()
+            .unwrap_or("")
+    }
+
+    pub fn set_referrer_policy(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "referrerpolicy", value);
+    }
+
+    /// The child text content of the script, its source when it is inline. The text is
+    /// kept exactly as written, the tokenizer does not decode anything in script data.
+    pub fn text(&self, document: &Document) -> String {
+        let arena = document.arena();
+        arena
+            .children(self.node)
+            .filter_map(|child| arena.get(child).as_text())
+            .collect()
+    }
+
+    /// Replaces the children of the script with a text node holding `value`
+    pub fn set_text(&self, document: &mut Document, value: &str) {
+        document.set_text_content(self.node, value);
+    }
+
+    /// The script block's type as determined when preparing the script element: an empty or
+    /// missing type (without a language attribute) is classic, so is a type that is exactly a
+    /// JavaScript MIME type; parameters are not allowed, `text/javascript; charset=utf-8` is a
+    /// data block. The legacy language attribute stands for `text/<language>`.
+    pub fn script_type(&self, document: &Document) -> ScriptType {
+        let type_attribute = document.get_attribute(self.node, "type");
+        let language = document.get_attribute(self.node, "language");
+        let type_string = match (type_attribute, language) {
+            (Some(""), _) | (None, None) | (None, Some("")) => return ScriptType::Classic,
+            (Some(type_), _) => type_.to_string(),
+            (None, Some(language)) => format!("text/{language}"),
+        };
+        let type_string = type_string.trim_matches(|c: char| c.is_ascii_whitespace());
+        if JAVASCRIPT_MIME_TYPES
+            .iter()
+            .any(|mime_type| mime_type.eq_ignore_ascii_case(type_string))
+        {
+            ScriptType::Classic
+        } else if type_string.eq_ignore_ascii_case("module") {
+            ScriptType::Module
+        } else if type_string.eq_ignore_ascii_case("importmap") {
+            ScriptType::ImportMap
+        } else if is_json_mime_type(type_string) {
+            ScriptType::JsonModule(type_string.to_string())
+        } else {
+            ScriptType::Unknown(type_string.to_string())
+        }
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn has_attribute(&self, document: &Document, name: &str) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute(name))
+    }
+
+    fn set_boolean_attribute(&self, document: &mut Document, name: &str, value: bool) {
+        if value {
+            document.set_attribute(self.node, name, "");
+        } else {
+            document.remove_attribute(self.node, name);
+        }
+    }
+}
+
+/// Whether the essence of the MIME type `type_` (parameters and surrounding whitespace
+/// ignored, case-insensitive) is one of the JavaScript MIME types
+pub fn is_javascript_mime_type(type_: &str) -> bool {
+    let essence = mime_type_essence(type_);
+    JAVASCRIPT_MIME_TYPES.contains(&essence.as_str())
+}
+
+/// A JSON MIME type: `application/json`, `text/json` or any `+json` subtype
+fn is_json_mime_type(type_: &str) -> bool {
+    let essence = mime_type_essence(type_);
+    essence == "application/json" || essence == "text/json" || essence.ends_with("+json")
+}
+
+fn mime_type_essence(type_: &str) -> String {
+    let essence = type_.split(';').next().unwrap_or("");
+    essence
+        .trim_matches(|c: char| c.is_ascii_whitespace())
+        .to_ascii_lowercase()
+}
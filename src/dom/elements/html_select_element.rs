@@ -1,150 +1,226 @@
 // src/dom/elements/html_select_element.rs
 
-use crate::dom::elements::{HTMLElement, HTMLFormElement, HTMLOptionElement, HTMLOptGroupElement, HTMLOptionsCollection, HTMLCollection, ValidityState, NodeList};
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_form_element::{
+    form_owner, is_disabled_form_control, HTMLFormElement,
+};
+use crate::dom::elements::html_label_element::labels_of;
+use crate::dom::elements::html_option_element::HTMLOptionElement;
+use crate::dom::elements::html_options_collection::HTMLOptionsCollection;
+use crate::dom::elements::node_list::NodeList;
+use crate::dom::elements::reflect::{reflect_bool, reflect_string, reflect_u32};
+use crate::dom::node::NodeId;
 
-#[derive(Default)]
 pub struct HTMLSelectElement {
-    autocomplete: String,
-    disabled: bool,
-    form: Option<HTMLFormElement>,
-    multiple: bool,
-    name: String,
-    required: bool,
-    size: u32,
-    options: HTMLOptionsCollection,
-    length: u32,
-    selected_index: i32,
-    value: String,
-    will_validate: bool,
+    node: NodeId,
     validity: ValidityState,
     validation_message: String,
-    labels: NodeList,
 }
 
 impl HTMLSelectElement {
-    pub fn new() -> Self {
-        HTMLSelectElement::default()
+    pub fn new(node: NodeId) -> Self {
+        HTMLSelectElement {
+            node,
+            validity: ValidityState::default(),
+            validation_message: String::new(),
+        }
     }
 
-    pub fn autocomplete(&self) -> &str {
-        &self.autocomplete
+    /// The select element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
     }
 
-    pub fn set_autocomplete(&mut self, value: String) {
-        self.autocomplete = value;
-    }
+    reflect_string!(
+        /// Reflects the autocomplete attribute
+        autocomplete, set_autocomplete, "autocomplete"
+    );
 
-    pub fn disabled(&self) -> bool {
-        self.disabled
-    }
+    reflect_bool!(
+        /// Reflects the disabled attribute, see `is_disabled` for the state inherited from
+        /// fieldsets
+        disabled, set_disabled, "disabled"
+    );
 
-    pub fn set_disabled(&mut self, value: bool) {
-        self.disabled = value;
+    /// Whether the select is disabled, by its own attribute or a disabled fieldset ancestor
+    pub fn is_disabled(&self, document: &Document) -> bool {
+        is_disabled_form_control(document, self.node)
     }
 
-    pub fn form(&self) -> Option<&HTMLFormElement> {
-        self.form.as_ref()
+    /// The form owner of the select, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
     }
 
-    pub fn multiple(&self) -> bool {
-        self.multiple
-    }
+    reflect_bool!(
+        /// Reflects the multiple attribute
+        multiple, set_multiple, "multiple"
+    );
 
-    pub fn set_multiple(&mut self, value: bool) {
-        self.multiple = value;
-    }
+    reflect_string!(
+        /// Reflects the name attribute
+        name, set_name, "name"
+    );
 
-    pub fn name(&self) -> &str {
-        &self.name
-    }
+    reflect_bool!(
+        /// Reflects the required attribute
+        required, set_required, "required"
+    );
 
-    pub fn set_name(&mut self, value: String) {
-        self.name = value;
-    }
+    reflect_u32!(
+        /// Reflects the size attribute, 0 when it is missing or not a valid non-negative
+        /// integer. See `effective_size` for the number of rows actually shown.
+        size, set_size, "size", 0
+    );
 
-    pub fn required(&self) -> bool {
-        self.required
+    /// The display size: the size attribute when above 0, otherwise 4 for a multiple select
+    /// and 1 for the others
+    pub fn effective_size(&self, document: &Document) -> u32 {
+        match self.size(document) {
+            0 if self.multiple(document) => 4,
+            0 => 1,
+            size => size,
+        }
     }
 
-    pub fn set_required(&mut self, value: bool) {
-        self.required = value;
+    /// "select-multiple" when the multiple attribute is present, otherwise "select-one"
+    pub fn r#type(&self, document: &Document) -> &'static str {
+        if self.multiple(document) {
+            "select-multiple"
+        } else {
+            "select-one"
+        }
     }
 
-    pub fn size(&self) -> u32 {
-        self.size
+    /// Live collection of the options of this select: its option children and the option
+    /// children of its optgroup children, in tree order
+    pub fn options(&self) -> HTMLOptionsCollection {
+        HTMLOptionsCollection::new(self.node)
     }
 
-    pub fn set_size(&mut self, value: u32) {
-        self.size = value;
+    pub fn length(&self, document: &Document) -> u32 {
+        self.options().length(document)
     }
 
-    pub fn r#type(&self) -> &str {
-        "select-one" // or "select-multiple" based on `multiple`
+    /// See `HTMLOptionsCollection::set_length`
+    pub fn set_length(&self, document: &mut Document, value: u32) {
+        self.options().set_length(document, value);
     }
 
-    pub fn options(&self) -> &HTMLOptionsCollection {
-        &self.options
+    pub fn item(&self, document: &Document, index: u32) -> Option<NodeId> {
+        self.options().item(document, index as usize)
     }
 
-    pub fn length(&self) -> u32 {
-        self.length
+    /// The first option whose id, or failing that name, is `name`
+    pub fn named_item(&self, document: &Document, name: &str) -> Option<NodeId> {
+        self.options().named_item(document, name)
     }
 
-    pub fn set_length(&mut self, value: u32) {
-        self.length = value;
+    /// See `HTMLOptionsCollection::add`
+    pub fn add(
+        &self,
+        document: &mut Document,
+        element: NodeId,
+        before: Option<AddBefore>,
+    ) -> Result<(), DomError> {
+        self.options().add(document, element, before)
     }
 
-    // Methods for item and namedItem
-    pub fn item(&self, index: u32) -> Option<&HTMLOptionElement> {
-        // Return None as default implementation
-        None
+    /// Removes the select itself from its parent. This is the ChildNode `remove()` overload,
+    /// use `remove_at` to remove an option.
+    pub fn remove(&self, document: &mut Document) {
+        document.remove(self.node);
     }
 
-    pub fn named_item(&self, name: &str) -> Option<&HTMLOptionElement> {
-        // Return None as default implementation
-        None
+    /// Removes the option at `index` from its parent, nothing happens when there is none
+    pub fn remove_at(&self, document: &mut Document, index: i32) {
+        self.options().remove(document, index);
     }
 
-    // Method stubs for add, remove, set, and showPicker
-    pub fn add(&mut self, element: HTMLOptionElement, before: Option<HTMLOptGroupElement>) {
-        // Do nothing
+    /// See `HTMLOptionsCollection::set`
+    pub fn set_at(
+        &self,
+        document: &mut Document,
+        index: u32,
+        option: Option<NodeId>,
+    ) -> Result<(), DomError> {
+        self.options().set(document, index, option)
     }
 
-    pub fn remove(&mut self) {
-        // Do nothing
+    /// Live collection of the options of this select whose selectedness is set, in tree order
+    pub fn selected_options(&self) -> HTMLCollection {
+        HTMLCollection::new(self.node, CollectionFilter::SelectedOptions)
     }
 
-    pub fn remove_at(&mut self, index: i32) {
-        // Do nothing
+    /// Index of the first selected option, or -1 when no option is selected
+    pub fn selected_index(&self, document: &Document) -> i32 {
+        self.options().selected_index(document)
     }
 
-    pub fn set_at(&mut self, index: u32, option: Option<HTMLOptionElement>) {
-        // Do nothing
+    /// Selects the option at `index` and deselects the others, an index matching no option
+    /// deselects them all
+    pub fn set_selected_index(&self, document: &mut Document, index: i32) {
+        self.options().set_selected_index(document, index);
     }
 
-    pub fn selected_options(&self) -> &HTMLCollection {
-        // Placeholder for selected options
-        &HTMLCollection::default() // Adjust based on actual type definition
+    /// The value of the first selected option, or an empty string when none is selected
+    pub fn value(&self, document: &Document) -> String {
+        self.options()
+            .iter(document)
+            .find(|&option| document.option_selectedness(option))
+            .map(|option| HTMLOptionElement::new(option).value(document))
+            .unwrap_or_default()
     }
 
-    pub fn selected_index(&self) -> i32 {
-        self.selected_index
+    /// Selects the first option whose value is `value` and deselects the others
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        let options: Vec<NodeId> = self.options().iter(document).collect();
+        let mut found = false;
+        for option in options {
+            let selected = !found && HTMLOptionElement::new(option).value(document) == value;
+            found |= selected;
+            document.set_option_selectedness(option, selected);
+        }
     }
 
-    pub fn set_selected_index(&mut self, index: i32) {
-        self.selected_index = index;
+    /// The selectedness setting algorithm, run after parsing. Without the multiple attribute
+    /// only the last selected option stays selected, and when none is and the display size
+    /// is 1 the first option that is not disabled gets selected.
+    pub fn reset_selectedness(&self, document: &mut Document) {
+        if self.multiple(document) {
+            return;
+        }
+        let options: Vec<NodeId> = self.options().iter(document).collect();
+        let last_selected = options
+            .iter()
+            .rposition(|&option| document.option_selectedness(option));
+        let selected = match last_selected {
+            Some(index) => Some(index),
+            None if self.effective_size(document) == 1 => options
+                .iter()
+                .position(|&option| !HTMLOptionElement::new(option).disabled(document)),
+            None => None,
+        };
+        for (position, option) in options.into_iter().enumerate() {
+            document.set_option_selectedness(option, Some(position) == selected);
+        }
     }
 
-    pub fn value(&self) -> &str {
-        &self.value
+    /// The reset algorithm: every option goes back to its selected attribute, then the
+    /// selectedness setting algorithm runs
+    pub fn reset(&self, document: &mut Document) {
+        let options: Vec<NodeId> = self.options().iter(document).collect();
+        for option in options {
+            document.clear_option_selectedness(option);
+        }
+        self.reset_selectedness(document);
     }
-
-    pub fn set_value(&mut self, value: String) {
-        self.value = value;
-    }
-
-    pub fn will_validate(&self) -> bool {
-        self.will_validate
+
+    /// Whether the select is a candidate for constraint validation, disabled ones are not
+    pub fn will_validate(&self, document: &Document) -> bool {
+        !self.is_disabled(document)
     }
 
     pub fn validity(&self) -> &ValidityState {
@@ -173,33 +249,24 @@ impl HTMLSelectElement {
         // Do nothing
     }
 
-    pub fn labels(&self) -> &NodeList {
-        &self.labels
+    /// Static list of the label elements whose control is this select, see
+    /// `HTMLLabelElement::control`
+    pub fn labels(&self, document: &Document) -> NodeList {
+        labels_of(document, self.node)
     }
 }
 
+/// The `before` argument of `HTMLSelectElement::add`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddBefore {
+    Element(NodeId),
+    /// Index in the options of the select
+    Index(i32),
+}
+
 // Example implementations of other structs (skeletons only)
 #[derive(Default)]
 pub struct HTMLElement {}
 
-#[derive(Default)]
-pub struct HTMLFormElement {}
-
-#[derive(Default)]
-pub struct HTMLOptionElement {}
-
-#[derive(Default)]
-pub struct HTMLOptGroupElement {}
-
-#[derive(Default)]
-pub struct HTMLOptionsCollection {}
-
-#[derive(Default)]
-pub struct HTMLCollection {}
-
 #[derive(Default)]
 pub struct ValidityState {}
-
-#[derive(Default)]
-pub struct NodeList {}
-
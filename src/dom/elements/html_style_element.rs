@@ -0,0 +1,80 @@
+// src/dom/elements/html_style_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+pub struct HTMLStyleElement {
+    node: NodeId,
+}
+
+impl HTMLStyleElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLStyleElement { node }
+    }
+
+    /// The style element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Reflects the media attribute
+    pub fn media(&self, document: &Document) -> String {
+        self.attribute(document, "media")
+    }
+
+    pub fn set_media(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "media", value);
+    }
+
+    /// Reflects the type attribute
+    pub fn r#type(&self, document: &Document) -> String {
+        self.attribute(document, "type")
+    }
+
+    pub fn set_type(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "type", value);
+    }
+
+    /// Whether the contents are CSS: the type attribute is missing, empty or "text/css"
+    /// (ASCII case-insensitive). Other types, like "text/less", are never applied.
+    pub fn is_css(&self, document: &Document) -> bool {
+        match document.get_attribute(self.node, "type") {
+            Some(value) => value.is_empty() || value.eq_ignore_ascii_case("text/css"),
+            None => true,
+        }
+    }
+
+    /// Whether the style sheet of the element is disabled. The flag belongs to the style
+    /// sheet, not to an attribute, so it is not serialized and resets when the element is
+    /// cloned.
+    pub fn disabled(&self, document: &Document) -> bool {
+        document.style_sheet_disabled(self.node)
+    }
+
+    pub fn set_disabled(&self, document: &mut Document, value: bool) {
+        document.set_style_sheet_disabled(self.node, value);
+    }
+
+    /// The child text content of the element, the style sheet source. The tokenizer reads
+    /// style contents as RAWTEXT, so the text is exactly what came before the `</style>` end
+    /// tag, markup-like sequences included.
+    pub fn text(&self, document: &Document) -> String {
+        let arena = document.arena();
+        arena
+            .children(self.node)
+            .filter_map(|child| arena.get(child).as_text())
+            .collect()
+    }
+
+    /// Replaces the children of the element with a text node holding `value`
+    pub fn set_text(&self, document: &mut Document, value: &str) {
+        document.set_text_content(self.node, value);
+    }
+
+    fn attribute(&self, document: &Document, name: &str) -> String {
+        document
+            .get_attribute(self.node, name)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
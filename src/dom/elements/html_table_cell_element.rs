@@ -0,0 +1,105 @@
+// src/dom/elements/html_table_cell_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::dom_token_list::DOMTokenList;
+use crate::dom::elements::html_table_row_element::{index_in, HTMLTableRowElement};
+use crate::dom::microsyntax::parse_non_negative_integer;
+use crate::dom::node::NodeId;
+
+/// A td or th element
+pub struct HTMLTableCellElement {
+    node: NodeId,
+}
+
+impl HTMLTableCellElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLTableCellElement { node }
+    }
+
+    /// The td or th element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Index of the cell in the cells of its parent row, -1 when its parent is not a tr
+    pub fn cell_index(&self, document: &Document) -> i32 {
+        let row = document.arena().parent(self.node).filter(|&parent| {
+            document
+                .element(parent)
+                .is_some_and(|element| element.is_html_named("tr"))
+        });
+        match row {
+            Some(row) => index_in(document, &HTMLTableRowElement::new(row).cells(), self.node),
+            None => -1,
+        }
+    }
+
+    /// The colspan attribute clamped to 1..=1000, 1 when it is missing or invalid
+    pub fn col_span(&self, document: &Document) -> u32 {
+        self.clamped(document, "colspan", 1, 1000)
+    }
+
+    pub fn set_col_span(&self, document: &mut Document, value: u32) {
+        document.set_attribute(self.node, "colspan", &value.to_string());
+    }
+
+    /// The rowspan attribute clamped to 0..=65534, 1 when it is missing or invalid. 0 makes
+    /// the cell span the rest of its row group.
+    pub fn row_span(&self, document: &Document) -> u32 {
+        self.clamped(document, "rowspan", 0, 65534)
+    }
+
+    pub fn set_row_span(&self, document: &mut Document, value: u32) {
+        document.set_attribute(self.node, "rowspan", &value.to_string());
+    }
+
+    /// Token list over the headers attribute, the ids of the th cells of this cell
+    pub fn headers(&self) -> DOMTokenList {
+        DOMTokenList::new(self.node, "headers")
+    }
+
+    /// The scope attribute as one of "row", "col", "rowgroup" and "colgroup", the empty
+    /// string when it is missing or invalid
+    pub fn scope(&self, document: &Document) -> &'static str {
+        let value = document
+            .get_attribute(self.node, "scope")
+            .unwrap_or_default();
+        ["row", "col", "rowgroup", "colgroup"]
+            .into_iter()
+            .find(|keyword| value.eq_ignore_ascii_case(keyword))
+            .unwrap_or("")
+    }
+
+    pub fn set_scope(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "scope", value);
+    }
+
+    /// Reflects the abbr attribute
+    pub fn abbr(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "abbr")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_abbr(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "abbr", value);
+    }
+
+    fn clamped(&self, document: &Document, name: &str, min: u32, max: u32) -> u32 {
+        match document.get_attribute(self.node, name) {
+            // out of range numbers are clamped, not treated as invalid
+            Some(value) => parse_non_negative_integer(value)
+                .or_else(|| has_digits(value).then_some(max))
+                .map_or(1, |number| number.clamp(min, max)),
+            None => 1,
+        }
+    }
+}
+
+/// Whether `value` starts like a non-negative integer that was too large to parse
+fn has_digits(value: &str) -> bool {
+    let value = value.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let value = value.strip_prefix('+').unwrap_or(value);
+    value.starts_with(|c: char| c.is_ascii_digit())
+}
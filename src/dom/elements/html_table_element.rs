@@ -0,0 +1,186 @@
+// src/dom/elements/html_table_element.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::node::NodeId;
+
+pub struct HTMLTableElement {
+    node: NodeId,
+}
+
+impl HTMLTableElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLTableElement { node }
+    }
+
+    /// The table element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The first caption child of the table
+    pub fn caption(&self, document: &Document) -> Option<NodeId> {
+        self.first_child_named(document, "caption")
+    }
+
+    /// The caption of the table, created as its first child when it has none
+    pub fn create_caption(&self, document: &mut Document) -> NodeId {
+        if let Some(caption) = self.caption(document) {
+            return caption;
+        }
+        let caption = document.create_element("caption");
+        let first_child = document.arena().get(self.node).first_child();
+        self.insert(document, caption, first_child);
+        caption
+    }
+
+    /// Removes the first caption child of the table, if any
+    pub fn delete_caption(&self, document: &mut Document) {
+        if let Some(caption) = self.caption(document) {
+            document.remove(caption);
+        }
+    }
+
+    /// The first thead child of the table
+    pub fn t_head(&self, document: &Document) -> Option<NodeId> {
+        self.first_child_named(document, "thead")
+    }
+
+    /// The thead of the table, created when it has none before its first element child that
+    /// is not a caption or a colgroup
+    pub fn create_t_head(&self, document: &mut Document) -> NodeId {
+        if let Some(t_head) = self.t_head(document) {
+            return t_head;
+        }
+        let t_head = document.create_element("thead");
+        let reference = document.arena().children(self.node).find(|&child| {
+            document.element(child).is_some_and(|element| {
+                !element.is_html_named("caption") && !element.is_html_named("colgroup")
+            })
+        });
+        self.insert(document, t_head, reference);
+        t_head
+    }
+
+    /// Removes the first thead child of the table, if any
+    pub fn delete_t_head(&self, document: &mut Document) {
+        if let Some(t_head) = self.t_head(document) {
+            document.remove(t_head);
+        }
+    }
+
+    /// The first tfoot child of the table
+    pub fn t_foot(&self, document: &Document) -> Option<NodeId> {
+        self.first_child_named(document, "tfoot")
+    }
+
+    /// The tfoot of the table, created as its last child when it has none
+    pub fn create_t_foot(&self, document: &mut Document) -> NodeId {
+        if let Some(t_foot) = self.t_foot(document) {
+            return t_foot;
+        }
+        let t_foot = document.create_element("tfoot");
+        self.insert(document, t_foot, None);
+        t_foot
+    }
+
+    /// Removes the first tfoot child of the table, if any
+    pub fn delete_t_foot(&self, document: &mut Document) {
+        if let Some(t_foot) = self.t_foot(document) {
+            document.remove(t_foot);
+        }
+    }
+
+    /// Live collection of the tbody children of the table
+    pub fn t_bodies(&self) -> HTMLCollection {
+        HTMLCollection::new(
+            self.node,
+            CollectionFilter::HtmlChildren(vec!["tbody".to_string()]),
+        )
+    }
+
+    /// Creates a tbody and inserts it after the last tbody child of the table, or at its end
+    pub fn create_t_body(&self, document: &mut Document) -> NodeId {
+        let t_body = document.create_element("tbody");
+        let reference = self
+            .t_bodies()
+            .iter(document)
+            .last()
+            .and_then(|last| document.arena().get(last).next_sibling);
+        self.insert(document, t_body, reference);
+        t_body
+    }
+
+    /// Live collection of the rows of the table: the rows of its thead children, then its tr
+    /// children and the rows of its tbody children in tree order, then the rows of its tfoot
+    /// children
+    pub fn rows(&self) -> HTMLCollection {
+        HTMLCollection::new(self.node, CollectionFilter::TableRows)
+    }
+
+    /// Creates a tr and inserts it so that it becomes row `index` of `rows`, -1 appending it.
+    /// A table without rows gets it in its last tbody, created when there is none. Fails with
+    /// IndexSize when `index` is below -1 or above the number of rows.
+    pub fn insert_row(&self, document: &mut Document, index: i32) -> Result<NodeId, DomError> {
+        let rows: Vec<NodeId> = self.rows().iter(document).collect();
+        if index < -1 || index > rows.len() as i32 {
+            return Err(DomError::IndexSize);
+        }
+        let row = document.create_element("tr");
+        match rows.last() {
+            None => {
+                let t_body = match self.t_bodies().iter(document).last() {
+                    Some(t_body) => t_body,
+                    None => {
+                        let t_body = document.create_element("tbody");
+                        self.insert(document, t_body, None);
+                        t_body
+                    }
+                };
+                document.append_child(t_body, row)?;
+            }
+            Some(&last) if index == -1 || index as usize == rows.len() => {
+                let parent = document.arena().parent(last).unwrap_or(self.node);
+                document.append_child(parent, row)?;
+            }
+            Some(_) => {
+                let reference = rows[index as usize];
+                let parent = document.arena().parent(reference).unwrap_or(self.node);
+                document.insert_before(parent, row, Some(reference))?;
+            }
+        }
+        Ok(row)
+    }
+
+    /// Removes row `index` of `rows`, -1 removing the last row if there is one. Fails with
+    /// IndexSize when `index` is below -1 or not below the number of rows.
+    pub fn delete_row(&self, document: &mut Document, index: i32) -> Result<(), DomError> {
+        let rows = self.rows();
+        let row = match index {
+            -1 => match rows.iter(document).last() {
+                Some(row) => row,
+                None => return Ok(()),
+            },
+            index if index < 0 => return Err(DomError::IndexSize),
+            index => rows
+                .item(document, index as usize)
+                .ok_or(DomError::IndexSize)?,
+        };
+        document.remove(row);
+        Ok(())
+    }
+
+    fn first_child_named(&self, document: &Document, name: &str) -> Option<NodeId> {
+        document.arena().children(self.node).find(|&child| {
+            document
+                .element(child)
+                .is_some_and(|element| element.is_html_named(name))
+        })
+    }
+
+    /// Inserts an element the table created itself, which cannot fail
+    fn insert(&self, document: &mut Document, child: NodeId, reference: Option<NodeId>) {
+        document.insert_before(self.node, child, reference).ok();
+    }
+}
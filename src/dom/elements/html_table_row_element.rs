@@ -0,0 +1,113 @@
+// src/dom/elements/html_table_row_element.rs
+
+use crate::dom::arena::DomError;
+use crate::dom::document::Document;
+use crate::dom::elements::html_collection::{CollectionFilter, HTMLCollection};
+use crate::dom::elements::html_table_element::HTMLTableElement;
+use crate::dom::node::NodeId;
+
+pub struct HTMLTableRowElement {
+    node: NodeId,
+}
+
+impl HTMLTableRowElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLTableRowElement { node }
+    }
+
+    /// The tr element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// Live collection of the td and th children of the row
+    pub fn cells(&self) -> HTMLCollection {
+        HTMLCollection::new(
+            self.node,
+            CollectionFilter::HtmlChildren(vec!["td".to_string(), "th".to_string()]),
+        )
+    }
+
+    /// Creates a td and inserts it so that it becomes cell `index` of `cells`, -1 appending
+    /// it. Fails with IndexSize when `index` is below -1 or above the number of cells.
+    pub fn insert_cell(&self, document: &mut Document, index: i32) -> Result<NodeId, DomError> {
+        let cells: Vec<NodeId> = self.cells().iter(document).collect();
+        if index < -1 || index > cells.len() as i32 {
+            return Err(DomError::IndexSize);
+        }
+        let reference = cells.get(index as usize).copied().filter(|_| index != -1);
+        let cell = document.create_element("td");
+        document.insert_before(self.node, cell, reference)?;
+        Ok(cell)
+    }
+
+    /// Removes cell `index` of `cells`, -1 removing the last cell if there is one. Fails
+    /// with IndexSize when `index` is below -1 or not below the number of cells.
+    pub fn delete_cell(&self, document: &mut Document, index: i32) -> Result<(), DomError> {
+        let cells = self.cells();
+        let cell = match index {
+            -1 => match cells.iter(document).last() {
+                Some(cell) => cell,
+                None => return Ok(()),
+            },
+            index if index < 0 => return Err(DomError::IndexSize),
+            index => cells
+                .item(document, index as usize)
+                .ok_or(DomError::IndexSize)?,
+        };
+        document.remove(cell);
+        Ok(())
+    }
+
+    /// Index of the row in the rows of its table, -1 when it is not a row of a table
+    pub fn row_index(&self, document: &Document) -> i32 {
+        let arena = document.arena();
+        let table = arena.parent(self.node).and_then(|parent| {
+            if is_html_element(document, parent, &["table"]) {
+                Some(parent)
+            } else if is_html_element(document, parent, &["thead", "tbody", "tfoot"]) {
+                arena
+                    .parent(parent)
+                    .filter(|&grandparent| is_html_element(document, grandparent, &["table"]))
+            } else {
+                None
+            }
+        });
+        match table {
+            Some(table) => index_in(document, &HTMLTableElement::new(table).rows(), self.node),
+            None => -1,
+        }
+    }
+
+    /// Index of the row among the rows of its parent table or table section, -1 when it has
+    /// neither parent
+    pub fn section_row_index(&self, document: &Document) -> i32 {
+        match document.arena().parent(self.node) {
+            Some(parent) if is_html_element(document, parent, &["table"]) => {
+                index_in(document, &HTMLTableElement::new(parent).rows(), self.node)
+            }
+            Some(parent) if is_html_element(document, parent, &["thead", "tbody", "tfoot"]) => {
+                let rows = HTMLCollection::new(
+                    parent,
+                    CollectionFilter::HtmlChildren(vec!["tr".to_string()]),
+                );
+                index_in(document, &rows, self.node)
+            }
+            _ => -1,
+        }
+    }
+}
+
+fn is_html_element(document: &Document, node: NodeId, names: &[&str]) -> bool {
+    document
+        .element(node)
+        .is_some_and(|element| names.iter().any(|name| element.is_html_named(name)))
+}
+
+/// Position of `node` in `collection`, -1 when it is not a member
+pub(crate) fn index_in(document: &Document, collection: &HTMLCollection, node: NodeId) -> i32 {
+    collection
+        .iter(document)
+        .position(|member| member == node)
+        .map_or(-1, |index| index as i32)
+}
@@ -0,0 +1,29 @@
+// src/dom/elements/html_template_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+/// A template element and the DocumentFragment holding its contents. Children parsed inside
+/// `<template>` go to the fragment, so they are not part of the document tree.
+pub struct HTMLTemplateElement {
+    node: NodeId,
+    content: NodeId,
+}
+
+impl HTMLTemplateElement {
+    /// `None` when `node` is not an HTML template element of `document`
+    pub fn new(document: &Document, node: NodeId) -> Option<Self> {
+        let content = document.template_content(node)?;
+        Some(HTMLTemplateElement { node, content })
+    }
+
+    /// The template element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The DocumentFragment holding the template contents
+    pub fn content(&self) -> NodeId {
+        self.content
+    }
+}
@@ -0,0 +1,156 @@
+// src/dom/elements/html_text_area_element.rs
+
+use crate::dom::document::Document;
+use crate::dom::elements::html_form_element::{
+    form_owner, is_disabled_form_control, HTMLFormElement,
+};
+use crate::dom::elements::reflect::{reflect_non_negative_i32, reflect_positive_u32};
+use crate::dom::node::NodeId;
+
+pub struct HTMLTextAreaElement {
+    node: NodeId,
+}
+
+impl HTMLTextAreaElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLTextAreaElement { node }
+    }
+
+    /// The textarea element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The form owner of the textarea, see `form_owner`
+    pub fn form(&self, document: &Document) -> Option<HTMLFormElement> {
+        form_owner(document, self.node).map(HTMLFormElement::new)
+    }
+
+    /// The child text content of the textarea. The parser drops a line feed right after the
+    /// start tag, so markup starting the text on the next line does not show it.
+    pub fn default_value(&self, document: &Document) -> String {
+        let arena = document.arena();
+        arena
+            .children(self.node)
+            .filter_map(|child| arena.get(child).as_text())
+            .collect()
+    }
+
+    /// Replaces the children of the textarea with a text node holding `value`
+    pub fn set_default_value(&self, document: &mut Document, value: &str) {
+        document.set_text_content(self.node, value);
+    }
+
+    /// The raw value: the one set with `set_value`, or failing that the default value, which
+    /// keeps following the children until then
+    pub fn value(&self, document: &Document) -> String {
+        match document.control_value(self.node) {
+            Some(value) => value.to_string(),
+            None => self.default_value(document),
+        }
+    }
+
+    /// Sets the raw value with its line breaks normalized to line feeds and marks the
+    /// textarea dirty
+    pub fn set_value(&self, document: &mut Document, value: &str) {
+        let value = value.replace("\r\n", "\n").replace('\r', "\n");
+        document.set_control_value(self.node, value);
+    }
+
+    /// Length of the value in UTF-16 code units
+    pub fn text_length(&self, document: &Document) -> u32 {
+        self.value(document).encode_utf16().count() as u32
+    }
+
+    /// The reset algorithm: clears the dirty flag, the value follows the default value again
+    pub fn reset(&self, document: &mut Document) {
+        document.clear_control_value(self.node);
+    }
+
+    reflect_positive_u32!(
+        /// Reflects the rows attribute, 2 when it is missing, invalid or 0
+        rows, set_rows, "rows", 2
+    );
+
+    reflect_positive_u32!(
+        /// Reflects the cols attribute, 20 when it is missing, invalid or 0
+        cols, set_cols, "cols", 20
+    );
+
+    /// Reflects the wrap attribute, the empty string when it is missing
+    pub fn wrap(&self, document: &Document) -> String {
+        document
+            .get_attribute(self.node, "wrap")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn set_wrap(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "wrap", value);
+    }
+
+    reflect_non_negative_i32!(
+        /// Reflects the maxlength attribute, -1 when it is missing or not a valid non-negative
+        /// integer
+        max_length, set_max_length, "maxlength"
+    );
+
+    /// Reflects the readonly attribute
+    pub fn read_only(&self, document: &Document) -> bool {
+        self.has_attribute(document, "readonly")
+    }
+
+    pub fn set_read_only(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "readonly", value);
+    }
+
+    /// Reflects the required attribute
+    pub fn required(&self, document: &Document) -> bool {
+        self.has_attribute(document, "required")
+    }
+
+    pub fn set_required(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "required", value);
+    }
+
+    /// Reflects the disabled attribute, see `is_disabled` for the state inherited from
+    /// fieldsets
+    pub fn disabled(&self, document: &Document) -> bool {
+        self.has_attribute(document, "disabled")
+    }
+
+    pub fn set_disabled(&self, document: &mut Document, value: bool) {
+        self.set_boolean_attribute(document, "disabled", value);
+    }
+
+    /// Whether the textarea is disabled, by its own attribute or a disabled fieldset ancestor
+    pub fn is_disabled(&self, document: &Document) -> bool {
+        is_disabled_form_control(document, self.node)
+    }
+
+    /// Whether the textarea is a candidate for constraint validation: it must be neither
+    /// disabled nor read-only
+    pub fn will_validate(&self, document: &Document) -> bool {
+        !self.is_disabled(document) && !self.read_only(document)
+    }
+
+    /// The valueMissing validity state: a required textarea that is a candidate for
+    /// constraint validation and has an empty value
+    pub fn value_missing(&self, document: &Document) -> bool {
+        self.required(document) && self.will_validate(document) && self.value(document).is_empty()
+    }
+
+    fn has_attribute(&self, document: &Document, name: &str) -> bool {
+        document
+            .element(self.node)
+            .is_some_and(|element| element.has_attribute(name))
+    }
+
+    fn set_boolean_attribute(&self, document: &mut Document, name: &str, value: bool) {
+        if value {
+            document.set_attribute(self.node, name, "");
+        } else {
+            document.remove_attribute(self.node, name);
+        }
+    }
+}
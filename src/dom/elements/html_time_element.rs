@@ -0,0 +1,38 @@
+// src/dom/elements/html_time_element.rs
+
+use crate::dom::datetime::{parse_date_time_value, DateTimeValue};
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+
+pub struct HTMLTimeElement {
+    node: NodeId,
+}
+
+impl HTMLTimeElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLTimeElement { node }
+    }
+
+    /// The time element node this wraps
+    pub fn node(&self) -> NodeId {
+        self.node
+    }
+
+    /// The machine-readable value: the datetime attribute, or the text content without one
+    pub fn date_time(&self, document: &Document) -> String {
+        match document.get_attribute(self.node, "datetime") {
+            Some(value) => value.to_string(),
+            None => document.text_content(self.node),
+        }
+    }
+
+    pub fn set_date_time(&self, document: &mut Document, value: &str) {
+        document.set_attribute(self.node, "datetime", value);
+    }
+
+    /// `date_time` parsed as one of the date and time microsyntaxes, None when it is
+    /// valid for none of them
+    pub fn parsed(&self, document: &Document) -> Option<DateTimeValue> {
+        parse_date_time_value(&self.date_time(document))
+    }
+}
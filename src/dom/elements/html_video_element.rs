@@ -0,0 +1,42 @@
+// src/dom/elements/html_video_element.rs
+
+use crate::dom::elements::html_media_element::HTMLMediaElement;
+use crate::dom::elements::reflect::{reflect_u32, reflect_url};
+use crate::dom::node::NodeId;
+use std::ops::Deref;
+
+/// A video element: the media element API plus the dimensions and the poster frame
+pub struct HTMLVideoElement {
+    media: HTMLMediaElement,
+}
+
+impl HTMLVideoElement {
+    pub fn new(node: NodeId) -> Self {
+        HTMLVideoElement {
+            media: HTMLMediaElement::new(node),
+        }
+    }
+
+    reflect_u32!(
+        /// The width attribute, 0 when it is missing or not a valid non-negative integer
+        width, set_width, "width", 0
+    );
+
+    reflect_u32!(
+        /// The height attribute, 0 when it is missing or not a valid non-negative integer
+        height, set_height, "height", 0
+    );
+
+    reflect_url!(
+        /// The poster attribute resolved against the base URL, None when it is missing
+        poster, set_poster, "poster"
+    );
+}
+
+impl Deref for HTMLVideoElement {
+    type Target = HTMLMediaElement;
+
+    fn deref(&self) -> &HTMLMediaElement {
+        &self.media
+    }
+}
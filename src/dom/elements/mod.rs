@@ -0,0 +1,40 @@
+pub mod dom_token_list;
+pub mod element_factory;
+pub mod html_anchor_element;
+pub mod html_audio_element;
+pub mod html_button_element;
+pub mod html_canvas_element;
+pub mod html_collection;
+pub mod html_data_list_element;
+pub mod html_details_element;
+pub mod html_dialog_element;
+pub mod html_field_set_element;
+pub mod html_form_element;
+pub mod html_heading_element;
+pub mod html_label_element;
+pub mod html_li_element;
+pub mod html_link_element;
+pub mod html_media_element;
+pub mod html_meta_element;
+pub mod html_meter_element;
+pub mod html_iframe_element;
+pub mod html_image_element;
+pub mod html_input_element;
+pub mod html_o_list_element;
+pub mod html_opt_group_element;
+pub mod html_option_element;
+pub mod html_options_collection;
+pub mod html_output_element;
+pub mod html_progress_element;
+pub mod html_script_element;
+pub mod html_select_element;
+pub mod html_style_element;
+pub mod html_table_cell_element;
+pub mod html_table_element;
+pub mod html_table_row_element;
+pub mod html_template_element;
+pub mod html_text_area_element;
+pub mod html_time_element;
+pub mod html_video_element;
+pub mod node_list;
+mod reflect;
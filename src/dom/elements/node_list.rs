@@ -0,0 +1,171 @@
+// src/dom/elements/node_list.rs
+
+use crate::dom::document::Document;
+use crate::dom::node::NodeId;
+use std::slice;
+
+/// Where the members of a `NodeList` come from
+#[derive(Debug, Clone, PartialEq)]
+enum NodeListSource {
+    /// Fixed members, captured when the list was produced
+    Static(Vec<NodeId>),
+    /// The children of the node, recomputed on every access
+    ChildNodes(NodeId),
+}
+
+/// An ordered list of nodes.
+///
+/// Lists are either static or live depending on their producer, as in the DOM standard:
+/// - `Document::child_nodes` returns a live list, appends and removals made after the list was
+///   obtained are observed on the next access.
+/// - Snapshot producers (`NodeList::from_nodes`, e.g. for querySelectorAll, or `labels`) return
+///   a static list that never changes afterwards.
+///
+/// Like `HTMLCollection`, the list does not borrow the document, it is handed in on every
+/// access. `view` binds the two together for use with `for` loops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeList {
+    source: NodeListSource,
+}
+
+impl NodeList {
+    /// Static list holding exactly `nodes`
+    pub fn from_nodes(nodes: Vec<NodeId>) -> Self {
+        NodeList {
+            source: NodeListSource::Static(nodes),
+        }
+    }
+
+    /// Live list of the children of `parent`
+    pub fn child_nodes(parent: NodeId) -> Self {
+        NodeList {
+            source: NodeListSource::ChildNodes(parent),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        matches!(self.source, NodeListSource::ChildNodes(_))
+    }
+
+    /// Members of the list in order
+    pub fn iter<'a>(&'a self, document: &'a Document) -> NodeListIter<'a> {
+        match &self.source {
+            NodeListSource::Static(nodes) => NodeListIter::Static(nodes.iter()),
+            NodeListSource::ChildNodes(parent) => NodeListIter::Live {
+                next: document.arena().get(*parent).first_child(),
+                remaining: document.arena().children(*parent).count(),
+                document,
+            },
+        }
+    }
+
+    pub fn len(&self, document: &Document) -> usize {
+        match &self.source {
+            NodeListSource::Static(nodes) => nodes.len(),
+            NodeListSource::ChildNodes(parent) => document.arena().children(*parent).count(),
+        }
+    }
+
+    pub fn is_empty(&self, document: &Document) -> bool {
+        self.len(document) == 0
+    }
+
+    /// The node at `index`, or None when out of bounds
+    pub fn item(&self, document: &Document, index: usize) -> Option<NodeId> {
+        match &self.source {
+            NodeListSource::Static(nodes) => nodes.get(index).copied(),
+            NodeListSource::ChildNodes(parent) => document.arena().children(*parent).nth(index),
+        }
+    }
+
+    /// Binds the list to a document so it can be iterated with `for`
+    pub fn view<'a>(&'a self, document: &'a Document) -> NodeListView<'a> {
+        NodeListView {
+            list: self,
+            document,
+        }
+    }
+}
+
+/// Iterator over the members of a `NodeList`
+pub enum NodeListIter<'a> {
+    Static(slice::Iter<'a, NodeId>),
+    Live {
+        document: &'a Document,
+        next: Option<NodeId>,
+        remaining: usize,
+    },
+}
+
+impl Iterator for NodeListIter<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        match self {
+            NodeListIter::Static(nodes) => nodes.next().copied(),
+            NodeListIter::Live {
+                document,
+                next,
+                remaining,
+            } => {
+                let current = (*next)?;
+                *next = document.arena().get(current).next_sibling();
+                *remaining -= 1;
+                Some(current)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self {
+            NodeListIter::Static(nodes) => nodes.len(),
+            NodeListIter::Live { remaining, .. } => *remaining,
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for NodeListIter<'_> {}
+
+/// A `NodeList` together with the document it reads from
+#[derive(Clone, Copy)]
+pub struct NodeListView<'a> {
+    list: &'a NodeList,
+    document: &'a Document,
+}
+
+impl<'a> NodeListView<'a> {
+    pub fn len(&self) -> usize {
+        self.list.len(self.document)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty(self.document)
+    }
+
+    pub fn item(&self, index: usize) -> Option<NodeId> {
+        self.list.item(self.document, index)
+    }
+
+    pub fn iter(&self) -> NodeListIter<'a> {
+        self.list.iter(self.document)
+    }
+}
+
+impl<'a> IntoIterator for NodeListView<'a> {
+    type Item = NodeId;
+    type IntoIter = NodeListIter<'a>;
+
+    fn into_iter(self) -> NodeListIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &NodeListView<'a> {
+    type Item = NodeId;
+    type IntoIter = NodeListIter<'a>;
+
+    fn into_iter(self) -> NodeListIter<'a> {
+        self.iter()
+    }
+}
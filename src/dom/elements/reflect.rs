@@ -0,0 +1,182 @@
+// src/dom/elements/reflect.rs
+
+//! Macros writing the getter and setter of a reflected content attribute inside the `impl`
+//! of an element wrapper. They take the getter and setter names, the attribute name and,
+//! before them, the doc comment of the getter. The wrapper needs a `node()` method.
+
+/// A string attribute, empty when it is missing
+macro_rules! reflect_string {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> String {
+            document
+                .get_attribute(self.node(), $attribute)
+                .unwrap_or_default()
+                .to_string()
+        }
+
+        pub fn $setter(&self, document: &mut $crate::dom::document::Document, value: &str) {
+            document.set_attribute(self.node(), $attribute, value);
+        }
+    };
+}
+
+/// A boolean attribute, true when it is present whatever its value. Setting false removes it.
+macro_rules! reflect_bool {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> bool {
+            document
+                .element(self.node())
+                .is_some_and(|element| element.has_attribute($attribute))
+        }
+
+        pub fn $setter(&self, document: &mut $crate::dom::document::Document, value: bool) {
+            if value {
+                document.set_attribute(self.node(), $attribute, "");
+            } else {
+                document.remove_attribute(self.node(), $attribute);
+            }
+        }
+    };
+}
+
+/// An unsigned long attribute read with the rules for parsing non-negative integers, `default`
+/// when it is missing or invalid. Values above 2147483647 are set as `default`.
+macro_rules! reflect_u32 {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal, $default:expr) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> u32 {
+            document
+                .get_attribute(self.node(), $attribute)
+                .and_then($crate::dom::microsyntax::parse_non_negative_integer)
+                .unwrap_or($default)
+        }
+
+        #[doc = concat!(
+            "Sets the ", $attribute, " attribute, values above 2147483647 set it to ",
+            stringify!($default)
+        )]
+        pub fn $setter(&self, document: &mut $crate::dom::document::Document, value: u32) {
+            let value =
+                if value > $crate::dom::microsyntax::MAX_REFLECTED_UNSIGNED {
+                    $default
+                } else {
+                    value
+                };
+            document.set_attribute(self.node(), $attribute, &value.to_string());
+        }
+    };
+}
+
+/// An unsigned long attribute limited to positive numbers, `default` when it is missing,
+/// invalid or 0. Setting 0 is an IndexSize error and values above 2147483647 set `default`.
+macro_rules! reflect_positive_u32 {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal, $default:expr) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> u32 {
+            document
+                .get_attribute(self.node(), $attribute)
+                .and_then($crate::dom::microsyntax::parse_non_negative_integer)
+                .filter(|&value| value > 0)
+                .unwrap_or($default)
+        }
+
+        #[doc = concat!(
+            "Sets the ", $attribute, " attribute, 0 is an IndexSize error and values above ",
+            "2147483647 set it to ", stringify!($default)
+        )]
+        pub fn $setter(
+            &self,
+            document: &mut $crate::dom::document::Document,
+            value: u32,
+        ) -> Result<(), $crate::dom::arena::DomError> {
+            if value == 0 {
+                return Err($crate::dom::arena::DomError::IndexSize);
+            }
+            let value =
+                if value > $crate::dom::microsyntax::MAX_REFLECTED_UNSIGNED {
+                    $default
+                } else {
+                    value
+                };
+            document.set_attribute(self.node(), $attribute, &value.to_string());
+            Ok(())
+        }
+    };
+}
+
+/// A long attribute limited to non-negative numbers, -1 when it is missing or not a valid
+/// non-negative integer. Setting a negative value is an IndexSize error.
+macro_rules! reflect_non_negative_i32 {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> i32 {
+            document
+                .get_attribute(self.node(), $attribute)
+                .and_then($crate::dom::microsyntax::parse_non_negative_integer)
+                .map_or(-1, |value| value as i32)
+        }
+
+        #[doc = concat!(
+            "Sets the ", $attribute, " attribute, negative values are an IndexSize error"
+        )]
+        pub fn $setter(
+            &self,
+            document: &mut $crate::dom::document::Document,
+            value: i32,
+        ) -> Result<(), $crate::dom::arena::DomError> {
+            if value < 0 {
+                return Err($crate::dom::arena::DomError::IndexSize);
+            }
+            document.set_attribute(self.node(), $attribute, &value.to_string());
+            Ok(())
+        }
+    };
+}
+
+/// An enumerated attribute, limited to the known `keywords` which match ASCII
+/// case-insensitively. The getter gives the matching keyword, `default` when the attribute is
+/// missing or matches none. The setter writes the value as given.
+macro_rules! reflect_enum {
+    (
+        $(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal,
+        [$($keyword:literal),+ $(,)?], $default:literal
+    ) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> &'static str {
+            document
+                .get_attribute(self.node(), $attribute)
+                .and_then(|value| {
+                    [$($keyword),+]
+                        .into_iter()
+                        .find(|keyword| value.eq_ignore_ascii_case(keyword))
+                })
+                .unwrap_or($default)
+        }
+
+        pub fn $setter(&self, document: &mut $crate::dom::document::Document, value: &str) {
+            document.set_attribute(self.node(), $attribute, value);
+        }
+    };
+}
+
+/// A URL attribute, resolved against the base URL as `Document::resolved_url` does. None when
+/// it is missing.
+macro_rules! reflect_url {
+    ($(#[$doc:meta])* $getter:ident, $setter:ident, $attribute:literal) => {
+        $(#[$doc])*
+        pub fn $getter(&self, document: &$crate::dom::document::Document) -> Option<String> {
+            document.resolved_url(self.node(), $attribute)
+        }
+
+        pub fn $setter(&self, document: &mut $crate::dom::document::Document, value: &str) {
+            document.set_attribute(self.node(), $attribute, value);
+        }
+    };
+}
+
+pub(crate) use {
+    reflect_bool, reflect_enum, reflect_non_negative_i32, reflect_positive_u32, reflect_string,
+    reflect_u32, reflect_url,
+};
@@ -1,7 +1,6 @@
 use serde::{Deserialize};
 use serde_json::Result;
 use std::collections::HashMap;
-use std::fs;
 use once_cell::sync::Lazy; // Use sync::Lazy for thread-safe access
 
 #[derive(Debug, Deserialize)]
@@ -12,13 +11,16 @@ pub struct Entity {
 
 pub type EntityMap = HashMap<String, Entity>;
 
+/// The entity table is compiled in, so lookups do not depend on the working directory
+static ENTITIES_JSON: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/dom/entities.json"));
+
 pub static ENTITIES: Lazy<EntityMap> = Lazy::new(|| {
-    load_entities("./src/dom/entities.json").expect("Failed to load entities.json")
+    load_entities(ENTITIES_JSON).expect("Failed to load entities.json")
 });
 
-fn load_entities(file_path: &str) -> Result<EntityMap> {
-    let file_content = fs::read_to_string(file_path).unwrap();
-    let mut entities: EntityMap = serde_json::from_str(&file_content)?;
+fn load_entities(file_content: &str) -> Result<EntityMap> {
+    let mut entities: EntityMap = serde_json::from_str(file_content)?;
 
     entities = entities.into_iter()
         .map(|(k, v)| {
@@ -29,3 +31,78 @@ fn load_entities(file_path: &str) -> Result<EntityMap> {
 
     Ok(entities)
 }
+
+/// Length of the longest entity name, semicolon included
+pub static MAX_ENTITY_NAME_LENGTH: Lazy<usize> =
+    Lazy::new(|| ENTITIES.keys().map(String::len).max().unwrap_or(0));
+
+/// Finds the longest entity name at the start of `input` and returns its length with the
+/// entity. Names are runs of ASCII alphanumerics, optionally followed by a semicolon: the
+/// longest candidate is tried first, then one character less at a time, so `&notin;` wins
+/// over `&not` and `&notit;` falls back to `&not`. The semicolon-less names in the table are
+/// the historical ones, so only those match without a semicolon.
+pub fn longest_entity_match(input: &[u8]) -> Option<(usize, &'static Entity)> {
+    let max_length = *MAX_ENTITY_NAME_LENGTH;
+    let mut candidate_length = input
+        .iter()
+        .take(max_length)
+        .take_while(|byte| byte.is_ascii_alphanumeric())
+        .count();
+    if candidate_length < max_length && input.get(candidate_length) == Some(&b';') {
+        candidate_length += 1;
+    }
+    let entities: &'static EntityMap = &ENTITIES;
+    (1..=candidate_length).rev().find_map(|length| {
+        // the candidate is ASCII, so every prefix is valid UTF-8
+        let name = std::str::from_utf8(&input[..length]).ok()?;
+        entities.get(name).map(|entity| (length, entity))
+    })
+}
+
+/// Largest edit distance at which `closest_entity_name` still suggests a name
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// The entity name closest to `name`, e.g. `nbsp` for `nbps`, to suggest for an unknown named
+/// character reference. Only names starting with the same letter and at most 2 edits away
+/// count, an edit being an insertion, a deletion, a substitution or the swap of two adjacent
+/// letters. Names are given without the `&` and the semicolon.
+pub fn closest_entity_name(name: &str) -> Option<&'static str> {
+    let first = name.chars().next()?;
+    let entities: &'static EntityMap = &ENTITIES;
+    entities
+        .keys()
+        .filter_map(|key| key.strip_suffix(';'))
+        .filter(|candidate| {
+            candidate.starts_with(first)
+                && candidate.len().abs_diff(name.len()) <= MAX_SUGGESTION_DISTANCE
+        })
+        .filter_map(|candidate| {
+            let distance = edit_distance(name.as_bytes(), candidate.as_bytes());
+            (distance <= MAX_SUGGESTION_DISTANCE).then_some((distance, candidate))
+        })
+        // the table is a HashMap, ties go to the first name in byte order to stay stable
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Optimal string alignment distance: Levenshtein with adjacent transpositions
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
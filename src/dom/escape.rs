@@ -0,0 +1,96 @@
+use crate::dom::entities::ENTITIES;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// How characters are escaped when serializing text and attribute values.
+///
+/// Every policy escapes what the HTML serialization algorithm requires: `&`, `<`, `>` and
+/// U+00A0 in text, `&`, `"` and U+00A0 in attribute values. The policies differ in what
+/// happens to the remaining non-ASCII characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscapePolicy {
+    /// Only the required escapes, other characters are written as-is
+    #[default]
+    Minimal,
+    /// Non-ASCII characters that have a named character reference are written with it, the
+    /// others as numeric references, so the output is plain ASCII
+    NamedEntities,
+    /// All non-ASCII characters are written as hexadecimal references (`&#x1F600;`)
+    NumericNonAscii,
+}
+
+/// Reverse of the named character reference table: for each character representable by a
+/// single code point entity, the shortest name, lowercase preferred (`&amp;` over `&AMP;`)
+static ENTITY_NAMES: Lazy<HashMap<char, &'static str>> = Lazy::new(|| {
+    let mut names: HashMap<char, &'static str> = HashMap::new();
+    for (name, entity) in ENTITIES.iter() {
+        let ([code_point], true) = (entity.codepoints.as_slice(), name.ends_with(';')) else {
+            continue;
+        };
+        let Some(c) = char::from_u32(*code_point) else {
+            continue;
+        };
+        let rank = |name: &str| {
+            (
+                name.len(),
+                name.starts_with(|c: char| c.is_ascii_uppercase()),
+                name.to_string(),
+            )
+        };
+        match names.get(&c) {
+            Some(existing) if rank(existing) <= rank(name) => {}
+            _ => {
+                names.insert(c, name.as_str());
+            }
+        }
+    }
+    names
+});
+
+impl EscapePolicy {
+    pub fn escape_text(self, text: &str) -> String {
+        self.escape(text, |c| matches!(c, '&' | '<' | '>'))
+    }
+
+    pub fn escape_attribute_value(self, value: &str) -> String {
+        self.escape(value, |c| matches!(c, '&' | '"'))
+    }
+
+    fn escape(self, input: &str, required: impl Fn(char) -> bool) -> String {
+        let mut escaped = String::with_capacity(input.len());
+        for c in input.chars() {
+            match c {
+                '&' | '<' | '>' | '"' if required(c) => escaped.push_str(required_escape(c)),
+                '\u{A0}' => escaped.push_str("&nbsp;"),
+                c if c.is_ascii() || self == EscapePolicy::Minimal => escaped.push(c),
+                c => match (self, ENTITY_NAMES.get(&c)) {
+                    (EscapePolicy::NamedEntities, Some(name)) => {
+                        escaped.push('&');
+                        escaped.push_str(name);
+                    }
+                    _ => escaped.push_str(&format!("&#x{:X};", c as u32)),
+                },
+            }
+        }
+        escaped
+    }
+}
+
+fn required_escape(c: char) -> &'static str {
+    match c {
+        '&' => "&amp;",
+        '<' => "&lt;",
+        '>' => "&gt;",
+        _ => "&quot;",
+    }
+}
+
+/// Escapes text content with the minimal policy
+pub fn escape_text(text: &str) -> String {
+    EscapePolicy::Minimal.escape_text(text)
+}
+
+/// Escapes a double quoted attribute value with the minimal policy
+pub fn escape_attribute_value(value: &str) -> String {
+    EscapePolicy::Minimal.escape_attribute_value(value)
+}
@@ -0,0 +1,218 @@
+use crate::dom::arena::NodeIdMap;
+use crate::dom::node::NodeId;
+use std::fmt;
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
+
+/// Where an event is in its dispatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventPhase {
+    /// Not being dispatched
+    #[default]
+    None,
+    /// Going down from the root to the parent of the target
+    Capturing,
+    AtTarget,
+    /// Going back up from the parent of the target to the root
+    Bubbling,
+}
+
+/// The flags an event is created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventInit {
+    pub bubbles: bool,
+    pub cancelable: bool,
+    pub composed: bool,
+}
+
+/// An event dispatched with `Document::dispatch_event`. Listeners receive it mutably, to stop
+/// its propagation or cancel it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub type_: String,
+    pub bubbles: bool,
+    pub cancelable: bool,
+    /// Kept for API parity, there are no shadow trees to cross
+    pub composed: bool,
+    default_prevented: bool,
+    target: Option<NodeId>,
+    current_target: Option<NodeId>,
+    phase: EventPhase,
+    stop_propagation: bool,
+    stop_immediate_propagation: bool,
+}
+
+impl Event {
+    pub fn new(type_: &str, init: EventInit) -> Self {
+        Event {
+            type_: type_.to_string(),
+            bubbles: init.bubbles,
+            cancelable: init.cancelable,
+            composed: init.composed,
+            default_prevented: false,
+            target: None,
+            current_target: None,
+            phase: EventPhase::None,
+            stop_propagation: false,
+            stop_immediate_propagation: false,
+        }
+    }
+
+    /// The node the event was dispatched to
+    pub fn target(&self) -> Option<NodeId> {
+        self.target
+    }
+
+    /// The node whose listeners are being called
+    pub fn current_target(&self) -> Option<NodeId> {
+        self.current_target
+    }
+
+    pub fn event_phase(&self) -> EventPhase {
+        self.phase
+    }
+
+    /// Stops the event once the listeners of the current node have been called
+    pub fn stop_propagation(&mut self) {
+        self.stop_propagation = true;
+    }
+
+    /// Stops the event right away, the remaining listeners of the current node included
+    pub fn stop_immediate_propagation(&mut self) {
+        self.stop_propagation = true;
+        self.stop_immediate_propagation = true;
+    }
+
+    /// Cancels the event, which only has an effect when it is cancelable
+    pub fn prevent_default(&mut self) {
+        if self.cancelable {
+            self.default_prevented = true;
+        }
+    }
+
+    pub fn default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
+/// Handle of a listener added with `Document::add_event_listener`, used to remove it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventListenerId(usize);
+
+// same bounds as the mutation observer callbacks, the document stays Send + Sync
+type EventCallback = Arc<dyn Fn(&mut Event) + Send + Sync + RefUnwindSafe>;
+
+#[derive(Clone)]
+struct Listener {
+    target: NodeId,
+    type_: String,
+    capture: bool,
+    callback: EventCallback,
+}
+
+/// The event listeners of a document, in the order they were added
+#[derive(Clone, Default)]
+pub(crate) struct EventListeners {
+    /// Indexed by `EventListenerId`, None once removed
+    listeners: Vec<Option<Listener>>,
+}
+
+impl fmt::Debug for EventListeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.listeners
+                    .iter()
+                    .flatten()
+                    .map(|listener| (listener.target, &listener.type_, listener.capture)),
+            )
+            .finish()
+    }
+}
+
+impl EventListeners {
+    pub(crate) fn add(
+        &mut self,
+        target: NodeId,
+        type_: &str,
+        capture: bool,
+        callback: impl Fn(&mut Event) + Send + Sync + RefUnwindSafe + 'static,
+    ) -> EventListenerId {
+        self.listeners.push(Some(Listener {
+            target,
+            type_: type_.to_string(),
+            capture,
+            callback: Arc::new(callback),
+        }));
+        EventListenerId(self.listeners.len() - 1)
+    }
+
+    pub(crate) fn remove(&mut self, id: EventListenerId) {
+        if let Some(listener) = self.listeners.get_mut(id.0) {
+            *listener = None;
+        }
+    }
+
+    /// Dispatches `event` to `target` along `path`, the inclusive ancestors of the target
+    /// from the target up. Returns false when a listener canceled the event.
+    pub(crate) fn dispatch(&self, event: &mut Event, path: &[NodeId]) -> bool {
+        let Some(&target) = path.first() else {
+            return true;
+        };
+        event.target = Some(target);
+        event.stop_propagation = false;
+        event.stop_immediate_propagation = false;
+
+        event.phase = EventPhase::Capturing;
+        for &node in path[1..].iter().rev() {
+            self.invoke(event, node, |listener| listener.capture);
+        }
+        event.phase = EventPhase::AtTarget;
+        self.invoke(event, target, |listener| listener.capture);
+        self.invoke(event, target, |listener| !listener.capture);
+        if event.bubbles {
+            event.phase = EventPhase::Bubbling;
+            for &node in &path[1..] {
+                self.invoke(event, node, |listener| !listener.capture);
+            }
+        }
+
+        event.phase = EventPhase::None;
+        event.current_target = None;
+        !event.default_prevented
+    }
+
+    fn invoke(&self, event: &mut Event, node: NodeId, phase: impl Fn(&Listener) -> bool) {
+        if event.stop_propagation {
+            return;
+        }
+        event.current_target = Some(node);
+        // a listener may rename the event, the ones called are picked beforehand
+        let listeners: Vec<&Listener> = self
+            .listeners
+            .iter()
+            .flatten()
+            .filter(|listener| {
+                listener.target == node && listener.type_ == event.type_ && phase(listener)
+            })
+            .collect();
+        for listener in listeners {
+            (listener.callback)(event);
+            if event.stop_immediate_propagation {
+                return;
+            }
+        }
+    }
+
+    /// Drops the listeners of the nodes removed by a compaction and retargets the others
+    pub(crate) fn remap(&mut self, map: &NodeIdMap) {
+        for slot in &mut self.listeners {
+            *slot = slot.take().and_then(|listener| {
+                Some(Listener {
+                    target: map.get(listener.target)?,
+                    ..listener
+                })
+            });
+        }
+    }
+}
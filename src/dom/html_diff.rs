@@ -0,0 +1,153 @@
+use crate::dom::canonicalizer::canonicalize;
+use crate::dom::parser::html_parser::parse_document;
+
+/// Number of unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Unified diff of two HTML documents. Both strings are parsed and canonicalized first, so the
+/// diff is taken between the resulting DOM trees rather than the raw sources: changes that do
+/// not affect the tree (attribute order, quoting, insignificant whitespace) are not reported.
+/// Returns an empty string when the trees are identical.
+pub fn html_diff(before: &str, after: &str) -> String {
+    let before = canonicalize(&parse_document(before));
+    let after = canonicalize(&parse_document(after));
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+    let edits = myers_diff(&before_lines, &after_lines);
+    format_unified(&edits)
+}
+
+/// Shortest edit script between `a` and `b` (Myers, "An O(ND) Difference Algorithm")
+fn myers_diff<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<Edit<'a>> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max as isize {
+        trace.push(v.clone());
+        let mut k = -d;
+        while k <= d {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+            k += 2;
+        }
+    }
+
+    // walk the trace backwards to recover the path
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let index = (k + offset) as usize;
+        let prev_k = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(a[x as usize]));
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(b[prev_y as usize]));
+            } else {
+                edits.push(Edit::Delete(a[prev_x as usize]));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    edits
+}
+
+fn format_unified(edits: &[Edit]) -> String {
+    let mut output = String::new();
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Equal(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let Some(&first_change) = changes.first() else {
+        return output;
+    };
+
+    // group changes whose context windows touch into hunks of edit indices
+    let mut hunks = Vec::new();
+    let mut start = first_change.saturating_sub(CONTEXT_LINES);
+    let mut end = first_change;
+    for &change in &changes[1..] {
+        if change - end > 2 * CONTEXT_LINES {
+            hunks.push((start, (end + CONTEXT_LINES + 1).min(edits.len())));
+            start = change - CONTEXT_LINES;
+        }
+        end = change;
+    }
+    hunks.push((start, (end + CONTEXT_LINES + 1).min(edits.len())));
+
+    for (start, end) in hunks {
+        let mut old_start = 1;
+        let mut new_start = 1;
+        for edit in &edits[..start] {
+            match edit {
+                Edit::Equal(_) => {
+                    old_start += 1;
+                    new_start += 1;
+                }
+                Edit::Delete(_) => old_start += 1,
+                Edit::Insert(_) => new_start += 1,
+            }
+        }
+        let hunk = &edits[start..end];
+        let old_len = hunk.iter().filter(|e| !matches!(e, Edit::Insert(_))).count();
+        let new_len = hunk.iter().filter(|e| !matches!(e, Edit::Delete(_))).count();
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_len == 0 { old_start - 1 } else { old_start },
+            old_len,
+            if new_len == 0 { new_start - 1 } else { new_start },
+            new_len
+        ));
+        for edit in hunk {
+            let (prefix, line) = match edit {
+                Edit::Equal(line) => (' ', line),
+                Edit::Delete(line) => ('-', line),
+                Edit::Insert(line) => ('+', line),
+            };
+            output.push(prefix);
+            output.push(' ');
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
@@ -0,0 +1,83 @@
+// src/dom/microsyntax.rs
+
+//! Common microsyntaxes of the HTML standard shared by the element interfaces
+
+/// ASCII whitespace: tab, line feed, form feed, carriage return and space
+pub(crate) const ASCII_WHITESPACE: &[char] = &['\t', '\n', '\x0C', '\r', ' '];
+
+/// Largest value of an unsigned long reflected attribute
+pub(crate) const MAX_REFLECTED_UNSIGNED: u32 = i32::MAX as u32;
+
+/// The rules for parsing integers: leading whitespace, an optional sign and digits, anything
+/// after the digits is ignored. None when there are no digits or the value overflows.
+pub(crate) fn parse_integer(value: &str) -> Option<i32> {
+    let value = value.trim_start_matches(ASCII_WHITESPACE);
+    let (negative, value) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let magnitude = value[..end].parse::<i64>().ok()?;
+    i32::try_from(if negative { -magnitude } else { magnitude }).ok()
+}
+
+/// The rules for parsing non-negative integers: the rules for parsing integers with negative
+/// values rejected, so `-0` is 0. None without digits or beyond the range of an unsigned long
+/// reflected attribute.
+pub(crate) fn parse_non_negative_integer(value: &str) -> Option<u32> {
+    parse_integer(value).and_then(|number| u32::try_from(number).ok())
+}
+
+/// The rules for parsing floating-point number values: leading ASCII whitespace and a sign
+/// are skipped, then an integer part, a fraction and an exponent are read as far as they go,
+/// trailing garbage ignored. An exponent marker without digits ends the number, so "1e" is 1.
+/// None without digits or when the result is not finite.
+pub(crate) fn parse_floating_point_number(input: &str) -> Option<f64> {
+    let input = input.trim_start_matches(ASCII_WHITESPACE).as_bytes();
+    let mut position = 0;
+    let mut sign = 1.0;
+    match input.first() {
+        Some(b'-') => {
+            sign = -1.0;
+            position += 1;
+        }
+        Some(b'+') => position += 1,
+        _ => {}
+    }
+    let digits = |from: usize| {
+        input[from..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+    let integer_digits = digits(position);
+    let fraction_digits = match input.get(position + integer_digits) {
+        Some(b'.') => digits(position + integer_digits + 1),
+        _ => 0,
+    };
+    if integer_digits == 0 && fraction_digits == 0 {
+        return None;
+    }
+    let mut end = position + integer_digits;
+    if fraction_digits > 0 {
+        end += 1 + fraction_digits;
+    }
+    // the exponent only counts when it has digits
+    if let Some(b'e' | b'E') = input.get(end) {
+        let mut exponent_start = end + 1;
+        if let Some(b'-' | b'+') = input.get(exponent_start) {
+            exponent_start += 1;
+        }
+        let exponent_digits = digits(exponent_start);
+        if exponent_digits > 0 {
+            end = exponent_start + exponent_digits;
+        }
+    }
+    let number = std::str::from_utf8(&input[position..end]).ok()?;
+    let value = sign * number.parse::<f64>().ok()?;
+    // -0 becomes 0
+    Some(value + 0.0).filter(|value| value.is_finite())
+}
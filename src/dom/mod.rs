@@ -1,3 +1,22 @@
 pub mod parser;
 pub mod entities;
-pub mod elements;
\ No newline at end of file
+pub mod elements;
+pub mod node;
+pub mod arena;
+pub mod document;
+pub mod escape;
+pub mod serializer;
+pub mod url;
+pub mod datetime;
+pub mod canonicalizer;
+pub mod html_diff;
+pub mod aria;
+pub mod rewriter;
+pub mod sanitizer;
+pub mod testing;
+pub mod style;
+pub mod tree_walker;
+pub mod mutation_observer;
+pub mod event;
+pub mod doctype;
+pub mod microsyntax;
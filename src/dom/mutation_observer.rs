@@ -0,0 +1,253 @@
+use crate::dom::arena::NodeIdMap;
+use crate::dom::node::NodeId;
+use std::fmt;
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
+
+/// The kind of change a `MutationRecord` describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationType {
+    /// Nodes were added to or removed from the children of the target
+    ChildList,
+    /// An attribute of the target element was set or removed
+    Attributes,
+}
+
+/// One change, as handed to the callback of an observer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationRecord {
+    pub type_: MutationType,
+    pub target: NodeId,
+    pub added_nodes: Vec<NodeId>,
+    pub removed_nodes: Vec<NodeId>,
+    pub attribute_name: Option<String>,
+    /// The previous value of the attribute, when the observer asked for it
+    pub old_value: Option<String>,
+}
+
+/// Which changes of the observed node an observer is told about, and with `subtree` the
+/// changes of its descendants as well. `attribute_old_value` and `attribute_filter` imply
+/// `attributes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MutationObserverInit {
+    pub child_list: bool,
+    pub attributes: bool,
+    pub attribute_old_value: bool,
+    /// Only the attributes with these names, when set
+    pub attribute_filter: Option<Vec<String>>,
+    pub subtree: bool,
+}
+
+impl MutationObserverInit {
+    fn wants_attribute(&self, name: &str) -> bool {
+        (self.attributes || self.attribute_old_value || self.attribute_filter.is_some())
+            && self
+                .attribute_filter
+                .as_ref()
+                .is_none_or(|filter| filter.iter().any(|n| n == name))
+    }
+}
+
+/// Handle of an observer added to an arena with `NodeArena::add_mutation_observer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MutationObserverId(usize);
+
+// Send + Sync and unwind safety keep the same guarantees for the document holding it
+type MutationCallback = Arc<dyn Fn(Vec<MutationRecord>) + Send + Sync + RefUnwindSafe>;
+
+/// Collects records about the changes made to the nodes it observes, and hands them to its
+/// callback when the arena delivers mutation records. Only changes made through the arena
+/// and the attribute methods of `Document` are seen, not edits through `element_mut`.
+///
+/// Moving nodes around queues one child list record per moved node, a fragment insertion
+/// included.
+#[derive(Clone)]
+pub struct MutationObserver {
+    callback: MutationCallback,
+    registrations: Vec<(NodeId, MutationObserverInit)>,
+    records: Vec<MutationRecord>,
+}
+
+impl fmt::Debug for MutationObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutationObserver")
+            .field("registrations", &self.registrations)
+            .field("records", &self.records)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MutationObserver {
+    pub fn new(
+        callback: impl Fn(Vec<MutationRecord>) + Send + Sync + RefUnwindSafe + 'static,
+    ) -> Self {
+        MutationObserver {
+            callback: Arc::new(callback),
+            registrations: Vec::new(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Starts observing `target`, replacing the options when it is already observed
+    pub fn observe(&mut self, target: NodeId, options: MutationObserverInit) {
+        match self
+            .registrations
+            .iter_mut()
+            .find(|(node, _)| *node == target)
+        {
+            Some(registration) => registration.1 = options,
+            None => self.registrations.push((target, options)),
+        }
+    }
+
+    /// Stops observing every node and drops the records not delivered yet
+    pub fn disconnect(&mut self) {
+        self.registrations.clear();
+        self.records.clear();
+    }
+
+    /// The records not delivered yet, which will not be delivered any more
+    pub fn take_records(&mut self) -> Vec<MutationRecord> {
+        std::mem::take(&mut self.records)
+    }
+
+    /// Queues a record for a change of `target` if a registration on `target`, or on one of
+    /// its `ancestors` with `subtree`, wants it. `wants` returns None when a registration
+    /// does not want the change, otherwise whether it asks for the old value.
+    fn queue(
+        &mut self,
+        target: NodeId,
+        ancestors: &[NodeId],
+        wants: impl Fn(&MutationObserverInit) -> Option<bool>,
+        record: impl FnOnce(bool) -> MutationRecord,
+    ) {
+        let mut interested = None;
+        for (node, options) in &self.registrations {
+            if *node != target && !(options.subtree && ancestors.contains(node)) {
+                continue;
+            }
+            if let Some(old_value) = wants(options) {
+                interested = Some(interested.unwrap_or(false) || old_value);
+            }
+        }
+        if let Some(old_value) = interested {
+            self.records.push(record(old_value));
+        }
+    }
+
+    fn remap(&mut self, map: &NodeIdMap) {
+        self.registrations = self
+            .registrations
+            .drain(..)
+            .filter_map(|(node, options)| Some((map.get(node)?, options)))
+            .collect();
+        let remap_all =
+            |nodes: Vec<NodeId>| nodes.into_iter().filter_map(|node| map.get(node)).collect();
+        self.records = self
+            .records
+            .drain(..)
+            .filter_map(|record| {
+                Some(MutationRecord {
+                    target: map.get(record.target)?,
+                    added_nodes: remap_all(record.added_nodes),
+                    removed_nodes: remap_all(record.removed_nodes),
+                    ..record
+                })
+            })
+            .collect();
+    }
+}
+
+/// The observers added to an arena
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MutationObservers {
+    observers: Vec<MutationObserver>,
+}
+
+impl MutationObservers {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    pub(crate) fn add(&mut self, observer: MutationObserver) -> MutationObserverId {
+        self.observers.push(observer);
+        MutationObserverId(self.observers.len() - 1)
+    }
+
+    pub(crate) fn get(&self, id: MutationObserverId) -> &MutationObserver {
+        &self.observers[id.0]
+    }
+
+    pub(crate) fn get_mut(&mut self, id: MutationObserverId) -> &mut MutationObserver {
+        &mut self.observers[id.0]
+    }
+
+    pub(crate) fn queue_child_list(
+        &mut self,
+        target: NodeId,
+        ancestors: &[NodeId],
+        added: Option<NodeId>,
+        removed: Option<NodeId>,
+    ) {
+        for observer in &mut self.observers {
+            observer.queue(
+                target,
+                ancestors,
+                |options| options.child_list.then_some(false),
+                |_| MutationRecord {
+                    type_: MutationType::ChildList,
+                    target,
+                    added_nodes: added.into_iter().collect(),
+                    removed_nodes: removed.into_iter().collect(),
+                    attribute_name: None,
+                    old_value: None,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn queue_attribute(
+        &mut self,
+        target: NodeId,
+        ancestors: &[NodeId],
+        name: &str,
+        old_value: Option<&str>,
+    ) {
+        for observer in &mut self.observers {
+            observer.queue(
+                target,
+                ancestors,
+                |options| {
+                    options
+                        .wants_attribute(name)
+                        .then_some(options.attribute_old_value)
+                },
+                |with_old_value| MutationRecord {
+                    type_: MutationType::Attributes,
+                    target,
+                    added_nodes: Vec::new(),
+                    removed_nodes: Vec::new(),
+                    attribute_name: Some(name.to_string()),
+                    old_value: old_value.filter(|_| with_old_value).map(str::to_string),
+                },
+            );
+        }
+    }
+
+    /// Hands each observer with pending records its records, in the order the observers
+    /// were added
+    pub(crate) fn deliver(&mut self) {
+        for observer in &mut self.observers {
+            let records = observer.take_records();
+            if !records.is_empty() {
+                (observer.callback)(records);
+            }
+        }
+    }
+
+    pub(crate) fn remap(&mut self, map: &NodeIdMap) {
+        for observer in &mut self.observers {
+            observer.remap(map);
+        }
+    }
+}
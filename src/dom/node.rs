@@ -0,0 +1,367 @@
+use crate::dom::elements::element_factory::{html_element_for, ElementKind};
+use crate::helper::small_str::SmallStr;
+
+/// Index of a node inside its `NodeArena`, tagged with the generation of the arena when the
+/// id was handed out. Compacting the arena moves nodes and starts a new generation, so ids
+/// from before are rejected instead of silently pointing at another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    pub(crate) index: usize,
+    pub(crate) generation: u32,
+}
+
+impl NodeId {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeData {
+    Document,
+    /// A parentless container, its children are moved out when it is inserted somewhere
+    DocumentFragment,
+    Doctype {
+        name: String,
+        public_id: String,
+        system_id: String,
+    },
+    Element(Element),
+    Text(String),
+    Comment(String),
+}
+
+/// A node of the tree, linked to its relatives by `NodeId`
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub(crate) parent: Option<NodeId>,
+    pub(crate) prev_sibling: Option<NodeId>,
+    pub(crate) next_sibling: Option<NodeId>,
+    pub(crate) first_child: Option<NodeId>,
+    pub(crate) last_child: Option<NodeId>,
+    pub data: NodeData,
+}
+
+impl Node {
+    pub fn new(data: NodeData) -> Self {
+        Node {
+            parent: None,
+            prev_sibling: None,
+            next_sibling: None,
+            first_child: None,
+            last_child: None,
+            data,
+        }
+    }
+
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    pub fn prev_sibling(&self) -> Option<NodeId> {
+        self.prev_sibling
+    }
+
+    pub fn next_sibling(&self) -> Option<NodeId> {
+        self.next_sibling
+    }
+
+    pub fn first_child(&self) -> Option<NodeId> {
+        self.first_child
+    }
+
+    pub fn last_child(&self) -> Option<NodeId> {
+        self.last_child
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.data {
+            NodeData::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_doctype(&self) -> Option<DocumentType<'_>> {
+        match &self.data {
+            NodeData::Doctype {
+                name,
+                public_id,
+                system_id,
+            } => Some(DocumentType {
+                name,
+                public_id,
+                system_id,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn as_element(&self) -> Option<&Element> {
+        match &self.data {
+            NodeData::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    pub fn as_element_mut(&mut self) -> Option<&mut Element> {
+        match &mut self.data {
+            NodeData::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    pub fn is_element(&self) -> bool {
+        matches!(self.data, NodeData::Element(_))
+    }
+
+    /// Checks whether this is an HTML element with the given (lowercase) name
+    pub fn is_element_named(&self, name: &str) -> bool {
+        self.as_element().is_some_and(|e| e.is_html_named(name))
+    }
+
+    /// Checks whether this is an element with the given namespace and local name
+    pub fn is_element_in(&self, namespace: Namespace, local_name: &str) -> bool {
+        self.as_element()
+            .is_some_and(|e| e.namespace == namespace && e.name == local_name)
+    }
+
+    /// Only documents, fragments and elements may have children
+    pub fn can_have_children(&self) -> bool {
+        matches!(
+            self.data,
+            NodeData::Document | NodeData::DocumentFragment | NodeData::Element(_)
+        )
+    }
+
+    pub fn is_document_fragment(&self) -> bool {
+        matches!(self.data, NodeData::DocumentFragment)
+    }
+}
+
+/// Borrowed view of a doctype node. As in the DOM, a missing identifier is the empty string:
+/// `<!DOCTYPE html>` and `<!DOCTYPE html PUBLIC "">` both have an empty public id, and the
+/// serializer only writes non-empty identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentType<'a> {
+    pub name: &'a str,
+    pub public_id: &'a str,
+    pub system_id: &'a str,
+}
+
+/// Namespaces elements and attributes can live in (13.2.6.5 and the Infra standard)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Namespace {
+    #[default]
+    Html,
+    MathMl,
+    Svg,
+    XLink,
+    Xml,
+    XmlNs,
+}
+
+impl Namespace {
+    pub fn url(&self) -> &'static str {
+        match self {
+            Namespace::Html => "http://www.w3.org/1999/xhtml",
+            Namespace::MathMl => "http://www.w3.org/1998/Math/MathML",
+            Namespace::Svg => "http://www.w3.org/2000/svg",
+            Namespace::XLink => "http://www.w3.org/1999/xlink",
+            Namespace::Xml => "http://www.w3.org/XML/1998/namespace",
+            Namespace::XmlNs => "http://www.w3.org/2000/xmlns/",
+        }
+    }
+}
+
+/// An attribute of an element. Attributes set by HTML markup have no prefix and no namespace,
+/// foreign attributes such as `xlink:href` keep both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attribute {
+    pub prefix: Option<String>,
+    pub local_name: String,
+    pub namespace: Option<Namespace>,
+    pub value: String,
+}
+
+impl Attribute {
+    /// Attribute in no namespace
+    pub fn new(name: &str, value: &str) -> Self {
+        Attribute {
+            prefix: None,
+            local_name: name.to_string(),
+            namespace: None,
+            value: value.to_string(),
+        }
+    }
+
+    pub fn new_ns(prefix: Option<&str>, local_name: &str, namespace: Namespace, value: &str) -> Self {
+        Attribute {
+            prefix: prefix.map(str::to_string),
+            local_name: local_name.to_string(),
+            namespace: Some(namespace),
+            value: value.to_string(),
+        }
+    }
+
+    /// `prefix:local_name`, or just the local name when there is no prefix
+    pub fn qualified_name(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}:{}", self.local_name),
+            None => self.local_name.clone(),
+        }
+    }
+
+    fn has_qualified_name(&self, name: &str) -> bool {
+        match &self.prefix {
+            Some(prefix) => name
+                .strip_prefix(prefix.as_str())
+                .and_then(|rest| rest.strip_prefix(':'))
+                .is_some_and(|local_name| local_name == self.local_name),
+            None => self.local_name == name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Element {
+    /// The local name, lowercase for HTML elements
+    pub name: String,
+    pub namespace: Namespace,
+    pub attributes: Vec<Attribute>,
+    /// Decided from the name and namespace when the element is created
+    kind: ElementKind,
+}
+
+impl Element {
+    /// HTML element
+    pub fn new(name: &str) -> Self {
+        Element::with_attributes(name, Vec::new())
+    }
+
+    /// HTML element with attributes in no namespace
+    pub fn with_attributes(name: &str, attributes: Vec<(SmallStr, String)>) -> Self {
+        let name = name.to_ascii_lowercase();
+        Element {
+            kind: html_element_for(&name),
+            name,
+            namespace: Namespace::Html,
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| Attribute::new(name, value))
+                .collect(),
+        }
+    }
+
+    /// Element in any namespace, the local name is kept as given
+    pub fn new_ns(namespace: Namespace, local_name: &str, attributes: Vec<Attribute>) -> Self {
+        let kind = match namespace {
+            Namespace::Html => html_element_for(local_name),
+            _ => ElementKind::Foreign,
+        };
+        Element {
+            name: local_name.to_string(),
+            namespace,
+            attributes,
+            kind,
+        }
+    }
+
+    /// The interface of the element, see `html_element_for`
+    pub fn kind(&self) -> ElementKind {
+        self.kind
+    }
+
+    pub fn local_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn namespace(&self) -> Namespace {
+        self.namespace
+    }
+
+    pub fn is_html(&self) -> bool {
+        self.namespace == Namespace::Html
+    }
+
+    /// Checks whether this is an HTML element with the given local name
+    pub fn is_html_named(&self, name: &str) -> bool {
+        self.is_html() && self.name == name
+    }
+
+    /// Value of the first attribute whose qualified name is `name`
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.has_qualified_name(name))
+            .map(|attr| attr.value.as_str())
+    }
+
+    pub fn get_attribute_ns(&self, namespace: Option<Namespace>, local_name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.namespace == namespace && attr.local_name == local_name)
+            .map(|attr| attr.value.as_str())
+    }
+
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes.iter().any(|attr| attr.has_qualified_name(name))
+    }
+
+    /// Sets the value of an attribute, appending it when it does not exist yet.
+    /// Names are lowercased on HTML elements.
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        let name = if self.is_html() {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_string()
+        };
+        match self.attributes.iter_mut().find(|attr| attr.has_qualified_name(&name)) {
+            Some(attr) => attr.value = value.to_string(),
+            None => self.attributes.push(Attribute::new(&name, value)),
+        }
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) {
+        self.attributes.retain(|attr| !attr.has_qualified_name(name));
+    }
+
+    /// The class attribute split on ASCII whitespace
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.get_attribute("class")
+            .unwrap_or("")
+            .split_ascii_whitespace()
+    }
+
+    pub fn has_class(&self, class_name: &str) -> bool {
+        self.classes().any(|c| c == class_name)
+    }
+
+    /// Reflects the id attribute, empty when absent
+    pub fn id(&self) -> &str {
+        self.get_attribute("id").unwrap_or("")
+    }
+
+    /// Sets the id attribute. An empty id removes the attribute instead of leaving `id=""`,
+    /// which would never match `get_element_by_id` anyway.
+    pub fn set_id(&mut self, id: &str) {
+        self.set_or_remove_attribute("id", id);
+    }
+
+    /// Reflects the class attribute, empty when absent
+    pub fn class_name(&self) -> &str {
+        self.get_attribute("class").unwrap_or("")
+    }
+
+    /// Sets the class attribute. An empty value removes the attribute, like `set_id`.
+    pub fn set_class_name(&mut self, class_name: &str) {
+        self.set_or_remove_attribute("class", class_name);
+    }
+
+    fn set_or_remove_attribute(&mut self, name: &str, value: &str) {
+        if value.is_empty() {
+            self.remove_attribute(name);
+        } else {
+            self.set_attribute(name, value);
+        }
+    }
+}
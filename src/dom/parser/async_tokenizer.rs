@@ -0,0 +1,57 @@
+use crate::dom::parser::tokenizer::{Token, Tokenizer};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio_stream::Stream;
+
+/// Size of the reads from the underlying source
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Tokenizes HTML read from `reader` in 8 KB chunks, yielding tokens as soon as the input read
+/// so far allows, so e.g. an HTTP response body does not have to be buffered first. The stream
+/// ends after `Token::EOF`, or after the first read error.
+pub async fn tokenize_async<R: AsyncRead + Unpin>(
+    reader: R,
+) -> impl Stream<Item = io::Result<Token>> {
+    TokenStream {
+        reader,
+        tokenizer: Tokenizer::streaming(),
+        buffer: vec![0; READ_CHUNK_SIZE].into_boxed_slice(),
+        finished: false,
+    }
+}
+
+struct TokenStream<R> {
+    reader: R,
+    tokenizer: Tokenizer<'static>,
+    buffer: Box<[u8]>,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> Stream for TokenStream<R> {
+    type Item = io::Result<Token>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.finished {
+                return Poll::Ready(None);
+            }
+            if let Some(token) = this.tokenizer.next_available_token() {
+                this.finished = token == Token::EOF;
+                return Poll::Ready(Some(Ok(token)));
+            }
+            let mut read_buf = ReadBuf::new(&mut this.buffer);
+            match Pin::new(&mut this.reader).poll_read(cx, &mut read_buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(error)) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+                Poll::Ready(Ok(())) if read_buf.filled().is_empty() => this.tokenizer.end_input(),
+                Poll::Ready(Ok(())) => this.tokenizer.feed(read_buf.filled()),
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// src/dom/parser/encoding.rs
+
+//! Turning the bytes of a document into the text the tokenizer reads: the encoding sniffing
+//! algorithm of the HTML standard and the decoders of the encodings it can pick.
+
+use crate::dom::elements::html_meta_element::extract_charset_from_meta;
+
+/// How many bytes the prescan looks at for a meta element declaring the encoding
+pub const PRESCAN_LENGTH: usize = 1024;
+
+/// A character encoding of the Encoding Standard that documents can be decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Also what the iso-8859-1 and us-ascii labels stand for
+    Windows1252,
+}
+
+impl Encoding {
+    /// The encoding an encoding label stands for, the label being matched ASCII
+    /// case-insensitively after trimming whitespace. None for labels of encodings that
+    /// cannot be decoded here.
+    pub fn for_label(label: &str) -> Option<Self> {
+        let label = label
+            .trim_matches(|c: char| c.is_ascii_whitespace())
+            .to_ascii_lowercase();
+        match label.as_str() {
+            "unicode-1-1-utf-8" | "unicode11utf8" | "unicode20utf8" | "utf-8" | "utf8"
+            | "x-unicode20utf8" => Some(Encoding::Utf8),
+            "unicodefffe" | "utf-16be" => Some(Encoding::Utf16Be),
+            "csunicode" | "iso-10646-ucs-2" | "ucs-2" | "unicode" | "unicodefeff" | "utf-16"
+            | "utf-16le" => Some(Encoding::Utf16Le),
+            "ansi_x3.4-1968" | "ascii" | "cp1252" | "cp819" | "csisolatin1" | "ibm819"
+            | "iso-8859-1" | "iso-ir-100" | "iso8859-1" | "iso88591" | "iso_8859-1"
+            | "iso_8859-1:1987" | "l1" | "latin1" | "us-ascii" | "windows-1252" | "x-cp1252" => {
+                Some(Encoding::Windows1252)
+            }
+            _ => None,
+        }
+    }
+
+    /// The name of the encoding in the Encoding Standard, as `document.characterSet` gives it
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Windows1252 => "windows-1252",
+        }
+    }
+
+    /// Decodes `bytes`, malformed sequences become U+FFFD
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Encoding::Utf16Le => decode_utf16(bytes, u16::from_le_bytes),
+            Encoding::Utf16Be => decode_utf16(bytes, u16::from_be_bytes),
+            Encoding::Windows1252 => bytes.iter().map(|&byte| windows_1252_char(byte)).collect(),
+        }
+    }
+}
+
+/// The characters of the bytes 0x80 to 0x9F in windows-1252, the other bytes are the code
+/// point of the same value. The five bytes windows-1252 leaves undefined map to C1 controls.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+/// The encoding a meta element declaring `label` makes the document decode with. A UTF-16
+/// label means UTF-8, since the declaration could be read as ASCII, and x-user-defined means
+/// windows-1252. None for labels that name no encoding decodable here.
+pub fn declared_encoding(label: &str) -> Option<Encoding> {
+    if label
+        .trim_matches(|c: char| c.is_ascii_whitespace())
+        .eq_ignore_ascii_case("x-user-defined")
+    {
+        return Some(Encoding::Windows1252);
+    }
+    match Encoding::for_label(label)? {
+        Encoding::Utf16Le | Encoding::Utf16Be => Some(Encoding::Utf8),
+        encoding => Some(encoding),
+    }
+}
+
+/// An encoding label given by the caller that names no encoding decodable here, see
+/// `Encoding::for_label`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEncodingLabel(pub String);
+
+/// How sure the parser is of the encoding it decodes with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    /// A guess a meta element met while parsing may still change
+    Tentative,
+    /// Given by a byte order mark, the transport layer or the caller
+    Certain,
+}
+
+/// The encoding given by a byte order mark at the start of `bytes`, with the length of the
+/// mark
+pub fn sniff_bom(bytes: &[u8]) -> Option<(Encoding, usize)> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Some((Encoding::Utf8, 3)),
+        [0xFF, 0xFE, ..] => Some((Encoding::Utf16Le, 2)),
+        [0xFE, 0xFF, ..] => Some((Encoding::Utf16Be, 2)),
+        _ => None,
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let chunks = bytes.chunks_exact(2);
+    let odd_byte = !chunks.remainder().is_empty();
+    let units = chunks.map(|pair| unit([pair[0], pair[1]]));
+    let mut text: String = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if odd_byte {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
+
+/// What the prescan found in the first `PRESCAN_LENGTH` bytes of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrescanDeclaration {
+    /// The encoding declared by the first meta element naming a known one, see
+    /// `declared_encoding`
+    Encoding(Encoding),
+    /// No meta element named a known encoding, this is the label of the first one that
+    /// named an unknown encoding
+    UnknownLabel(String),
+}
+
+/// Prescans the first `PRESCAN_LENGTH` bytes of a document for a meta element declaring its
+/// encoding and returns that encoding, see `prescan_declaration`. When the metas only
+/// declare unknown labels the result is windows-1252.
+pub fn prescan(bytes: &[u8]) -> Option<Encoding> {
+    prescan_declaration(bytes).map(|declaration| match declaration {
+        PrescanDeclaration::Encoding(encoding) => encoding,
+        PrescanDeclaration::UnknownLabel(_) => Encoding::Windows1252,
+    })
+}
+
+/// Prescans the first `PRESCAN_LENGTH` bytes of a document for a meta element declaring its
+/// encoding, with `charset` or with `http-equiv="content-type"` and a `content` holding a
+/// charset parameter. Comments are skipped and so are the attributes of other tags. A meta
+/// whose label names no known encoding does not end the prescan, it is only remembered in
+/// case no later meta declares a known one.
+pub fn prescan_declaration(bytes: &[u8]) -> Option<PrescanDeclaration> {
+    let bytes = &bytes[..bytes.len().min(PRESCAN_LENGTH)];
+    let mut unknown_label = None;
+    let mut position = 0;
+    while position < bytes.len() {
+        let rest = &bytes[position..];
+        if rest.starts_with(b"<!--") {
+            // the dashes of `<!--` may close it too, as in `<!-->`
+            match find(&bytes[position + 2..], b"-->") {
+                Some(end) => position += 2 + end + 2,
+                None => break,
+            }
+        } else if starts_with_ignore_case(rest, b"<meta")
+            && rest
+                .get(5)
+                .is_some_and(|&byte| is_space(byte) || byte == b'/')
+        {
+            position += 5;
+            if let Some(label) = meta_label(bytes, &mut position) {
+                match declared_encoding(&label) {
+                    Some(encoding) => return Some(PrescanDeclaration::Encoding(encoding)),
+                    None => {
+                        unknown_label.get_or_insert(label);
+                    }
+                }
+            }
+            if position >= bytes.len() {
+                break;
+            }
+        } else if rest.len() > 2
+            && rest[0] == b'<'
+            && (rest[1].is_ascii_alphabetic() || (rest[1] == b'/' && rest[2].is_ascii_alphabetic()))
+        {
+            position += bytes[position..]
+                .iter()
+                .position(|&byte| is_space(byte) || byte == b'>')
+                .unwrap_or(bytes.len() - position);
+            while get_attribute(bytes, &mut position).is_some() {}
+            if position >= bytes.len() {
+                break;
+            }
+        } else if rest.starts_with(b"<!") || rest.starts_with(b"</") || rest.starts_with(b"<?") {
+            match bytes[position..].iter().position(|&byte| byte == b'>') {
+                Some(end) => position += end,
+                None => break,
+            }
+        }
+        position += 1;
+    }
+    unknown_label.map(PrescanDeclaration::UnknownLabel)
+}
+
+/// Reads the attributes of a meta tag, `position` being just past `<meta`, and returns the
+/// encoding label they declare. `position` is left on the `>`, or past the end of `bytes` when the
+/// tag is cut off.
+fn meta_label(bytes: &[u8], position: &mut usize) -> Option<String> {
+    let mut seen = Vec::new();
+    let mut got_pragma = false;
+    // None until a charset or a content attribute declared one, then whether the declaration
+    // needs an http-equiv pragma
+    let mut need_pragma = None;
+    let mut charset = None;
+    while let Some((name, value)) = get_attribute(bytes, position) {
+        if seen.contains(&name) {
+            continue;
+        }
+        match name.as_str() {
+            "http-equiv" => got_pragma |= value == "content-type",
+            "content" if charset.is_none() => {
+                if let Some(label) = extract_charset_from_meta(&value) {
+                    charset = Some(label);
+                    need_pragma = Some(true);
+                }
+            }
+            "charset" => {
+                charset = Some(value);
+                need_pragma = Some(false);
+            }
+            _ => {}
+        }
+        seen.push(name);
+    }
+    if *position >= bytes.len() {
+        return None;
+    }
+    match need_pragma {
+        Some(true) if !got_pragma => None,
+        Some(_) => charset,
+        None => None,
+    }
+}
+
+/// The get an attribute algorithm of the prescan: reads the next attribute of a tag,
+/// lowercased, leaving `position` just after it. None at the `>` ending the tag and at the
+/// end of `bytes`.
+fn get_attribute(bytes: &[u8], position: &mut usize) -> Option<(String, String)> {
+    let at = |position: usize| bytes.get(position).copied();
+    while at(*position).is_some_and(|byte| is_space(byte) || byte == b'/') {
+        *position += 1;
+    }
+    if at(*position)? == b'>' {
+        return None;
+    }
+    let mut name = Vec::new();
+    let mut value = Vec::new();
+    loop {
+        match at(*position)? {
+            b'=' if !name.is_empty() => {
+                *position += 1;
+                break;
+            }
+            byte if is_space(byte) => {
+                while at(*position).is_some_and(is_space) {
+                    *position += 1;
+                }
+                if at(*position)? != b'=' {
+                    return Some((lossy(&name), String::new()));
+                }
+                *position += 1;
+                break;
+            }
+            b'/' | b'>' => return Some((lossy(&name), String::new())),
+            byte => name.push(byte.to_ascii_lowercase()),
+        }
+        *position += 1;
+    }
+    while at(*position).is_some_and(is_space) {
+        *position += 1;
+    }
+    match at(*position)? {
+        quote @ (b'"' | b'\'') => loop {
+            *position += 1;
+            match at(*position)? {
+                byte if byte == quote => {
+                    *position += 1;
+                    return Some((lossy(&name), lossy(&value)));
+                }
+                byte => value.push(byte.to_ascii_lowercase()),
+            }
+        },
+        b'>' => Some((lossy(&name), String::new())),
+        _ => {
+            while let Some(byte) = at(*position).filter(|&byte| !is_space(byte) && byte != b'>') {
+                value.push(byte.to_ascii_lowercase());
+                *position += 1;
+            }
+            at(*position)?;
+            Some((lossy(&name), lossy(&value)))
+        }
+    }
+}
+
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b'\t' | b'\n' | b'\x0C' | b'\r' | b' ')
+}
+
+fn starts_with_ignore_case(bytes: &[u8], prefix: &[u8]) -> bool {
+    bytes.len() >= prefix.len() && bytes[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn find(bytes: &[u8], needle: &[u8]) -> Option<usize> {
+    bytes
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
@@ -0,0 +1,294 @@
+use crate::dom::document::Document;
+use crate::dom::elements::html_select_element::HTMLSelectElement;
+use crate::dom::node::{Element, NodeId};
+use crate::dom::parser::encoding::{
+    prescan_declaration, sniff_bom, Confidence, Encoding, PrescanDeclaration, UnknownEncodingLabel,
+};
+use crate::dom::parser::parse_error::{ErrorPolicy, ParseError, StageError, TreeError};
+use crate::dom::parser::tokenizer::{Token, Tokenizer};
+use crate::dom::parser::tree_constructor::TreeConstructor;
+
+/// Parses a complete HTML document: tokens are pulled from the tokenizer one at a time and fed
+/// to the tree constructor, which may switch the tokenizer state (e.g. to RCDATA after `<title>`)
+pub fn parse_document(html: &str) -> Document {
+    run(html, tree_constructor_for(html))
+}
+
+/// A document parsed from bytes, with the encoding it was decoded from
+#[derive(Debug)]
+pub struct ParseResult {
+    pub document: Document,
+    pub encoding: Encoding,
+    pub confidence: Confidence,
+    /// Problems met choosing the encoding, e.g. a declared label naming no known encoding
+    pub warnings: Vec<String>,
+    /// Parse errors kept under the error policy, their offsets are into the decoded text
+    pub tokenizer_errors: Vec<ParseError>,
+    pub tree_errors: Vec<TreeError>,
+    /// Parse errors met, kept or not. Always 0 under `ErrorPolicy::Ignore`.
+    pub total_errors: usize,
+    /// Whether errors were met past the `max` of `ErrorPolicy::Collect`
+    pub truncated: bool,
+}
+
+impl ParseResult {
+    /// The kept errors of both stages by offset. At the same offset tokenizer errors come
+    /// first, the tokenizer having met them before handing over the token.
+    pub fn errors(&self) -> impl Iterator<Item = StageError<'_>> {
+        let mut errors: Vec<StageError> = self
+            .tokenizer_errors
+            .iter()
+            .map(StageError::Tokenizer)
+            .chain(self.tree_errors.iter().map(StageError::Tree))
+            .collect();
+        // stable, so each stage keeps its own order among equal offsets
+        errors.sort_by_key(StageError::offset);
+        errors.into_iter()
+    }
+}
+
+/// Settings of `parse_document_bytes_with`
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    encoding_override: Option<Encoding>,
+    error_policy: ErrorPolicy,
+    suggest_entity_names: bool,
+}
+
+impl ParserOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes with the encoding `label` stands for, as given by the charset parameter of a
+    /// Content-Type header. It is certain and outranks meta elements, only a byte order mark
+    /// wins over it. Fails for labels naming no encoding decodable here.
+    pub fn encoding_override(mut self, label: &str) -> Result<Self, UnknownEncodingLabel> {
+        let encoding =
+            Encoding::for_label(label).ok_or_else(|| UnknownEncodingLabel(label.to_string()))?;
+        self.encoding_override = Some(encoding);
+        Ok(self)
+    }
+
+    /// What to keep of the parse errors, by default all of them. Bounding them keeps memory
+    /// in check on garbage input, which can give an error every few bytes.
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Gives unknown-named-character-reference errors the closest entity name as their
+    /// suggestion, see `closest_entity_name`. Off by default since looking it up costs time
+    /// on documents full of such errors.
+    pub fn suggest_entity_names(mut self, suggest: bool) -> Self {
+        self.suggest_entity_names = suggest;
+        self
+    }
+}
+
+/// Parses a document from its bytes. A byte order mark decides the encoding for certain and
+/// is not part of the text. Without one the encoding declared by a meta element in the first
+/// 1024 bytes is used, see `prescan`, and failing that UTF-8. When the metas there only
+/// declare labels naming no known encoding, windows-1252 is used with a warning.
+///
+/// Short of a byte order mark the encoding is only tentative. A meta element met while
+/// parsing that declares another encoding restarts the parse, once, with that encoding.
+pub fn parse_document_bytes(bytes: &[u8]) -> ParseResult {
+    parse_document_bytes_with(bytes, &ParserOptions::default())
+}
+
+/// `parse_document_bytes` with `options`. An encoding override takes the place of the
+/// prescan and of any meta element met while parsing.
+pub fn parse_document_bytes_with(bytes: &[u8], options: &ParserOptions) -> ParseResult {
+    let mut warnings = Vec::new();
+    let (encoding, confidence, bytes) = match (sniff_bom(bytes), options.encoding_override) {
+        (Some((encoding, bom_length)), _) => (encoding, Confidence::Certain, &bytes[bom_length..]),
+        (None, Some(encoding)) => (encoding, Confidence::Certain, bytes),
+        (None, None) => {
+            let encoding = match prescan_declaration(bytes) {
+                Some(PrescanDeclaration::Encoding(encoding)) => encoding,
+                Some(PrescanDeclaration::UnknownLabel(label)) => {
+                    warnings.push(format!(
+                        "unknown encoding label {label:?}, decoding as windows-1252"
+                    ));
+                    Encoding::Windows1252
+                }
+                None => Encoding::Utf8,
+            };
+            (encoding, Confidence::Tentative, bytes)
+        }
+    };
+    let text = encoding.decode(bytes);
+    let mut tree_constructor = tree_constructor_for(&text);
+    if confidence == Confidence::Tentative {
+        tree_constructor.set_tentative_encoding(encoding);
+    }
+    let mut errors = ErrorCollector::new(options);
+    let mut tree_constructor = tokenize_into(&text, tree_constructor, &mut errors);
+    let (encoding, confidence) = match tree_constructor.encoding_change() {
+        Some(declared) => {
+            // the errors of the abandoned parse are those of text that was decoded wrongly
+            let text = declared.decode(bytes);
+            errors = ErrorCollector::new(options);
+            tree_constructor = tokenize_into(&text, tree_constructor_for(&text), &mut errors);
+            (declared, Confidence::Certain)
+        }
+        None => match tree_constructor.tentative_encoding() {
+            Some(_) => (encoding, Confidence::Tentative),
+            None => (encoding, Confidence::Certain),
+        },
+    };
+    ParseResult {
+        document: finish(tree_constructor),
+        encoding,
+        confidence,
+        warnings,
+        tokenizer_errors: errors.tokenizer_errors,
+        tree_errors: errors.tree_errors,
+        total_errors: errors.total,
+        truncated: errors.truncated,
+    }
+}
+
+/// Rough number of nodes parsed from `html_bytes` bytes of typical HTML, about one node
+/// every 10 bytes, to size the arena up front
+pub fn estimate_node_count(html_bytes: usize) -> usize {
+    html_bytes / 10
+}
+
+/// Tree constructor whose arena is sized for `html`: half the estimated nodes are elements,
+/// the other half the text between them
+fn tree_constructor_for(html: &str) -> TreeConstructor {
+    TreeConstructor::with_capacity(estimate_node_count(html.len()) / 2, html.len())
+}
+
+/// Parses a document loaded from `url`, relative references in it resolve against that URL
+/// unless it has a base element
+pub fn parse_document_with_url(html: &str, url: &str) -> Document {
+    let mut document = parse_document(html);
+    document.set_url(url);
+    document
+}
+
+/// Parses the srcdoc attribute value of an iframe as an iframe srcdoc document, which is in
+/// no-quirks mode whatever its DOCTYPE, or lack of one
+pub fn parse_srcdoc_document(html: &str) -> Document {
+    run(html, TreeConstructor::new_iframe_srcdoc())
+}
+
+/// Parses `html` as the contents of `context` (13.4 Parsing HTML fragments). The parsed nodes
+/// are the children of the document element of the returned document, ready to be moved into
+/// another tree with `Document::import_node`.
+pub fn parse_fragment(html: &str, context: &Element) -> Document {
+    run(html, TreeConstructor::new_fragment(context))
+}
+
+fn run(html: &str, tree_constructor: TreeConstructor) -> Document {
+    let mut errors = ErrorCollector::new(&ParserOptions::new().error_policy(ErrorPolicy::Ignore));
+    finish(tokenize_into(html, tree_constructor, &mut errors))
+}
+
+/// Feeds the tokens of `html` to `tree_constructor` until it stops, moving the parse errors
+/// of both into `errors` after every token
+fn tokenize_into(
+    html: &str,
+    mut tree_constructor: TreeConstructor,
+    errors: &mut ErrorCollector,
+) -> TreeConstructor {
+    let mut tokenizer = Tokenizer::new(html.as_bytes());
+    tokenizer.set_suggest_entity_names(errors.suggest_entity_names);
+    if let Some(state) = tree_constructor.take_tokenizer_state() {
+        tokenizer.set_state(state);
+    }
+    loop {
+        let token = tokenizer.next_token();
+        let is_eof = token == Token::EOF;
+        tree_constructor.set_token_offset(tokenizer.offset());
+        tree_constructor.process_token(token);
+        errors.add_tokenizer_errors(tokenizer.take_errors());
+        errors.add_tree_errors(tree_constructor.take_errors());
+        if let Some(state) = tree_constructor.take_tokenizer_state() {
+            tokenizer.set_state(state);
+        }
+        tokenizer.set_cdata_allowed(tree_constructor.is_in_foreign_content());
+        if is_eof || tree_constructor.is_stopped() {
+            break;
+        }
+    }
+    tree_constructor
+}
+
+/// The parse errors of a parse, kept and counted as the error policy says. The limit of
+/// `ErrorPolicy::Collect` applies to both kinds of errors together.
+struct ErrorCollector {
+    policy: ErrorPolicy,
+    /// Only when errors are kept
+    suggest_entity_names: bool,
+    tokenizer_errors: Vec<ParseError>,
+    tree_errors: Vec<TreeError>,
+    total: usize,
+    truncated: bool,
+}
+
+impl ErrorCollector {
+    fn new(options: &ParserOptions) -> Self {
+        let policy = options.error_policy;
+        ErrorCollector {
+            policy,
+            suggest_entity_names: options.suggest_entity_names
+                && matches!(policy, ErrorPolicy::Collect { .. }),
+            tokenizer_errors: Vec::new(),
+            tree_errors: Vec::new(),
+            total: 0,
+            truncated: false,
+        }
+    }
+
+    fn add_tokenizer_errors(&mut self, errors: Vec<ParseError>) {
+        for error in errors {
+            if self.count() {
+                self.tokenizer_errors.push(error);
+            }
+        }
+    }
+
+    fn add_tree_errors(&mut self, errors: Vec<TreeError>) {
+        for error in errors {
+            if self.count() {
+                self.tree_errors.push(error);
+            }
+        }
+    }
+
+    /// Counts one more error and tells whether to keep it
+    fn count(&mut self) -> bool {
+        match self.policy {
+            ErrorPolicy::Ignore => false,
+            ErrorPolicy::CountOnly => {
+                self.total += 1;
+                false
+            }
+            ErrorPolicy::Collect { max } => {
+                self.total += 1;
+                let keep = self.total <= max;
+                self.truncated |= !keep;
+                keep
+            }
+        }
+    }
+}
+
+fn finish(tree_constructor: TreeConstructor) -> Document {
+    let mut document = tree_constructor.into_document();
+    // the selectedness setting algorithm runs as options are inserted, running it once per
+    // select after parsing gives the same result
+    let selects: Vec<NodeId> = document
+        .arena()
+        .descendants(document.root())
+        .filter(|&node| document.arena().get(node).is_element_named("select"))
+        .collect();
+    for select in selects {
+        HTMLSelectElement::new(select).reset_selectedness(&mut document);
+    }
+    document
+}
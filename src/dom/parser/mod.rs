@@ -1 +1,9 @@
-pub mod tokenizer;
\ No newline at end of file
+pub mod encoding;
+pub mod parse_error;
+pub mod tokenizer;
+pub mod token_pattern;
+pub mod insertion_mode;
+pub mod tree_constructor;
+pub mod html_parser;
+#[cfg(feature = "async")]
+pub mod async_tokenizer;
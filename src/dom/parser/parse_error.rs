@@ -0,0 +1,324 @@
+// src/dom/parser/parse_error.rs
+
+//! Parse errors of the HTML standard. Parsing never fails on them, they are collected by the
+//! tokenizer and the tree constructor for conformance checkers and linters.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Writes `ParseErrorCode` with one variant per error id, its `as_str` and `ALL`
+macro_rules! parse_error_codes {
+    (
+        tokenizer { $($tokenizer:ident => $tokenizer_name:literal,)+ }
+        tree { $($(#[$doc:meta])* $tree:ident => $tree_name:literal,)+ }
+    ) => {
+        /// The id of a parse error. The tokenizer errors are those of the HTML standard (13.2.2
+        /// Parse errors), named as there and in the html5lib tokenizer tests. The standard does
+        /// not name tree construction errors, their names follow html5lib where it has one.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ParseErrorCode {
+            $($tokenizer,)+
+            $($(#[$doc])* $tree,)+
+        }
+
+        impl ParseErrorCode {
+            /// Every error id, the tokenizer ones first
+            pub const ALL: &'static [ParseErrorCode] =
+                &[$(ParseErrorCode::$tokenizer,)+ $(ParseErrorCode::$tree,)+];
+
+            /// The kebab-case name of the error, e.g. `duplicate-attribute`
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ParseErrorCode::$tokenizer => $tokenizer_name,)+
+                    $(ParseErrorCode::$tree => $tree_name,)+
+                }
+            }
+
+            /// Whether the tokenizer reports this error, as opposed to the tree constructor
+            pub fn is_tokenizer_error(&self) -> bool {
+                matches!(self, $(ParseErrorCode::$tokenizer)|+)
+            }
+        }
+    };
+}
+
+parse_error_codes! {
+    tokenizer {
+        AbruptClosingOfEmptyComment => "abrupt-closing-of-empty-comment",
+        AbruptDoctypePublicIdentifier => "abrupt-doctype-public-identifier",
+        AbruptDoctypeSystemIdentifier => "abrupt-doctype-system-identifier",
+        AbsenceOfDigitsInNumericCharacterReference =>
+            "absence-of-digits-in-numeric-character-reference",
+        CdataInHtmlContent => "cdata-in-html-content",
+        CharacterReferenceOutsideUnicodeRange => "character-reference-outside-unicode-range",
+        ControlCharacterInInputStream => "control-character-in-input-stream",
+        ControlCharacterReference => "control-character-reference",
+        DuplicateAttribute => "duplicate-attribute",
+        EndTagWithAttributes => "end-tag-with-attributes",
+        EndTagWithTrailingSolidus => "end-tag-with-trailing-solidus",
+        EofBeforeTagName => "eof-before-tag-name",
+        EofInCdata => "eof-in-cdata",
+        EofInComment => "eof-in-comment",
+        EofInDoctype => "eof-in-doctype",
+        EofInScriptHtmlCommentLikeText => "eof-in-script-html-comment-like-text",
+        EofInTag => "eof-in-tag",
+        IncorrectlyClosedComment => "incorrectly-closed-comment",
+        IncorrectlyOpenedComment => "incorrectly-opened-comment",
+        InvalidCharacterSequenceAfterDoctypeName => "invalid-character-sequence-after-doctype-name",
+        InvalidFirstCharacterOfTagName => "invalid-first-character-of-tag-name",
+        MissingAttributeValue => "missing-attribute-value",
+        MissingDoctypeName => "missing-doctype-name",
+        MissingDoctypePublicIdentifier => "missing-doctype-public-identifier",
+        MissingDoctypeSystemIdentifier => "missing-doctype-system-identifier",
+        MissingEndTagName => "missing-end-tag-name",
+        MissingQuoteBeforeDoctypePublicIdentifier =>
+            "missing-quote-before-doctype-public-identifier",
+        MissingQuoteBeforeDoctypeSystemIdentifier =>
+            "missing-quote-before-doctype-system-identifier",
+        MissingSemicolonAfterCharacterReference => "missing-semicolon-after-character-reference",
+        MissingWhitespaceAfterDoctypePublicKeyword =>
+            "missing-whitespace-after-doctype-public-keyword",
+        MissingWhitespaceAfterDoctypeSystemKeyword =>
+            "missing-whitespace-after-doctype-system-keyword",
+        MissingWhitespaceBeforeDoctypeName => "missing-whitespace-before-doctype-name",
+        MissingWhitespaceBetweenAttributes => "missing-whitespace-between-attributes",
+        MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers =>
+            "missing-whitespace-between-doctype-public-and-system-identifiers",
+        NestedComment => "nested-comment",
+        NoncharacterCharacterReference => "noncharacter-character-reference",
+        NoncharacterInInputStream => "noncharacter-in-input-stream",
+        NonVoidHtmlElementStartTagWithTrailingSolidus =>
+            "non-void-html-element-start-tag-with-trailing-solidus",
+        NullCharacterReference => "null-character-reference",
+        SurrogateCharacterReference => "surrogate-character-reference",
+        SurrogateInInputStream => "surrogate-in-input-stream",
+        UnexpectedCharacterAfterDoctypeSystemIdentifier =>
+            "unexpected-character-after-doctype-system-identifier",
+        UnexpectedCharacterInAttributeName => "unexpected-character-in-attribute-name",
+        UnexpectedCharacterInUnquotedAttributeValue =>
+            "unexpected-character-in-unquoted-attribute-value",
+        UnexpectedEqualsSignBeforeAttributeName => "unexpected-equals-sign-before-attribute-name",
+        UnexpectedNullCharacter => "unexpected-null-character",
+        UnexpectedQuestionMarkInsteadOfTagName => "unexpected-question-mark-instead-of-tag-name",
+        UnexpectedSolidusInTag => "unexpected-solidus-in-tag",
+        UnknownNamedCharacterReference => "unknown-named-character-reference",
+    }
+    tree {
+        MissingDoctype => "missing-doctype",
+        UnexpectedDoctype => "unexpected-doctype",
+        UnexpectedStartTag => "unexpected-start-tag",
+        UnexpectedEndTag => "unexpected-end-tag",
+        EndTagWithOpenElements => "end-tag-with-open-elements",
+        UnexpectedHtmlElementInForeignContent => "unexpected-html-element-in-foreign-content",
+        UnexpectedTokenInHeadNoscript => "unexpected-token-in-head-noscript",
+        UnexpectedTokenInColumnGroup => "unexpected-token-in-column-group",
+        UnexpectedTokenInTableBody => "unexpected-token-in-table-body",
+        UnexpectedCellInTableBody => "unexpected-cell-in-table-body",
+        UnexpectedCaptionEnd => "unexpected-caption-end",
+        UnexpectedTokenInFrameset => "unexpected-token-in-frameset",
+        UnexpectedTokenAfterFrameset => "unexpected-token-after-frameset",
+        UnexpectedTokenAfterAfterFrameset => "unexpected-token-after-after-frameset",
+        UnexpectedTokenAfterBody => "unexpected-token-after-body",
+        UnexpectedTokenAfterAfterBody => "unexpected-token-after-after-body",
+        EofInElementThatCanContainOnlyText => "eof-in-element-that-can-contain-only-text",
+        EofInTemplate => "eof-in-template",
+        EofInFrameset => "eof-in-frameset",
+        FosterParenting => "foster-parenting",
+        AdoptionAgency1_2 => "adoption-agency-1.2",
+        AdoptionAgency1_3 => "adoption-agency-1.3",
+        AdoptionAgency4_4 => "adoption-agency-4.4",
+        /// Not an error of the standard: the DOM refused a node the tree constructor inserted
+        NodeInsertionFailed => "node-insertion-failed",
+    }
+}
+
+/// A name given to `ParseErrorCode::from_str` that is no error id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownParseErrorCode(pub String);
+
+impl FromStr for ParseErrorCode {
+    type Err = UnknownParseErrorCode;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ParseErrorCode::ALL
+            .iter()
+            .find(|code| code.as_str() == name)
+            .copied()
+            .ok_or_else(|| UnknownParseErrorCode(name.to_string()))
+    }
+}
+
+/// An error the tokenizer met, at the byte offset in its input of the character it was
+/// reading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub code: ParseErrorCode,
+    pub offset: usize,
+    /// What the error is about, when the tokenizer knows: the tag name for tag errors, the
+    /// attribute name for attribute errors, the text after the `&` for character reference
+    /// errors and the name for DOCTYPE errors
+    pub context: Option<String>,
+    /// For unknown-named-character-reference, the closest entity name when
+    /// `ParserOptions::suggest_entity_names` is on
+    pub suggestion: Option<String>,
+}
+
+impl ParseError {
+    /// Displays as `render_error` renders the error against `source`
+    pub fn display<'a>(&'a self, source: &'a str) -> ErrorDisplay<'a> {
+        ErrorDisplay {
+            error: self,
+            source,
+        }
+    }
+}
+
+/// A `ParseError` with the text it was met in, see `ParseError::display`
+pub struct ErrorDisplay<'a> {
+    error: &'a ParseError,
+    source: &'a str,
+}
+
+impl fmt::Display for ErrorDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render_error(self.error, self.source))
+    }
+}
+
+/// An error the tree constructor met processing a token, at the byte offset the tokenizer had
+/// reached when it handed over the token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeError {
+    pub code: ParseErrorCode,
+    pub offset: usize,
+    /// The tag name of the token, for start and end tags
+    pub context: Option<String>,
+}
+
+/// A parse error of either stage, as `ParseResult::errors` gives them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageError<'a> {
+    Tokenizer(&'a ParseError),
+    Tree(&'a TreeError),
+}
+
+impl StageError<'_> {
+    pub fn code(&self) -> ParseErrorCode {
+        match self {
+            StageError::Tokenizer(error) => error.code,
+            StageError::Tree(error) => error.code,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        match self {
+            StageError::Tokenizer(error) => error.offset,
+            StageError::Tree(error) => error.offset,
+        }
+    }
+
+    pub fn context(&self) -> Option<&str> {
+        match self {
+            StageError::Tokenizer(error) => error.context.as_deref(),
+            StageError::Tree(error) => error.context.as_deref(),
+        }
+    }
+}
+
+/// What the parser keeps of the errors it meets, see `ParserOptions::error_policy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Keeps the first `max` errors and counts the rest
+    Collect { max: usize },
+    /// Counts the errors without keeping any
+    CountOnly,
+    /// Neither keeps nor counts errors
+    Ignore,
+}
+
+impl Default for ErrorPolicy {
+    /// Keeps every error
+    fn default() -> Self {
+        ErrorPolicy::Collect { max: usize::MAX }
+    }
+}
+
+/// Widest part of a source line `render_error` shows, in characters
+const MAX_EXCERPT_WIDTH: usize = 80;
+
+const TAB_WIDTH: usize = 4;
+
+/// Renders `error`, met parsing `source`, the way rustc shows diagnostics: the code, the line
+/// and column (1-based, the column counting characters), then the source line with a caret
+/// under the error.
+///
+/// ```text
+/// error[duplicate-attribute] at 1:8, on `x`
+///   |
+/// 1 | <a x=1 x=2>
+///   |        ^
+/// ```
+///
+/// A suggested entity name adds a last line, `= help: did you mean ...?`. Tabs are shown as
+/// 4 spaces. Lines wider than 80 characters are cut around the caret, with
+/// `...` where they were cut. Errors at the end of the input point just past the last
+/// character.
+pub fn render_error(error: &ParseError, source: &str) -> String {
+    let mut offset = error.offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index);
+    let line_number = source[..line_start].matches('\n').count() + 1;
+    let column = source[line_start..offset].chars().count() + 1;
+    let line = source[line_start..line_end].trim_end_matches('\r');
+
+    let mut shown = Vec::new();
+    let mut caret = None;
+    for (index, c) in line.char_indices() {
+        if line_start + index == offset {
+            caret = Some(shown.len());
+        }
+        if c == '\t' {
+            shown.extend(std::iter::repeat_n(' ', TAB_WIDTH));
+        } else {
+            shown.push(c);
+        }
+    }
+    let caret = caret.unwrap_or(shown.len());
+    let (start, end) = if shown.len() > MAX_EXCERPT_WIDTH {
+        let start = caret
+            .saturating_sub(MAX_EXCERPT_WIDTH / 2)
+            .min(shown.len() - MAX_EXCERPT_WIDTH);
+        (start, start + MAX_EXCERPT_WIDTH)
+    } else {
+        (0, shown.len())
+    };
+    let mut excerpt = String::new();
+    let mut caret_column = caret - start;
+    if start > 0 {
+        excerpt.push_str("...");
+        caret_column += 3;
+    }
+    excerpt.extend(&shown[start..end]);
+    if end < shown.len() {
+        excerpt.push_str("...");
+    }
+
+    let mut output = format!("error[{}] at {line_number}:{column}", error.code.as_str());
+    if let Some(context) = &error.context {
+        output.push_str(&format!(", on `{context}`"));
+    }
+    let gutter = " ".repeat(line_number.to_string().len());
+    output.push_str(&format!("\n{gutter} |\n{line_number} | {excerpt}"));
+    output.push_str(&format!("\n{gutter} | {}^", " ".repeat(caret_column)));
+    if let Some(suggestion) = &error.suggestion {
+        output.push_str(&format!(
+            "\n{gutter} = help: did you mean `&{suggestion};`?"
+        ));
+    }
+    output
+}
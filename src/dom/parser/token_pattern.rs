@@ -0,0 +1,184 @@
+use crate::dom::parser::tokenizer::Token;
+use crate::helper::small_str::SmallStr;
+
+/// The variant of a token, without any of its payload
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TokenKind {
+    DOCTYPE,
+    StartTag,
+    EndTag,
+    Comment,
+    Character,
+    EOF,
+}
+
+/// A partial description of a token used for flexible assertions.
+/// Every field left as `None` is a wildcard, so `TokenPattern::start_tag().tag_name("a")`
+/// matches any `<a>` start tag no matter its attributes or self-closing flag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenPattern {
+    kind: Option<TokenKind>,
+    tag_name: Option<String>,
+    self_closing: Option<bool>,
+    attributes: Option<Vec<(SmallStr, String)>>,
+    required_attributes: Vec<(String, Option<String>)>,
+    data: Option<String>,
+    public_id: Option<Option<String>>,
+    system_id: Option<Option<String>>,
+    force_quirks: Option<bool>,
+}
+
+impl TokenPattern {
+    /// Matches any token
+    pub fn any() -> Self {
+        TokenPattern::default()
+    }
+
+    pub fn of_kind(kind: TokenKind) -> Self {
+        TokenPattern {
+            kind: Some(kind),
+            ..TokenPattern::default()
+        }
+    }
+
+    pub fn doctype() -> Self {
+        Self::of_kind(TokenKind::DOCTYPE)
+    }
+
+    pub fn start_tag() -> Self {
+        Self::of_kind(TokenKind::StartTag)
+    }
+
+    pub fn end_tag() -> Self {
+        Self::of_kind(TokenKind::EndTag)
+    }
+
+    pub fn comment() -> Self {
+        Self::of_kind(TokenKind::Comment)
+    }
+
+    pub fn character() -> Self {
+        Self::of_kind(TokenKind::Character)
+    }
+
+    pub fn eof() -> Self {
+        Self::of_kind(TokenKind::EOF)
+    }
+
+    /// Tag name for start/end tags, or the name of a DOCTYPE
+    pub fn tag_name(mut self, name: &str) -> Self {
+        self.tag_name = Some(name.to_string());
+        self
+    }
+
+    pub fn self_closing(mut self, flag: bool) -> Self {
+        self.self_closing = Some(flag);
+        self
+    }
+
+    /// Requires the exact attribute list, in the same order
+    pub fn attributes(mut self, attributes: &[(&str, &str)]) -> Self {
+        self.attributes = Some(
+            attributes
+                .iter()
+                .map(|(name, value)| (SmallStr::from(*name), value.to_string()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Requires an attribute with the given name and value, other attributes are ignored
+    pub fn attribute(mut self, name: &str, value: &str) -> Self {
+        self.required_attributes
+            .push((name.to_string(), Some(value.to_string())));
+        self
+    }
+
+    /// Requires an attribute with the given name, whatever its value
+    pub fn has_attribute(mut self, name: &str) -> Self {
+        self.required_attributes.push((name.to_string(), None));
+        self
+    }
+
+    /// Comment data, or the character of a character token
+    pub fn data(mut self, data: &str) -> Self {
+        self.data = Some(data.to_string());
+        self
+    }
+
+    pub fn public_id(mut self, public_id: Option<&str>) -> Self {
+        self.public_id = Some(public_id.map(str::to_string));
+        self
+    }
+
+    pub fn system_id(mut self, system_id: Option<&str>) -> Self {
+        self.system_id = Some(system_id.map(str::to_string));
+        self
+    }
+
+    pub fn force_quirks(mut self, flag: bool) -> Self {
+        self.force_quirks = Some(flag);
+        self
+    }
+
+    pub fn is_matched_by(&self, token: &Token) -> bool {
+        if let Some(kind) = self.kind {
+            if token.kind() != kind {
+                return false;
+            }
+        }
+        match token {
+            Token::StartTag {
+                tag_name,
+                self_closing,
+                attributes,
+                ..
+            }
+            | Token::EndTag {
+                tag_name,
+                self_closing,
+                attributes,
+            } => {
+                self.tag_name.as_ref().is_none_or(|n| n == tag_name)
+                    && self.self_closing.is_none_or(|f| f == *self_closing)
+                    && self.attributes.as_ref().is_none_or(|a| a == attributes)
+                    && self.required_attributes.iter().all(|(name, value)| {
+                        attributes.iter().any(|(n, v)| {
+                            n == name && value.as_ref().is_none_or(|value| value == v)
+                        })
+                    })
+                    && self.data.is_none()
+            }
+            Token::Comment { data } => {
+                self.data.as_ref().is_none_or(|d| d == data) && self.has_no_tag_fields()
+            }
+            Token::Character { data } => {
+                self.data
+                    .as_ref()
+                    .is_none_or(|d| d.chars().eq(std::iter::once(*data)))
+                    && self.has_no_tag_fields()
+            }
+            Token::DOCTYPE {
+                name,
+                public_id,
+                system_id,
+                force_quirks,
+            } => {
+                self.tag_name
+                    .as_ref()
+                    .is_none_or(|n| Some(n) == name.as_ref())
+                    && self.public_id.as_ref().is_none_or(|p| p == public_id)
+                    && self.system_id.as_ref().is_none_or(|s| s == system_id)
+                    && self.force_quirks.is_none_or(|f| f == *force_quirks)
+            }
+            Token::EOF => self.has_no_tag_fields() && self.data.is_none(),
+        }
+    }
+
+    fn has_no_tag_fields(&self) -> bool {
+        self.tag_name.is_none()
+            && self.self_closing.is_none()
+            && self.attributes.is_none()
+            && self.required_attributes.is_empty()
+    }
+}
@@ -1,9 +1,12 @@
+use crate::helper::small_str::SmallStr;
 use crate::helper::stream::Stream;
-use std::cmp::max;
-use std::collections::VecDeque;
-use crate::dom::entities::ENTITIES;
-use crate::dom::entities::Entity;
-#[derive(Debug, Clone)]
+use crate::dom::entities::{closest_entity_name, longest_entity_match, MAX_ENTITY_NAME_LENGTH};
+use crate::dom::escape::{escape_attribute_value, escape_text};
+use crate::dom::parser::parse_error::{ParseError, ParseErrorCode};
+use crate::dom::parser::token_pattern::{TokenKind, TokenPattern};
+use std::fmt;
+use std::ops::Range;
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Token {
     DOCTYPE {
         name: Option<String>,
@@ -12,14 +15,17 @@ pub enum Token {
         force_quirks: bool,
     },
     StartTag {
-        tag_name: String,
+        tag_name: SmallStr,
         self_closing: bool,
-        attributes: Vec<(String, String)>,
+        attributes: Vec<(SmallStr, String)>,
+        /// Byte ranges of the attribute names and values in the input, filled instead of
+        /// `attributes` when the tokenizer runs with lazy attributes
+        raw_attributes: Vec<(Range<usize>, Range<usize>)>,
     },
     EndTag {
-        tag_name: String,
+        tag_name: SmallStr,
         self_closing: bool,
-        attributes: Vec<(String, String)>,
+        attributes: Vec<(SmallStr, String)>,
     },
     Comment {
         data: String,
@@ -38,7 +44,7 @@ impl Token {
             _ => false,
         }
     }
-    pub fn add_attribute(&mut self, name: String, value: String) {
+    pub fn add_attribute(&mut self, name: SmallStr, value: String) {
         match self {
             Token::StartTag { attributes, .. } | Token::EndTag { attributes, .. } => {
                 if !attributes.iter().any(|(attr_name, _)| *attr_name == name) {
@@ -56,6 +62,86 @@ impl Token {
             _ => {}
         }
     }
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::DOCTYPE { .. } => TokenKind::DOCTYPE,
+            Token::StartTag { .. } => TokenKind::StartTag,
+            Token::EndTag { .. } => TokenKind::EndTag,
+            Token::Comment { .. } => TokenKind::Comment,
+            Token::Character { .. } => TokenKind::Character,
+            Token::EOF => TokenKind::EOF,
+        }
+    }
+    /// The tag name of start and end tag tokens
+    pub fn tag_name(&self) -> Option<&str> {
+        match self {
+            Token::StartTag { tag_name, .. } | Token::EndTag { tag_name, .. } => Some(tag_name),
+            _ => None,
+        }
+    }
+    /// Checks whether this is a start tag named `name`
+    pub fn is_start_tag(&self, name: &str) -> bool {
+        matches!(self, Token::StartTag { tag_name, .. } if tag_name == name)
+    }
+    /// Checks whether this is an end tag named `name`
+    pub fn is_end_tag(&self, name: &str) -> bool {
+        matches!(self, Token::EndTag { tag_name, .. } if tag_name == name)
+    }
+    /// Checks this token against a pattern, fields left unset in the pattern match anything
+    pub fn matches(&self, pattern: &TokenPattern) -> bool {
+        pattern.is_matched_by(self)
+    }
+}
+
+/// Markup for the token: tags with double quoted attribute values, characters escaped as text
+/// outside raw text elements, and nothing for EOF
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::DOCTYPE {
+                name,
+                public_id,
+                system_id,
+                ..
+            } => {
+                write!(f, "<!DOCTYPE")?;
+                if let Some(name) = name {
+                    write!(f, " {name}")?;
+                }
+                match (public_id, system_id) {
+                    (Some(public_id), Some(system_id)) => {
+                        write!(f, " PUBLIC \"{public_id}\" \"{system_id}\"")?
+                    }
+                    (Some(public_id), None) => write!(f, " PUBLIC \"{public_id}\"")?,
+                    (None, Some(system_id)) => write!(f, " SYSTEM \"{system_id}\"")?,
+                    (None, None) => {}
+                }
+                write!(f, ">")
+            }
+            Token::StartTag {
+                tag_name,
+                self_closing,
+                attributes,
+                ..
+            } => {
+                write!(f, "<{tag_name}")?;
+                for (name, value) in attributes {
+                    write!(f, " {name}=\"{}\"", escape_attribute_value(value))?;
+                }
+                if *self_closing {
+                    write!(f, "/")?;
+                }
+                write!(f, ">")
+            }
+            Token::EndTag { tag_name, .. } => write!(f, "</{tag_name}>"),
+            Token::Comment { data } => write!(f, "<!--{data}-->"),
+            Token::Character { data } => {
+                let mut buffer = [0; 4];
+                f.write_str(&escape_text(data.encode_utf8(&mut buffer)))
+            }
+            Token::EOF => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -170,6 +256,21 @@ const CONTROL_CHARACTER_REPLACEMENTS: &[(u32, u32)] = &[
     (0x9E, 0x017E), // LATIN SMALL LETTER Z WITH CARON (ž)
     (0x9F, 0x0178), // LATIN CAPITAL LETTER Y WITH DIAERESIS (Ÿ)
 ];
+/// Decodes the UTF-8 sequence at the start of `bytes`, returning the character and the number
+/// of bytes it spans. Malformed sequences yield U+FFFD for their first byte only.
+fn decode_utf8(bytes: &[u8]) -> (char, usize) {
+    let len = match bytes.first() {
+        Some(0xC2..=0xDF) => 2,
+        Some(0xE0..=0xEF) => 3,
+        Some(0xF0..=0xF4) => 4,
+        _ => return ('\u{FFFD}', 1),
+    };
+    match bytes.get(..len).map(std::str::from_utf8) {
+        Some(Ok(decoded)) => (decoded.chars().next().unwrap_or('\u{FFFD}'), len),
+        _ => ('\u{FFFD}', 1),
+    }
+}
+
 pub struct Tokenizer<'a> {
     input_stream: Stream<'a, u8>,
     state: TokenizerState,
@@ -178,13 +279,29 @@ pub struct Tokenizer<'a> {
     current_comment_token: Option<Token>,
     current_doctype_token: Option<Token>,
     tokens: Vec<Token>,
+    next_token_index: usize,
     temporary_buffer: String,
     last_start_tag_token: Option<Token>, // this field is for end tag token validity check
     current_tag_name: String,            //remember to clear after put into current_tag_token
     current_tag_value: String,           //same as above
     character_reference_code: u32,
+    cdata_allowed: bool, // set while the adjusted current node is not in the HTML namespace
+    current_input_character: char, // decoded character last returned by consume_next_input_char
+    current_input_len: usize,      // its length in bytes
+    lazy_attributes: bool,
+    raw_attribute_pending: bool, // lazy mode: an attribute has started and was not added yet
+    raw_attribute_name: Range<usize>,
+    raw_attribute_value: Range<usize>,
+    input_complete: bool, // false while a streaming tokenizer may still be fed more input
+    errors: Vec<ParseError>,
+    character_reference_start: usize, // just past the `&` of the character reference being read
+    suggest_entity_names: bool,
 }
 
+/// Bytes a streaming tokenizer keeps unread until the end of the input is known, enough for
+/// the longest lookahead of a single step (a named character reference)
+const STREAMING_LOOKAHEAD: usize = 64;
+
 impl<'a> Tokenizer<'a> {
     pub fn new(input: &'a [u8]) -> Self {
         Tokenizer {
@@ -195,17 +312,259 @@ impl<'a> Tokenizer<'a> {
             current_comment_token: None,
             current_doctype_token: None,
             tokens: Vec::new(),
+            next_token_index: 0,
             temporary_buffer: String::new(),
             last_start_tag_token: None,
             current_tag_name: String::new(),
             current_tag_value: String::new(),
             character_reference_code: 0,
+            cdata_allowed: false,
+            current_input_character: '\0',
+            current_input_len: 1,
+            lazy_attributes: false,
+            raw_attribute_pending: false,
+            raw_attribute_name: 0..0,
+            raw_attribute_value: 0..0,
+            input_complete: true,
+            errors: Vec::new(),
+            character_reference_start: 0,
+            suggest_entity_names: false,
+        }
+    }
+
+    /// Tokenizer over input that arrives in chunks: it owns a growable buffer which `feed`
+    /// appends to, and `next_available_token` only tokenizes as far as the input fed so far
+    /// allows. `end_input` marks the end of the input.
+    pub fn streaming() -> Tokenizer<'static> {
+        Tokenizer {
+            input_stream: Stream::owned(),
+            input_complete: false,
+            ..Tokenizer::new(&[])
+        }
+    }
+
+    /// Appends a chunk of input. Bytes already consumed stay in the buffer, so the byte
+    /// ranges of lazy attributes remain valid.
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.input_stream.extend(chunk);
+    }
+
+    /// Marks the end of the input of a streaming tokenizer, see `streaming`
+    pub fn end_input(&mut self) {
+        self.input_complete = true;
+    }
+
+    /// Like `next_token`, but None when the tokenizer cannot go on before more input is fed.
+    /// EOF comes only after `end_input`.
+    pub fn next_available_token(&mut self) -> Option<Token> {
+        while self.next_token_index >= self.tokens.len() {
+            if self.is_eof_emitted() {
+                return Some(Token::EOF);
+            }
+            if !self.input_complete && self.input_stream.remaining() < STREAMING_LOOKAHEAD {
+                return None;
+            }
+            self.step_checked();
+        }
+        self.next_token_index += 1;
+        Some(self.tokens[self.next_token_index - 1].clone())
+    }
+
+    /// Re-targets the tokenizer at new input, as if freshly constructed except for the lazy
+    /// attributes and entity name suggestion settings. The token list and scratch buffers keep
+    /// their allocations, so pooled tokenizers avoid re-allocating between documents. Returns a
+    /// tokenizer borrowing the new input.
+    pub fn reset<'b>(self, new_input: &'b [u8]) -> Tokenizer<'b> {
+        let mut tokens = self.tokens;
+        tokens.clear();
+        let mut temporary_buffer = self.temporary_buffer;
+        temporary_buffer.clear();
+        let mut current_tag_name = self.current_tag_name;
+        current_tag_name.clear();
+        let mut current_tag_value = self.current_tag_value;
+        current_tag_value.clear();
+        let mut errors = self.errors;
+        errors.clear();
+        Tokenizer {
+            input_stream: Stream::new(new_input),
+            state: TokenizerState::Data,
+            ret_state: TokenizerState::Data,
+            current_tag_token: None,
+            current_comment_token: None,
+            current_doctype_token: None,
+            tokens,
+            next_token_index: 0,
+            temporary_buffer,
+            last_start_tag_token: None,
+            current_tag_name,
+            current_tag_value,
+            character_reference_code: 0,
+            cdata_allowed: false,
+            current_input_character: '\0',
+            current_input_len: 1,
+            lazy_attributes: self.lazy_attributes,
+            raw_attribute_pending: false,
+            raw_attribute_name: 0..0,
+            raw_attribute_value: 0..0,
+            input_complete: true,
+            errors,
+            character_reference_start: 0,
+            suggest_entity_names: self.suggest_entity_names,
         }
     }
 
     pub fn run(&mut self) {
         //NEED_TO_IMPLEMENT: :Before each step of the tokenizer, the user agent must first check the parser pause flag
-        while !self.input_stream.is_eof() {
+        while !self.is_eof_emitted() {
+            self.step_checked();
+        }
+    }
+
+    /// Runs the state machine until a token not returned before is available, and returns it.
+    /// Keeps returning EOF once the input is exhausted.
+    pub fn next_token(&mut self) -> Token {
+        while self.next_token_index >= self.tokens.len() {
+            if self.is_eof_emitted() {
+                return Token::EOF;
+            }
+            self.step_checked();
+        }
+        self.next_token_index += 1;
+        self.tokens[self.next_token_index - 1].clone()
+    }
+
+    /// Opt-in: unknown-named-character-reference errors get the closest entity name as their
+    /// suggestion. Off by default, looking it up costs time on documents with many of them.
+    pub fn set_suggest_entity_names(&mut self, suggest: bool) {
+        self.suggest_entity_names = suggest;
+    }
+
+    /// Byte offset in the input of the next character to be read
+    pub fn offset(&self) -> usize {
+        self.input_stream.idx.min(self.input_stream.len())
+    }
+
+    /// The parse errors met so far, in input order
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// Moves the parse errors met so far out of the tokenizer
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    pub fn current_state(&self) -> &TokenizerState {
+        &self.state
+    }
+
+    /// Used by the tree constructor, e.g. to switch to RCDATA after a title start tag, and by
+    /// fragment parsing to pick the initial state from the context element
+    pub fn set_state(&mut self, state: TokenizerState) {
+        self.state = state;
+    }
+
+    /// Makes `</tag_name>` an appropriate end tag, as if `<tag_name>` had just been emitted,
+    /// so the RCDATA, RAWTEXT and script data states can be left by it
+    pub fn set_last_start_tag(&mut self, tag_name: String) {
+        self.last_start_tag_token = Some(Token::StartTag {
+            tag_name: tag_name.into(),
+            self_closing: false,
+            attributes: Vec::new(),
+            raw_attributes: Vec::new(),
+        });
+    }
+
+    /// CDATA sections are only tokenized in foreign content, the tree constructor keeps this
+    /// in sync with the adjusted current node
+    pub(crate) fn set_cdata_allowed(&mut self, allowed: bool) {
+        self.cdata_allowed = allowed;
+    }
+
+    /// Opt-in lazy attributes: start tags carry the byte ranges of their attributes in
+    /// `raw_attributes` and leave `attributes` empty, skipping the decoding of names, values
+    /// and character references until `decode_attributes` or `attribute_value` is called.
+    /// Ranges refer to this tokenizer's input, so tokens must be decoded before `reset`.
+    /// End tag attributes, which the tree constructor ignores anyway, are dropped.
+    pub fn set_lazy_attributes(&mut self, lazy: bool) {
+        self.lazy_attributes = lazy;
+    }
+
+    /// Decodes the raw attributes of a lazily tokenized start tag into `attributes`
+    pub fn decode_attributes(&self, token: &mut Token) {
+        if let Token::StartTag {
+            attributes,
+            raw_attributes,
+            ..
+        } = token
+        {
+            for (name, value) in raw_attributes.drain(..) {
+                attributes.push(self.decode_raw_attribute(name, value));
+            }
+        }
+    }
+
+    /// Value of the attribute `name` of a start tag, decoding only that attribute when the
+    /// token was produced with lazy attributes
+    pub fn attribute_value(&self, token: &Token, name: &str) -> Option<String> {
+        let Token::StartTag {
+            attributes,
+            raw_attributes,
+            ..
+        } = token
+        else {
+            return None;
+        };
+        if let Some((_, value)) = attributes.iter().find(|(attr_name, _)| attr_name == name) {
+            return Some(value.clone());
+        }
+        let input = self.input_stream.data();
+        raw_attributes
+            .iter()
+            .find(|(raw_name, _)| input[raw_name.clone()].eq_ignore_ascii_case(name.as_bytes()))
+            .map(|(raw_name, raw_value)| {
+                self.decode_raw_attribute(raw_name.clone(), raw_value.clone()).1
+            })
+    }
+
+    /// Runs the attribute source through an eager tokenizer, so decoding follows exactly the
+    /// same rules (lowercasing, NULs, character references in attribute values)
+    fn decode_raw_attribute(&self, name: Range<usize>, value: Range<usize>) -> (SmallStr, String) {
+        let input = self.input_stream.data();
+        let quoted = value.start > name.end
+            && matches!(input[value.start - 1], b'"' | b'\'')
+            && input.get(value.end) == Some(&input[value.start - 1]);
+        let end = if quoted { value.end + 1 } else { value.end };
+        let mut source = b"<a ".to_vec();
+        source.extend_from_slice(&input[name.start..end]);
+        source.push(b'>');
+        match Tokenizer::new(&source).next_token() {
+            Token::StartTag { mut attributes, .. } if !attributes.is_empty() => {
+                attributes.swap_remove(0)
+            }
+            _ => (SmallStr::new(), String::new()),
+        }
+    }
+
+    fn step_checked(&mut self) {
+        self.step();
+        // a state that consumed EOF without handling it would otherwise spin forever
+        if self.input_stream.idx > self.input_stream.len() + 1 {
+            self.emit_token(Token::EOF);
+        }
+    }
+
+    /// Tokens emitted so far
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    fn is_eof_emitted(&self) -> bool {
+        matches!(self.tokens.last(), Some(Token::EOF))
+    }
+
+    fn step(&mut self) {
+        {
             match self.state {
                 TokenizerState::Data => self.handle_data_state(),
                 TokenizerState::RCDATA => self.handle_rcdata_state(),
@@ -385,13 +744,13 @@ impl<'a> Tokenizer<'a> {
             }
             Some(b'<') => self.state = TokenizerState::TagOpen,
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character {
-                    data: next_char.unwrap() as char,
+                    data: self.current_input_character,
                 });
             }
             None => self.emit_token(Token::EOF),
-            Some(ch) => self.emit_token(Token::Character { data: ch as char }),
+            Some(_) => self.emit_token(Token::Character { data: self.current_input_character }),
         }
     }
 
@@ -405,11 +764,11 @@ impl<'a> Tokenizer<'a> {
             }
             Some(b'<') => self.state = TokenizerState::RCDATALessThanSign,
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' }); //REPLACEMENT CHARACTER character token.
             }
             None => self.emit_token(Token::EOF),
-            Some(ch) => self.emit_token(Token::Character { data: ch as char }),
+            Some(_) => self.emit_token(Token::Character { data: self.current_input_character }),
         }
     }
 
@@ -419,11 +778,11 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(b'<') => self.state = TokenizerState::RAWTEXTLessThanSign,
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
             None => self.emit_token(Token::EOF),
-            Some(ch) => self.emit_token(Token::Character { data: ch as char }),
+            Some(_) => self.emit_token(Token::Character { data: self.current_input_character }),
         }
     }
 
@@ -433,11 +792,11 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(b'<') => self.state = TokenizerState::ScriptDataLessThanSign,
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
             None => self.emit_token(Token::EOF),
-            Some(ch) => self.emit_token(Token::Character { data: ch as char }),
+            Some(_) => self.emit_token(Token::Character { data: self.current_input_character }),
         }
     }
 
@@ -446,11 +805,11 @@ impl<'a> Tokenizer<'a> {
 
         match next_char {
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
             None => self.emit_token(Token::EOF),
-            Some(ch) => self.emit_token(Token::Character { data: ch as char }),
+            Some(_) => self.emit_token(Token::Character { data: self.current_input_character }),
         }
     }
 
@@ -462,15 +821,16 @@ impl<'a> Tokenizer<'a> {
             Some(b'/') => self.state = TokenizerState::EndTagOpen,
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::StartTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
+                    raw_attributes: Vec::new(),
                 });
                 self.state = TokenizerState::TagName;
                 self.reconsume_char();
             }
             Some(b'?') => {
-                self.emit_parse_error("unexpected-question-mark-instead-of-tag-name");
+                self.emit_parse_error(ParseErrorCode::UnexpectedQuestionMarkInsteadOfTagName);
                 self.current_comment_token = Some(Token::Comment {
                     data: String::new(),
                 });
@@ -478,12 +838,12 @@ impl<'a> Tokenizer<'a> {
                 self.reconsume_char();
             }
             None => {
-                self.emit_parse_error(" eof-before-tag-name");
+                self.emit_parse_error(ParseErrorCode::EofBeforeTagName);
                 self.emit_token(Token::Character { data: '<' });
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
-                self.emit_parse_error("invalid-first-character-of-tag-name");
+                self.emit_parse_error(ParseErrorCode::InvalidFirstCharacterOfTagName);
                 self.emit_token(Token::Character { data: '<' });
                 self.state = TokenizerState::Data;
                 self.reconsume_char();
@@ -497,7 +857,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::EndTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
                 });
@@ -505,17 +865,17 @@ impl<'a> Tokenizer<'a> {
                 self.reconsume_char();
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-end-tag-name");
+                self.emit_parse_error(ParseErrorCode::MissingEndTagName);
                 self.state = TokenizerState::Data;
             }
             None => {
-                self.emit_parse_error("eof-before-tag-name");
+                self.emit_parse_error(ParseErrorCode::EofBeforeTagName);
                 self.emit_token(Token::Character { data: '<' });
                 self.emit_token(Token::Character { data: '/' });
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
-                self.emit_parse_error("invalid-first-character-of-tag-name");
+                self.emit_parse_error(ParseErrorCode::InvalidFirstCharacterOfTagName);
                 self.current_comment_token = Some(Token::Comment {
                     data: String::new(),
                 });
@@ -542,24 +902,18 @@ impl<'a> Tokenizer<'a> {
                 }
             }
             Some(ch) if ch.is_ascii_uppercase() => {
-                if let Some(Token::StartTag { tag_name, .. }) = self.current_tag_token.as_mut() {
-                    tag_name.push((ch + 0x20) as char);
-                }
+                self.append_to_current_tag_name((ch + 0x20) as char);
             }
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
-                if let Some(Token::StartTag { tag_name, .. }) = self.current_tag_token.as_mut() {
-                    tag_name.push('\u{FFFD}');
-                }
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.append_to_current_tag_name('\u{FFFD}');
             }
             None => {
-                self.emit_parse_error("Parse error: EOF in tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
-            Some(ch) => {
-                if let Some(Token::StartTag { tag_name, .. }) = self.current_tag_token.as_mut() {
-                    tag_name.push(ch as char);
-                }
+            Some(_) => {
+                self.append_to_current_tag_name(self.current_input_character);
             }
         }
     }
@@ -586,7 +940,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::EndTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
                 });
@@ -640,7 +994,7 @@ impl<'a> Tokenizer<'a> {
                 {
                     tag_name.push((ch + 0x20) as char);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
@@ -648,9 +1002,9 @@ impl<'a> Tokenizer<'a> {
                     ref mut tag_name, ..
                 }) = self.current_tag_token.as_mut()
                 {
-                    tag_name.push(ch as char);
+                    tag_name.push(self.current_input_character);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             _ => {
@@ -710,7 +1064,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::EndTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
                 });
@@ -764,7 +1118,7 @@ impl<'a> Tokenizer<'a> {
                 {
                     tag_name.push((ch + 0x20) as char);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
@@ -772,9 +1126,9 @@ impl<'a> Tokenizer<'a> {
                     ref mut tag_name, ..
                 }) = self.current_tag_token.as_mut()
                 {
-                    tag_name.push(ch as char);
+                    tag_name.push(self.current_input_character);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             _ => {
@@ -811,6 +1165,7 @@ impl<'a> Tokenizer<'a> {
             }
             _ => {
                 self.emit_token(Token::Character { data: '<' });
+                self.state = TokenizerState::ScriptData;
                 self.reconsume_char();
             }
         }
@@ -821,7 +1176,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::EndTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
                 });
@@ -874,7 +1229,7 @@ impl<'a> Tokenizer<'a> {
                 {
                     tag_name.push((ch + 0x20) as char);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
@@ -882,9 +1237,9 @@ impl<'a> Tokenizer<'a> {
                     ref mut tag_name, ..
                 }) = self.current_tag_token.as_mut()
                 {
-                    tag_name.push(ch as char);
+                    tag_name.push(self.current_input_character);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             _ => {
@@ -953,17 +1308,17 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' }); // Emit a replacement character (U+FFFD)
             }
 
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
-                self.emit_token(Token::Character { data: ch as char });
+            Some(_) => {
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -983,20 +1338,20 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.state = TokenizerState::ScriptDataEscaped;
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
 
             // Handling EOF
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
+            Some(_) => {
                 self.state = TokenizerState::ScriptDataEscaped;
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -1020,19 +1375,19 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.state = TokenizerState::ScriptDataEscaped;
                 self.emit_token(Token::Character { data: '\u{FFFD}' }); // Emit a replacement character (U+FFFD)
             }
 
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
+            Some(_) => {
                 self.state = TokenizerState::ScriptDataEscaped;
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -1069,7 +1424,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(ch) if ch.is_ascii_alphabetic() => {
                 self.current_tag_token = Some(Token::EndTag {
-                    tag_name: String::new(),
+                    tag_name: SmallStr::new(),
                     self_closing: false,
                     attributes: Vec::new(),
                 });
@@ -1125,7 +1480,7 @@ impl<'a> Tokenizer<'a> {
                 {
                     tag_name.push((ch + 0x20) as char);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
@@ -1133,9 +1488,9 @@ impl<'a> Tokenizer<'a> {
                     ref mut tag_name, ..
                 }) = self.current_tag_token.as_mut()
                 {
-                    tag_name.push(ch as char);
+                    tag_name.push(self.current_input_character);
                 }
-                self.temporary_buffer.push(ch as char);
+                self.temporary_buffer.push(self.current_input_character);
             }
 
             _ => {
@@ -1171,18 +1526,18 @@ impl<'a> Tokenizer<'a> {
                     self.state = TokenizerState::ScriptDataEscaped;
                 }
                 self.emit_token(Token::Character {
-                    data: next_char.unwrap() as char,
+                    data: self.current_input_character,
                 });
             }
 
             Some(ch) if ch.is_ascii_uppercase() => {
                 self.temporary_buffer.push((ch + 0x20) as char);
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
-                self.temporary_buffer.push(ch as char);
-                self.emit_token(Token::Character { data: ch as char });
+                self.temporary_buffer.push(self.current_input_character);
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
 
             _ => {
@@ -1208,17 +1563,17 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
 
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
-                self.emit_token(Token::Character { data: ch as char });
+            Some(_) => {
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -1239,19 +1594,19 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.state = TokenizerState::ScriptDataDoubleEscaped;
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
 
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
+            Some(_) => {
                 self.state = TokenizerState::ScriptDataDoubleEscaped;
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -1276,19 +1631,19 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(0x00) => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.state = TokenizerState::ScriptDataDoubleEscaped;
                 self.emit_token(Token::Character { data: '\u{FFFD}' });
             }
 
             None => {
-                self.emit_parse_error("eof-in-script-html-comment-like-text");
+                self.emit_parse_error(ParseErrorCode::EofInScriptHtmlCommentLikeText);
                 self.emit_token(Token::EOF);
             }
 
-            Some(ch) => {
+            Some(_) => {
                 self.state = TokenizerState::ScriptDataDoubleEscaped;
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -1323,18 +1678,18 @@ impl<'a> Tokenizer<'a> {
                     self.state = TokenizerState::ScriptDataDoubleEscaped;
                 }
                 self.emit_token(Token::Character {
-                    data: next_char.unwrap() as char,
+                    data: self.current_input_character,
                 });
             }
 
             Some(ch) if ch.is_ascii_uppercase() => {
                 self.temporary_buffer.push((ch + 0x20) as char);
-                self.emit_token(Token::Character { data: ch as char });
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
 
             Some(ch) if ch.is_ascii_lowercase() => {
-                self.temporary_buffer.push(ch as char);
-                self.emit_token(Token::Character { data: ch as char });
+                self.temporary_buffer.push(self.current_input_character);
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
 
             _ => {
@@ -1356,9 +1711,10 @@ impl<'a> Tokenizer<'a> {
             }
 
             Some(b'=') => {
-                self.emit_parse_error("unexpected-equals-sign-before-attribute-name");
-                let name = "=".to_string(); //need to check attribute name duplication before putting in the current_tag_token
+                self.emit_parse_error(ParseErrorCode::UnexpectedEqualsSignBeforeAttributeName);
+                self.current_tag_name = "=".to_string(); //need to check attribute name duplication before putting in the current_tag_token
                 self.current_tag_value.clear();
+                self.start_raw_attribute(self.input_stream.idx - 1);
                 self.state = TokenizerState::AttributeName;
             }
 
@@ -1367,6 +1723,7 @@ impl<'a> Tokenizer<'a> {
                 self.current_tag_value.clear();
                 self.state = TokenizerState::AttributeName;
                 self.reconsume_char();
+                self.start_raw_attribute(self.input_stream.idx);
             }
         }
     }
@@ -1380,28 +1737,30 @@ impl<'a> Tokenizer<'a> {
             | None => {
                 self.state = TokenizerState::AfterAttributeName;
                 self.reconsume_char();
+                self.end_raw_attribute_name(self.input_stream.idx);
             }
 
             Some(b'=') => {
+                self.end_raw_attribute_name(self.input_stream.idx - 1);
                 self.state = TokenizerState::BeforeAttributeValue;
             }
 
             Some(c) if c.is_ascii_uppercase() => {
-                self.current_tag_name.push((c + 0x20) as char);
+                self.push_to_attribute_name((c + 0x20) as char);
             }
 
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
-                self.current_tag_name.push('\u{FFFD}' as char);
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.push_to_attribute_name('\u{FFFD}');
             }
 
             Some(b'"') | Some(b'\'') | Some(b'<') => {
-                self.emit_parse_error("unexpected-character-in-attribute-name");
-                self.current_tag_name.push(next_char.unwrap() as char);
+                self.emit_parse_error(ParseErrorCode::UnexpectedCharacterInAttributeName);
+                self.push_to_attribute_name(self.current_input_character);
             }
 
             Some(_) => {
-                self.current_tag_name.push(next_char.unwrap() as char);
+                self.push_to_attribute_name(self.current_input_character);
             }
         }
     }
@@ -1437,7 +1796,7 @@ impl<'a> Tokenizer<'a> {
                 //no value next so add name to current_tag_token
                 self.add_attribute_to_current_tag_token();
 
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
 
@@ -1458,21 +1817,24 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(b'\t') | Some(b'\n') | Some(b'\x0C') | Some(b' ') => {}
             Some(b'"') => {
+                self.raw_attribute_value = self.input_stream.idx..self.input_stream.idx;
                 self.state = TokenizerState::AttributeValueDoubleQuoted;
             }
             Some(b'\'') => {
+                self.raw_attribute_value = self.input_stream.idx..self.input_stream.idx;
                 self.state = TokenizerState::AttributeValueSingleQuoted;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-attribute-value");
+                self.emit_parse_error(ParseErrorCode::MissingAttributeValue);
+                self.add_attribute_to_current_tag_token();
                 self.state = TokenizerState::Data;
                 self.emit_current_tag_token();
             }
-            Some(_) => {
+            Some(_) | None => {
                 self.state = TokenizerState::AttributeValueUnquoted;
                 self.reconsume_char();
+                self.raw_attribute_value = self.input_stream.idx..self.input_stream.idx;
             }
-            None => {}
         }
     }
 
@@ -1482,6 +1844,8 @@ impl<'a> Tokenizer<'a> {
 
         match next_char {
             Some(b'"') => {
+                self.raw_attribute_value.end = self.input_stream.idx - 1;
+                self.add_attribute_to_current_tag_token();
                 self.state = TokenizerState::AfterAttributeValueQuoted;
             }
             Some(b'&') => {
@@ -1489,15 +1853,15 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CharacterReference;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
-                self.current_tag_value.push('\u{FFFD}');
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.push_to_attribute_value('\u{FFFD}');
             }
             Some(_) => {
-                self.current_tag_value.push(next_char.unwrap() as char);
+                self.push_to_attribute_value(self.current_input_character);
             }
             None => {
                 // eof-in-tag parse error.
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
         }
@@ -1509,6 +1873,8 @@ impl<'a> Tokenizer<'a> {
 
         match next_char {
             Some(b'\'') => {
+                self.raw_attribute_value.end = self.input_stream.idx - 1;
+                self.add_attribute_to_current_tag_token();
                 self.state = TokenizerState::AfterAttributeValueQuoted;
             }
             Some(b'&') => {
@@ -1516,14 +1882,14 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CharacterReference;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
-                self.current_tag_value.push('\u{FFFD}');
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.push_to_attribute_value('\u{FFFD}');
             }
             Some(_) => {
-                self.current_tag_value.push(next_char.unwrap() as char);
+                self.push_to_attribute_value(self.current_input_character);
             }
             None => {
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
         }
@@ -1535,6 +1901,8 @@ impl<'a> Tokenizer<'a> {
 
         match next_char {
             Some(b'\t') | Some(b'\n') | Some(b'\x0C') | Some(b' ') => {
+                self.raw_attribute_value.end = self.input_stream.idx - 1;
+                self.add_attribute_to_current_tag_token();
                 self.state = TokenizerState::BeforeAttributeName;
             }
             Some(b'&') => {
@@ -1542,22 +1910,24 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CharacterReference;
             }
             Some(b'>') => {
+                self.raw_attribute_value.end = self.input_stream.idx - 1;
+                self.add_attribute_to_current_tag_token();
                 self.state = TokenizerState::Data;
                 self.emit_current_tag_token();
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
-                self.current_tag_value.push('\u{FFFD}');
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.push_to_attribute_value('\u{FFFD}');
             }
             Some(b'"') | Some(b'\'') | Some(b'<') | Some(b'=') | Some(b'`') => {
-                self.emit_parse_error("unexpected-character-in-unquoted-attribute-value");
-                self.current_tag_value.push(next_char.unwrap() as char);
+                self.emit_parse_error(ParseErrorCode::UnexpectedCharacterInUnquotedAttributeValue);
+                self.push_to_attribute_value(self.current_input_character);
             }
             Some(_) => {
-                self.current_tag_value.push(next_char.unwrap() as char);
+                self.push_to_attribute_value(self.current_input_character);
             }
             None => {
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
         }
@@ -1579,12 +1949,12 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_tag_token();
             }
             Some(_) => {
-                self.emit_parse_error("missing-whitespace-between-attributes");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceBetweenAttributes);
                 self.state = TokenizerState::BeforeAttributeName;
                 self.reconsume_char();
             }
             None => {
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
         }
@@ -1603,12 +1973,12 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_tag_token();
             }
             Some(_) => {
-                self.emit_parse_error("unexpected-solidus-in-tag");
+                self.emit_parse_error(ParseErrorCode::UnexpectedSolidusInTag);
                 self.state = TokenizerState::BeforeAttributeName;
                 self.reconsume_char();
             }
             None => {
-                self.emit_parse_error("eof-in-tag");
+                self.emit_parse_error(ParseErrorCode::EofInTag);
                 self.emit_token(Token::EOF);
             }
         }
@@ -1624,7 +1994,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_comment_token();
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::Comment { ref mut data, .. }) =
                     self.current_comment_token.as_mut()
                 {
@@ -1635,7 +2005,7 @@ impl<'a> Tokenizer<'a> {
                 if let Some(Token::Comment { ref mut data, .. }) =
                     self.current_comment_token.as_mut()
                 {
-                    data.push(next_char.unwrap() as char);
+                    data.push(self.current_input_character);
                 }
             }
             None => {
@@ -1653,12 +2023,10 @@ impl<'a> Tokenizer<'a> {
             });
             self.state = TokenizerState::CommentStart;
         } else if self.consume_if_expected(b"DOCTYPE", true) {
-            self.consume_next_input_char();
             self.state = TokenizerState::DOCTYPE;
         } else if self.consume_if_expected(b"[CDATA[", false) {
-            // NEED_IMPLEMENT_LATER
-            if true {
-                self.emit_parse_error("cdata-in-html-content");
+            if !self.cdata_allowed {
+                self.emit_parse_error(ParseErrorCode::CdataInHtmlContent);
                 self.current_comment_token = Some(Token::Comment {
                     data: "[CDATA[".to_string(),
                 });
@@ -1667,7 +2035,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CDATASection;
             }
         } else {
-            self.emit_parse_error("incorrectly-opened-comment");
+            self.emit_parse_error(ParseErrorCode::IncorrectlyOpenedComment);
             self.current_comment_token = Some(Token::Comment {
                 data: String::new(),
             });
@@ -1684,7 +2052,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CommentStartDash;
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-closing-of-empty-comment");
+                self.emit_parse_error(ParseErrorCode::AbruptClosingOfEmptyComment);
                 self.state = TokenizerState::Data;
                 self.emit_current_comment_token();
             }
@@ -1705,7 +2073,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CommentEnd;
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-closing-of-empty-comment");
+                self.emit_parse_error(ParseErrorCode::AbruptClosingOfEmptyComment);
                 self.state = TokenizerState::Data;
                 self.emit_current_comment_token();
             }
@@ -1719,7 +2087,7 @@ impl<'a> Tokenizer<'a> {
                 self.reconsume_char();
             }
             None => {
-                self.emit_parse_error("eof-in-comment");
+                self.emit_parse_error(ParseErrorCode::EofInComment);
                 self.emit_current_comment_token();
                 self.emit_token(Token::EOF);
             }
@@ -1743,7 +2111,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CommentEndDash;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::Comment { ref mut data, .. }) =
                     self.current_comment_token.as_mut()
                 {
@@ -1754,11 +2122,11 @@ impl<'a> Tokenizer<'a> {
                 if let Some(Token::Comment { ref mut data, .. }) =
                     self.current_comment_token.as_mut()
                 {
-                    data.push(next_char.unwrap() as char);
+                    data.push(self.current_input_character);
                 }
             }
             None => {
-                self.emit_parse_error("eof-in-comment");
+                self.emit_parse_error(ParseErrorCode::EofInComment);
                 self.emit_current_comment_token();
                 self.emit_token(Token::EOF);
             }
@@ -1830,7 +2198,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CommentEnd;
             }
             Some(_) => {
-                self.emit_parse_error("nested-comment");
+                self.emit_parse_error(ParseErrorCode::NestedComment);
                 self.reconsume_char();
                 self.state = TokenizerState::CommentEnd;
             }
@@ -1854,7 +2222,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::Comment;
             }
             None => {
-                self.emit_parse_error("eof-in-comment");
+                self.emit_parse_error(ParseErrorCode::EofInComment);
                 self.emit_current_comment_token();
                 self.emit_token(Token::EOF);
             }
@@ -1890,7 +2258,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::Comment;
             }
             None => {
-                self.emit_parse_error("eof-in-comment");
+                self.emit_parse_error(ParseErrorCode::EofInComment);
                 self.emit_current_comment_token();
                 self.emit_token(Token::EOF);
             }
@@ -1911,7 +2279,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CommentEndDash;
             }
             Some(b'>') => {
-                self.emit_parse_error("incorrectly-closed-comment");
+                self.emit_parse_error(ParseErrorCode::IncorrectlyClosedComment);
                 self.state = TokenizerState::Data;
                 self.emit_current_comment_token();
             }
@@ -1925,7 +2293,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::Comment;
             }
             None => {
-                self.emit_parse_error("eof-in-comment");
+                self.emit_parse_error(ParseErrorCode::EofInComment);
                 self.emit_current_comment_token();
                 self.emit_token(Token::EOF);
             }
@@ -1945,12 +2313,12 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::BeforeDOCTYPEName;
             }
             Some(_) => {
-                self.emit_parse_error("missing-whitespace-before-doctype-name");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceBeforeDoctypeName);
                 self.reconsume_char();
                 self.state = TokenizerState::BeforeDOCTYPEName;
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 let token = Token::DOCTYPE {
                     name: None,
                     public_id: None,
@@ -1970,7 +2338,7 @@ impl<'a> Tokenizer<'a> {
         match next_char {
             Some(b'\t') | Some(b'\n') | Some(b'\x0C') | Some(b' ') => {}
             Some(c) if c.is_ascii_uppercase() => {
-                let name = (c as char).to_ascii_lowercase().to_string();
+                let name = self.current_input_character.to_ascii_lowercase().to_string();
                 self.current_doctype_token = Some(Token::DOCTYPE {
                     name: Some(name),
                     public_id: None,
@@ -1980,7 +2348,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEName;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 self.current_doctype_token = Some(Token::DOCTYPE {
                     name: Some("\u{FFFD}".to_string()),
                     public_id: None,
@@ -1990,7 +2358,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEName;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-doctype-name");
+                self.emit_parse_error(ParseErrorCode::MissingDoctypeName);
                 self.current_doctype_token = Some(Token::DOCTYPE {
                     name: None,
                     public_id: None,
@@ -2000,8 +2368,8 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::Data;
                 self.emit_current_doctype_token();
             }
-            Some(c) => {
-                let name = (c as char).to_string();
+            Some(_) => {
+                let name = self.current_input_character.to_string();
                 self.current_doctype_token = Some(Token::DOCTYPE {
                     name: Some(name),
                     public_id: None,
@@ -2011,7 +2379,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEName;
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 let token = Token::DOCTYPE {
                     name: None,
                     public_id: None,
@@ -2042,26 +2410,26 @@ impl<'a> Tokenizer<'a> {
                 {
                     name.as_mut()
                         .unwrap()
-                        .push((c as char).to_ascii_lowercase());
+                        .push(self.current_input_character.to_ascii_lowercase());
                 }
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::DOCTYPE { ref mut name, .. }) =
                     self.current_doctype_token.as_mut()
                 {
                     name.as_mut().unwrap().push('\u{FFFD}');
                 }
             }
-            Some(c) => {
+            Some(_) => {
                 if let Some(Token::DOCTYPE { ref mut name, .. }) =
                     self.current_doctype_token.as_mut()
                 {
-                    name.as_mut().unwrap().push(c as char);
+                    name.as_mut().unwrap().push(self.current_input_character);
                 }
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2086,7 +2454,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2098,12 +2466,14 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
+                // the keywords are matched from the current input character on
+                self.reconsume_char();
                 if self.consume_if_expected(b"PUBLIC", true) {
                     self.state = TokenizerState::AfterDOCTYPEPublicKeyword;
                 } else if self.consume_if_expected(b"SYSTEM", true) {
                     self.state = TokenizerState::AfterDOCTYPESystemKeyword;
                 } else {
-                    self.emit_parse_error("invalid-character-sequence-after-doctype-name");
+                    self.emit_parse_error(ParseErrorCode::InvalidCharacterSequenceAfterDoctypeName);
                     if let Some(Token::DOCTYPE {
                         ref mut force_quirks,
                         ..
@@ -2111,7 +2481,6 @@ impl<'a> Tokenizer<'a> {
                     {
                         *force_quirks = true;
                     }
-                    self.reconsume_char();
                     self.state = TokenizerState::BogusDOCTYPE;
                 }
             }
@@ -2127,7 +2496,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::BeforeDOCTYPEPublicIdentifier;
             }
             Some(b'"') => {
-                self.emit_parse_error("missing-whitespace-after-doctype-public-keyword");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceAfterDoctypePublicKeyword);
                 if let Some(Token::DOCTYPE {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2137,7 +2506,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEPublicIdentifierDoubleQuoted;
             }
             Some(b'\'') => {
-                self.emit_parse_error("missing-whitespace-after-doctype-public-keyword");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceAfterDoctypePublicKeyword);
                 if let Some(Token::DOCTYPE {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2147,7 +2516,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEPublicIdentifierSingleQuoted;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2159,7 +2528,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2171,7 +2540,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
-                self.emit_parse_error("missing-quote-before-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2210,7 +2579,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPEPublicIdentifierSingleQuoted;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2222,7 +2591,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2234,7 +2603,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
-                self.emit_parse_error("missing-quote-before-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2257,7 +2626,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::AfterDOCTYPEPublicIdentifier;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::DOCTYPE {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2266,7 +2635,7 @@ impl<'a> Tokenizer<'a> {
                 }
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::AbruptDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2278,7 +2647,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2294,7 +2663,7 @@ impl<'a> Tokenizer<'a> {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
                 {
-                    public_id.as_mut().unwrap().push('\u{FFFD}');
+                    public_id.as_mut().unwrap().push(self.current_input_character);
                 }
             }
         }
@@ -2309,7 +2678,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::AfterDOCTYPEPublicIdentifier;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::DOCTYPE {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2318,7 +2687,7 @@ impl<'a> Tokenizer<'a> {
                 }
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-doctype-public-identifier");
+                self.emit_parse_error(ParseErrorCode::AbruptDoctypePublicIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2330,7 +2699,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2346,7 +2715,7 @@ impl<'a> Tokenizer<'a> {
                     ref mut public_id, ..
                 }) = self.current_doctype_token.as_mut()
                 {
-                    public_id.as_mut().unwrap().push('\u{FFFD}');
+                    public_id.as_mut().unwrap().push(self.current_input_character);
                 }
             }
         }
@@ -2366,7 +2735,7 @@ impl<'a> Tokenizer<'a> {
             }
             Some(b'"') => {
                 self.emit_parse_error(
-                    "missing-whitespace-between-doctype-public-and-system-identifiers",
+                    ParseErrorCode::MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers,
                 );
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
@@ -2378,7 +2747,7 @@ impl<'a> Tokenizer<'a> {
             }
             Some(b'\'') => {
                 self.emit_parse_error(
-                    "missing-whitespace-between-doctype-public-and-system-identifiers",
+                    ParseErrorCode::MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers,
                 );
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
@@ -2389,7 +2758,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPESystemIdentifierSingleQuoted;
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2401,7 +2770,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             _ => {
-                self.emit_parse_error("missing-quote-before-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2444,7 +2813,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPESystemIdentifierSingleQuoted;
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2456,7 +2825,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             _ => {
-                self.emit_parse_error("missing-quote-before-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2479,7 +2848,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::BeforeDOCTYPESystemIdentifier;
             }
             Some(b'"') => {
-                self.emit_parse_error("missing-whitespace-after-doctype-system-keyword");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceAfterDoctypeSystemKeyword);
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2489,7 +2858,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPESystemIdentifierDoubleQuoted;
             }
             Some(b'\'') => {
-                self.emit_parse_error("missing-whitespace-after-doctype-system-keyword");
+                self.emit_parse_error(ParseErrorCode::MissingWhitespaceAfterDoctypeSystemKeyword);
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2499,7 +2868,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPESystemIdentifierSingleQuoted;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2511,7 +2880,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2523,7 +2892,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             _ => {
-                self.emit_parse_error("missing-quote-before-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2562,7 +2931,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::DOCTYPESystemIdentifierSingleQuoted;
             }
             Some(b'>') => {
-                self.emit_parse_error("missing-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2574,7 +2943,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2586,7 +2955,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             _ => {
-                self.emit_parse_error("missing-quote-before-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2609,7 +2978,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::AfterDOCTYPESystemIdentifier;
             }
             Some(b'\0') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2618,7 +2987,7 @@ impl<'a> Tokenizer<'a> {
                 }
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::AbruptDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2630,7 +2999,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2646,7 +3015,7 @@ impl<'a> Tokenizer<'a> {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
                 {
-                    system_id.as_mut().unwrap().push(next_char.unwrap() as char);
+                    system_id.as_mut().unwrap().push(self.current_input_character);
                 }
             }
         }
@@ -2661,7 +3030,7 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::AfterDOCTYPESystemIdentifier;
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
                 if let Some(Token::DOCTYPE {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
@@ -2670,7 +3039,7 @@ impl<'a> Tokenizer<'a> {
                 }
             }
             Some(b'>') => {
-                self.emit_parse_error("abrupt-doctype-system-identifier");
+                self.emit_parse_error(ParseErrorCode::AbruptDoctypeSystemIdentifier);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2682,7 +3051,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2698,7 +3067,7 @@ impl<'a> Tokenizer<'a> {
                     ref mut system_id, ..
                 }) = self.current_doctype_token.as_mut()
                 {
-                    system_id.as_mut().unwrap().push(next_char.unwrap() as char);
+                    system_id.as_mut().unwrap().push(self.current_input_character);
                 }
             }
         }
@@ -2715,7 +3084,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             None => {
-                self.emit_parse_error("eof-in-doctype");
+                self.emit_parse_error(ParseErrorCode::EofInDoctype);
                 if let Some(Token::DOCTYPE {
                     ref mut force_quirks,
                     ..
@@ -2727,7 +3096,9 @@ impl<'a> Tokenizer<'a> {
                 self.emit_token(Token::EOF);
             }
             Some(_) => {
-                self.emit_parse_error("unexpected-character-after-doctype-system-identifier");
+                self.emit_parse_error(
+                    ParseErrorCode::UnexpectedCharacterAfterDoctypeSystemIdentifier,
+                );
                 self.reconsume_char();
                 self.state = TokenizerState::BogusDOCTYPE;
             }
@@ -2744,7 +3115,7 @@ impl<'a> Tokenizer<'a> {
                 self.emit_current_doctype_token();
             }
             Some(b'\x00') => {
-                self.emit_parse_error("unexpected-null-character");
+                self.emit_parse_error(ParseErrorCode::UnexpectedNullCharacter);
             }
             None => {
                 self.emit_current_doctype_token();
@@ -2763,11 +3134,11 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::CDATASectionBracket;
             }
             None => {
-                self.emit_parse_error("eof-in-cdata");
+                self.emit_parse_error(ParseErrorCode::EofInCdata);
                 self.emit_token(Token::EOF);
             }
-            Some(ch) => {
-                self.emit_token(Token::Character { data: ch as char });
+            Some(_) => {
+                self.emit_token(Token::Character { data: self.current_input_character });
             }
         }
     }
@@ -2780,12 +3151,11 @@ impl<'a> Tokenizer<'a> {
             Some(b']') => {
                 self.state = TokenizerState::CDATASectionEnd;
             }
-            Some(_) => {
+            Some(_) | None => {
                 self.emit_token(Token::Character { data: ']' });
                 self.reconsume_char();
                 self.state = TokenizerState::CDATASection;
             }
-            None => {}
         }
     }
 
@@ -2800,23 +3170,24 @@ impl<'a> Tokenizer<'a> {
             Some(b'>') => {
                 self.state = TokenizerState::Data;
             }
-            Some(_) => {
+            Some(_) | None => {
                 self.emit_token(Token::Character { data: ']' });
                 self.reconsume_char();
                 self.state = TokenizerState::CDATASection;
             }
-            None => {}
         }
     }
 
     // 13.2.5.72 Character reference state
     fn handle_character_reference_state(&mut self) {
+        self.character_reference_start = self.input_stream.idx;
         self.temporary_buffer = String::new();
         self.temporary_buffer.push('&');
         let next_char = self.consume_next_input_char();
 
         match next_char {
             Some(c) if c.is_ascii_alphanumeric() => {
+                self.reconsume_char();
                 self.state = TokenizerState::NamedCharacterReference;
             }
             Some(b'#') => {
@@ -2825,42 +3196,37 @@ impl<'a> Tokenizer<'a> {
             }
             _ => {
                 self.flush_code_points_consumed_as_a_character_references();
-
+                self.reconsume_char();
             }
         }
     }
     //13.2.5.73 Named character reference state
     fn handle_named_character_reference_state(&mut self) {
-        let mut matchResult:Option<&Entity> = None;
-        self.temporary_buffer.clear();
-        while true {
-            let next_char = self.consume_next_input_char();
-            self.temporary_buffer.push(next_char.unwrap() as char);
-            if let Some(entity) = ENTITIES.get(&self.temporary_buffer) {
-                matchResult = Some(entity);
-            } else if !ENTITIES.keys().any(|k| k.starts_with(&self.temporary_buffer)){
-                self.temporary_buffer.pop();
-                self.reconsume_char();
-                break;
-            }          
-        }
-        match matchResult{
-            Some(E) => {
-                let next_char = self.consume_next_input_char().unwrap() as char ;
-                let last_character_match = self.temporary_buffer.chars().last().unwrap();
-                if (self.ret_state == TokenizerState::AttributeValueDoubleQuoted
-                    || self.ret_state == TokenizerState::AttributeValueSingleQuoted
-                    || self.ret_state == TokenizerState::AttributeValueUnquoted)
+        // temporary_buffer holds the '&' pushed by the character reference state
+        let lookahead = self.input_stream.slice_from_idx(*MAX_ENTITY_NAME_LENGTH);
+        let match_result = longest_entity_match(lookahead);
+        if let Some((length, _)) = match_result {
+            self.temporary_buffer.push_str(&String::from_utf8_lossy(&lookahead[..length]));
+            self.input_stream.idx += length;
+        }
+        match match_result.map(|(_, entity)| entity) {
+            Some(entity) => {
+                let next_char = self.input_stream.current_cpy().map(|c| c as char);
+                let last_character_match = self.temporary_buffer.chars().last().unwrap_or('&');
+                if self.is_consumed_as_part_of_an_attribute()
                     && last_character_match != ';'
-                    && (next_char == '=' || next_char.is_alphanumeric()) 
+                    && next_char.is_some_and(|c| c == '=' || c.is_ascii_alphanumeric())
                 {
                     self.flush_code_points_consumed_as_a_character_references();
                 } else {
                     if last_character_match != ';' {
-                        self.emit_parse_error("missing-semicolon-after-character-reference");
+                        self.emit_parse_error(
+                            ParseErrorCode::MissingSemicolonAfterCharacterReference,
+                        );
                     }
-                    self.temporary_buffer.push_str(&E.characters);
-            
+                    self.temporary_buffer.clear();
+                    self.temporary_buffer.push_str(&entity.characters);
+
                     self.flush_code_points_consumed_as_a_character_references();
                 }
             }
@@ -2869,7 +3235,6 @@ impl<'a> Tokenizer<'a> {
                 self.state = TokenizerState::AmbiguousAmpersand;
             }
         }
-        
     }
     //13.2.5.74 Ambiguous ampersand state
     fn handle_ambiguous_ampersand_state(&mut self) {
@@ -2879,13 +3244,22 @@ impl<'a> Tokenizer<'a> {
                 if self.ret_state == TokenizerState::AttributeValueDoubleQuoted ||
                     self.ret_state == TokenizerState::AttributeValueSingleQuoted ||
                     self.ret_state == TokenizerState::AttributeValueUnquoted {
-                    self.current_tag_value.push(c as char);
+                    self.push_to_attribute_value(self.current_input_character);
                 } else {
-                    self.emit_token(Token::Character { data: c as char });
+                    self.emit_token(Token::Character { data: self.current_input_character });
                 }
             }
             Some(b';') => {
-                self.emit_parse_error("unknown-named-character-reference");
+                self.emit_parse_error(ParseErrorCode::UnknownNamedCharacterReference);
+                if self.suggest_entity_names {
+                    if let Some(error) = self.errors.last_mut() {
+                        error.suggestion = error
+                            .context
+                            .as_deref()
+                            .and_then(closest_entity_name)
+                            .map(str::to_string);
+                    }
+                }
                 self.reconsume_char();
                 self.state = self.ret_state.clone();
             }
@@ -2899,11 +3273,11 @@ impl<'a> Tokenizer<'a> {
     //13.2.5.75 Numeric character reference state
     fn handle_numeric_character_reference_state(&mut self) {
         self.character_reference_code = 0; 
-        let next_char = self.consume_next_input_char().unwrap();
+        let next_char = self.consume_next_input_char();
     
         match next_char {
-            b'x' | b'X' => {
-                self.temporary_buffer.push(next_char as char); 
+            Some(b'x') | Some(b'X') => {
+                self.temporary_buffer.push(self.current_input_character); 
                 self.state = TokenizerState::HexadecimalCharacterReferenceStart;
             }
             _ => {
@@ -2914,50 +3288,51 @@ impl<'a> Tokenizer<'a> {
     }
     //13.2.5.76 Hexadecimal character reference start state
     fn handle_hexadecimal_character_reference_start_state(&mut self) {
-        let next_char = self.consume_next_input_char().unwrap();
+        let next_char = self.consume_next_input_char();
     
-        if next_char.is_ascii_hexdigit() {
+        if next_char.is_some_and(|c| c.is_ascii_hexdigit()) {
             self.reconsume_char(); 
             self.state = TokenizerState::HexadecimalCharacterReference;
         } else {
-            self.emit_parse_error("absence-of-digits-in-numeric-character-reference");
+            self.emit_parse_error(ParseErrorCode::AbsenceOfDigitsInNumericCharacterReference);
             self.flush_code_points_consumed_as_a_character_references();
+            self.reconsume_char();
         }
     }
     
     //13.2.5.77 Decimal character reference start state
     fn handle_decimal_character_reference_start_state(&mut self) {
-        let next_char = self.consume_next_input_char().unwrap();
+        let next_char = self.consume_next_input_char();
     
-        if next_char.is_ascii_digit() {
+        if next_char.is_some_and(|c| c.is_ascii_digit()) {
             self.reconsume_char();
             self.state = TokenizerState::DecimalCharacterReference;
         } else {
-            self.emit_parse_error("absence-of-digits-in-numeric-character-reference");
+            self.emit_parse_error(ParseErrorCode::AbsenceOfDigitsInNumericCharacterReference);
             self.flush_code_points_consumed_as_a_character_references();
+            self.reconsume_char();
         }
     }
     
     //13.2.5.78 Hexadecimal character reference state
     fn handle_hexadecimal_character_reference_state(&mut self) {
-        let next_char = self.consume_next_input_char().unwrap();
+        let next_char = self.consume_next_input_char().unwrap_or(0);
 
         match next_char {
             b'0'..=b'9' => {
-                self.character_reference_code = self.character_reference_code * 16 + (next_char - b'0') as u32;
+                self.character_reference_code = self.character_reference_code.saturating_mul(16).saturating_add((next_char - b'0') as u32);
             }
             b'A'..=b'F' => {
-                self.character_reference_code = self.character_reference_code * 16 + (next_char - 0x37) as u32;
+                self.character_reference_code = self.character_reference_code.saturating_mul(16).saturating_add((next_char - 0x37) as u32);
             }
             b'a'..=b'f' => {
-                self.character_reference_code = self.character_reference_code * 16 + (next_char - 0x57) as u32;
+                self.character_reference_code = self.character_reference_code.saturating_mul(16).saturating_add((next_char - 0x57) as u32);
             }
             b';' => {
                 self.state = TokenizerState::NumericCharacterReferenceEnd;
-                let a = b'A'; 
             }
             _ => {
-                self.emit_parse_error("missing-semicolon-after-character-reference");
+                self.emit_parse_error(ParseErrorCode::MissingSemicolonAfterCharacterReference);
                 self.reconsume_char();
                 self.state = TokenizerState::NumericCharacterReferenceEnd;
             }
@@ -2965,17 +3340,17 @@ impl<'a> Tokenizer<'a> {
     }
     //13.2.5.79 Decimal character reference state
     fn handle_decimal_character_reference_state(&mut self) {
-        let next_char = self.consume_next_input_char().unwrap();
+        let next_char = self.consume_next_input_char().unwrap_or(0);
     
         match next_char {
             b'0'..=b'9' => {
-                self.character_reference_code = self.character_reference_code * 10 + (next_char - b'0') as u32;
+                self.character_reference_code = self.character_reference_code.saturating_mul(10).saturating_add((next_char - b'0') as u32);
             }
             b';' => {
                 self.state = TokenizerState::NumericCharacterReferenceEnd; 
             }
             _ => {
-                self.emit_parse_error("missing-semicolon-after-character-reference");
+                self.emit_parse_error(ParseErrorCode::MissingSemicolonAfterCharacterReference);
                 self.reconsume_char();
                 self.state = TokenizerState::NumericCharacterReferenceEnd;
             }
@@ -2984,16 +3359,16 @@ impl<'a> Tokenizer<'a> {
 
     fn handle_numeric_character_reference_end_state(&mut self) {
         if self.character_reference_code == 0x00 {
-            self.emit_parse_error("Null character reference");
+            self.emit_parse_error(ParseErrorCode::NullCharacterReference);
             self.character_reference_code = 0xFFFD;
         } else if self.character_reference_code > 0x10FFFF {
-            self.emit_parse_error("Character reference outside Unicode range");
+            self.emit_parse_error(ParseErrorCode::CharacterReferenceOutsideUnicodeRange);
             self.character_reference_code = 0xFFFD;
         } else if is_surrogate(self.character_reference_code) {
-            self.emit_parse_error("Surrogate character reference");
+            self.emit_parse_error(ParseErrorCode::SurrogateCharacterReference);
             self.character_reference_code = 0xFFFD;
         } else if is_noncharacter(self.character_reference_code) {
-            self.emit_parse_error("Noncharacter character reference");
+            self.emit_parse_error(ParseErrorCode::NoncharacterCharacterReference);
         } else if is_control_character(self.character_reference_code) && self.character_reference_code != 0x0D {
             if let Some(replacement) = CONTROL_CHARACTER_REPLACEMENTS
                 .iter()
@@ -3007,7 +3382,7 @@ impl<'a> Tokenizer<'a> {
             {
                 self.character_reference_code = replacement;
             } else {
-                self.emit_parse_error("Control character reference");
+                self.emit_parse_error(ParseErrorCode::ControlCharacterReference);
             }
         }
         self.temporary_buffer.clear();
@@ -3016,30 +3391,36 @@ impl<'a> Tokenizer<'a> {
     }
 
     fn emit_token(&mut self, token: Token) {
-        match &token {
-            Token::StartTag { .. } => {
-                self.last_start_tag_token = Some(token.clone());
-            }
-            _ => {}
+        if let Token::StartTag { .. } = &token {
+            self.last_start_tag_token = Some(token.clone());
         }
-        println!("Emitting token: {:?}", token);
         self.tokens.push(token);
     }
 
+    /// Consumes the next input character and returns its first byte, which is all the state
+    /// machine matches on since the syntax is ASCII. The whole UTF-8 sequence is consumed and
+    /// its decoding kept in `current_input_character`, invalid sequences decode to U+FFFD.
     fn consume_next_input_char(&mut self) -> Option<u8> {
         let byte_character = self.input_stream.current_cpy();
-        self.input_stream.advance();
+        let (character, len) = match byte_character {
+            Some(byte) if byte >= 0x80 => decode_utf8(self.input_stream.slice_from_idx(4)),
+            Some(byte) => (byte as char, 1),
+            None => ('\0', 1),
+        };
+        self.current_input_character = character;
+        self.current_input_len = len;
+        self.input_stream.idx += len;
         byte_character
     }
     fn consume_if_expected(&mut self, expect: &[u8], ascii_insensitive: bool) -> bool {
         if !ascii_insensitive {
             self.input_stream.expect_many_and_skip(expect)
         } else {
-            let strSlice = self.input_stream.slice_from_idx(expect.len());
+            let str_slice = self.input_stream.slice_from_idx(expect.len());
             let result = expect
                 .iter()
                 .map(|c| c.to_ascii_lowercase())
-                .eq(strSlice.iter().map(|c| c.to_ascii_lowercase()));
+                .eq(str_slice.iter().map(|c| c.to_ascii_lowercase()));
             if result {
                 self.input_stream.idx += expect.len();
             }
@@ -3048,40 +3429,164 @@ impl<'a> Tokenizer<'a> {
     }
 
     fn reconsume_char(&mut self) {
-        self.input_stream.idx -= 1;
-        self.input_stream.idx = max(self.input_stream.idx, 0);
-    }
-
-    fn emit_parse_error(&self, err: &str) {
-        eprint!("{err}\n");
+        self.input_stream.idx = self.input_stream.idx.saturating_sub(self.current_input_len);
+        self.current_input_len = 1;
+    }
+
+    fn emit_parse_error(&mut self, code: ParseErrorCode) {
+        let offset = self
+            .input_stream
+            .idx
+            .saturating_sub(self.current_input_len)
+            .min(self.input_stream.len());
+        self.emit_parse_error_at(code, offset);
+    }
+
+    /// Reports an error about the input at `offset` rather than the current input character
+    fn emit_parse_error_at(&mut self, code: ParseErrorCode, offset: usize) {
+        let context = self.error_context(code);
+        self.errors.push(ParseError {
+            code,
+            offset,
+            context,
+            suggestion: None,
+        });
+    }
+
+    /// What the error `code` is about, from the token being built
+    fn error_context(&self, code: ParseErrorCode) -> Option<String> {
+        let code = code.as_str();
+        if code.contains("character-reference") {
+            let input = self.input_stream.data();
+            let start = self.character_reference_start.min(input.len());
+            let length = input[start..]
+                .iter()
+                .take_while(|&&byte| byte == b'#' || byte.is_ascii_alphanumeric())
+                .count();
+            Some(String::from_utf8_lossy(&input[start..start + length]).into_owned())
+        } else if code.contains("attribute") {
+            Some(self.current_tag_name.clone()).filter(|name| !name.is_empty())
+        } else if code.contains("doctype") {
+            match &self.current_doctype_token {
+                Some(Token::DOCTYPE { name, .. }) => name.clone(),
+                _ => None,
+            }
+        } else if code.contains("tag") {
+            self.current_tag_token
+                .as_ref()
+                .and_then(Token::tag_name)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+        } else {
+            None
+        }
     }
 
     fn add_attribute_to_current_tag_token(&mut self) {
-        let tag_name_exists = self.current_tag_attr_name_exist();
-        if let Some(ref mut t) = self.current_tag_token {
-            if tag_name_exists {
-                self.emit_parse_error("attribute-name-existed");
+        if self.lazy_attributes {
+            self.add_raw_attribute_to_current_tag_token();
+            return;
+        }
+        if self.current_tag_name.is_empty() {
+            // nothing pending, the attribute was already added when its value ended
+            return;
+        }
+        if self.current_tag_attr_name_exist() {
+            // the start of the attribute name is tracked in both modes
+            self.emit_parse_error_at(
+                ParseErrorCode::DuplicateAttribute,
+                self.raw_attribute_name.start,
+            );
+        } else if let Some(ref mut t) = self.current_tag_token {
+            t.add_attribute(
+                SmallStr::from(self.current_tag_name.as_str()),
+                self.current_tag_value.clone(),
+            );
+        }
+        self.current_tag_name.clear();
+        self.current_tag_value.clear();
+    }
+
+    fn start_raw_attribute(&mut self, name_start: usize) {
+        self.raw_attribute_pending = true;
+        self.raw_attribute_name = name_start..name_start;
+        self.raw_attribute_value = name_start..name_start;
+    }
+
+    /// Ends the name, an attribute without value keeps an empty value range right after it
+    fn end_raw_attribute_name(&mut self, name_end: usize) {
+        self.raw_attribute_name.end = name_end;
+        self.raw_attribute_value = name_end..name_end;
+    }
+
+    fn add_raw_attribute_to_current_tag_token(&mut self) {
+        if !self.raw_attribute_pending {
+            return;
+        }
+        self.raw_attribute_pending = false;
+        let input = self.input_stream.data();
+        let name = &input[self.raw_attribute_name.clone()];
+        let mut duplicate = false;
+        if let Some(Token::StartTag { raw_attributes, .. }) = self.current_tag_token.as_mut() {
+            if raw_attributes
+                .iter()
+                .any(|(existing, _)| input[existing.clone()].eq_ignore_ascii_case(name))
+            {
+                duplicate = true;
             } else {
-                t.add_attribute(
-                    self.current_tag_name.clone(),
-                    self.current_tag_value.clone(),
-                );
-                self.current_tag_name.clear();
-                self.current_tag_value.clear();
+                raw_attributes.push((
+                    self.raw_attribute_name.clone(),
+                    self.raw_attribute_value.clone(),
+                ));
+            }
+        }
+        if duplicate {
+            // lazy mode leaves the attribute name buffer empty, the name comes from the input
+            let name = String::from_utf8_lossy(name).to_ascii_lowercase();
+            self.emit_parse_error_at(
+                ParseErrorCode::DuplicateAttribute,
+                self.raw_attribute_name.start,
+            );
+            if let Some(error) = self.errors.last_mut() {
+                error.context = Some(name);
             }
-        } else {
-            self.emit_parse_error("Token is None; cannot add attribute.");
         }
     }
 
-    fn current_tag_attr_name_exist(&self) -> bool {
-        if let Some(ref t) = self.current_tag_token {
-            t.attribute_exists(&self.current_tag_name)
-        } else {
-            self.emit_parse_error("Token is None; cannot add attribute.");
-            false
+    fn push_to_attribute_name(&mut self, ch: char) {
+        if !self.lazy_attributes {
+            self.current_tag_name.push(ch);
+        }
+    }
+
+    fn push_to_attribute_value(&mut self, ch: char) {
+        if !self.lazy_attributes {
+            self.current_tag_value.push(ch);
         }
     }
+
+    fn append_to_current_tag_name(&mut self, ch: char) {
+        if let Some(Token::StartTag { tag_name, .. }) | Some(Token::EndTag { tag_name, .. }) =
+            self.current_tag_token.as_mut()
+        {
+            tag_name.push(ch);
+        }
+    }
+
+    fn is_consumed_as_part_of_an_attribute(&self) -> bool {
+        matches!(
+            self.ret_state,
+            TokenizerState::AttributeValueDoubleQuoted
+                | TokenizerState::AttributeValueSingleQuoted
+                | TokenizerState::AttributeValueUnquoted
+        )
+    }
+
+    fn current_tag_attr_name_exist(&self) -> bool {
+        self.current_tag_token
+            .as_ref()
+            .is_some_and(|t| t.attribute_exists(&self.current_tag_name))
+    }
     fn emit_current_tag_token(&mut self) {
         if let Some(token) = self.current_tag_token.take() {
             self.emit_token(token);
@@ -3107,7 +3612,9 @@ impl<'a> Tokenizer<'a> {
         match self.ret_state {
             TokenizerState::AttributeValueDoubleQuoted | TokenizerState::AttributeValueSingleQuoted 
             |  TokenizerState::AttributeValueUnquoted => {
-                self.current_tag_value.push_str(self.temporary_buffer.as_str());
+                if !self.lazy_attributes {
+                    self.current_tag_value.push_str(self.temporary_buffer.as_str());
+                }
             } 
             _ => {
                 let chars: Vec<char> = self.temporary_buffer.chars().collect();
@@ -3117,7 +3624,6 @@ impl<'a> Tokenizer<'a> {
             }
         }
         self.temporary_buffer.clear();
-        self.reconsume_char();
         self.state = self.ret_state.clone();
     }
 }
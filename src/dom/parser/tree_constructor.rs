@@ -1,30 +1,2866 @@
-use crate::parser::insertion_mode::InsertionMode;
-use crate::dom::elements::Node;
+use crate::dom::arena::NodeArena;
+use crate::dom::document::{Document, QuirksMode};
+use crate::dom::elements::html_form_element::is_listed_element;
+use crate::dom::elements::html_meta_element::extract_charset_from_meta;
+use crate::dom::node::{Attribute, Element, Namespace, NodeData, NodeId};
+use crate::dom::parser::encoding::{declared_encoding, Encoding};
+use crate::dom::parser::insertion_mode::InsertionMode;
+use crate::dom::parser::parse_error::{ParseErrorCode, TreeError};
+use crate::dom::parser::tokenizer::{Token, TokenizerState};
+use crate::dom::serializer::serialized_attribute_name;
+use crate::helper::small_str::SmallStr;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ActiveFormattingEntry {
+    Marker,
+    Element(NodeId),
+}
+
+const SPECIAL_ELEMENTS: &[&str] = &[
+    "address", "applet", "area", "article", "aside", "base", "basefont", "bgsound",
+    "blockquote", "body", "br", "button", "caption", "center", "col", "colgroup", "dd",
+    "details", "dir", "div", "dl", "dt", "embed", "fieldset", "figcaption", "figure", "footer",
+    "form", "frame", "frameset", "h1", "h2", "h3", "h4", "h5", "h6", "head", "header", "hgroup",
+    "hr", "html", "iframe", "img", "input", "keygen", "li", "link", "listing", "main",
+    "marquee", "menu", "meta", "nav", "noembed", "noframes", "noscript", "object", "ol", "p",
+    "param", "plaintext", "pre", "script", "search", "section", "select", "source", "style",
+    "summary", "table", "tbody", "td", "template", "textarea", "tfoot", "th", "thead", "title",
+    "tr", "track", "ul", "wbr", "xmp",
+];
+
+const IMPLIED_END_TAGS: &[&str] = &[
+    "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc",
+];
+
+const IMPLIED_END_TAGS_THOROUGHLY: &[&str] = &[
+    "caption", "colgroup", "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc",
+    "tbody", "td", "tfoot", "th", "thead", "tr",
+];
+
+const DEFAULT_SCOPE: &[&str] = &[
+    "applet", "caption", "html", "table", "td", "th", "marquee", "object", "template",
+];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Start tags that close foreign content (13.2.6.5)
+const FOREIGN_CONTENT_BREAKOUT: &[&str] = &[
+    "b", "big", "blockquote", "body", "br", "center", "code", "dd", "div", "dl", "dt", "em",
+    "embed", "h1", "h2", "h3", "h4", "h5", "h6", "head", "hr", "i", "img", "li", "listing",
+    "menu", "meta", "nobr", "ol", "p", "pre", "ruby", "s", "small", "span", "strong", "strike",
+    "sub", "sup", "table", "tt", "u", "ul", "var",
+];
+
+/// Lowercased SVG tag names and their correct case (13.2.6.5)
+const SVG_TAG_NAME_ADJUSTMENTS: &[(&str, &str)] = &[
+    ("altglyph", "altGlyph"),
+    ("altglyphdef", "altGlyphDef"),
+    ("altglyphitem", "altGlyphItem"),
+    ("animatecolor", "animateColor"),
+    ("animatemotion", "animateMotion"),
+    ("animatetransform", "animateTransform"),
+    ("clippath", "clipPath"),
+    ("feblend", "feBlend"),
+    ("fecolormatrix", "feColorMatrix"),
+    ("fecomponenttransfer", "feComponentTransfer"),
+    ("fecomposite", "feComposite"),
+    ("feconvolvematrix", "feConvolveMatrix"),
+    ("fediffuselighting", "feDiffuseLighting"),
+    ("fedisplacementmap", "feDisplacementMap"),
+    ("fedistantlight", "feDistantLight"),
+    ("fedropshadow", "feDropShadow"),
+    ("feflood", "feFlood"),
+    ("fefunca", "feFuncA"),
+    ("fefuncb", "feFuncB"),
+    ("fefuncg", "feFuncG"),
+    ("fefuncr", "feFuncR"),
+    ("fegaussianblur", "feGaussianBlur"),
+    ("feimage", "feImage"),
+    ("femerge", "feMerge"),
+    ("femergenode", "feMergeNode"),
+    ("femorphology", "feMorphology"),
+    ("feoffset", "feOffset"),
+    ("fepointlight", "fePointLight"),
+    ("fespecularlighting", "feSpecularLighting"),
+    ("fespotlight", "feSpotLight"),
+    ("fetile", "feTile"),
+    ("feturbulence", "feTurbulence"),
+    ("foreignobject", "foreignObject"),
+    ("glyphref", "glyphRef"),
+    ("lineargradient", "linearGradient"),
+    ("radialgradient", "radialGradient"),
+    ("textpath", "textPath"),
+];
+
+/// Lowercased SVG attribute names and their correct case (13.2.6.1 adjust SVG attributes)
+const SVG_ATTRIBUTE_ADJUSTMENTS: &[(&str, &str)] = &[
+    ("attributename", "attributeName"),
+    ("attributetype", "attributeType"),
+    ("basefrequency", "baseFrequency"),
+    ("baseprofile", "baseProfile"),
+    ("calcmode", "calcMode"),
+    ("clippathunits", "clipPathUnits"),
+    ("diffuseconstant", "diffuseConstant"),
+    ("edgemode", "edgeMode"),
+    ("filterunits", "filterUnits"),
+    ("glyphref", "glyphRef"),
+    ("gradienttransform", "gradientTransform"),
+    ("gradientunits", "gradientUnits"),
+    ("kernelmatrix", "kernelMatrix"),
+    ("kernelunitlength", "kernelUnitLength"),
+    ("keypoints", "keyPoints"),
+    ("keysplines", "keySplines"),
+    ("keytimes", "keyTimes"),
+    ("lengthadjust", "lengthAdjust"),
+    ("limitingconeangle", "limitingConeAngle"),
+    ("markerheight", "markerHeight"),
+    ("markerunits", "markerUnits"),
+    ("markerwidth", "markerWidth"),
+    ("maskcontentunits", "maskContentUnits"),
+    ("maskunits", "maskUnits"),
+    ("numoctaves", "numOctaves"),
+    ("pathlength", "pathLength"),
+    ("patterncontentunits", "patternContentUnits"),
+    ("patterntransform", "patternTransform"),
+    ("patternunits", "patternUnits"),
+    ("pointsatx", "pointsAtX"),
+    ("pointsaty", "pointsAtY"),
+    ("pointsatz", "pointsAtZ"),
+    ("preservealpha", "preserveAlpha"),
+    ("preserveaspectratio", "preserveAspectRatio"),
+    ("primitiveunits", "primitiveUnits"),
+    ("refx", "refX"),
+    ("refy", "refY"),
+    ("repeatcount", "repeatCount"),
+    ("repeatdur", "repeatDur"),
+    ("requiredextensions", "requiredExtensions"),
+    ("requiredfeatures", "requiredFeatures"),
+    ("specularconstant", "specularConstant"),
+    ("specularexponent", "specularExponent"),
+    ("spreadmethod", "spreadMethod"),
+    ("startoffset", "startOffset"),
+    ("stddeviation", "stdDeviation"),
+    ("stitchtiles", "stitchTiles"),
+    ("surfacescale", "surfaceScale"),
+    ("systemlanguage", "systemLanguage"),
+    ("tablevalues", "tableValues"),
+    ("targetx", "targetX"),
+    ("targety", "targetY"),
+    ("textlength", "textLength"),
+    ("viewbox", "viewBox"),
+    ("viewtarget", "viewTarget"),
+    ("xchannelselector", "xChannelSelector"),
+    ("ychannelselector", "yChannelSelector"),
+    ("zoomandpan", "zoomAndPan"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scope {
+    Default,
+    ListItem,
+    Button,
+    Table,
+    Select,
+}
+
+fn is_parser_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' ')
+}
+
+/// HTML elements whose contents are tokenized as raw text, with neither tags nor character
+/// references. The scripting flag is always disabled, so noscript is parsed as markup.
+pub fn is_raw_text_element(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "style" | "script" | "xmp" | "iframe" | "noembed" | "noframes"
+    )
+}
+
+/// HTML elements whose contents are tokenized as text with character references (RCDATA)
+pub fn is_escapable_raw_text_element(tag_name: &str) -> bool {
+    matches!(tag_name, "textarea" | "title")
+}
+
+/// The tokenizer state reading the contents of a raw text or escapable raw text element
+fn text_tokenizer_state(tag_name: &str) -> Option<TokenizerState> {
+    match tag_name {
+        "script" => Some(TokenizerState::ScriptData),
+        _ if is_raw_text_element(tag_name) => Some(TokenizerState::RAWTEXT),
+        _ if is_escapable_raw_text_element(tag_name) => Some(TokenizerState::RCDATA),
+        _ => None,
+    }
+}
+
+fn start_tag(name: &str) -> Token {
+    Token::StartTag {
+        tag_name: name.into(),
+        self_closing: false,
+        attributes: Vec::new(),
+        raw_attributes: Vec::new(),
+    }
+}
+
+/// Builds a `Document` from the tokens produced by the tokenizer (13.2.6 Tree construction)
 pub struct TreeConstructor {
+    document: Document,
     insertion_mode: InsertionMode,
-    stack_of_open_elements: Vec<Node>,
+    original_insertion_mode: InsertionMode,
+    template_insertion_modes: Vec<InsertionMode>,
+    stack_of_open_elements: Vec<NodeId>,
+    active_formatting_elements: Vec<ActiveFormattingEntry>,
+    head_element_pointer: Option<NodeId>,
+    form_element_pointer: Option<NodeId>,
+    frameset_ok: bool,
+    foster_parenting: bool,
     is_fragment_case: bool,
-    context_element: Option<Node>,
+    /// Parsing the srcdoc of an iframe, whose document never goes into quirks mode
+    is_iframe_srcdoc: bool,
+    context_element: Option<NodeId>,
+    pending_table_character_tokens: String,
+    ignore_next_line_feed: bool,
+    tokenizer_state: Option<TokenizerState>,
+    stopped: bool,
+    /// The encoding the input was decoded with while it is only a guess, a meta element
+    /// declaring another one then asks for a reparse
+    tentative_encoding: Option<Encoding>,
+    encoding_change: Option<Encoding>,
+    errors: Vec<TreeError>,
+    /// Where the tokenizer was when it handed over the token being processed, and the tag name
+    /// of that token, for the errors it causes
+    token_offset: usize,
+    token_tag_name: Option<String>,
+}
+
+impl Default for TreeConstructor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TreeConstructor {
     pub fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    /// Tree constructor building a document with room for `elements` elements and `chars`
+    /// characters of text, see `NodeArena::with_capacity`
+    pub fn with_capacity(elements: usize, chars: usize) -> Self {
         TreeConstructor {
+            document: Document::with_capacity(elements, chars),
             insertion_mode: InsertionMode::Initial,
+            original_insertion_mode: InsertionMode::Initial,
+            template_insertion_modes: Vec::new(),
             stack_of_open_elements: Vec::new(),
+            active_formatting_elements: Vec::new(),
+            head_element_pointer: None,
+            form_element_pointer: None,
+            frameset_ok: true,
+            foster_parenting: false,
             is_fragment_case: false,
+            is_iframe_srcdoc: false,
             context_element: None,
+            pending_table_character_tokens: String::new(),
+            ignore_next_line_feed: false,
+            tokenizer_state: None,
+            stopped: false,
+            tentative_encoding: None,
+            encoding_change: None,
+            errors: Vec::new(),
+            token_offset: 0,
+            token_tag_name: None,
+        }
+    }
+
+    /// Tree constructor for the HTML fragment parsing algorithm (13.4) with `context` as the
+    /// context element. The parsed nodes end up as children of the document element of the
+    /// resulting document. The tokenizer must start in the state returned by
+    /// `take_tokenizer_state` before the first token.
+    pub fn new_fragment(context: &Element) -> Self {
+        let mut tree_constructor = TreeConstructor::new();
+        tree_constructor.is_fragment_case = true;
+        // the context element only informs the parser, it is never inserted
+        let context_node = tree_constructor
+            .document
+            .arena_mut()
+            .new_node(NodeData::Element(context.clone()));
+        tree_constructor.context_element = Some(context_node);
+        if context.is_html() {
+            tree_constructor.tokenizer_state = match context.name.as_str() {
+                "plaintext" => Some(TokenizerState::PLAINTEXT),
+                name => text_tokenizer_state(name),
+            };
+        }
+        let root = tree_constructor.document.create_element("html");
+        let document_node = tree_constructor.document.root();
+        tree_constructor
+            .document
+            .append_child(document_node, root)
+            .expect("the new document has no children");
+        tree_constructor.stack_of_open_elements.push(root);
+        if context.is_html_named("template") {
+            tree_constructor
+                .template_insertion_modes
+                .push(InsertionMode::InTemplate);
         }
+        tree_constructor.reset_insertion_mode();
+        tree_constructor
+    }
+
+    /// Tree constructor for an iframe srcdoc document: a missing DOCTYPE is not an error and
+    /// no DOCTYPE puts the document in quirks or limited-quirks mode
+    pub fn new_iframe_srcdoc() -> Self {
+        let mut tree_constructor = TreeConstructor::new();
+        tree_constructor.is_iframe_srcdoc = true;
+        tree_constructor
+    }
+
+    pub fn document(&self) -> &Document {
+        &self.document
+    }
+
+    pub fn into_document(self) -> Document {
+        self.document
+    }
+
+    pub fn insertion_mode(&self) -> InsertionMode {
+        self.insertion_mode
+    }
+
+    /// Set once the EOF token has been processed, or when a meta element changed the encoding
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Tells the tree constructor the input was decoded with `encoding` with tentative
+    /// confidence, so meta elements declaring an encoding are acted upon
+    pub fn set_tentative_encoding(&mut self, encoding: Encoding) {
+        self.tentative_encoding = Some(encoding);
+    }
+
+    /// The encoding the input was decoded with, while no meta element confirmed it
+    pub fn tentative_encoding(&self) -> Option<Encoding> {
+        self.tentative_encoding
+    }
+
+    /// The encoding a meta element declared in place of the tentative one. Parsing stops
+    /// when it is set: the input has to be decoded again and parsed from the start.
+    pub fn encoding_change(&self) -> Option<Encoding> {
+        self.encoding_change
+    }
+
+    /// The parse errors met so far, in the order of the tokens causing them
+    pub fn errors(&self) -> &[TreeError] {
+        &self.errors
+    }
+
+    /// Moves the parse errors met so far out of the tree constructor
+    pub fn take_errors(&mut self) -> Vec<TreeError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Records the byte offset the tokenizer reached when handing over the next token, the
+    /// errors that token causes are reported there
+    pub fn set_token_offset(&mut self, offset: usize) {
+        self.token_offset = offset;
+    }
+
+    /// A tokenizer state switch requested by the last processed token, if any
+    pub fn take_tokenizer_state(&mut self) -> Option<TokenizerState> {
+        self.tokenizer_state.take()
     }
 
     pub fn reset_insertion_mode(&mut self) {
         self.insertion_mode = InsertionMode::reset_insertion_mode(
+            self.document.arena(),
             &self.stack_of_open_elements,
-            self.context_element.as_ref(),
+            self.context_element,
             self.is_fragment_case,
+            self.head_element_pointer,
+            &self.template_insertion_modes,
         );
     }
 
-    // Other methods for the tree construction logic
+    /// Indented view of the document built so far, one node per line, followed by the stack
+    /// of open elements. Meant for debugging the tree constructor.
+    pub fn dump_tree(&self, arena: &NodeArena) -> String {
+        let mut output = String::new();
+        dump_node(arena, &self.document, self.document.root(), 0, &mut output);
+        output.push_str("Stack of open elements:\n");
+        output.push_str(&self.dump_stack());
+        output
+    }
+
+    /// The stack of open elements, bottommost (html) first, one element per line
+    pub fn dump_stack(&self) -> String {
+        let mut output = String::new();
+        for &node in &self.stack_of_open_elements {
+            output.push_str("  ");
+            output.push_str(&describe_node(self.document.arena(), node));
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn process_token(&mut self, token: Token) {
+        if self.stopped {
+            return;
+        }
+        self.token_tag_name = token.tag_name().map(str::to_string);
+        if self.ignore_next_line_feed {
+            self.ignore_next_line_feed = false;
+            if let Token::Character { data: '\n' } = token {
+                return;
+            }
+        }
+        self.dispatch(token);
+    }
+
+    fn reprocess(&mut self, token: Token) {
+        self.dispatch(token);
+    }
+
+    // 13.2.6 Tree construction dispatcher
+    fn dispatch(&mut self, token: Token) {
+        if self.uses_foreign_content_rules(&token) {
+            self.handle_foreign_content(token);
+        } else {
+            self.process_using_rules(self.insertion_mode, token);
+        }
+    }
+
+    fn uses_foreign_content_rules(&self, token: &Token) -> bool {
+        let Some(node) = self.adjusted_current_node() else {
+            return false;
+        };
+        let Some(element) = self.document.element(node) else {
+            return false;
+        };
+        if element.is_html() {
+            return false;
+        }
+        let is_mathml_text_integration_point = element.namespace == Namespace::MathMl
+            && matches!(element.name.as_str(), "mi" | "mo" | "mn" | "ms" | "mtext");
+        match token {
+            Token::StartTag { tag_name, .. }
+                if is_mathml_text_integration_point
+                    && tag_name != "mglyph"
+                    && tag_name != "malignmark" =>
+            {
+                false
+            }
+            Token::Character { .. } if is_mathml_text_integration_point => false,
+            _ if token.is_start_tag("svg")
+                && element.namespace == Namespace::MathMl
+                && element.name == "annotation-xml" =>
+            {
+                false
+            }
+            Token::StartTag { .. } | Token::Character { .. }
+                if self.is_html_integration_point(node) =>
+            {
+                false
+            }
+            Token::EOF => false,
+            _ => true,
+        }
+    }
+
+    /// Whether the tokenizer is fed foreign content, where CDATA sections are recognized
+    pub fn is_in_foreign_content(&self) -> bool {
+        self.adjusted_current_node()
+            .and_then(|node| self.document.element(node))
+            .is_some_and(|element| !element.is_html())
+    }
+
+    fn adjusted_current_node(&self) -> Option<NodeId> {
+        if self.is_fragment_case && self.stack_of_open_elements.len() == 1 {
+            return self.context_element;
+        }
+        self.current_node()
+    }
+
+    fn is_html_integration_point(&self, node: NodeId) -> bool {
+        let Some(element) = self.document.element(node) else {
+            return false;
+        };
+        match element.namespace {
+            Namespace::MathMl => {
+                element.name == "annotation-xml"
+                    && element.get_attribute("encoding").is_some_and(|encoding| {
+                        encoding.eq_ignore_ascii_case("text/html")
+                            || encoding.eq_ignore_ascii_case("application/xhtml+xml")
+                    })
+            }
+            Namespace::Svg => matches!(element.name.as_str(), "foreignObject" | "desc" | "title"),
+            _ => false,
+        }
+    }
+
+    fn is_mathml_text_integration_point(&self, node: NodeId) -> bool {
+        self.document.element(node).is_some_and(|element| {
+            element.namespace == Namespace::MathMl
+                && matches!(element.name.as_str(), "mi" | "mo" | "mn" | "ms" | "mtext")
+        })
+    }
+
+    fn process_using_rules(&mut self, mode: InsertionMode, token: Token) {
+        match mode {
+            InsertionMode::Initial => self.handle_initial(token),
+            InsertionMode::BeforeHtml => self.handle_before_html(token),
+            InsertionMode::BeforeHead => self.handle_before_head(token),
+            InsertionMode::InHead => self.handle_in_head(token),
+            InsertionMode::InHeadNoscript => self.handle_in_head_noscript(token),
+            InsertionMode::AfterHead => self.handle_after_head(token),
+            InsertionMode::InBody => self.handle_in_body(token),
+            InsertionMode::Text => self.handle_text(token),
+            InsertionMode::InTable => self.handle_in_table(token),
+            InsertionMode::InTableText => self.handle_in_table_text(token),
+            InsertionMode::InCaption => self.handle_in_caption(token),
+            InsertionMode::InColumnGroup => self.handle_in_column_group(token),
+            InsertionMode::InTableBody => self.handle_in_table_body(token),
+            InsertionMode::InRow => self.handle_in_row(token),
+            InsertionMode::InCell => self.handle_in_cell(token),
+            InsertionMode::InSelect => self.handle_in_select(token),
+            InsertionMode::InSelectInTable => self.handle_in_select_in_table(token),
+            InsertionMode::InTemplate => self.handle_in_template(token),
+            InsertionMode::AfterBody => self.handle_after_body(token),
+            InsertionMode::InFrameset => self.handle_in_frameset(token),
+            InsertionMode::AfterFrameset => self.handle_after_frameset(token),
+            InsertionMode::AfterAfterBody => self.handle_after_after_body(token),
+            InsertionMode::AfterAfterFrameset => self.handle_after_after_frameset(token),
+        }
+    }
+
+    //13.2.6.4.1 The "initial" insertion mode
+    fn handle_initial(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => {}
+            Token::Comment { data } => {
+                let root = self.document.root();
+                self.append_comment(root, &data);
+            }
+            Token::DOCTYPE {
+                name,
+                public_id,
+                system_id,
+                force_quirks,
+            } => {
+                let name = name.unwrap_or_default();
+                if name != "html"
+                    || public_id.is_some()
+                    || system_id.as_deref().is_some_and(|s| s != "about:legacy-compat")
+                {
+                    self.parse_error(ParseErrorCode::UnexpectedDoctype);
+                }
+                let mode = quirks_mode_for_doctype(
+                    &name,
+                    public_id.as_deref(),
+                    system_id.as_deref(),
+                    force_quirks,
+                );
+                let doctype = self.document.arena_mut().new_node(NodeData::Doctype {
+                    name,
+                    public_id: public_id.unwrap_or_default(),
+                    system_id: system_id.unwrap_or_default(),
+                });
+                let root = self.document.root();
+                self.append(root, doctype);
+                if !self.is_iframe_srcdoc {
+                    self.document.set_quirks_mode(mode);
+                }
+                self.insertion_mode = InsertionMode::BeforeHtml;
+            }
+            _ => {
+                if !self.is_iframe_srcdoc {
+                    self.parse_error(ParseErrorCode::MissingDoctype);
+                    self.document.set_quirks_mode(QuirksMode::Quirks);
+                }
+                self.insertion_mode = InsertionMode::BeforeHtml;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.2 The "before html" insertion mode
+    fn handle_before_html(&mut self, token: Token) {
+        match token {
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::Comment { data } => {
+                let root = self.document.root();
+                self.append_comment(root, &data);
+            }
+            Token::Character { data } if is_parser_whitespace(data) => {}
+            _ if token.is_start_tag("html") => {
+                let html = self.create_element_for_token(&token);
+                let root = self.document.root();
+                self.append(root, html);
+                self.stack_of_open_elements.push(html);
+                self.insertion_mode = InsertionMode::BeforeHead;
+            }
+            Token::EndTag { ref tag_name, .. }
+                if !matches!(tag_name.as_str(), "head" | "body" | "html" | "br") =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => {
+                let html = self.create_element_for_token(&start_tag("html"));
+                let root = self.document.root();
+                self.append(root, html);
+                self.stack_of_open_elements.push(html);
+                self.insertion_mode = InsertionMode::BeforeHead;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.3 The "before head" insertion mode
+    fn handle_before_head(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => {}
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_start_tag("head") => {
+                let head = self.insert_html_element(&token);
+                self.head_element_pointer = Some(head);
+                self.insertion_mode = InsertionMode::InHead;
+            }
+            Token::EndTag { ref tag_name, .. }
+                if !matches!(tag_name.as_str(), "head" | "body" | "html" | "br") =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => {
+                let head = self.insert_html_element(&start_tag("head"));
+                self.head_element_pointer = Some(head);
+                self.insertion_mode = InsertionMode::InHead;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.4 The "in head" insertion mode
+    fn handle_in_head(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag { ref tag_name, .. } => match tag_name.as_str() {
+                "html" => self.handle_in_body(token),
+                "base" => {
+                    let element = self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                    self.update_base_url_if_needed(element);
+                }
+                "basefont" | "bgsound" | "link" => {
+                    self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                }
+                "meta" => {
+                    let element = self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                    self.change_encoding_if_declared(element);
+                }
+                "title" => self.parse_generic_text_element(&token),
+                "noscript" => {
+                    // the scripting flag is always disabled
+                    self.insert_html_element(&token);
+                    self.insertion_mode = InsertionMode::InHeadNoscript;
+                }
+                "noframes" | "style" => self.parse_generic_text_element(&token),
+                "script" => self.parse_generic_text_element(&token),
+                "template" => {
+                    self.insert_html_element(&token);
+                    self.active_formatting_elements
+                        .push(ActiveFormattingEntry::Marker);
+                    self.frameset_ok = false;
+                    self.insertion_mode = InsertionMode::InTemplate;
+                    self.template_insertion_modes
+                        .push(InsertionMode::InTemplate);
+                }
+                "head" => self.parse_error(ParseErrorCode::UnexpectedStartTag),
+                _ => self.anything_else_in_head(token),
+            },
+            Token::EndTag { ref tag_name, .. } => match tag_name.as_str() {
+                "head" => {
+                    self.stack_of_open_elements.pop();
+                    self.insertion_mode = InsertionMode::AfterHead;
+                }
+                "body" | "html" | "br" => self.anything_else_in_head(token),
+                "template" => {
+                    if !self.stack_contains("template") {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                        return;
+                    }
+                    self.generate_all_implied_end_tags_thoroughly();
+                    if !self.current_node_is("template") {
+                        self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                    }
+                    self.pop_until_one_of(&["template"]);
+                    self.clear_active_formatting_elements_to_last_marker();
+                    self.template_insertion_modes.pop();
+                    self.reset_insertion_mode();
+                }
+                _ => self.parse_error(ParseErrorCode::UnexpectedEndTag),
+            },
+            _ => self.anything_else_in_head(token),
+        }
+    }
+
+    fn anything_else_in_head(&mut self, token: Token) {
+        self.stack_of_open_elements.pop();
+        self.insertion_mode = InsertionMode::AfterHead;
+        self.reprocess(token);
+    }
+
+    //13.2.6.4.5 The "in head noscript" insertion mode
+    fn handle_in_head_noscript(&mut self, token: Token) {
+        match token {
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_end_tag("noscript") => {
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = InsertionMode::InHead;
+            }
+            Token::Character { data } if is_parser_whitespace(data) => self.handle_in_head(token),
+            Token::Comment { .. } => self.handle_in_head(token),
+            Token::StartTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "basefont" | "bgsound" | "link" | "meta" | "noframes" | "style"
+                ) =>
+            {
+                self.handle_in_head(token)
+            }
+            Token::StartTag { ref tag_name, .. }
+                if matches!(tag_name.as_str(), "head" | "noscript") =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+            }
+            Token::EndTag { ref tag_name, .. } if tag_name != "br" => {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => {
+                self.parse_error(ParseErrorCode::UnexpectedTokenInHeadNoscript);
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = InsertionMode::InHead;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.6 The "after head" insertion mode
+    fn handle_after_head(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag { ref tag_name, .. } => match tag_name.as_str() {
+                "html" => self.handle_in_body(token),
+                "body" => {
+                    self.insert_html_element(&token);
+                    self.frameset_ok = false;
+                    self.insertion_mode = InsertionMode::InBody;
+                }
+                "frameset" => {
+                    self.insert_html_element(&token);
+                    self.insertion_mode = InsertionMode::InFrameset;
+                }
+                "base" | "basefont" | "bgsound" | "link" | "meta" | "noframes" | "script"
+                | "style" | "template" | "title" => {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    let Some(head) = self.head_element_pointer else {
+                        return;
+                    };
+                    self.stack_of_open_elements.push(head);
+                    self.handle_in_head(token);
+                    self.remove_from_stack(head);
+                }
+                "head" => self.parse_error(ParseErrorCode::UnexpectedStartTag),
+                _ => self.anything_else_after_head(token),
+            },
+            Token::EndTag { ref tag_name, .. } => match tag_name.as_str() {
+                "template" => self.handle_in_head(token),
+                "body" | "html" | "br" => self.anything_else_after_head(token),
+                _ => self.parse_error(ParseErrorCode::UnexpectedEndTag),
+            },
+            _ => self.anything_else_after_head(token),
+        }
+    }
+
+    fn anything_else_after_head(&mut self, token: Token) {
+        self.insert_html_element(&start_tag("body"));
+        self.insertion_mode = InsertionMode::InBody;
+        self.reprocess(token);
+    }
+
+    //13.2.6.4.7 The "in body" insertion mode
+    fn handle_in_body(&mut self, token: Token) {
+        match token {
+            Token::Character { data: '\0' } => {
+                self.parse_error(ParseErrorCode::UnexpectedNullCharacter)
+            }
+            Token::Character { data } => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_character(data);
+                if !is_parser_whitespace(data) {
+                    self.frameset_ok = false;
+                }
+            }
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag { .. } => self.handle_start_tag_in_body(token),
+            Token::EndTag { .. } => self.handle_end_tag_in_body(token),
+            Token::EOF => {
+                if !self.template_insertion_modes.is_empty() {
+                    self.handle_in_template(token);
+                } else {
+                    self.stop_parsing();
+                }
+            }
+        }
+    }
+
+    fn handle_start_tag_in_body(&mut self, token: Token) {
+        let Token::StartTag {
+            ref tag_name,
+            self_closing,
+            ref attributes,
+            ..
+        } = token
+        else {
+            return;
+        };
+        match tag_name.as_str() {
+            "html" => {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                if self.stack_contains("template") {
+                    return;
+                }
+                if let Some(&html) = self.stack_of_open_elements.first() {
+                    self.add_missing_attributes(html, attributes);
+                }
+            }
+            "base" | "basefont" | "bgsound" | "link" | "meta" | "noframes" | "script"
+            | "style" | "template" | "title" => self.handle_in_head(token),
+            "body" => {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                let body = self.stack_of_open_elements.get(1).copied();
+                match body {
+                    Some(body) if self.is_element_named(body, "body")
+                        && !self.stack_contains("template") =>
+                    {
+                        self.frameset_ok = false;
+                        self.add_missing_attributes(body, attributes);
+                    }
+                    _ => {}
+                }
+            }
+            "frameset" => {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                let body = self.stack_of_open_elements.get(1).copied();
+                let Some(body) = body.filter(|&b| self.is_element_named(b, "body")) else {
+                    return;
+                };
+                if !self.frameset_ok {
+                    return;
+                }
+                self.document.arena_mut().detach(body);
+                self.stack_of_open_elements.truncate(1);
+                self.insert_html_element(&token);
+                self.insertion_mode = InsertionMode::InFrameset;
+            }
+            "address" | "article" | "aside" | "blockquote" | "center" | "details" | "dialog"
+            | "dir" | "div" | "dl" | "fieldset" | "figcaption" | "figure" | "footer"
+            | "header" | "hgroup" | "main" | "menu" | "nav" | "ol" | "p" | "search"
+            | "section" | "summary" | "ul" => {
+                self.close_p_element_in_button_scope();
+                self.insert_html_element(&token);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.close_p_element_in_button_scope();
+                if self.current_node_is_one_of(HEADINGS) {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    self.stack_of_open_elements.pop();
+                }
+                self.insert_html_element(&token);
+            }
+            "pre" | "listing" => {
+                self.close_p_element_in_button_scope();
+                self.insert_html_element(&token);
+                self.ignore_next_line_feed = true;
+                self.frameset_ok = false;
+            }
+            "form" => {
+                let has_template = self.stack_contains("template");
+                if self.form_element_pointer.is_some() && !has_template {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    return;
+                }
+                self.close_p_element_in_button_scope();
+                let form = self.insert_html_element(&token);
+                if !has_template {
+                    self.form_element_pointer = Some(form);
+                }
+            }
+            "li" | "dd" | "dt" => {
+                self.frameset_ok = false;
+                let closes: &[&str] = if tag_name == "li" { &["li"] } else { &["dd", "dt"] };
+                for index in (0..self.stack_of_open_elements.len()).rev() {
+                    let node = self.stack_of_open_elements[index];
+                    let node_name = self.element_name(node).to_string();
+                    if closes.contains(&node_name.as_str()) {
+                        self.generate_implied_end_tags_except(&node_name);
+                        if !self.current_node_is(&node_name) {
+                            self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                        }
+                        self.pop_until_one_of(&[node_name.as_str()]);
+                        break;
+                    }
+                    if self.is_special(node)
+                        && !matches!(node_name.as_str(), "address" | "div" | "p")
+                    {
+                        break;
+                    }
+                }
+                self.close_p_element_in_button_scope();
+                self.insert_html_element(&token);
+            }
+            "plaintext" => {
+                self.close_p_element_in_button_scope();
+                self.insert_html_element(&token);
+                self.tokenizer_state = Some(TokenizerState::PLAINTEXT);
+            }
+            "button" => {
+                if self.has_element_in_scope("button", Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    self.generate_implied_end_tags_except("");
+                    self.pop_until_one_of(&["button"]);
+                }
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+                self.frameset_ok = false;
+            }
+            "a" => {
+                let existing = self.formatting_element_after_last_marker("a");
+                if let Some(existing) = existing {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    self.adoption_agency_algorithm("a");
+                    self.remove_from_active_formatting_elements(existing);
+                    self.remove_from_stack(existing);
+                }
+                self.reconstruct_active_formatting_elements();
+                let element = self.insert_html_element(&token);
+                self.push_onto_active_formatting_elements(element);
+            }
+            "b" | "big" | "code" | "em" | "font" | "i" | "s" | "small" | "strike" | "strong"
+            | "tt" | "u" => {
+                self.reconstruct_active_formatting_elements();
+                let element = self.insert_html_element(&token);
+                self.push_onto_active_formatting_elements(element);
+            }
+            "nobr" => {
+                self.reconstruct_active_formatting_elements();
+                if self.has_element_in_scope("nobr", Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    self.adoption_agency_algorithm("nobr");
+                    self.reconstruct_active_formatting_elements();
+                }
+                let element = self.insert_html_element(&token);
+                self.push_onto_active_formatting_elements(element);
+            }
+            "applet" | "marquee" | "object" => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+                self.active_formatting_elements
+                    .push(ActiveFormattingEntry::Marker);
+                self.frameset_ok = false;
+            }
+            "table" => {
+                if self.document.quirks_mode() != QuirksMode::Quirks {
+                    self.close_p_element_in_button_scope();
+                }
+                self.insert_html_element(&token);
+                self.frameset_ok = false;
+                self.insertion_mode = InsertionMode::InTable;
+            }
+            "area" | "br" | "embed" | "img" | "keygen" | "wbr" => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+                self.stack_of_open_elements.pop();
+                self.frameset_ok = false;
+            }
+            "input" => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+                self.stack_of_open_elements.pop();
+                let is_hidden = attributes
+                    .iter()
+                    .any(|(n, v)| n == "type" && v.eq_ignore_ascii_case("hidden"));
+                if !is_hidden {
+                    self.frameset_ok = false;
+                }
+            }
+            "param" | "source" | "track" => {
+                self.insert_html_element(&token);
+                self.stack_of_open_elements.pop();
+            }
+            "hr" => {
+                self.close_p_element_in_button_scope();
+                self.insert_html_element(&token);
+                self.stack_of_open_elements.pop();
+                self.frameset_ok = false;
+            }
+            "image" => {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                self.reprocess(Token::StartTag {
+                    tag_name: "img".into(),
+                    self_closing,
+                    attributes: attributes.clone(),
+                    raw_attributes: Vec::new(),
+                });
+            }
+            "textarea" => {
+                self.insert_html_element(&token);
+                self.ignore_next_line_feed = true;
+                self.tokenizer_state = text_tokenizer_state("textarea");
+                self.original_insertion_mode = self.insertion_mode;
+                self.frameset_ok = false;
+                self.insertion_mode = InsertionMode::Text;
+            }
+            "xmp" => {
+                self.close_p_element_in_button_scope();
+                self.reconstruct_active_formatting_elements();
+                self.frameset_ok = false;
+                self.parse_generic_text_element(&token);
+            }
+            "iframe" => {
+                self.frameset_ok = false;
+                self.parse_generic_text_element(&token);
+            }
+            "noembed" => self.parse_generic_text_element(&token),
+            "select" => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+                self.frameset_ok = false;
+                self.insertion_mode = match self.insertion_mode {
+                    InsertionMode::InTable
+                    | InsertionMode::InCaption
+                    | InsertionMode::InTableBody
+                    | InsertionMode::InRow
+                    | InsertionMode::InCell => InsertionMode::InSelectInTable,
+                    _ => InsertionMode::InSelect,
+                };
+            }
+            "optgroup" | "option" => {
+                if self.current_node_is("option") {
+                    self.stack_of_open_elements.pop();
+                }
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+            }
+            "rb" | "rtc" => {
+                if self.has_element_in_scope("ruby", Scope::Default) {
+                    self.generate_implied_end_tags_except("");
+                    if !self.current_node_is("ruby") {
+                        self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    }
+                }
+                self.insert_html_element(&token);
+            }
+            "rp" | "rt" => {
+                if self.has_element_in_scope("ruby", Scope::Default) {
+                    self.generate_implied_end_tags_except("rtc");
+                    if !self.current_node_is_one_of(&["rtc", "ruby"]) {
+                        self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    }
+                }
+                self.insert_html_element(&token);
+            }
+            "math" | "svg" => {
+                self.reconstruct_active_formatting_elements();
+                let namespace = if tag_name == "math" {
+                    Namespace::MathMl
+                } else {
+                    Namespace::Svg
+                };
+                self.insert_foreign_element(&token, namespace);
+                if self_closing {
+                    self.stack_of_open_elements.pop();
+                }
+            }
+            "caption" | "col" | "colgroup" | "frame" | "head" | "tbody" | "td" | "tfoot"
+            | "th" | "thead" | "tr" => self.parse_error(ParseErrorCode::UnexpectedStartTag),
+            _ => {
+                self.reconstruct_active_formatting_elements();
+                self.insert_html_element(&token);
+            }
+        }
+    }
+
+    fn handle_end_tag_in_body(&mut self, token: Token) {
+        let Token::EndTag { ref tag_name, .. } = token else {
+            return;
+        };
+        match tag_name.as_str() {
+            "template" => self.handle_in_head(token),
+            "body" | "html" => {
+                if !self.has_element_in_scope("body", Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.insertion_mode = InsertionMode::AfterBody;
+                if tag_name == "html" {
+                    self.reprocess(token);
+                }
+            }
+            "address" | "article" | "aside" | "blockquote" | "button" | "center" | "details"
+            | "dialog" | "dir" | "div" | "dl" | "fieldset" | "figcaption" | "figure"
+            | "footer" | "header" | "hgroup" | "listing" | "main" | "menu" | "nav" | "ol"
+            | "pre" | "search" | "section" | "summary" | "ul" => {
+                if !self.has_element_in_scope(tag_name, Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except("");
+                if !self.current_node_is(tag_name) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(&[tag_name.as_str()]);
+            }
+            "form" => {
+                if !self.stack_contains("template") {
+                    let node = self.form_element_pointer.take();
+                    let Some(node) = node.filter(|&n| self.has_node_in_scope(n)) else {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                        return;
+                    };
+                    self.generate_implied_end_tags_except("");
+                    if self.current_node() != Some(node) {
+                        self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                    }
+                    self.remove_from_stack(node);
+                } else {
+                    if !self.has_element_in_scope("form", Scope::Default) {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                        return;
+                    }
+                    self.generate_implied_end_tags_except("");
+                    if !self.current_node_is("form") {
+                        self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                    }
+                    self.pop_until_one_of(&["form"]);
+                }
+            }
+            "p" => {
+                if !self.has_element_in_scope("p", Scope::Button) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    self.insert_html_element(&start_tag("p"));
+                }
+                self.close_p_element();
+            }
+            "li" => {
+                if !self.has_element_in_scope("li", Scope::ListItem) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except("li");
+                if !self.current_node_is("li") {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(&["li"]);
+            }
+            "dd" | "dt" => {
+                if !self.has_element_in_scope(tag_name, Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except(tag_name);
+                if !self.current_node_is(tag_name) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(&[tag_name.as_str()]);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if !HEADINGS
+                    .iter()
+                    .any(|h| self.has_element_in_scope(h, Scope::Default))
+                {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except("");
+                if !self.current_node_is(tag_name) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(HEADINGS);
+            }
+            "a" | "b" | "big" | "code" | "em" | "font" | "i" | "nobr" | "s" | "small"
+            | "strike" | "strong" | "tt" | "u" => {
+                if self.adoption_agency_algorithm(tag_name) {
+                    self.any_other_end_tag_in_body(tag_name);
+                }
+            }
+            "applet" | "marquee" | "object" => {
+                if !self.has_element_in_scope(tag_name, Scope::Default) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except("");
+                if !self.current_node_is(tag_name) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(&[tag_name.as_str()]);
+                self.clear_active_formatting_elements_to_last_marker();
+            }
+            "br" => {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                self.handle_start_tag_in_body(start_tag("br"));
+            }
+            _ => self.any_other_end_tag_in_body(tag_name),
+        }
+    }
+
+    fn any_other_end_tag_in_body(&mut self, tag_name: &str) {
+        for index in (0..self.stack_of_open_elements.len()).rev() {
+            let node = self.stack_of_open_elements[index];
+            if self.is_element_named(node, tag_name) {
+                self.generate_implied_end_tags_except(tag_name);
+                if self.current_node() != Some(node) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.stack_of_open_elements.truncate(index);
+                return;
+            }
+            if self.is_special(node) {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                return;
+            }
+        }
+    }
+
+    //13.2.6.4.8 The "text" insertion mode
+    fn handle_text(&mut self, token: Token) {
+        match token {
+            Token::Character { data } => self.insert_character(data),
+            Token::EOF => {
+                self.parse_error(ParseErrorCode::EofInElementThatCanContainOnlyText);
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = self.original_insertion_mode;
+                self.reprocess(token);
+            }
+            _ => {
+                // script elements are never executed, so every end tag just closes the element
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = self.original_insertion_mode;
+            }
+        }
+    }
+
+    //13.2.6.4.9 The "in table" insertion mode
+    fn handle_in_table(&mut self, token: Token) {
+        match token {
+            Token::Character { .. }
+                if self.current_node_is_one_of(&[
+                    "table", "tbody", "template", "tfoot", "thead", "tr",
+                ]) =>
+            {
+                self.pending_table_character_tokens.clear();
+                self.original_insertion_mode = self.insertion_mode;
+                self.insertion_mode = InsertionMode::InTableText;
+                self.reprocess(token);
+            }
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag {
+                ref tag_name,
+                ref attributes,
+                ..
+            } => match tag_name.as_str() {
+                "caption" => {
+                    self.clear_stack_back_to(&["table", "template", "html"]);
+                    self.active_formatting_elements
+                        .push(ActiveFormattingEntry::Marker);
+                    self.insert_html_element(&token);
+                    self.insertion_mode = InsertionMode::InCaption;
+                }
+                "colgroup" => {
+                    self.clear_stack_back_to(&["table", "template", "html"]);
+                    self.insert_html_element(&token);
+                    self.insertion_mode = InsertionMode::InColumnGroup;
+                }
+                "col" => {
+                    self.clear_stack_back_to(&["table", "template", "html"]);
+                    self.insert_html_element(&start_tag("colgroup"));
+                    self.insertion_mode = InsertionMode::InColumnGroup;
+                    self.reprocess(token);
+                }
+                "tbody" | "tfoot" | "thead" => {
+                    self.clear_stack_back_to(&["table", "template", "html"]);
+                    self.insert_html_element(&token);
+                    self.insertion_mode = InsertionMode::InTableBody;
+                }
+                "td" | "th" | "tr" => {
+                    self.clear_stack_back_to(&["table", "template", "html"]);
+                    self.insert_html_element(&start_tag("tbody"));
+                    self.insertion_mode = InsertionMode::InTableBody;
+                    self.reprocess(token);
+                }
+                "table" => {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    if !self.has_element_in_scope("table", Scope::Table) {
+                        return;
+                    }
+                    self.pop_until_one_of(&["table"]);
+                    self.reset_insertion_mode();
+                    self.reprocess(token);
+                }
+                "style" | "script" | "template" => self.handle_in_head(token),
+                "input"
+                    if attributes
+                        .iter()
+                        .any(|(n, v)| n == "type" && v.eq_ignore_ascii_case("hidden")) =>
+                {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                }
+                "form" => {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    if self.stack_contains("template") || self.form_element_pointer.is_some() {
+                        return;
+                    }
+                    let form = self.insert_html_element(&token);
+                    self.form_element_pointer = Some(form);
+                    self.stack_of_open_elements.pop();
+                }
+                _ => self.anything_else_in_table(token),
+            },
+            Token::EndTag { ref tag_name, .. } => match tag_name.as_str() {
+                "table" => {
+                    if !self.has_element_in_scope("table", Scope::Table) {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                        return;
+                    }
+                    self.pop_until_one_of(&["table"]);
+                    self.reset_insertion_mode();
+                }
+                "body" | "caption" | "col" | "colgroup" | "html" | "tbody" | "td" | "tfoot"
+                | "th" | "thead" | "tr" => self.parse_error(ParseErrorCode::UnexpectedEndTag),
+                "template" => self.handle_in_head(token),
+                _ => self.anything_else_in_table(token),
+            },
+            Token::EOF => self.handle_in_body(token),
+            Token::Character { .. } => self.anything_else_in_table(token),
+        }
+    }
+
+    fn anything_else_in_table(&mut self, token: Token) {
+        self.parse_error(ParseErrorCode::FosterParenting);
+        self.foster_parenting = true;
+        self.handle_in_body(token);
+        self.foster_parenting = false;
+    }
+
+    //13.2.6.4.10 The "in table text" insertion mode
+    fn handle_in_table_text(&mut self, token: Token) {
+        match token {
+            Token::Character { data: '\0' } => {
+                self.parse_error(ParseErrorCode::UnexpectedNullCharacter)
+            }
+            Token::Character { data } => self.pending_table_character_tokens.push(data),
+            _ => {
+                let pending = std::mem::take(&mut self.pending_table_character_tokens);
+                if pending.chars().any(|c| !is_parser_whitespace(c)) {
+                    for c in pending.chars() {
+                        self.anything_else_in_table(Token::Character { data: c });
+                    }
+                } else {
+                    for c in pending.chars() {
+                        self.insert_character(c);
+                    }
+                }
+                self.insertion_mode = self.original_insertion_mode;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.11 The "in caption" insertion mode
+    fn handle_in_caption(&mut self, token: Token) {
+        match token {
+            _ if token.is_end_tag("caption") => {
+                self.close_caption();
+            }
+            Token::StartTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "td" | "tfoot" | "th" | "thead"
+                        | "tr"
+                ) =>
+            {
+                if self.close_caption() {
+                    self.reprocess(token);
+                }
+            }
+            _ if token.is_end_tag("table") => {
+                if self.close_caption() {
+                    self.reprocess(token);
+                }
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "body" | "col" | "colgroup" | "html" | "tbody" | "td" | "tfoot" | "th"
+                        | "thead" | "tr"
+                ) =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => self.handle_in_body(token),
+        }
+    }
+
+    fn close_caption(&mut self) -> bool {
+        if !self.has_element_in_scope("caption", Scope::Table) {
+            self.parse_error(ParseErrorCode::UnexpectedCaptionEnd);
+            return false;
+        }
+        self.generate_implied_end_tags_except("");
+        if !self.current_node_is("caption") {
+            self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+        }
+        self.pop_until_one_of(&["caption"]);
+        self.clear_active_formatting_elements_to_last_marker();
+        self.insertion_mode = InsertionMode::InTable;
+        true
+    }
+
+    //13.2.6.4.12 The "in column group" insertion mode
+    fn handle_in_column_group(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_start_tag("col") => {
+                self.insert_html_element(&token);
+                self.stack_of_open_elements.pop();
+            }
+            _ if token.is_end_tag("colgroup") => {
+                if !self.current_node_is("colgroup") {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = InsertionMode::InTable;
+            }
+            _ if token.is_end_tag("col") => {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ if token.tag_name() == Some("template") =>
+            {
+                self.handle_in_head(token)
+            }
+            Token::EOF => self.handle_in_body(token),
+            _ => {
+                if !self.current_node_is("colgroup") {
+                    self.parse_error(ParseErrorCode::UnexpectedTokenInColumnGroup);
+                    return;
+                }
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = InsertionMode::InTable;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.13 The "in table body" insertion mode
+    fn handle_in_table_body(&mut self, token: Token) {
+        const TABLE_BODY_CONTEXT: &[&str] = &["tbody", "tfoot", "thead", "template", "html"];
+        match token {
+            _ if token.is_start_tag("tr") => {
+                self.clear_stack_back_to(TABLE_BODY_CONTEXT);
+                self.insert_html_element(&token);
+                self.insertion_mode = InsertionMode::InRow;
+            }
+            Token::StartTag { ref tag_name, .. } if matches!(tag_name.as_str(), "th" | "td") => {
+                self.parse_error(ParseErrorCode::UnexpectedCellInTableBody);
+                self.clear_stack_back_to(TABLE_BODY_CONTEXT);
+                self.insert_html_element(&start_tag("tr"));
+                self.insertion_mode = InsertionMode::InRow;
+                self.reprocess(token);
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(tag_name.as_str(), "tbody" | "tfoot" | "thead") =>
+            {
+                if !self.has_element_in_scope(tag_name, Scope::Table) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.clear_stack_back_to(TABLE_BODY_CONTEXT);
+                self.stack_of_open_elements.pop();
+                self.insertion_mode = InsertionMode::InTable;
+            }
+            Token::StartTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "tfoot" | "thead"
+                ) =>
+            {
+                self.close_table_body_and_reprocess(token);
+            }
+            _ if token.is_end_tag("table") => {
+                self.close_table_body_and_reprocess(token);
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html" | "td" | "th" | "tr"
+                ) =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => self.handle_in_table(token),
+        }
+    }
+
+    fn close_table_body_and_reprocess(&mut self, token: Token) {
+        if !["tbody", "thead", "tfoot"]
+            .iter()
+            .any(|name| self.has_element_in_scope(name, Scope::Table))
+        {
+            self.parse_error(ParseErrorCode::UnexpectedTokenInTableBody);
+            return;
+        }
+        self.clear_stack_back_to(&["tbody", "tfoot", "thead", "template", "html"]);
+        self.stack_of_open_elements.pop();
+        self.insertion_mode = InsertionMode::InTable;
+        self.reprocess(token);
+    }
+
+    //13.2.6.4.14 The "in row" insertion mode
+    fn handle_in_row(&mut self, token: Token) {
+        const TABLE_ROW_CONTEXT: &[&str] = &["tr", "template", "html"];
+        match token {
+            Token::StartTag { ref tag_name, .. } if matches!(tag_name.as_str(), "th" | "td") => {
+                self.clear_stack_back_to(TABLE_ROW_CONTEXT);
+                self.insert_html_element(&token);
+                self.insertion_mode = InsertionMode::InCell;
+                self.active_formatting_elements
+                    .push(ActiveFormattingEntry::Marker);
+            }
+            _ if token.is_end_tag("tr") => {
+                self.close_row();
+            }
+            Token::StartTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "tfoot" | "thead" | "tr"
+                ) =>
+            {
+                if self.close_row() {
+                    self.reprocess(token);
+                }
+            }
+            _ if token.is_end_tag("table") => {
+                if self.close_row() {
+                    self.reprocess(token);
+                }
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(tag_name.as_str(), "tbody" | "tfoot" | "thead") =>
+            {
+                if !self.has_element_in_scope(tag_name, Scope::Table) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                if self.close_row() {
+                    self.reprocess(token);
+                }
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html" | "td" | "th"
+                ) =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            _ => self.handle_in_table(token),
+        }
+    }
+
+    fn close_row(&mut self) -> bool {
+        if !self.has_element_in_scope("tr", Scope::Table) {
+            self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            return false;
+        }
+        self.clear_stack_back_to(&["tr", "template", "html"]);
+        self.stack_of_open_elements.pop();
+        self.insertion_mode = InsertionMode::InTableBody;
+        true
+    }
+
+    //13.2.6.4.15 The "in cell" insertion mode
+    fn handle_in_cell(&mut self, token: Token) {
+        match token {
+            Token::EndTag { ref tag_name, .. } if matches!(tag_name.as_str(), "td" | "th") => {
+                if !self.has_element_in_scope(tag_name, Scope::Table) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.generate_implied_end_tags_except("");
+                if !self.current_node_is(tag_name) {
+                    self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+                }
+                self.pop_until_one_of(&[tag_name.as_str()]);
+                self.clear_active_formatting_elements_to_last_marker();
+                self.insertion_mode = InsertionMode::InRow;
+            }
+            Token::StartTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "caption" | "col" | "colgroup" | "tbody" | "td" | "tfoot" | "th" | "thead"
+                        | "tr"
+                ) =>
+            {
+                if !self.has_element_in_scope("td", Scope::Table)
+                    && !self.has_element_in_scope("th", Scope::Table)
+                {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    return;
+                }
+                self.close_the_cell();
+                self.reprocess(token);
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "body" | "caption" | "col" | "colgroup" | "html"
+                ) =>
+            {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+            }
+            Token::EndTag { ref tag_name, .. }
+                if matches!(
+                    tag_name.as_str(),
+                    "table" | "tbody" | "tfoot" | "thead" | "tr"
+                ) =>
+            {
+                if !self.has_element_in_scope(tag_name, Scope::Table) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.close_the_cell();
+                self.reprocess(token);
+            }
+            _ => self.handle_in_body(token),
+        }
+    }
+
+    fn close_the_cell(&mut self) {
+        self.generate_implied_end_tags_except("");
+        if !self.current_node_is_one_of(&["td", "th"]) {
+            self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+        }
+        self.pop_until_one_of(&["td", "th"]);
+        self.clear_active_formatting_elements_to_last_marker();
+        self.insertion_mode = InsertionMode::InRow;
+    }
+
+    //13.2.6.4.16 The "in select" insertion mode
+    fn handle_in_select(&mut self, token: Token) {
+        match token {
+            Token::Character { data: '\0' } => {
+                self.parse_error(ParseErrorCode::UnexpectedNullCharacter)
+            }
+            Token::Character { data } => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag { ref tag_name, .. } => match tag_name.as_str() {
+                "html" => self.handle_in_body(token),
+                "option" => {
+                    if self.current_node_is("option") {
+                        self.stack_of_open_elements.pop();
+                    }
+                    self.insert_html_element(&token);
+                }
+                "optgroup" => {
+                    if self.current_node_is("option") {
+                        self.stack_of_open_elements.pop();
+                    }
+                    if self.current_node_is("optgroup") {
+                        self.stack_of_open_elements.pop();
+                    }
+                    self.insert_html_element(&token);
+                }
+                "hr" => {
+                    if self.current_node_is("option") {
+                        self.stack_of_open_elements.pop();
+                    }
+                    if self.current_node_is("optgroup") {
+                        self.stack_of_open_elements.pop();
+                    }
+                    self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                }
+                "select" => {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    if !self.has_element_in_scope("select", Scope::Select) {
+                        return;
+                    }
+                    self.pop_until_one_of(&["select"]);
+                    self.reset_insertion_mode();
+                }
+                "input" | "keygen" | "textarea" => {
+                    self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                    if !self.has_element_in_scope("select", Scope::Select) {
+                        return;
+                    }
+                    self.pop_until_one_of(&["select"]);
+                    self.reset_insertion_mode();
+                    self.reprocess(token);
+                }
+                "script" | "template" => self.handle_in_head(token),
+                _ => self.parse_error(ParseErrorCode::UnexpectedStartTag),
+            },
+            Token::EndTag { ref tag_name, .. } => match tag_name.as_str() {
+                "optgroup" => {
+                    let len = self.stack_of_open_elements.len();
+                    if self.current_node_is("option")
+                        && len >= 2
+                        && self.is_element_named(self.stack_of_open_elements[len - 2], "optgroup")
+                    {
+                        self.stack_of_open_elements.pop();
+                    }
+                    if self.current_node_is("optgroup") {
+                        self.stack_of_open_elements.pop();
+                    } else {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    }
+                }
+                "option" => {
+                    if self.current_node_is("option") {
+                        self.stack_of_open_elements.pop();
+                    } else {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    }
+                }
+                "select" => {
+                    if !self.has_element_in_scope("select", Scope::Select) {
+                        self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                        return;
+                    }
+                    self.pop_until_one_of(&["select"]);
+                    self.reset_insertion_mode();
+                }
+                "template" => self.handle_in_head(token),
+                _ => self.parse_error(ParseErrorCode::UnexpectedEndTag),
+            },
+            Token::EOF => self.handle_in_body(token),
+        }
+    }
+
+    //13.2.6.4.17 The "in select in table" insertion mode
+    fn handle_in_select_in_table(&mut self, token: Token) {
+        const TABLE_TAGS: &[&str] = &["caption", "table", "tbody", "tfoot", "thead", "tr", "td", "th"];
+        match token {
+            Token::StartTag { ref tag_name, .. } if TABLE_TAGS.contains(&tag_name.as_str()) => {
+                self.parse_error(ParseErrorCode::UnexpectedStartTag);
+                self.pop_until_one_of(&["select"]);
+                self.reset_insertion_mode();
+                self.reprocess(token);
+            }
+            Token::EndTag { ref tag_name, .. } if TABLE_TAGS.contains(&tag_name.as_str()) => {
+                self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                if !self.has_element_in_scope(tag_name, Scope::Table) {
+                    return;
+                }
+                self.pop_until_one_of(&["select"]);
+                self.reset_insertion_mode();
+                self.reprocess(token);
+            }
+            _ => self.handle_in_select(token),
+        }
+    }
+
+    //13.2.6.4.18 The "in template" insertion mode
+    fn handle_in_template(&mut self, token: Token) {
+        match token {
+            Token::Character { .. } | Token::Comment { .. } | Token::DOCTYPE { .. } => {
+                self.handle_in_body(token)
+            }
+            Token::StartTag { ref tag_name, .. } => {
+                let mode = match tag_name.as_str() {
+                    "base" | "basefont" | "bgsound" | "link" | "meta" | "noframes" | "script"
+                    | "style" | "template" | "title" => {
+                        self.handle_in_head(token);
+                        return;
+                    }
+                    "caption" | "colgroup" | "tbody" | "tfoot" | "thead" => InsertionMode::InTable,
+                    "col" => InsertionMode::InColumnGroup,
+                    "tr" => InsertionMode::InTableBody,
+                    "td" | "th" => InsertionMode::InRow,
+                    _ => InsertionMode::InBody,
+                };
+                self.template_insertion_modes.pop();
+                self.template_insertion_modes.push(mode);
+                self.insertion_mode = mode;
+                self.reprocess(token);
+            }
+            Token::EndTag { ref tag_name, .. } => {
+                if tag_name == "template" {
+                    self.handle_in_head(token);
+                } else {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                }
+            }
+            Token::EOF => {
+                if !self.stack_contains("template") {
+                    self.stop_parsing();
+                    return;
+                }
+                self.parse_error(ParseErrorCode::EofInTemplate);
+                self.pop_until_one_of(&["template"]);
+                self.clear_active_formatting_elements_to_last_marker();
+                self.template_insertion_modes.pop();
+                self.reset_insertion_mode();
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.19 The "after body" insertion mode
+    fn handle_after_body(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.handle_in_body(token),
+            Token::Comment { data } => {
+                if let Some(&html) = self.stack_of_open_elements.first() {
+                    self.append_comment(html, &data);
+                }
+            }
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_end_tag("html") => {
+                if self.is_fragment_case {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.insertion_mode = InsertionMode::AfterAfterBody;
+            }
+            Token::EOF => self.stop_parsing(),
+            _ => {
+                self.parse_error(ParseErrorCode::UnexpectedTokenAfterBody);
+                self.insertion_mode = InsertionMode::InBody;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.20 The "in frameset" insertion mode
+    fn handle_in_frameset(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag { ref tag_name, .. } => match tag_name.as_str() {
+                "html" => self.handle_in_body(token),
+                "frameset" => {
+                    self.insert_html_element(&token);
+                }
+                "frame" => {
+                    self.insert_html_element(&token);
+                    self.stack_of_open_elements.pop();
+                }
+                "noframes" => self.handle_in_head(token),
+                _ => self.parse_error(ParseErrorCode::UnexpectedStartTag),
+            },
+            _ if token.is_end_tag("frameset") => {
+                if self.stack_of_open_elements.len() <= 1 {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                    return;
+                }
+                self.stack_of_open_elements.pop();
+                if !self.is_fragment_case && !self.current_node_is("frameset") {
+                    self.insertion_mode = InsertionMode::AfterFrameset;
+                }
+            }
+            Token::EOF => {
+                if self.stack_of_open_elements.len() > 1 {
+                    self.parse_error(ParseErrorCode::EofInFrameset);
+                }
+                self.stop_parsing();
+            }
+            _ => self.parse_error(ParseErrorCode::UnexpectedTokenInFrameset),
+        }
+    }
+
+    //13.2.6.4.21 The "after frameset" insertion mode
+    fn handle_after_frameset(&mut self, token: Token) {
+        match token {
+            Token::Character { data } if is_parser_whitespace(data) => self.insert_character(data),
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_end_tag("html") => {
+                self.insertion_mode = InsertionMode::AfterAfterFrameset;
+            }
+            _ if token.is_start_tag("noframes") => {
+                self.handle_in_head(token)
+            }
+            Token::EOF => self.stop_parsing(),
+            _ => self.parse_error(ParseErrorCode::UnexpectedTokenAfterFrameset),
+        }
+    }
+
+    //13.2.6.4.22 The "after after body" insertion mode
+    fn handle_after_after_body(&mut self, token: Token) {
+        match token {
+            Token::Comment { data } => {
+                let root = self.document.root();
+                self.append_comment(root, &data);
+            }
+            Token::DOCTYPE { .. } => self.handle_in_body(token),
+            Token::Character { data } if is_parser_whitespace(data) => self.handle_in_body(token),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            Token::EOF => self.stop_parsing(),
+            _ => {
+                self.parse_error(ParseErrorCode::UnexpectedTokenAfterAfterBody);
+                self.insertion_mode = InsertionMode::InBody;
+                self.reprocess(token);
+            }
+        }
+    }
+
+    //13.2.6.4.23 The "after after frameset" insertion mode
+    fn handle_after_after_frameset(&mut self, token: Token) {
+        match token {
+            Token::Comment { data } => {
+                let root = self.document.root();
+                self.append_comment(root, &data);
+            }
+            Token::DOCTYPE { .. } => self.handle_in_body(token),
+            Token::Character { data } if is_parser_whitespace(data) => self.handle_in_body(token),
+            _ if token.is_start_tag("html") => {
+                self.handle_in_body(token)
+            }
+            _ if token.is_start_tag("noframes") => {
+                self.handle_in_head(token)
+            }
+            Token::EOF => self.stop_parsing(),
+            _ => self.parse_error(ParseErrorCode::UnexpectedTokenAfterAfterFrameset),
+        }
+    }
+
+    //13.2.6.5 The rules for parsing tokens in foreign content
+    fn handle_foreign_content(&mut self, token: Token) {
+        match token {
+            Token::Character { data: '\0' } => {
+                self.parse_error(ParseErrorCode::UnexpectedNullCharacter);
+                self.insert_character('\u{FFFD}');
+            }
+            Token::Character { data } => {
+                self.insert_character(data);
+                if !is_parser_whitespace(data) {
+                    self.frameset_ok = false;
+                }
+            }
+            Token::Comment { data } => self.insert_comment(&data),
+            Token::DOCTYPE { .. } => self.parse_error(ParseErrorCode::UnexpectedDoctype),
+            Token::StartTag {
+                ref tag_name,
+                ref attributes,
+                self_closing,
+                ..
+            } => {
+                let breaks_out = FOREIGN_CONTENT_BREAKOUT.contains(&tag_name.as_str())
+                    || (tag_name == "font"
+                        && attributes
+                            .iter()
+                            .any(|(name, _)| matches!(name.as_str(), "color" | "face" | "size")));
+                if breaks_out {
+                    self.parse_error(ParseErrorCode::UnexpectedHtmlElementInForeignContent);
+                    self.pop_until_html_or_integration_point();
+                    self.process_using_rules(self.insertion_mode, token);
+                    return;
+                }
+                let namespace = self
+                    .adjusted_current_node()
+                    .and_then(|node| self.document.element(node))
+                    .map(|element| element.namespace)
+                    .unwrap_or(Namespace::Html);
+                self.insert_foreign_element(&token, namespace);
+                if self_closing {
+                    self.stack_of_open_elements.pop();
+                }
+            }
+            Token::EndTag { ref tag_name, .. } if matches!(tag_name.as_str(), "br" | "p") => {
+                self.parse_error(ParseErrorCode::UnexpectedHtmlElementInForeignContent);
+                self.pop_until_html_or_integration_point();
+                self.process_using_rules(self.insertion_mode, token);
+            }
+            Token::EndTag { ref tag_name, .. } => {
+                let Some(mut index) = self.stack_of_open_elements.len().checked_sub(1) else {
+                    return;
+                };
+                let matches_token = |this: &Self, node: NodeId| {
+                    this.document
+                        .element(node)
+                        .is_some_and(|element| element.name.to_ascii_lowercase() == *tag_name)
+                };
+                if !matches_token(self, self.stack_of_open_elements[index]) {
+                    self.parse_error(ParseErrorCode::UnexpectedEndTag);
+                }
+                loop {
+                    if index == 0 {
+                        return;
+                    }
+                    let node = self.stack_of_open_elements[index];
+                    if matches_token(self, node) {
+                        self.stack_of_open_elements.truncate(index);
+                        return;
+                    }
+                    index -= 1;
+                    let is_html = self
+                        .document
+                        .element(self.stack_of_open_elements[index])
+                        .is_some_and(|element| element.is_html());
+                    if is_html {
+                        self.process_using_rules(self.insertion_mode, token);
+                        return;
+                    }
+                }
+            }
+            Token::EOF => self.process_using_rules(self.insertion_mode, token),
+        }
+    }
+
+    fn pop_until_html_or_integration_point(&mut self) {
+        while let Some(node) = self.current_node() {
+            let is_html = self
+                .document
+                .element(node)
+                .is_some_and(|element| element.is_html());
+            if is_html
+                || self.is_mathml_text_integration_point(node)
+                || self.is_html_integration_point(node)
+            {
+                break;
+            }
+            self.stack_of_open_elements.pop();
+        }
+    }
+
+    //13.2.7 The end
+    fn stop_parsing(&mut self) {
+        self.stack_of_open_elements.clear();
+        self.active_formatting_elements.clear();
+        self.stopped = true;
+    }
+
+    fn parse_error(&mut self, code: ParseErrorCode) {
+        self.errors.push(TreeError {
+            code,
+            offset: self.token_offset,
+            context: self.token_tag_name.clone(),
+        });
+    }
+
+    // ---------------------------------------------------------------------
+    // 13.2.6.1 Creating and inserting nodes
+
+    fn create_element_for_token(&mut self, token: &Token) -> NodeId {
+        let element = match token {
+            Token::StartTag {
+                tag_name,
+                attributes,
+                ..
+            } => Element::with_attributes(tag_name, attributes.clone()),
+            _ => Element::default(),
+        };
+        let node = self
+            .document
+            .arena_mut()
+            .new_node(NodeData::Element(element));
+        self.document.attach_template_content(node);
+        node
+    }
+
+    /// Appropriate place for inserting a node, as (parent, insert before). Insertions into a
+    /// template element go to its contents fragment instead.
+    fn appropriate_place_for_inserting(
+        &self,
+        override_target: Option<NodeId>,
+    ) -> (NodeId, Option<NodeId>) {
+        let (parent, reference) = self.adjusted_insertion_location(override_target);
+        match self.document.template_content(parent) {
+            Some(content) => (content, None),
+            None => (parent, reference),
+        }
+    }
+
+    fn adjusted_insertion_location(
+        &self,
+        override_target: Option<NodeId>,
+    ) -> (NodeId, Option<NodeId>) {
+        let target = override_target
+            .or(self.current_node())
+            .unwrap_or(self.document.root());
+        if self.foster_parenting
+            && matches!(
+                self.element_name(target),
+                "table" | "tbody" | "tfoot" | "thead" | "tr"
+            )
+        {
+            let last_template = self.last_in_stack("template");
+            let last_table = self.last_in_stack("table");
+            if let Some(template_index) = last_template {
+                if last_table.is_none_or(|table_index| template_index > table_index) {
+                    return (self.stack_of_open_elements[template_index], None);
+                }
+            }
+            let Some(table_index) = last_table else {
+                return (self.stack_of_open_elements[0], None);
+            };
+            let table = self.stack_of_open_elements[table_index];
+            if let Some(parent) = self.document.arena().parent(table) {
+                return (parent, Some(table));
+            }
+            return (self.stack_of_open_elements[table_index - 1], None);
+        }
+        (target, None)
+    }
+
+    fn insert_at(&mut self, place: (NodeId, Option<NodeId>), node: NodeId) {
+        let (parent, reference) = place;
+        self.document.arena_mut().detach(node);
+        if self.document.insert_before(parent, node, reference).is_err() {
+            self.parse_error(ParseErrorCode::NodeInsertionFailed);
+        }
+    }
+
+    fn append(&mut self, parent: NodeId, node: NodeId) {
+        self.insert_at((parent, None), node);
+    }
+
+    fn insert_html_element(&mut self, token: &Token) -> NodeId {
+        let place = self.appropriate_place_for_inserting(None);
+        let element = self.create_element_for_token(token);
+        self.associate_with_form_if_needed(element, place.0);
+        self.insert_at(place, element);
+        self.stack_of_open_elements.push(element);
+        element
+    }
+
+    /// Associates a listed control with the form element pointer, which stays set after the
+    /// form is popped off the stack and may not end up an ancestor of the control (13.2.6.1,
+    /// creating an element for a token)
+    fn associate_with_form_if_needed(&mut self, element: NodeId, intended_parent: NodeId) {
+        let Some(form) = self.form_element_pointer else {
+            return;
+        };
+        let associated = self
+            .document
+            .element(element)
+            .is_some_and(|element| is_listed_element(element) && !element.has_attribute("form"));
+        if !associated || self.stack_contains("template") {
+            return;
+        }
+        let arena = self.document.arena();
+        let tree_root = |node: NodeId| arena.ancestors(node).last().unwrap_or(node);
+        if tree_root(form) == tree_root(intended_parent) {
+            self.document
+                .arena_mut()
+                .set_parser_form_owner(element, form);
+        }
+    }
+
+    /// Freezes the document base URL on the first base element with an href attribute. Later
+    /// base elements, and base elements inside template contents, leave it unchanged.
+    fn update_base_url_if_needed(&mut self, element: NodeId) {
+        let arena = self.document.arena();
+        if arena.ancestors(element).last() != Some(self.document.root()) {
+            return;
+        }
+        let Some(href) = arena.get(element).as_element().and_then(|e| e.get_attribute("href"))
+        else {
+            return;
+        };
+        let href = href.to_string();
+        self.document.set_base_href(&href);
+    }
+
+    // The meta handling of the "in head" insertion mode: a declared encoding confirms the
+    // tentative one or changes it (13.2.3.4 Changing the encoding while parsing)
+    fn change_encoding_if_declared(&mut self, meta: NodeId) {
+        let Some(current) = self.tentative_encoding else {
+            return;
+        };
+        let Some(element) = self.document.element(meta) else {
+            return;
+        };
+        let declared = element
+            .get_attribute("charset")
+            .and_then(declared_encoding)
+            .or_else(|| {
+                let pragma = element.get_attribute("http-equiv")?;
+                if !pragma.eq_ignore_ascii_case("content-type") {
+                    return None;
+                }
+                declared_encoding(&extract_charset_from_meta(element.get_attribute("content")?)?)
+            });
+        let Some(declared) = declared else {
+            return;
+        };
+        self.tentative_encoding = None;
+        if matches!(current, Encoding::Utf16Le | Encoding::Utf16Be) || declared == current {
+            return;
+        }
+        self.encoding_change = Some(declared);
+        self.stopped = true;
+    }
+
+    // 13.2.6.1 Insert a foreign element, with the attribute adjustments of the same section
+    fn insert_foreign_element(&mut self, token: &Token, namespace: Namespace) -> NodeId {
+        let Token::StartTag {
+            tag_name,
+            attributes,
+            ..
+        } = token
+        else {
+            return self.insert_html_element(token);
+        };
+        let local_name = match namespace {
+            Namespace::Svg => adjust_svg_tag_name(tag_name),
+            _ => tag_name.as_str(),
+        };
+        let attributes = attributes
+            .iter()
+            .map(|(name, value)| adjust_foreign_attribute(namespace, name, value))
+            .collect();
+        let place = self.appropriate_place_for_inserting(None);
+        let element = self
+            .document
+            .arena_mut()
+            .new_node(NodeData::Element(Element::new_ns(namespace, local_name, attributes)));
+        self.insert_at(place, element);
+        self.stack_of_open_elements.push(element);
+        element
+    }
+
+    fn insert_comment(&mut self, data: &str) {
+        let place = self.appropriate_place_for_inserting(None);
+        let comment = self.document.create_comment(data);
+        self.insert_at(place, comment);
+    }
+
+    fn append_comment(&mut self, parent: NodeId, data: &str) {
+        let comment = self.document.create_comment(data);
+        self.append(parent, comment);
+    }
+
+    fn insert_character(&mut self, data: char) {
+        let (parent, reference) = self.appropriate_place_for_inserting(None);
+        if parent == self.document.root() {
+            return;
+        }
+        let arena = self.document.arena_mut();
+        let previous = match reference {
+            Some(reference) => arena.get(reference).prev_sibling(),
+            None => arena.get(parent).last_child(),
+        };
+        if let Some(previous) = previous {
+            if let NodeData::Text(text) = &mut arena.get_mut(previous).data {
+                text.push(data);
+                return;
+            }
+        }
+        let text = arena.new_node(NodeData::Text(data.to_string()));
+        self.insert_at((parent, reference), text);
+    }
+
+    // 13.2.6.2 Parsing elements that contain only text, the tag name picks the tokenizer state
+    fn parse_generic_text_element(&mut self, token: &Token) {
+        self.insert_html_element(token);
+        if let Token::StartTag { tag_name, .. } = token {
+            self.tokenizer_state = text_tokenizer_state(tag_name);
+        }
+        self.original_insertion_mode = self.insertion_mode;
+        self.insertion_mode = InsertionMode::Text;
+    }
+
+    fn add_missing_attributes(&mut self, node: NodeId, attributes: &[(SmallStr, String)]) {
+        if let Some(element) = self.document.element_mut(node) {
+            for (name, value) in attributes {
+                if !element.has_attribute(name) {
+                    element.attributes.push(Attribute::new(name, value));
+                }
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // 13.2.4.3 The stack of open elements
+
+    /// The node at the top of the stack of open elements, None while the stack is empty
+    pub fn current_node(&self) -> Option<NodeId> {
+        self.stack_of_open_elements.last().copied()
+    }
+
+    /// The element of the current node, looked up in `arena`
+    pub fn current_element<'a>(&self, arena: &'a NodeArena) -> Option<&'a Element> {
+        self.current_node().and_then(|node| arena.get(node).as_element())
+    }
+
+    /// Tag name of the current node, whatever its namespace
+    pub fn current_node_tag_name<'a>(&self, arena: &'a NodeArena) -> Option<&'a str> {
+        self.current_element(arena).map(|element| element.name.as_str())
+    }
+
+    /// Local name of an HTML element, empty for any other node so that the name based checks
+    /// below never match foreign elements
+    fn element_name(&self, node: NodeId) -> &str {
+        self.document
+            .arena()
+            .get(node)
+            .as_element()
+            .filter(|e| e.is_html())
+            .map(|e| e.name.as_str())
+            .unwrap_or("")
+    }
+
+    fn is_foreign_scope_boundary(&self, node: NodeId) -> bool {
+        self.document.element(node).is_some_and(|element| match element.namespace {
+            Namespace::MathMl => matches!(
+                element.name.as_str(),
+                "mi" | "mo" | "mn" | "ms" | "mtext" | "annotation-xml"
+            ),
+            Namespace::Svg => matches!(element.name.as_str(), "foreignObject" | "desc" | "title"),
+            _ => false,
+        })
+    }
+
+    fn is_element_named(&self, node: NodeId, name: &str) -> bool {
+        self.element_name(node) == name
+    }
+
+    fn is_special(&self, node: NodeId) -> bool {
+        let name = self.element_name(node);
+        (!name.is_empty() && SPECIAL_ELEMENTS.contains(&name))
+            || self.is_foreign_scope_boundary(node)
+    }
+
+    fn current_node_is(&self, name: &str) -> bool {
+        self.current_node()
+            .is_some_and(|node| self.is_element_named(node, name))
+    }
+
+    fn current_node_is_one_of(&self, names: &[&str]) -> bool {
+        self.current_node()
+            .is_some_and(|node| names.contains(&self.element_name(node)))
+    }
+
+    fn stack_contains(&self, name: &str) -> bool {
+        self.last_in_stack(name).is_some()
+    }
+
+    fn last_in_stack(&self, name: &str) -> Option<usize> {
+        self.stack_of_open_elements
+            .iter()
+            .rposition(|&node| self.is_element_named(node, name))
+    }
+
+    fn remove_from_stack(&mut self, node: NodeId) {
+        self.stack_of_open_elements.retain(|&n| n != node);
+    }
+
+    fn pop_until_one_of(&mut self, names: &[&str]) {
+        while let Some(node) = self.stack_of_open_elements.pop() {
+            if names.contains(&self.element_name(node)) {
+                break;
+            }
+        }
+    }
+
+    fn clear_stack_back_to(&mut self, names: &[&str]) {
+        while !self.current_node_is_one_of(names) && self.stack_of_open_elements.len() > 1 {
+            self.stack_of_open_elements.pop();
+        }
+    }
+
+    fn is_scope_boundary(&self, node: NodeId, scope: Scope) -> bool {
+        let name = self.element_name(node);
+        let in_default_scope =
+            DEFAULT_SCOPE.contains(&name) || self.is_foreign_scope_boundary(node);
+        match scope {
+            Scope::Default => in_default_scope,
+            Scope::ListItem => in_default_scope || matches!(name, "ol" | "ul"),
+            Scope::Button => in_default_scope || name == "button",
+            Scope::Table => matches!(name, "html" | "table" | "template"),
+            Scope::Select => !matches!(name, "optgroup" | "option"),
+        }
+    }
+
+    fn has_element_in_scope(&self, name: &str, scope: Scope) -> bool {
+        for &node in self.stack_of_open_elements.iter().rev() {
+            if self.is_element_named(node, name) {
+                return true;
+            }
+            if self.is_scope_boundary(node, scope) {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn has_node_in_scope(&self, target: NodeId) -> bool {
+        for &node in self.stack_of_open_elements.iter().rev() {
+            if node == target {
+                return true;
+            }
+            if self.is_scope_boundary(node, Scope::Default) {
+                return false;
+            }
+        }
+        false
+    }
+
+    // 13.2.6.3 Closing elements that have implied end tags
+    fn generate_implied_end_tags_except(&mut self, exception: &str) {
+        while self.current_node_is_one_of(IMPLIED_END_TAGS) && !self.current_node_is(exception) {
+            self.stack_of_open_elements.pop();
+        }
+    }
+
+    fn generate_all_implied_end_tags_thoroughly(&mut self) {
+        while self.current_node_is_one_of(IMPLIED_END_TAGS_THOROUGHLY) {
+            self.stack_of_open_elements.pop();
+        }
+    }
+
+    fn close_p_element(&mut self) {
+        self.generate_implied_end_tags_except("p");
+        if !self.current_node_is("p") {
+            self.parse_error(ParseErrorCode::EndTagWithOpenElements);
+        }
+        self.pop_until_one_of(&["p"]);
+    }
+
+    fn close_p_element_in_button_scope(&mut self) {
+        if self.has_element_in_scope("p", Scope::Button) {
+            self.close_p_element();
+        }
+    }
+
+    // ---------------------------------------------------------------------
+    // 13.2.4.3 The list of active formatting elements
+
+    fn formatting_element_after_last_marker(&self, name: &str) -> Option<NodeId> {
+        for entry in self.active_formatting_elements.iter().rev() {
+            match *entry {
+                ActiveFormattingEntry::Marker => return None,
+                ActiveFormattingEntry::Element(node) if self.is_element_named(node, name) => {
+                    return Some(node)
+                }
+                ActiveFormattingEntry::Element(_) => {}
+            }
+        }
+        None
+    }
+
+    fn position_in_active_formatting_elements(&self, node: NodeId) -> Option<usize> {
+        self.active_formatting_elements
+            .iter()
+            .position(|entry| *entry == ActiveFormattingEntry::Element(node))
+    }
+
+    fn remove_from_active_formatting_elements(&mut self, node: NodeId) {
+        self.active_formatting_elements
+            .retain(|entry| *entry != ActiveFormattingEntry::Element(node));
+    }
+
+    fn push_onto_active_formatting_elements(&mut self, node: NodeId) {
+        // Noah's Ark clause: at most three identical elements after the last marker
+        let element = self.document.element(node).cloned();
+        let mut identical = Vec::new();
+        for entry in self.active_formatting_elements.iter().rev() {
+            match *entry {
+                ActiveFormattingEntry::Marker => break,
+                ActiveFormattingEntry::Element(other) => {
+                    let other_element = self.document.element(other);
+                    let same = match (&element, other_element) {
+                        (Some(a), Some(b)) => {
+                            a.name == b.name
+                                && a.namespace == b.namespace
+                                && a.attributes.len() == b.attributes.len()
+                                && a.attributes.iter().all(|attr| b.attributes.contains(attr))
+                        }
+                        _ => false,
+                    };
+                    if same {
+                        identical.push(other);
+                    }
+                }
+            }
+        }
+        if identical.len() >= 3 {
+            if let Some(&earliest) = identical.last() {
+                self.remove_from_active_formatting_elements(earliest);
+            }
+        }
+        self.active_formatting_elements
+            .push(ActiveFormattingEntry::Element(node));
+    }
+
+    fn clear_active_formatting_elements_to_last_marker(&mut self) {
+        while let Some(entry) = self.active_formatting_elements.pop() {
+            if entry == ActiveFormattingEntry::Marker {
+                break;
+            }
+        }
+    }
+
+    fn reconstruct_active_formatting_elements(&mut self) {
+        let is_open_or_marker = |this: &Self, entry: &ActiveFormattingEntry| match *entry {
+            ActiveFormattingEntry::Marker => true,
+            ActiveFormattingEntry::Element(node) => this.stack_of_open_elements.contains(&node),
+        };
+        let Some(last) = self.active_formatting_elements.last() else {
+            return;
+        };
+        if is_open_or_marker(self, last) {
+            return;
+        }
+        let mut index = self.active_formatting_elements.len() - 1;
+        // Rewind
+        while index > 0 {
+            if is_open_or_marker(self, &self.active_formatting_elements[index - 1]) {
+                break;
+            }
+            index -= 1;
+        }
+        // Advance and create
+        for i in index..self.active_formatting_elements.len() {
+            let ActiveFormattingEntry::Element(old) = self.active_formatting_elements[i] else {
+                continue;
+            };
+            let new_element = self.clone_element(old);
+            let place = self.appropriate_place_for_inserting(None);
+            self.insert_at(place, new_element);
+            self.stack_of_open_elements.push(new_element);
+            self.active_formatting_elements[i] = ActiveFormattingEntry::Element(new_element);
+        }
+    }
+
+    fn clone_element(&mut self, node: NodeId) -> NodeId {
+        let data = self.document.arena().get(node).data.clone();
+        self.document.arena_mut().new_node(data)
+    }
+
+    // 13.2.6.4.7 Adoption agency algorithm, returns true when the caller must act as
+    // described in the "any other end tag" entry
+    fn adoption_agency_algorithm(&mut self, subject: &str) -> bool {
+        if let Some(current) = self.current_node() {
+            if self.is_element_named(current, subject)
+                && self.position_in_active_formatting_elements(current).is_none()
+            {
+                self.stack_of_open_elements.pop();
+                return false;
+            }
+        }
+
+        for _ in 0..8 {
+            let Some(formatting_element) = self.formatting_element_after_last_marker(subject)
+            else {
+                return true;
+            };
+            let Some(formatting_index) = self
+                .stack_of_open_elements
+                .iter()
+                .position(|&n| n == formatting_element)
+            else {
+                self.parse_error(ParseErrorCode::AdoptionAgency1_2);
+                self.remove_from_active_formatting_elements(formatting_element);
+                return false;
+            };
+            if !self.has_node_in_scope(formatting_element) {
+                self.parse_error(ParseErrorCode::AdoptionAgency4_4);
+                return false;
+            }
+            if self.current_node() != Some(formatting_element) {
+                self.parse_error(ParseErrorCode::AdoptionAgency1_3);
+            }
+
+            let furthest_block = self.stack_of_open_elements[formatting_index + 1..]
+                .iter()
+                .copied()
+                .find(|&n| self.is_special(n));
+            let Some(furthest_block) = furthest_block else {
+                self.stack_of_open_elements.truncate(formatting_index);
+                self.remove_from_active_formatting_elements(formatting_element);
+                return false;
+            };
+
+            let common_ancestor = self.stack_of_open_elements[formatting_index - 1];
+            let mut bookmark = self
+                .position_in_active_formatting_elements(formatting_element)
+                .unwrap_or(0);
+
+            let mut node_index = self
+                .stack_of_open_elements
+                .iter()
+                .position(|&n| n == furthest_block)
+                .unwrap_or(0);
+            let mut last_node = furthest_block;
+            let mut inner_loop_counter = 0;
+            loop {
+                inner_loop_counter += 1;
+                node_index -= 1;
+                let mut node = self.stack_of_open_elements[node_index];
+                if node == formatting_element {
+                    break;
+                }
+                if inner_loop_counter > 3 {
+                    if let Some(position) = self.position_in_active_formatting_elements(node) {
+                        self.active_formatting_elements.remove(position);
+                        if position < bookmark {
+                            bookmark -= 1;
+                        }
+                    }
+                }
+                let Some(afe_position) = self.position_in_active_formatting_elements(node) else {
+                    self.stack_of_open_elements.remove(node_index);
+                    continue;
+                };
+                let new_element = self.clone_element(node);
+                self.active_formatting_elements[afe_position] =
+                    ActiveFormattingEntry::Element(new_element);
+                self.stack_of_open_elements[node_index] = new_element;
+                node = new_element;
+                if last_node == furthest_block {
+                    bookmark = afe_position + 1;
+                }
+                self.append(node, last_node);
+                last_node = node;
+            }
+
+            let place = self.appropriate_place_for_inserting(Some(common_ancestor));
+            self.insert_at(place, last_node);
+
+            let new_element = self.clone_element(formatting_element);
+            let children: Vec<NodeId> = self.document.arena().children(furthest_block).collect();
+            for child in children {
+                self.append(new_element, child);
+            }
+            self.append(furthest_block, new_element);
+
+            if let Some(position) = self.position_in_active_formatting_elements(formatting_element)
+            {
+                self.active_formatting_elements.remove(position);
+                if position < bookmark {
+                    bookmark -= 1;
+                }
+            }
+            let bookmark = bookmark.min(self.active_formatting_elements.len());
+            self.active_formatting_elements
+                .insert(bookmark, ActiveFormattingEntry::Element(new_element));
+
+            self.remove_from_stack(formatting_element);
+            let furthest_index = self
+                .stack_of_open_elements
+                .iter()
+                .position(|&n| n == furthest_block)
+                .unwrap_or(self.stack_of_open_elements.len() - 1);
+            self.stack_of_open_elements
+                .insert(furthest_index + 1, new_element);
+        }
+        false
+    }
+}
+
+/// Template contents are dumped as a DocumentFragment child of their template
+fn dump_node(
+    arena: &NodeArena,
+    document: &Document,
+    node: NodeId,
+    depth: usize,
+    output: &mut String,
+) {
+    output.push_str(&"  ".repeat(depth));
+    output.push_str(&describe_node(arena, node));
+    output.push('\n');
+    if let Some(content) = document.template_content(node) {
+        dump_node(arena, document, content, depth + 1, output);
+    }
+    for child in arena.children(node) {
+        dump_node(arena, document, child, depth + 1, output);
+    }
+}
+
+/// Single line description of a node: element names with their attributes inline, text quoted
+fn describe_node(arena: &NodeArena, node: NodeId) -> String {
+    match &arena.get(node).data {
+        NodeData::Document => "Document".to_string(),
+        NodeData::DocumentFragment => "DocumentFragment".to_string(),
+        NodeData::Doctype { name, .. } => format!("<!DOCTYPE {name}>"),
+        NodeData::Element(element) => {
+            let mut description = match element.namespace {
+                Namespace::Svg => format!("svg {}", element.name),
+                Namespace::MathMl => format!("math {}", element.name),
+                _ => element.name.clone(),
+            };
+            for attribute in &element.attributes {
+                description.push_str(&format!(
+                    " {}=\"{}\"",
+                    serialized_attribute_name(attribute),
+                    attribute.value
+                ));
+            }
+            description
+        }
+        NodeData::Text(text) => format!("{text:?}"),
+        NodeData::Comment(data) => format!("<!-- {data} -->"),
+    }
+}
+
+fn adjust_svg_tag_name(name: &str) -> &str {
+    SVG_TAG_NAME_ADJUSTMENTS
+        .iter()
+        .find(|(lowercase, _)| *lowercase == name)
+        .map_or(name, |(_, adjusted)| adjusted)
+}
+
+/// Applies "adjust MathML/SVG attributes" and "adjust foreign attributes" (13.2.6.1)
+fn adjust_foreign_attribute(namespace: Namespace, name: &str, value: &str) -> Attribute {
+    let (prefix, local_name, attribute_namespace) = match name {
+        "xlink:actuate" | "xlink:arcrole" | "xlink:href" | "xlink:role" | "xlink:show"
+        | "xlink:title" | "xlink:type" => (Some("xlink"), &name[6..], Namespace::XLink),
+        "xml:lang" | "xml:space" => (Some("xml"), &name[4..], Namespace::Xml),
+        "xmlns" => (None, name, Namespace::XmlNs),
+        "xmlns:xlink" => (Some("xmlns"), "xlink", Namespace::XmlNs),
+        _ => {
+            let adjusted = match namespace {
+                Namespace::MathMl if name == "definitionurl" => "definitionURL",
+                Namespace::Svg => SVG_ATTRIBUTE_ADJUSTMENTS
+                    .iter()
+                    .find(|(lowercase, _)| *lowercase == name)
+                    .map_or(name, |(_, adjusted)| adjusted),
+                _ => name,
+            };
+            return Attribute::new(adjusted, value);
+        }
+    };
+    Attribute::new_ns(prefix, local_name, attribute_namespace, value)
+}
+
+/// Document mode implied by a DOCTYPE token (13.2.6.4.1)
+fn quirks_mode_for_doctype(
+    name: &str,
+    public_id: Option<&str>,
+    system_id: Option<&str>,
+    force_quirks: bool,
+) -> QuirksMode {
+    const QUIRKY_PUBLIC_PREFIXES: &[&str] = &[
+        "+//silmaril//dtd html pro v0r11 19970101//",
+        "-//as//dtd html 3.0 aswedit + extensions//",
+        "-//advasoft ltd//dtd html 3.0 aswedit + extensions//",
+        "-//ietf//dtd html 2.0 level 1//",
+        "-//ietf//dtd html 2.0 level 2//",
+        "-//ietf//dtd html 2.0 strict level 1//",
+        "-//ietf//dtd html 2.0 strict level 2//",
+        "-//ietf//dtd html 2.0 strict//",
+        "-//ietf//dtd html 2.0//",
+        "-//ietf//dtd html 2.1e//",
+        "-//ietf//dtd html 3.0//",
+        "-//ietf//dtd html 3.2 final//",
+        "-//ietf//dtd html 3.2//",
+        "-//ietf//dtd html 3//",
+        "-//ietf//dtd html level 0//",
+        "-//ietf//dtd html level 1//",
+        "-//ietf//dtd html level 2//",
+        "-//ietf//dtd html level 3//",
+        "-//ietf//dtd html strict level 0//",
+        "-//ietf//dtd html strict level 1//",
+        "-//ietf//dtd html strict level 2//",
+        "-//ietf//dtd html strict level 3//",
+        "-//ietf//dtd html strict//",
+        "-//ietf//dtd html//",
+        "-//metrius//dtd metrius presentational//",
+        "-//microsoft//dtd internet explorer 2.0 html strict//",
+        "-//microsoft//dtd internet explorer 2.0 html//",
+        "-//microsoft//dtd internet explorer 2.0 tables//",
+        "-//microsoft//dtd internet explorer 3.0 html strict//",
+        "-//microsoft//dtd internet explorer 3.0 html//",
+        "-//microsoft//dtd internet explorer 3.0 tables//",
+        "-//netscape comm. corp.//dtd html//",
+        "-//netscape comm. corp.//dtd strict html//",
+        "-//o'reilly and associates//dtd html 2.0//",
+        "-//o'reilly and associates//dtd html extended 1.0//",
+        "-//o'reilly and associates//dtd html extended relaxed 1.0//",
+        "-//sq//dtd html 2.0 hotmetal + extensions//",
+        "-//softquad software//dtd hotmetal pro 6.0::19990601::extensions to html 4.0//",
+        "-//softquad//dtd hotmetal pro 4.0::19971010::extensions to html 4.0//",
+        "-//spyglass//dtd html 2.0 extended//",
+        "-//sun microsystems corp.//dtd hotjava html//",
+        "-//sun microsystems corp.//dtd hotjava strict html//",
+        "-//w3c//dtd html 3 1995-03-24//",
+        "-//w3c//dtd html 3.2 draft//",
+        "-//w3c//dtd html 3.2 final//",
+        "-//w3c//dtd html 3.2//",
+        "-//w3c//dtd html 3.2s draft//",
+        "-//w3c//dtd html 4.0 frameset//",
+        "-//w3c//dtd html 4.0 transitional//",
+        "-//w3c//dtd html experimental 19960712//",
+        "-//w3c//dtd html experimental 970421//",
+        "-//w3c//dtd w3 html//",
+        "-//w3o//dtd w3 html 3.0//",
+        "-//webtechs//dtd mozilla html 2.0//",
+        "-//webtechs//dtd mozilla html//",
+    ];
+    if force_quirks || name != "html" {
+        return QuirksMode::Quirks;
+    }
+    let public_id = public_id.map(str::to_ascii_lowercase);
+    let system_id = system_id.map(str::to_ascii_lowercase);
+    if let Some(public_id) = public_id.as_deref() {
+        if matches!(
+            public_id,
+            "-//w3o//dtd w3 html strict 3.0//en//" | "-/w3c/dtd html 4.0 transitional/en" | "html"
+        ) || QUIRKY_PUBLIC_PREFIXES
+            .iter()
+            .any(|prefix| public_id.starts_with(prefix))
+        {
+            return QuirksMode::Quirks;
+        }
+        let html4_loose = public_id.starts_with("-//w3c//dtd html 4.01 frameset//")
+            || public_id.starts_with("-//w3c//dtd html 4.01 transitional//");
+        if html4_loose && system_id.is_none() {
+            return QuirksMode::Quirks;
+        }
+        if public_id.starts_with("-//w3c//dtd xhtml 1.0 frameset//")
+            || public_id.starts_with("-//w3c//dtd xhtml 1.0 transitional//")
+        {
+            return QuirksMode::LimitedQuirks;
+        }
+        if html4_loose {
+            return QuirksMode::LimitedQuirks;
+        }
+    }
+    if system_id.as_deref() == Some("http://www.ibm.com/data/dtd/v11/ibmxhtml1-transitional.dtd") {
+        return QuirksMode::Quirks;
+    }
+    QuirksMode::NoQuirks
 }
@@ -0,0 +1,265 @@
+use crate::dom::escape::escape_text;
+use crate::dom::parser::tokenizer::{Token, Tokenizer, TokenizerState};
+use crate::helper::small_str::SmallStr;
+
+/// What the rewriter does with a token once its handler returns
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Emit the (possibly modified) token
+    Keep,
+    /// Drop the token from the output
+    Remove,
+    /// Emit these bytes verbatim in place of the token
+    Replace(Vec<u8>),
+}
+
+/// Callbacks invoked by `HtmlRewriter` for every token of the input, in source order.
+/// All methods default to keeping the token unchanged.
+pub trait RewriterHandler {
+    fn on_start_tag(&mut self, _tag: &mut StartTagMut) -> Action {
+        Action::Keep
+    }
+
+    fn on_end_tag(&mut self, _tag: &mut EndTagMut) -> Action {
+        Action::Keep
+    }
+
+    /// Called once per run of consecutive characters
+    fn on_text(&mut self, _text: &mut TextMut) -> Action {
+        Action::Keep
+    }
+
+    fn on_comment(&mut self, _comment: &mut CommentMut) -> Action {
+        Action::Keep
+    }
+}
+
+/// A start tag handed to `RewriterHandler::on_start_tag`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartTagMut {
+    name: SmallStr,
+    attributes: Vec<(SmallStr, String)>,
+    self_closing: bool,
+}
+
+impl StartTagMut {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_ascii_lowercase().into();
+    }
+
+    /// Attributes as (name, value) pairs in source order
+    pub fn attributes(&self) -> &[(SmallStr, String)] {
+        &self.attributes
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr_name, _)| attr_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Replaces the value of `name`, or appends the attribute when it is not present
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        let name = SmallStr::from(name.to_ascii_lowercase());
+        match self.attributes.iter_mut().find(|(attr_name, _)| *attr_name == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.attributes.push((name, value.to_string())),
+        }
+    }
+
+    /// Removes `name`, returning its value if it was present
+    pub fn remove_attribute(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|(attr_name, _)| attr_name == name)?;
+        Some(self.attributes.remove(index).1)
+    }
+
+    pub fn self_closing(&self) -> bool {
+        self.self_closing
+    }
+
+    pub fn set_self_closing(&mut self, self_closing: bool) {
+        self.self_closing = self_closing;
+    }
+}
+
+/// An end tag handed to `RewriterHandler::on_end_tag`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndTagMut {
+    name: SmallStr,
+}
+
+impl EndTagMut {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_ascii_lowercase().into();
+    }
+}
+
+/// A run of text handed to `RewriterHandler::on_text`, character references already decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMut {
+    text: String,
+    raw: bool,
+}
+
+impl TextMut {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    /// True inside script, style and the other raw text elements, where the text is emitted
+    /// without escaping
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+}
+
+/// A comment handed to `RewriterHandler::on_comment`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentMut {
+    data: String,
+}
+
+impl CommentMut {
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn set_data(&mut self, data: &str) {
+        self.data = data.to_string();
+    }
+}
+
+/// Streaming rewriter: tokenizes the input and re-serializes each token after passing it to the
+/// handler, without building a tree. The output is a normalized form of the input (attribute
+/// values are double-quoted, character references in text are re-escaped only where needed),
+/// so untouched markup is equivalent but not always byte-identical to the source.
+pub struct HtmlRewriter<H: RewriterHandler> {
+    handler: H,
+}
+
+impl<H: RewriterHandler> HtmlRewriter<H> {
+    pub fn new(handler: H) -> Self {
+        HtmlRewriter { handler }
+    }
+
+    pub fn process(mut self, input: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(input.len());
+        let mut tokenizer = Tokenizer::new(input);
+        let mut pending_text = String::new();
+        // whether the text being accumulated sits inside a raw text element
+        let mut raw_text = false;
+        loop {
+            let token = tokenizer.next_token();
+            if let Token::Character { data } = token {
+                pending_text.push(data);
+                continue;
+            }
+            self.flush_text(&mut pending_text, raw_text, &mut output);
+            match token {
+                doctype @ Token::DOCTYPE { .. } => {
+                    output.extend_from_slice(doctype.to_string().as_bytes());
+                }
+                Token::StartTag {
+                    tag_name,
+                    self_closing,
+                    attributes,
+                    ..
+                } => {
+                    // the tokenizer state follows the tag as written, not as rewritten
+                    if let Some(state) = text_state_for(&tag_name) {
+                        raw_text = state != TokenizerState::RCDATA;
+                        tokenizer.set_state(state);
+                    }
+                    let mut tag = StartTagMut {
+                        name: tag_name,
+                        attributes,
+                        self_closing,
+                    };
+                    match self.handler.on_start_tag(&mut tag) {
+                        Action::Keep => {
+                            let token = Token::StartTag {
+                                tag_name: tag.name,
+                                self_closing: tag.self_closing,
+                                attributes: tag.attributes,
+                                raw_attributes: Vec::new(),
+                            };
+                            output.extend_from_slice(token.to_string().as_bytes());
+                        }
+                        Action::Remove => {}
+                        Action::Replace(bytes) => output.extend_from_slice(&bytes),
+                    }
+                }
+                Token::EndTag { tag_name, .. } => {
+                    raw_text = false;
+                    let mut tag = EndTagMut { name: tag_name };
+                    match self.handler.on_end_tag(&mut tag) {
+                        Action::Keep => {
+                            let token = Token::EndTag {
+                                tag_name: tag.name,
+                                self_closing: false,
+                                attributes: Vec::new(),
+                            };
+                            output.extend_from_slice(token.to_string().as_bytes());
+                        }
+                        Action::Remove => {}
+                        Action::Replace(bytes) => output.extend_from_slice(&bytes),
+                    }
+                }
+                Token::Comment { data } => {
+                    let mut comment = CommentMut { data };
+                    match self.handler.on_comment(&mut comment) {
+                        Action::Keep => {
+                            let token = Token::Comment { data: comment.data };
+                            output.extend_from_slice(token.to_string().as_bytes());
+                        }
+                        Action::Remove => {}
+                        Action::Replace(bytes) => output.extend_from_slice(&bytes),
+                    }
+                }
+                Token::Character { .. } => unreachable!("characters are buffered above"),
+                Token::EOF => break,
+            }
+        }
+        output
+    }
+
+    fn flush_text(&mut self, pending_text: &mut String, raw: bool, output: &mut Vec<u8>) {
+        if pending_text.is_empty() {
+            return;
+        }
+        let mut text = TextMut {
+            text: std::mem::take(pending_text),
+            raw,
+        };
+        match self.handler.on_text(&mut text) {
+            Action::Keep if raw => output.extend_from_slice(text.text.as_bytes()),
+            Action::Keep => output.extend_from_slice(escape_text(&text.text).as_bytes()),
+            Action::Remove => {}
+            Action::Replace(bytes) => output.extend_from_slice(&bytes),
+        }
+    }
+}
+
+/// Tokenizer state the tree constructor switches to after a start tag with this name
+fn text_state_for(tag_name: &str) -> Option<TokenizerState> {
+    match tag_name {
+        "title" | "textarea" => Some(TokenizerState::RCDATA),
+        "style" | "xmp" | "iframe" | "noembed" | "noframes" => Some(TokenizerState::RAWTEXT),
+        "script" => Some(TokenizerState::ScriptData),
+        "plaintext" => Some(TokenizerState::PLAINTEXT),
+        _ => None,
+    }
+}
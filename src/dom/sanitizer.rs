@@ -0,0 +1,224 @@
+use crate::dom::rewriter::{
+    Action, CommentMut, EndTagMut, HtmlRewriter, RewriterHandler, StartTagMut, TextMut,
+};
+use crate::helper::small_str::SmallStr;
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_TAGS: &[&str] = &[
+    "p", "a", "img", "ul", "ol", "li", "b", "i", "strong", "em", "code", "pre",
+];
+
+const DEFAULT_ATTRIBUTES: &[(&str, &[&str])] = &[("a", &["href"]), ("img", &["src", "alt"])];
+
+const DEFAULT_PROTOCOLS: &[&str] = &["http", "https", "mailto"];
+
+/// Attributes holding a URL, checked against the allowed protocols
+const URL_ATTRIBUTES: &[&str] = &["href", "src"];
+
+/// Elements whose content is dropped along with the tags when they are not allowed; the rest
+/// of the disallowed elements are unwrapped, keeping their text
+const CONTENT_DROPPING_TAGS: &[&str] = &[
+    "script",
+    "style",
+    "iframe",
+    "noembed",
+    "noframes",
+    "xmp",
+    "plaintext",
+    "template",
+];
+
+/// Allowlist based HTML sanitizer. Tags, attributes and URL schemes that are not explicitly
+/// allowed are stripped; comments are always removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sanitizer {
+    allowed_tags: HashSet<String>,
+    allowed_attributes: HashMap<String, HashSet<String>>,
+    allowed_protocols: HashSet<String>,
+}
+
+impl Default for Sanitizer {
+    /// Common safe formatting: paragraphs, lists, emphasis, code, links and images over
+    /// http(s) or mailto
+    fn default() -> Self {
+        let mut builder = SanitizerBuilder::new();
+        for tag in DEFAULT_TAGS {
+            builder = builder.allow_tag(tag);
+        }
+        for (tag, attributes) in DEFAULT_ATTRIBUTES {
+            for attribute in *attributes {
+                builder = builder.allow_attribute(tag, attribute);
+            }
+        }
+        for protocol in DEFAULT_PROTOCOLS {
+            builder = builder.allow_protocol(protocol);
+        }
+        builder.build()
+    }
+}
+
+impl Sanitizer {
+    /// Builder starting from empty allowlists
+    pub fn builder() -> SanitizerBuilder {
+        SanitizerBuilder::new()
+    }
+
+    pub fn clean(&self, html: &str) -> String {
+        let handler = SanitizingHandler {
+            sanitizer: self,
+            dropping: None,
+        };
+        let output = HtmlRewriter::new(handler).process(html.as_bytes());
+        String::from_utf8(output).expect("the rewriter emits UTF-8 for UTF-8 input")
+    }
+
+    pub fn is_tag_allowed(&self, tag: &str) -> bool {
+        self.allowed_tags.contains(tag)
+    }
+
+    pub fn is_attribute_allowed(&self, tag: &str, attribute: &str) -> bool {
+        self.allowed_attributes
+            .get(tag)
+            .is_some_and(|attributes| attributes.contains(attribute))
+    }
+
+    /// Checks the scheme of a URL; relative URLs have none and are always allowed
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        match url_scheme(url) {
+            Some(scheme) => self.allowed_protocols.contains(&scheme),
+            None => true,
+        }
+    }
+}
+
+/// Builds a `Sanitizer` from explicit allowlists. Names are case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct SanitizerBuilder {
+    allowed_tags: HashSet<String>,
+    allowed_attributes: HashMap<String, HashSet<String>>,
+    allowed_protocols: HashSet<String>,
+}
+
+impl SanitizerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_tag(mut self, tag: &str) -> Self {
+        self.allowed_tags.insert(tag.to_ascii_lowercase());
+        self
+    }
+
+    /// Allows `attribute` on `tag`; the tag itself still has to be allowed
+    pub fn allow_attribute(mut self, tag: &str, attribute: &str) -> Self {
+        self.allowed_attributes
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .insert(attribute.to_ascii_lowercase());
+        self
+    }
+
+    pub fn allow_protocol(mut self, protocol: &str) -> Self {
+        self.allowed_protocols.insert(protocol.to_ascii_lowercase());
+        self
+    }
+
+    pub fn build(self) -> Sanitizer {
+        Sanitizer {
+            allowed_tags: self.allowed_tags,
+            allowed_attributes: self.allowed_attributes,
+            allowed_protocols: self.allowed_protocols,
+        }
+    }
+}
+
+/// Lowercased scheme of `url`, parsed the way a browser would: surrounding spaces and control
+/// characters are trimmed and tabs and newlines are ignored, so "java\tscript:" is caught
+fn url_scheme(url: &str) -> Option<String> {
+    let url: String = url
+        .trim_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let end = url.find([':', '/', '?', '#'])?;
+    if !url[end..].starts_with(':') {
+        return None;
+    }
+    let scheme = &url[..end];
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if valid {
+        Some(scheme.to_ascii_lowercase())
+    } else {
+        // not a scheme a browser would accept, so it cannot be allowlisted either
+        Some(String::new())
+    }
+}
+
+struct SanitizingHandler<'a> {
+    sanitizer: &'a Sanitizer,
+    /// Disallowed element whose content is being dropped until its end tag, with the number
+    /// of nested elements of the same name still open
+    dropping: Option<(String, usize)>,
+}
+
+impl RewriterHandler for SanitizingHandler<'_> {
+    fn on_start_tag(&mut self, tag: &mut StartTagMut) -> Action {
+        if let Some((dropping, depth)) = &mut self.dropping {
+            if dropping == tag.name() && !tag.self_closing() {
+                *depth += 1;
+            }
+            return Action::Remove;
+        }
+        if !self.sanitizer.is_tag_allowed(tag.name()) {
+            if CONTENT_DROPPING_TAGS.contains(&tag.name()) && !tag.self_closing() {
+                self.dropping = Some((tag.name().to_string(), 0));
+            }
+            return Action::Remove;
+        }
+        let disallowed: Vec<SmallStr> = tag
+            .attributes()
+            .iter()
+            .filter(|(name, value)| {
+                !self.sanitizer.is_attribute_allowed(tag.name(), name)
+                    || (URL_ATTRIBUTES.contains(&name.as_str())
+                        && !self.sanitizer.is_url_allowed(value))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in disallowed {
+            tag.remove_attribute(&name);
+        }
+        Action::Keep
+    }
+
+    fn on_end_tag(&mut self, tag: &mut EndTagMut) -> Action {
+        if let Some((dropping, depth)) = &mut self.dropping {
+            if dropping == tag.name() {
+                match depth.checked_sub(1) {
+                    Some(outer) => *depth = outer,
+                    None => self.dropping = None,
+                }
+            }
+            return Action::Remove;
+        }
+        if self.sanitizer.is_tag_allowed(tag.name()) {
+            Action::Keep
+        } else {
+            Action::Remove
+        }
+    }
+
+    fn on_text(&mut self, _text: &mut TextMut) -> Action {
+        if self.dropping.is_some() {
+            Action::Remove
+        } else {
+            Action::Keep
+        }
+    }
+
+    fn on_comment(&mut self, _comment: &mut CommentMut) -> Action {
+        Action::Remove
+    }
+}
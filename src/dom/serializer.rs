@@ -0,0 +1,316 @@
+use crate::dom::document::Document;
+use crate::dom::escape::EscapePolicy;
+use crate::dom::node::{Attribute, Namespace, NodeData, NodeId};
+
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "basefont", "bgsound", "br", "col", "embed", "frame", "hr", "img", "input",
+    "keygen", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// Whether `tag_name` is one of `VOID_ELEMENTS`, which have no end tag nor children
+pub fn is_void_element(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "area"
+            | "base"
+            | "basefont"
+            | "bgsound"
+            | "br"
+            | "col"
+            | "embed"
+            | "frame"
+            | "hr"
+            | "img"
+            | "input"
+            | "keygen"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Elements whose text children are serialized without escaping
+const RAW_TEXT_ELEMENTS: &[&str] = &[
+    "style", "script", "xmp", "iframe", "noembed", "noframes", "plaintext",
+];
+
+/// Attribute's serialized name (13.3): the well-known namespaces get their usual prefix
+pub fn serialized_attribute_name(attribute: &Attribute) -> String {
+    match attribute.namespace {
+        None => attribute.local_name.clone(),
+        Some(Namespace::Xml) => format!("xml:{}", attribute.local_name),
+        Some(Namespace::XmlNs) if attribute.local_name == "xmlns" => "xmlns".to_string(),
+        Some(Namespace::XmlNs) => format!("xmlns:{}", attribute.local_name),
+        Some(Namespace::XLink) => format!("xlink:{}", attribute.local_name),
+        Some(_) => attribute.qualified_name(),
+    }
+}
+
+/// Boolean attributes of HTML elements, written without value when
+/// `SerializeOpts::minimize_boolean_attributes` is set
+pub const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen", "async", "autofocus", "autoplay", "checked", "controls", "default",
+    "defer", "disabled", "formnovalidate", "hidden", "inert", "ismap", "itemscope", "loop",
+    "multiple", "muted", "nomodule", "novalidate", "open", "playsinline", "readonly", "required",
+    "reversed", "selected",
+];
+
+/// Elements whose first newline is dropped by the parser
+const LEADING_NEWLINE_ELEMENTS: &[&str] = &["pre", "textarea", "listing"];
+
+/// Serializer settings, the default escapes minimally and never fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerializeOpts {
+    pub escape_policy: EscapePolicy,
+    /// Fail on nodes whose markup would not parse back to the same node instead of rewriting
+    /// them, see `SerializeError`
+    pub strict: bool,
+    /// Write boolean attributes whose value is empty or their own name as `disabled` rather
+    /// than `disabled=""`
+    pub minimize_boolean_attributes: bool,
+    /// Write an extra newline after the start tag of pre, textarea and listing elements whose
+    /// text starts with one, since the parser drops a newline right after these start tags.
+    /// On by default, otherwise such a leading newline is lost when reparsing.
+    pub preserve_leading_newline: bool,
+}
+
+impl Default for SerializeOpts {
+    fn default() -> Self {
+        SerializeOpts {
+            escape_policy: EscapePolicy::default(),
+            strict: false,
+            minimize_boolean_attributes: false,
+            preserve_leading_newline: true,
+        }
+    }
+}
+
+/// A node that cannot be written as markup that parses back to an equal node. Outside strict
+/// mode such nodes are rewritten so that the output still parses to an equivalent tree:
+///
+/// - comment data starting with `>` or `->`, containing `-->` or `--!>`, or ending with `<!-`
+///   gets a space inserted before the offending `>` (or after the trailing `<!-`)
+/// - a doctype identifier containing both quote characters has its `"` replaced with `'`,
+///   one containing only `"` is quoted with `'`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializeError {
+    Comment(NodeId),
+    Doctype(NodeId),
+}
+
+/// 13.3 Serializing HTML fragments: the markup of the children of `node` (innerHTML)
+pub fn serialize_children(document: &Document, node: NodeId) -> String {
+    serialize_children_with(document, node, EscapePolicy::Minimal)
+}
+
+/// `serialize_children` escaping text and attribute values with `policy`
+pub fn serialize_children_with(
+    document: &Document,
+    node: NodeId,
+    policy: EscapePolicy,
+) -> String {
+    let opts = SerializeOpts {
+        escape_policy: policy,
+        ..SerializeOpts::default()
+    };
+    serialize_children_opts(document, node, &opts).expect("only strict serialization fails")
+}
+
+/// `serialize_children` with full control over the output
+pub fn serialize_children_opts(
+    document: &Document,
+    node: NodeId,
+    opts: &SerializeOpts,
+) -> Result<String, SerializeError> {
+    let mut output = String::new();
+    for child in document.arena().children(node) {
+        serialize_node(document, child, opts, &mut output)?;
+    }
+    Ok(output)
+}
+
+/// The markup of `node` itself followed by its descendants (outerHTML)
+pub fn serialize_node_to_string(document: &Document, node: NodeId) -> String {
+    serialize_node_to_string_with(document, node, EscapePolicy::Minimal)
+}
+
+/// `serialize_node_to_string` escaping text and attribute values with `policy`
+pub fn serialize_node_to_string_with(
+    document: &Document,
+    node: NodeId,
+    policy: EscapePolicy,
+) -> String {
+    let opts = SerializeOpts {
+        escape_policy: policy,
+        ..SerializeOpts::default()
+    };
+    serialize_node_to_string_opts(document, node, &opts).expect("only strict serialization fails")
+}
+
+/// `serialize_node_to_string` with full control over the output
+pub fn serialize_node_to_string_opts(
+    document: &Document,
+    node: NodeId,
+    opts: &SerializeOpts,
+) -> Result<String, SerializeError> {
+    let mut output = String::new();
+    serialize_node(document, node, opts, &mut output)?;
+    Ok(output)
+}
+
+fn serialize_node(
+    document: &Document,
+    node: NodeId,
+    opts: &SerializeOpts,
+    output: &mut String,
+) -> Result<(), SerializeError> {
+    let policy = opts.escape_policy;
+    match &document.arena().get(node).data {
+        NodeData::Document | NodeData::DocumentFragment => {
+            for child in document.arena().children(node) {
+                serialize_node(document, child, opts, output)?;
+            }
+        }
+        NodeData::Doctype {
+            name,
+            public_id,
+            system_id,
+        } => {
+            output.push_str("<!DOCTYPE ");
+            output.push_str(name);
+            if !public_id.is_empty() {
+                output.push_str(" PUBLIC ");
+                push_doctype_identifier(public_id, node, opts, output)?;
+                if !system_id.is_empty() {
+                    output.push(' ');
+                    push_doctype_identifier(system_id, node, opts, output)?;
+                }
+            } else if !system_id.is_empty() {
+                output.push_str(" SYSTEM ");
+                push_doctype_identifier(system_id, node, opts, output)?;
+            }
+            output.push('>');
+        }
+        NodeData::Element(element) => {
+            output.push('<');
+            output.push_str(&element.name);
+            for attribute in &element.attributes {
+                output.push(' ');
+                output.push_str(&serialized_attribute_name(attribute));
+                let minimize = opts.minimize_boolean_attributes
+                    && element.is_html()
+                    && attribute.namespace.is_none()
+                    && BOOLEAN_ATTRIBUTES.contains(&attribute.local_name.as_str())
+                    && (attribute.value.is_empty()
+                        || attribute.value.eq_ignore_ascii_case(&attribute.local_name));
+                if !minimize {
+                    output.push_str("=\"");
+                    output.push_str(&policy.escape_attribute_value(&attribute.value));
+                    output.push('"');
+                }
+            }
+            output.push('>');
+            // void elements have neither children nor an end tag, even when built by hand
+            if element.is_html() && is_void_element(&element.name) {
+                return Ok(());
+            }
+            // a template serializes its contents, not its (normally absent) children
+            let children_of = document.template_content(node).unwrap_or(node);
+            if opts.preserve_leading_newline
+                && element.is_html()
+                && LEADING_NEWLINE_ELEMENTS.contains(&element.name.as_str())
+                && document
+                    .arena()
+                    .get(node)
+                    .first_child()
+                    .and_then(|child| document.arena().get(child).as_text())
+                    .is_some_and(|text| text.starts_with('\n'))
+            {
+                output.push('\n');
+            }
+            for child in document.arena().children(children_of) {
+                serialize_node(document, child, opts, output)?;
+            }
+            output.push_str("</");
+            output.push_str(&element.name);
+            output.push('>');
+        }
+        NodeData::Text(text) => {
+            let parent_is_raw = document
+                .arena()
+                .parent(node)
+                .and_then(|parent| document.element(parent))
+                .is_some_and(|parent| {
+                    parent.is_html() && RAW_TEXT_ELEMENTS.contains(&parent.name.as_str())
+                });
+            if parent_is_raw {
+                output.push_str(text);
+            } else {
+                output.push_str(&policy.escape_text(text));
+            }
+        }
+        NodeData::Comment(data) => {
+            output.push_str("<!--");
+            if is_comment_data_serializable(data) {
+                output.push_str(data);
+            } else if opts.strict {
+                return Err(SerializeError::Comment(node));
+            } else {
+                output.push_str(&rewrite_comment_data(data));
+            }
+            output.push_str("-->");
+        }
+    }
+    Ok(())
+}
+
+/// Whether `<!--data-->` tokenizes back to a single comment holding `data`
+fn is_comment_data_serializable(data: &str) -> bool {
+    !(data.starts_with('>')
+        || data.starts_with("->")
+        || data.contains("-->")
+        || data.contains("--!>")
+        || data.ends_with("<!-"))
+}
+
+fn rewrite_comment_data(data: &str) -> String {
+    let mut rewritten = data.replace("-->", "-- >").replace("--!>", "--! >");
+    if rewritten.starts_with('>') || rewritten.starts_with("->") {
+        rewritten.insert(0, ' ');
+    }
+    if rewritten.ends_with("<!-") {
+        rewritten.push(' ');
+    }
+    rewritten
+}
+
+/// Writes a quoted doctype identifier, using single quotes when it contains double quotes
+fn push_doctype_identifier(
+    identifier: &str,
+    node: NodeId,
+    opts: &SerializeOpts,
+    output: &mut String,
+) -> Result<(), SerializeError> {
+    match (identifier.contains('"'), identifier.contains('\'')) {
+        (false, _) => {
+            output.push('"');
+            output.push_str(identifier);
+            output.push('"');
+        }
+        (true, false) => {
+            output.push('\'');
+            output.push_str(identifier);
+            output.push('\'');
+        }
+        (true, true) if opts.strict => return Err(SerializeError::Doctype(node)),
+        (true, true) => {
+            output.push('"');
+            output.push_str(&identifier.replace('"', "'"));
+            output.push('"');
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use crate::dom::node::Element;
+use std::ops::Deref;
+
+/// One `name: value` declaration of a style attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssDeclaration {
+    /// Lowercased, except for custom properties (`--name`) which are case-sensitive
+    pub name: String,
+    pub value: String,
+    pub important: bool,
+}
+
+/// The declarations of a style attribute, in declaration order.
+///
+/// Parsing is tolerant and does not validate property names or values: declarations are split
+/// on `;` outside quotes, parentheses and comments (so `url(a;b)` stays whole), a missing final
+/// `;` is fine and malformed declarations without a `:` are dropped.
+///
+/// Duplicate properties are all preserved, so serializing gives back what was written, while
+/// `get_property` returns the last one, the one that applies. Setting or removing a property
+/// collapses its duplicates: `set_property` updates the last declaration of that name in place
+/// and drops the earlier ones.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CssStyleDeclaration {
+    declarations: Vec<CssDeclaration>,
+}
+
+impl CssStyleDeclaration {
+    pub fn parse(css_text: &str) -> Self {
+        let declarations = split_top_level(css_text, ';')
+            .into_iter()
+            .filter_map(parse_declaration)
+            .collect();
+        CssStyleDeclaration { declarations }
+    }
+
+    /// `name: value; other: value !important`, in declaration order
+    pub fn css_text(&self) -> String {
+        self.declarations
+            .iter()
+            .map(|declaration| {
+                let important = if declaration.important {
+                    " !important"
+                } else {
+                    ""
+                };
+                format!("{}: {}{important}", declaration.name, declaration.value)
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    pub fn len(&self) -> usize {
+        self.declarations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CssDeclaration> {
+        self.declarations.iter()
+    }
+
+    /// Value of the last declaration of `name`, without `!important`
+    pub fn get_property(&self, name: &str) -> Option<&str> {
+        self.last_declaration(name)
+            .map(|declaration| declaration.value.as_str())
+    }
+
+    pub fn is_important(&self, name: &str) -> bool {
+        self.last_declaration(name)
+            .is_some_and(|declaration| declaration.important)
+    }
+
+    pub fn set_property(&mut self, name: &str, value: &str, important: bool) {
+        let name = normalize_name(name);
+        let declaration = CssDeclaration {
+            name: name.clone(),
+            value: value.trim().to_string(),
+            important,
+        };
+        match self
+            .declarations
+            .iter()
+            .rposition(|existing| existing.name == name)
+        {
+            Some(last) => {
+                self.declarations[last] = declaration;
+                let mut index = 0;
+                self.declarations.retain(|existing| {
+                    index += 1;
+                    index - 1 == last || existing.name != name
+                });
+            }
+            None => self.declarations.push(declaration),
+        }
+    }
+
+    /// Removes every declaration of `name`, returning the value that applied
+    pub fn remove_property(&mut self, name: &str) -> Option<String> {
+        let value = self.get_property(name).map(str::to_string);
+        let name = normalize_name(name);
+        self.declarations
+            .retain(|declaration| declaration.name != name);
+        value
+    }
+
+    fn last_declaration(&self, name: &str) -> Option<&CssDeclaration> {
+        let name = normalize_name(name);
+        self.declarations
+            .iter()
+            .rev()
+            .find(|declaration| declaration.name == name)
+    }
+}
+
+/// A style declaration bound to its element: mutations are written back to the style
+/// attribute right away, which is removed once no declaration is left
+pub struct StyleMut<'a> {
+    element: &'a mut Element,
+    style: CssStyleDeclaration,
+}
+
+impl StyleMut<'_> {
+    pub fn set_property(&mut self, name: &str, value: &str, important: bool) {
+        self.style.set_property(name, value, important);
+        self.write_back();
+    }
+
+    pub fn remove_property(&mut self, name: &str) -> Option<String> {
+        let value = self.style.remove_property(name);
+        self.write_back();
+        value
+    }
+
+    fn write_back(&mut self) {
+        if self.style.is_empty() {
+            self.element.remove_attribute("style");
+        } else {
+            self.element.set_attribute("style", &self.style.css_text());
+        }
+    }
+}
+
+impl Deref for StyleMut<'_> {
+    type Target = CssStyleDeclaration;
+
+    fn deref(&self) -> &CssStyleDeclaration {
+        &self.style
+    }
+}
+
+impl Element {
+    /// The parsed style attribute, empty when there is none
+    pub fn style(&self) -> CssStyleDeclaration {
+        CssStyleDeclaration::parse(self.get_attribute("style").unwrap_or(""))
+    }
+
+    /// The parsed style attribute, with setters updating the attribute
+    pub fn style_mut(&mut self) -> StyleMut<'_> {
+        let style = self.style();
+        StyleMut {
+            element: self,
+            style,
+        }
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    let name = name.trim();
+    if name.starts_with("--") {
+        name.to_string()
+    } else {
+        name.to_ascii_lowercase()
+    }
+}
+
+fn parse_declaration(text: String) -> Option<CssDeclaration> {
+    let mut parts = split_top_level(&text, ':').into_iter();
+    let name = normalize_name(&parts.next()?);
+    let rest: Vec<String> = parts.collect();
+    if name.is_empty() || rest.is_empty() {
+        return None;
+    }
+    // colons after the first one belong to the value, e.g. in `url(http://...)` outside quotes
+    let mut value = rest.join(":").trim().to_string();
+    let mut important = false;
+    if let Some(bang) = value.rfind('!') {
+        if value[bang + 1..].trim().eq_ignore_ascii_case("important") {
+            important = true;
+            value = value[..bang].trim_end().to_string();
+        }
+    }
+    Some(CssDeclaration {
+        name,
+        value,
+        important,
+    })
+}
+
+/// Splits on `separator` outside of quoted strings, parentheses and comments. Comments are
+/// dropped, quoted strings and escapes are kept as written.
+fn split_top_level(text: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if Some(c) == quote => {
+                quote = None;
+                current.push(c);
+            }
+            _ if quote.is_some() => current.push(c),
+            '"' | '\'' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            c if c == separator && depth == 0 => parts.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    parts.push(current);
+    parts
+}
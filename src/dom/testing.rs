@@ -0,0 +1,368 @@
+use crate::dom::document::Document;
+use crate::dom::node::{Element, Namespace, Node, NodeData, NodeId};
+use crate::dom::serializer::serialized_attribute_name;
+
+/// Asserts that two documents have the same structure, panicking with the path to the first
+/// divergence and the digest of both trees otherwise. Attributes are compared as sets unless
+/// `DomCompareOptions` asking for an ordered comparison is passed as third argument.
+#[macro_export]
+macro_rules! assert_dom_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_dom_eq!(
+            $left,
+            $right,
+            $crate::dom::testing::DomCompareOptions::default()
+        )
+    };
+    ($left:expr, $right:expr, $options:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if let Err(difference) = $crate::dom::testing::compare_dom(left, right, $options) {
+            panic!(
+                "DOM trees differ at {}\nleft:\n{}right:\n{}",
+                difference,
+                $crate::dom::testing::tree_digest(left, left.root()),
+                $crate::dom::testing::tree_digest(right, right.root()),
+            );
+        }
+    }};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DomCompareOptions {
+    /// Compare attributes in source order instead of as sets
+    pub ordered_attributes: bool,
+}
+
+/// Compares the trees under the document nodes of `left` and `right`. The error describes the
+/// first divergence in tree order as `path: detail`, e.g.
+/// `html > body > div[2]: attribute class "a" != "b"`.
+pub fn compare_dom(
+    left: &Document,
+    right: &Document,
+    options: DomCompareOptions,
+) -> Result<(), String> {
+    let mut path = Vec::new();
+    compare_nodes(left, left.root(), right, right.root(), options, &mut path).map_err(|detail| {
+        if path.is_empty() {
+            format!("#document: {detail}")
+        } else {
+            format!("{}: {detail}", path.join(" > "))
+        }
+    })
+}
+
+fn compare_nodes(
+    left: &Document,
+    left_node: NodeId,
+    right: &Document,
+    right_node: NodeId,
+    options: DomCompareOptions,
+    path: &mut Vec<String>,
+) -> Result<(), String> {
+    match (
+        &left.arena().get(left_node).data,
+        &right.arena().get(right_node).data,
+    ) {
+        (NodeData::Document, NodeData::Document)
+        | (NodeData::DocumentFragment, NodeData::DocumentFragment) => {}
+        (
+            NodeData::Doctype {
+                name,
+                public_id,
+                system_id,
+            },
+            NodeData::Doctype {
+                name: right_name,
+                public_id: right_public_id,
+                system_id: right_system_id,
+            },
+        ) => {
+            if (name, public_id, system_id) != (right_name, right_public_id, right_system_id) {
+                return Err(format!(
+                    "doctype {} != {}",
+                    doctype_digest(name, public_id, system_id),
+                    doctype_digest(right_name, right_public_id, right_system_id)
+                ));
+            }
+        }
+        (NodeData::Element(element), NodeData::Element(right_element)) => {
+            compare_elements(element, right_element, options)?;
+        }
+        (NodeData::Text(text), NodeData::Text(right_text)) => {
+            if text != right_text {
+                return Err(format!("text {text:?} != {right_text:?}"));
+            }
+        }
+        (NodeData::Comment(data), NodeData::Comment(right_data)) => {
+            if data != right_data {
+                return Err(format!("comment {data:?} != {right_data:?}"));
+            }
+        }
+        (data, right_data) => {
+            return Err(format!("{} != {}", kind_name(data), kind_name(right_data)));
+        }
+    }
+
+    let left_children: Vec<NodeId> = left.arena().children(left_node).collect();
+    let right_children: Vec<NodeId> = right.arena().children(right_node).collect();
+    for (&left_child, &right_child) in left_children.iter().zip(&right_children) {
+        path.push(path_segment(left, left_child));
+        compare_nodes(left, left_child, right, right_child, options, path)?;
+        path.pop();
+    }
+    match left_children.len().cmp(&right_children.len()) {
+        std::cmp::Ordering::Equal => Ok(()),
+        std::cmp::Ordering::Greater => Err(format!(
+            "extra child {} on the left",
+            path_segment(left, left_children[right_children.len()])
+        )),
+        std::cmp::Ordering::Less => Err(format!(
+            "extra child {} on the right",
+            path_segment(right, right_children[left_children.len()])
+        )),
+    }
+}
+
+fn compare_elements(
+    left: &Element,
+    right: &Element,
+    options: DomCompareOptions,
+) -> Result<(), String> {
+    if left.namespace != right.namespace {
+        return Err(format!(
+            "namespace {} != {}",
+            left.namespace.url(),
+            right.namespace.url()
+        ));
+    }
+    if left.name != right.name {
+        return Err(format!("name {:?} != {:?}", left.name, right.name));
+    }
+    for attribute in &left.attributes {
+        let name = serialized_attribute_name(attribute);
+        match right.attributes.iter().find(|other| {
+            other.namespace == attribute.namespace && other.local_name == attribute.local_name
+        }) {
+            None => return Err(format!("attribute {name} missing on the right")),
+            Some(other) if other.value != attribute.value => {
+                return Err(format!(
+                    "attribute {name} {:?} != {:?}",
+                    attribute.value, other.value
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(extra) = right.attributes.iter().find(|attribute| {
+        !left.attributes.iter().any(|other| {
+            other.namespace == attribute.namespace && other.local_name == attribute.local_name
+        })
+    }) {
+        return Err(format!(
+            "attribute {} missing on the left",
+            serialized_attribute_name(extra)
+        ));
+    }
+    if options.ordered_attributes {
+        let names = |element: &Element| -> Vec<String> {
+            element
+                .attributes
+                .iter()
+                .map(serialized_attribute_name)
+                .collect()
+        };
+        if names(left) != names(right) {
+            return Err(format!(
+                "attribute order [{}] != [{}]",
+                names(left).join(", "),
+                names(right).join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn kind_name(data: &NodeData) -> String {
+    match data {
+        NodeData::Document => "#document".to_string(),
+        NodeData::DocumentFragment => "#document-fragment".to_string(),
+        NodeData::Doctype { .. } => "#doctype".to_string(),
+        NodeData::Element(element) => format!("element <{}>", element.name),
+        NodeData::Text(_) => "#text".to_string(),
+        NodeData::Comment(_) => "#comment".to_string(),
+    }
+}
+
+/// Name of a node in a divergence path: the tag name, followed by its 1-based position among
+/// the element siblings of the same name when there are several
+fn path_segment(document: &Document, node: NodeId) -> String {
+    let NodeData::Element(element) = &document.arena().get(node).data else {
+        return kind_name(&document.arena().get(node).data);
+    };
+    let same_name = |sibling: &NodeId| {
+        document
+            .element(*sibling)
+            .is_some_and(|other| other.name == element.name && other.namespace == element.namespace)
+    };
+    let Some(parent) = document.arena().parent(node) else {
+        return element.name.clone();
+    };
+    let siblings: Vec<NodeId> = document
+        .arena()
+        .children(parent)
+        .filter(same_name)
+        .collect();
+    if siblings.len() == 1 {
+        return element.name.clone();
+    }
+    let position = siblings
+        .iter()
+        .position(|&sibling| sibling == node)
+        .unwrap_or(0);
+    format!("{}[{}]", element.name, position + 1)
+}
+
+fn doctype_digest(name: &str, public_id: &str, system_id: &str) -> String {
+    if public_id.is_empty() && system_id.is_empty() {
+        format!("<!DOCTYPE {name}>")
+    } else {
+        format!("<!DOCTYPE {name} \"{public_id}\" \"{system_id}\">")
+    }
+}
+
+/// Checks the structural invariants of the tree under the document node, for fuzzers and
+/// property tests. Every link points into the arena, children and siblings agree with each
+/// other and with the parent links, no chain of links loops, and the document element, if
+/// any, is the only html element. The error names the first broken invariant.
+pub fn check_tree_invariants(document: &Document) -> Result<(), String> {
+    let arena = document.arena();
+    let fetch = |id: NodeId, link: &str, from: NodeId| -> Result<&Node, String> {
+        if id.index() < arena.len() {
+            Ok(arena.get(id))
+        } else {
+            Err(format!("{link} of {from:?} points outside the arena: {id:?}"))
+        }
+    };
+    let mut html_elements = 0;
+    let mut visited = 0;
+    let mut pending = vec![document.root()];
+    while let Some(node) = pending.pop() {
+        visited += 1;
+        if visited > arena.len() {
+            return Err("children links loop, the tree has more nodes than the arena".into());
+        }
+        let data = arena.get(node);
+        if data.is_element_named("html") {
+            html_elements += 1;
+        }
+        let mut steps = 0;
+        let mut ancestor = data.parent;
+        while let Some(id) = ancestor {
+            steps += 1;
+            if steps > arena.len() {
+                return Err(format!("parent links of {node:?} loop"));
+            }
+            ancestor = fetch(id, "parent", node)?.parent;
+        }
+        let mut previous = None;
+        let mut child = data.first_child;
+        while let Some(id) = child {
+            let child_data = fetch(id, "child link", node)?;
+            if child_data.parent != Some(node) {
+                return Err(format!(
+                    "{id:?} is a child of {node:?} but its parent is {:?}",
+                    child_data.parent
+                ));
+            }
+            // also catches looping next links, a child cannot have two previous siblings
+            if child_data.prev_sibling != previous {
+                return Err(format!(
+                    "previous sibling of {id:?} is {:?} instead of {previous:?}",
+                    child_data.prev_sibling
+                ));
+            }
+            pending.push(id);
+            previous = child;
+            child = child_data.next_sibling;
+        }
+        if data.last_child != previous {
+            return Err(format!(
+                "last child of {node:?} is {:?} instead of {previous:?}",
+                data.last_child
+            ));
+        }
+    }
+    if html_elements > 1 {
+        return Err(format!("{html_elements} html elements in the tree"));
+    }
+    match document.document_element() {
+        Some(element) if !arena.get(element).is_element_named("html") => Err(format!(
+            "the document element {element:?} is not an html element"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The descendants of `node` in the html5lib tree construction test format: one `| ` prefixed
+/// line per node, indented by two spaces per level, attributes sorted on the lines following
+/// their element and foreign elements prefixed with their namespace (`<svg circle>`).
+pub fn tree_digest(document: &Document, node: NodeId) -> String {
+    let mut output = String::new();
+    for child in document.arena().children(node) {
+        digest_node(document, child, 0, &mut output);
+    }
+    output
+}
+
+fn digest_node(document: &Document, node: NodeId, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    match &document.arena().get(node).data {
+        NodeData::Document | NodeData::DocumentFragment => {}
+        NodeData::Doctype {
+            name,
+            public_id,
+            system_id,
+        } => {
+            output.push_str(&format!(
+                "| {indent}{}\n",
+                doctype_digest(name, public_id, system_id)
+            ));
+        }
+        NodeData::Element(element) => {
+            let name = match element.namespace {
+                Namespace::Svg => format!("svg {}", element.name),
+                Namespace::MathMl => format!("math {}", element.name),
+                _ => element.name.clone(),
+            };
+            output.push_str(&format!("| {indent}<{name}>\n"));
+            let mut attributes: Vec<(String, &str)> = element
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    let name = match attribute.namespace {
+                        Some(Namespace::XLink) => format!("xlink {}", attribute.local_name),
+                        Some(Namespace::Xml) => format!("xml {}", attribute.local_name),
+                        Some(Namespace::XmlNs) => format!("xmlns {}", attribute.local_name),
+                        _ => attribute.local_name.clone(),
+                    };
+                    (name, attribute.value.as_str())
+                })
+                .collect();
+            attributes.sort();
+            for (name, value) in attributes {
+                output.push_str(&format!("| {indent}  {name}=\"{value}\"\n"));
+            }
+            if let Some(content) = document.template_content(node) {
+                output.push_str(&format!("| {indent}  content\n"));
+                for child in document.arena().children(content) {
+                    digest_node(document, child, depth + 2, output);
+                }
+            }
+        }
+        NodeData::Text(text) => output.push_str(&format!("| {indent}\"{text}\"\n")),
+        NodeData::Comment(data) => output.push_str(&format!("| {indent}<!-- {data} -->\n")),
+    }
+    for child in document.arena().children(node) {
+        digest_node(document, child, depth + 1, output);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::dom::document::Document;
+use crate::dom::node::{NodeData, NodeId};
+use std::ops::BitOr;
+
+/// Node types a `TreeWalker` shows, combined with `|`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WhatToShow(u32);
+
+impl WhatToShow {
+    pub const ALL: WhatToShow = WhatToShow(u32::MAX);
+    pub const ELEMENT: WhatToShow = WhatToShow(0x1);
+    pub const TEXT: WhatToShow = WhatToShow(0x4);
+    pub const COMMENT: WhatToShow = WhatToShow(0x80);
+    pub const DOCUMENT: WhatToShow = WhatToShow(0x100);
+    pub const DOCUMENT_TYPE: WhatToShow = WhatToShow(0x200);
+    pub const DOCUMENT_FRAGMENT: WhatToShow = WhatToShow(0x400);
+
+    pub fn contains(self, other: WhatToShow) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn shows(self, data: &NodeData) -> bool {
+        let bit = match data {
+            NodeData::Element(_) => WhatToShow::ELEMENT,
+            NodeData::Text(_) => WhatToShow::TEXT,
+            NodeData::Comment(_) => WhatToShow::COMMENT,
+            NodeData::Document => WhatToShow::DOCUMENT,
+            NodeData::Doctype { .. } => WhatToShow::DOCUMENT_TYPE,
+            NodeData::DocumentFragment => WhatToShow::DOCUMENT_FRAGMENT,
+        };
+        self.contains(bit)
+    }
+}
+
+impl BitOr for WhatToShow {
+    type Output = WhatToShow;
+
+    fn bitor(self, other: WhatToShow) -> WhatToShow {
+        WhatToShow(self.0 | other.0)
+    }
+}
+
+/// Verdict of a `TreeWalker` filter on a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterResult {
+    Accept,
+    /// Hides the node and its whole subtree
+    Reject,
+    /// Hides the node only, its children are still considered
+    Skip,
+}
+
+type NodeFilter = Box<dyn Fn(&Document, NodeId) -> FilterResult>;
+
+/// Filtered traversal of the subtree under a root, following the DOM `TreeWalker`: nodes whose
+/// type is not in `what_to_show` are skipped, the others are passed to the filter. Every move
+/// starts from the current node and, when it finds an accepted node, makes it current.
+///
+/// Like the collections, the walker holds no borrow of the document, so the tree can be
+/// mutated between moves.
+pub struct TreeWalker {
+    root: NodeId,
+    what_to_show: WhatToShow,
+    filter: Option<NodeFilter>,
+    current: NodeId,
+}
+
+impl TreeWalker {
+    pub fn new(root: NodeId, what_to_show: WhatToShow) -> Self {
+        TreeWalker {
+            root,
+            what_to_show,
+            filter: None,
+            current: root,
+        }
+    }
+
+    pub fn with_filter(
+        root: NodeId,
+        what_to_show: WhatToShow,
+        filter: impl Fn(&Document, NodeId) -> FilterResult + 'static,
+    ) -> Self {
+        TreeWalker {
+            filter: Some(Box::new(filter)),
+            ..TreeWalker::new(root, what_to_show)
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn what_to_show(&self) -> WhatToShow {
+        self.what_to_show
+    }
+
+    pub fn current_node(&self) -> NodeId {
+        self.current
+    }
+
+    /// Moves to `node`, which does not need to be accepted nor even inside the root
+    pub fn set_current_node(&mut self, node: NodeId) {
+        self.current = node;
+    }
+
+    /// The closest accepted ancestor of the current node, up to the root
+    pub fn parent_node(&mut self, document: &Document) -> Option<NodeId> {
+        let arena = document.arena();
+        let mut node = self.current;
+        while node != self.root {
+            node = arena.parent(node)?;
+            if self.filter(document, node) == FilterResult::Accept {
+                self.current = node;
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    pub fn first_child(&mut self, document: &Document) -> Option<NodeId> {
+        self.traverse_children(document, true)
+    }
+
+    pub fn last_child(&mut self, document: &Document) -> Option<NodeId> {
+        self.traverse_children(document, false)
+    }
+
+    pub fn next_sibling(&mut self, document: &Document) -> Option<NodeId> {
+        self.traverse_siblings(document, true)
+    }
+
+    pub fn previous_sibling(&mut self, document: &Document) -> Option<NodeId> {
+        self.traverse_siblings(document, false)
+    }
+
+    /// The previous accepted node in tree order: the deepest last accepted descendant of the
+    /// previous sibling, or the closest accepted ancestor
+    pub fn previous_node(&mut self, document: &Document) -> Option<NodeId> {
+        let arena = document.arena();
+        let mut node = self.current;
+        while node != self.root {
+            let mut sibling = arena.get(node).prev_sibling;
+            while let Some(previous) = sibling {
+                node = previous;
+                let mut result = self.filter(document, node);
+                while result != FilterResult::Reject {
+                    let Some(last_child) = arena.get(node).last_child else {
+                        break;
+                    };
+                    node = last_child;
+                    result = self.filter(document, node);
+                }
+                if result == FilterResult::Accept {
+                    self.current = node;
+                    return Some(node);
+                }
+                sibling = arena.get(node).prev_sibling;
+            }
+            if node == self.root {
+                return None;
+            }
+            node = arena.parent(node)?;
+            if self.filter(document, node) == FilterResult::Accept {
+                self.current = node;
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    /// The next accepted node in tree order, not descending into rejected subtrees
+    pub fn next_node(&mut self, document: &Document) -> Option<NodeId> {
+        let arena = document.arena();
+        let mut node = self.current;
+        let mut result = FilterResult::Accept;
+        loop {
+            while result != FilterResult::Reject {
+                let Some(first_child) = arena.get(node).first_child else {
+                    break;
+                };
+                node = first_child;
+                result = self.filter(document, node);
+                if result == FilterResult::Accept {
+                    self.current = node;
+                    return Some(node);
+                }
+            }
+            node = arena.following_skipping_children(node, self.root)?;
+            result = self.filter(document, node);
+            if result == FilterResult::Accept {
+                self.current = node;
+                return Some(node);
+            }
+        }
+    }
+
+    fn filter(&self, document: &Document, node: NodeId) -> FilterResult {
+        if !self.what_to_show.shows(&document.arena().get(node).data) {
+            return FilterResult::Skip;
+        }
+        match &self.filter {
+            Some(filter) => filter(document, node),
+            None => FilterResult::Accept,
+        }
+    }
+
+    /// The first (or last) accepted child, looking through skipped children into their own
+    fn traverse_children(&mut self, document: &Document, first: bool) -> Option<NodeId> {
+        let arena = document.arena();
+        let child_of = |node: NodeId| {
+            let node = arena.get(node);
+            if first {
+                node.first_child
+            } else {
+                node.last_child
+            }
+        };
+        let sibling_of = |node: NodeId| {
+            let node = arena.get(node);
+            if first {
+                node.next_sibling
+            } else {
+                node.prev_sibling
+            }
+        };
+        let mut node = child_of(self.current)?;
+        loop {
+            match self.filter(document, node) {
+                FilterResult::Accept => {
+                    self.current = node;
+                    return Some(node);
+                }
+                FilterResult::Skip => {
+                    if let Some(child) = child_of(node) {
+                        node = child;
+                        continue;
+                    }
+                }
+                FilterResult::Reject => {}
+            }
+            loop {
+                if let Some(sibling) = sibling_of(node) {
+                    node = sibling;
+                    break;
+                }
+                let parent = arena.parent(node)?;
+                if parent == self.root || parent == self.current {
+                    return None;
+                }
+                node = parent;
+            }
+        }
+    }
+
+    /// The next (or previous) accepted sibling, looking into skipped siblings and, when the
+    /// siblings run out, into the siblings of skipped ancestors
+    fn traverse_siblings(&mut self, document: &Document, next: bool) -> Option<NodeId> {
+        let arena = document.arena();
+        let sibling_of = |node: NodeId| {
+            let node = arena.get(node);
+            if next {
+                node.next_sibling
+            } else {
+                node.prev_sibling
+            }
+        };
+        let child_of = |node: NodeId| {
+            let node = arena.get(node);
+            if next {
+                node.first_child
+            } else {
+                node.last_child
+            }
+        };
+        let mut node = self.current;
+        if node == self.root {
+            return None;
+        }
+        loop {
+            let mut sibling = sibling_of(node);
+            while let Some(candidate) = sibling {
+                node = candidate;
+                let result = self.filter(document, node);
+                if result == FilterResult::Accept {
+                    self.current = node;
+                    return Some(node);
+                }
+                sibling = child_of(node);
+                if result == FilterResult::Reject || sibling.is_none() {
+                    sibling = sibling_of(node);
+                }
+            }
+            node = arena.parent(node)?;
+            if node == self.root || self.filter(document, node) == FilterResult::Accept {
+                return None;
+            }
+        }
+    }
+}
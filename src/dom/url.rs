@@ -0,0 +1,276 @@
+/// Why a reference could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// The base URL has no scheme, so relative references have nothing to resolve against
+    RelativeBase,
+    /// The scheme of the URL contains characters not allowed by RFC 3986
+    InvalidScheme,
+    /// The URL has no scheme where an absolute URL is needed
+    NotAbsolute,
+}
+
+/// An absolute URL split into the components the URL decomposition getters (protocol, host,
+/// pathname, ...) expose. Missing components are empty, not None.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UrlComponents {
+    /// Lowercased, without the colon
+    pub scheme: String,
+    /// Userinfo of the authority, before the `@`
+    pub userinfo: String,
+    /// Host of the authority, lowercased for the special schemes (http, https, ws, wss,
+    /// ftp, file). IPv6 addresses keep their brackets.
+    pub hostname: String,
+    /// Port of the authority, empty when it is the default port of the scheme
+    pub port: String,
+    /// The path, `/` for a special scheme URL with an authority and an empty path
+    pub path: String,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
+impl UrlComponents {
+    /// Splits the absolute URL `url`
+    pub fn parse(url: &str) -> Result<Self, UrlError> {
+        let parts = UrlParts::parse(url.trim())?;
+        let scheme = parts
+            .scheme
+            .ok_or(UrlError::NotAbsolute)?
+            .to_ascii_lowercase();
+        let special = default_port(&scheme).is_some() || scheme == "file";
+        let mut components = UrlComponents {
+            path: parts.path.to_string(),
+            query: parts.query.map(str::to_string),
+            fragment: parts.fragment.map(str::to_string),
+            ..Default::default()
+        };
+        if let Some(authority) = parts.authority {
+            let host_port = match authority.rfind('@') {
+                Some(at) => {
+                    components.userinfo = authority[..at].to_string();
+                    &authority[at + 1..]
+                }
+                None => authority,
+            };
+            // a colon inside IPv6 brackets is not a port delimiter
+            let port_colon = host_port
+                .rfind(':')
+                .filter(|&colon| !host_port[colon..].contains(']'));
+            let (hostname, port) = match port_colon {
+                Some(colon) => (&host_port[..colon], &host_port[colon + 1..]),
+                None => (host_port, ""),
+            };
+            components.hostname = if special {
+                hostname.to_ascii_lowercase()
+            } else {
+                hostname.to_string()
+            };
+            // leading zeros are dropped, as they are by the URL parser
+            let port = match port.trim_start_matches('0') {
+                "" if !port.is_empty() => "0",
+                trimmed => trimmed,
+            };
+            if Some(port) != default_port(&scheme) {
+                components.port = port.to_string();
+            }
+            if special && components.path.is_empty() {
+                components.path = "/".to_string();
+            }
+        }
+        components.scheme = scheme;
+        Ok(components)
+    }
+
+    /// `hostname:port`, or the hostname alone without a port
+    pub fn host(&self) -> String {
+        if self.port.is_empty() {
+            self.hostname.clone()
+        } else {
+            format!("{}:{}", self.hostname, self.port)
+        }
+    }
+
+    /// The ASCII serialization of the origin: `scheme://host` for the http, https, ws, wss
+    /// and ftp schemes, `null` for the others, whose origins are opaque
+    pub fn origin(&self) -> String {
+        if default_port(&self.scheme).is_some() {
+            format!("{}://{}", self.scheme, self.host())
+        } else {
+            "null".to_string()
+        }
+    }
+}
+
+/// The default port of the special schemes that have one
+fn default_port(scheme: &str) -> Option<&'static str> {
+    match scheme {
+        "http" | "ws" => Some("80"),
+        "https" | "wss" => Some("443"),
+        "ftp" => Some("21"),
+        _ => None,
+    }
+}
+
+/// The five components of a URI reference (RFC 3986, 3). Authority, query and fragment are
+/// `None` when their delimiter is absent, which differs from being present and empty.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct UrlParts<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UrlParts<'a> {
+    /// Splits a reference the way the regular expression of RFC 3986 appendix B does
+    fn parse(url: &'a str) -> Result<Self, UrlError> {
+        let mut rest = url;
+        let mut parts = UrlParts::default();
+        if let Some(hash) = rest.find('#') {
+            parts.fragment = Some(&rest[hash + 1..]);
+            rest = &rest[..hash];
+        }
+        if let Some(question) = rest.find('?') {
+            parts.query = Some(&rest[question + 1..]);
+            rest = &rest[..question];
+        }
+        if let Some(colon) = rest.find(':') {
+            if !rest[..colon].contains('/') {
+                let scheme = &rest[..colon];
+                let mut chars = scheme.chars();
+                let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+                    && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+                if !valid {
+                    return Err(UrlError::InvalidScheme);
+                }
+                parts.scheme = Some(scheme);
+                rest = &rest[colon + 1..];
+            }
+        }
+        if let Some(after_slashes) = rest.strip_prefix("//") {
+            let end = after_slashes.find('/').unwrap_or(after_slashes.len());
+            parts.authority = Some(&after_slashes[..end]);
+            rest = &after_slashes[end..];
+        }
+        parts.path = rest;
+        Ok(parts)
+    }
+
+    /// Recomposes the components (RFC 3986, 5.3)
+    fn to_url(&self) -> String {
+        let mut url = String::new();
+        if let Some(scheme) = self.scheme {
+            url.push_str(scheme);
+            url.push(':');
+        }
+        if let Some(authority) = self.authority {
+            url.push_str("//");
+            url.push_str(authority);
+        }
+        url.push_str(self.path);
+        if let Some(query) = self.query {
+            url.push('?');
+            url.push_str(query);
+        }
+        if let Some(fragment) = self.fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        url
+    }
+}
+
+/// Resolves `relative` against the absolute URL `base` following RFC 3986 section 5.2:
+/// absolute references are returned normalized, `//host/path` keeps the base scheme, `/path`
+/// keeps the base authority, relative paths are merged with the base path and `.`/`..`
+/// segments removed, and query or fragment only references keep the rest of the base.
+/// The scheme is lowercased, nothing else is normalized.
+pub fn resolve_url(base: &str, relative: &str) -> Result<String, UrlError> {
+    let base = UrlParts::parse(base.trim())?;
+    let reference = UrlParts::parse(relative.trim())?;
+    let Some(base_scheme) = base.scheme else {
+        return Err(UrlError::RelativeBase);
+    };
+
+    let merged_path;
+    let target_path;
+    let target = if reference.scheme.is_some() {
+        target_path = remove_dot_segments(reference.path);
+        UrlParts {
+            path: &target_path,
+            ..reference.clone()
+        }
+    } else if reference.authority.is_some() {
+        target_path = remove_dot_segments(reference.path);
+        UrlParts {
+            scheme: Some(base_scheme),
+            path: &target_path,
+            ..reference.clone()
+        }
+    } else if reference.path.is_empty() {
+        UrlParts {
+            query: reference.query.or(base.query),
+            fragment: reference.fragment,
+            ..base.clone()
+        }
+    } else {
+        target_path = if reference.path.starts_with('/') {
+            remove_dot_segments(reference.path)
+        } else {
+            merged_path = merge_paths(&base, reference.path);
+            remove_dot_segments(&merged_path)
+        };
+        UrlParts {
+            path: &target_path,
+            query: reference.query,
+            fragment: reference.fragment,
+            ..base.clone()
+        }
+    };
+    let scheme = target.scheme.map(str::to_ascii_lowercase);
+    Ok(UrlParts {
+        scheme: scheme.as_deref(),
+        ..target
+    }
+    .to_url())
+}
+
+/// RFC 3986, 5.2.3
+fn merge_paths(base: &UrlParts, reference_path: &str) -> String {
+    if base.authority.is_some() && base.path.is_empty() {
+        return format!("/{reference_path}");
+    }
+    match base.path.rfind('/') {
+        Some(last_slash) => format!("{}{reference_path}", &base.path[..=last_slash]),
+        None => reference_path.to_string(),
+    }
+}
+
+/// RFC 3986, 5.2.4
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest;
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            let last_slash = output.rfind('/').unwrap_or(0);
+            output.truncate(last_slash);
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output
+}
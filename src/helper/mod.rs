@@ -1 +1,2 @@
-pub mod stream;
\ No newline at end of file
+pub mod small_str;
+pub mod stream;
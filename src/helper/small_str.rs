@@ -0,0 +1,188 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// Longest string kept inline, without a heap allocation
+pub const INLINE_CAPACITY: usize = 22;
+
+/// Growable string that stores up to `INLINE_CAPACITY` bytes inline and moves to a `String`
+/// beyond that. Tag and attribute names are almost always short (`div`, `class`, `id`), so
+/// tokens carrying them mostly allocate nothing for their names.
+#[derive(Clone)]
+pub struct SmallStr(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_CAPACITY],
+    },
+    Heap(String),
+}
+
+impl SmallStr {
+    pub const fn new() -> Self {
+        SmallStr(Repr::Inline {
+            len: 0,
+            bytes: [0; INLINE_CAPACITY],
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            // only whole UTF-8 sequences are ever copied into the inline bytes
+            Repr::Inline { len, bytes } => {
+                std::str::from_utf8(&bytes[..*len as usize]).expect("inline bytes are valid UTF-8")
+            }
+            Repr::Heap(string) => string,
+        }
+    }
+
+    /// Whether the string is still stored inline
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    pub fn push(&mut self, ch: char) {
+        self.push_str(ch.encode_utf8(&mut [0; 4]));
+    }
+
+    pub fn push_str(&mut self, string: &str) {
+        match &mut self.0 {
+            Repr::Inline { len, bytes } => {
+                let start = *len as usize;
+                let end = start + string.len();
+                if end <= INLINE_CAPACITY {
+                    bytes[start..end].copy_from_slice(string.as_bytes());
+                    *len = end as u8;
+                } else {
+                    let mut heap = String::with_capacity(end);
+                    heap.push_str(self.as_str());
+                    heap.push_str(string);
+                    self.0 = Repr::Heap(heap);
+                }
+            }
+            Repr::Heap(heap) => heap.push_str(string),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = SmallStr::new();
+    }
+}
+
+impl Default for SmallStr {
+    fn default() -> Self {
+        SmallStr::new()
+    }
+}
+
+impl Deref for SmallStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SmallStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for SmallStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&str> for SmallStr {
+    fn from(string: &str) -> Self {
+        let mut small = SmallStr::new();
+        small.push_str(string);
+        small
+    }
+}
+
+impl From<String> for SmallStr {
+    fn from(string: String) -> Self {
+        if string.len() <= INLINE_CAPACITY {
+            SmallStr::from(string.as_str())
+        } else {
+            SmallStr(Repr::Heap(string))
+        }
+    }
+}
+
+impl From<SmallStr> for String {
+    fn from(small: SmallStr) -> Self {
+        match small.0 {
+            Repr::Inline { .. } => small.as_str().to_string(),
+            Repr::Heap(string) => string,
+        }
+    }
+}
+
+impl PartialEq for SmallStr {
+    fn eq(&self, other: &SmallStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SmallStr {}
+
+impl PartialEq<str> for SmallStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SmallStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SmallStr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<SmallStr> for str {
+    fn eq(&self, other: &SmallStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<SmallStr> for &str {
+    fn eq(&self, other: &SmallStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<SmallStr> for String {
+    fn eq(&self, other: &SmallStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Hash for SmallStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for SmallStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for SmallStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
@@ -1,10 +1,12 @@
+use std::borrow::Cow;
 use std::cmp::min;
 
-/// Internal struct for iterating over input bytes
+/// Internal struct for iterating over input bytes. The data is borrowed when the whole input
+/// is known up front, and owned once more of it is appended with `extend`.
 #[derive(Debug)]
-pub struct Stream<'a, T> {
+pub struct Stream<'a, T: Clone> {
     pub idx: usize,
-    data: &'a [T],
+    data: Cow<'a, [T]>,
 }
 
 impl<'a, T: Copy> Stream<'a, T> {
@@ -63,11 +65,33 @@ impl<'a, T: Eq + Copy> Stream<'a, T> {
     }
 }
 
-impl<'a, T> Stream<'a, T> {
+impl<'a, T: Clone> Stream<'a, T> {
     /// Creates a new stream
     #[inline]
-    pub fn new(data: &'a [T]) -> Stream<T> {
-        Self { data, idx: 0 }
+    pub fn new(data: &'a [T]) -> Stream<'a, T> {
+        Self {
+            data: Cow::Borrowed(data),
+            idx: 0,
+        }
+    }
+
+    /// Creates a stream owning its data, which starts out empty
+    pub fn owned() -> Stream<'static, T> {
+        Stream {
+            data: Cow::Owned(Vec::new()),
+            idx: 0,
+        }
+    }
+
+    /// Appends `more` to the data, copying borrowed data into an owned buffer first
+    pub fn extend(&mut self, more: &[T]) {
+        self.data.to_mut().extend_from_slice(more);
+    }
+
+    /// Number of elements from the current one to the end
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.idx)
     }
 
     /// Returns the length
@@ -76,6 +100,12 @@ impl<'a, T> Stream<'a, T> {
         self.data.len()
     }
 
+    /// Checks whether the underlying slice is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     /// Returns a reference to the underlying slice
     #[inline]
     pub fn data(&self) -> &[T] {
@@ -106,24 +136,24 @@ impl<'a, T> Stream<'a, T> {
 
     /// Returns a subslice of this stream, and panicks if out of bounds
     #[inline]
-    pub fn slice(&self, from: usize, to: usize) -> &'a [T] {
+    pub fn slice(&self, from: usize, to: usize) -> &[T] {
         &self.data[from..to]
     }
 
     /// Returns a subslice of this stream but also checks stream length
     /// to prevent out of bounds panicking
     #[inline]
-    pub fn slice_checked(&self, from: usize, to: usize) -> &'a [T] {
+    pub fn slice_checked(&self, from: usize, to: usize) -> &[T] {
         &self.data[from..min(self.data.len(), to)]
     }
 
     /// Same as slice, but the second argument is how many elements to slice
     #[inline]
-    pub fn slice_len(&self, from: usize, len: usize) -> &'a [T] {
+    pub fn slice_len(&self, from: usize, len: usize) -> &[T] {
         self.slice_checked(from, self.idx + len)
     }
     #[inline]
-    pub fn slice_from_idx(&self, len: usize) -> &'a [T] {
+    pub fn slice_from_idx(&self, len: usize) -> &[T] {
         self.slice_checked(self.idx, self.idx + len)
     }
 }
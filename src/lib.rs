@@ -0,0 +1,4 @@
+#![allow(clippy::upper_case_acronyms, clippy::collapsible_match)]
+
+pub mod dom;
+pub mod helper;
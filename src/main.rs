@@ -1,10 +1,6 @@
-mod helper;
-use helper::stream::Stream;
+use brooster_web_parser::dom::entities::ENTITIES;
 
-mod dom;
-use dom::parser::tokenizer;
-use dom::entities::ENTITIES;
-fn main() { 
+fn main() {
         // Access the singleton dictionary anywhere in the program
         if let Some(entity) = ENTITIES.get("AMP") {
             println!("Character: {}, Codepoints: {:?}", entity.characters, entity.codepoints );
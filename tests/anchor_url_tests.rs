@@ -0,0 +1,154 @@
+use brooster_web_parser::dom::elements::html_anchor_element::HTMLAnchorElement;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+use brooster_web_parser::dom::url::{UrlComponents, UrlError};
+
+const PAGE: &str = "https://Example.com:443/docs/page.html?x=1#top";
+
+#[test]
+fn component_getters() {
+    // href attribute, [href, protocol, host, hostname, port, pathname, search, hash, origin]
+    let cases = [
+        (
+            "https://user:pw@www.Example.org:8080/a/b?q=1#frag",
+            [
+                "https://user:pw@www.Example.org:8080/a/b?q=1#frag",
+                "https:",
+                "www.example.org:8080",
+                "www.example.org",
+                "8080",
+                "/a/b",
+                "?q=1",
+                "#frag",
+                "https://www.example.org:8080",
+            ],
+        ),
+        (
+            "../img/a.png?",
+            [
+                "https://Example.com:443/img/a.png?",
+                "https:",
+                "example.com",
+                "example.com",
+                "",
+                "/img/a.png",
+                "",
+                "",
+                "https://example.com",
+            ],
+        ),
+        (
+            "#section",
+            [
+                "https://Example.com:443/docs/page.html?x=1#section",
+                "https:",
+                "example.com",
+                "example.com",
+                "",
+                "/docs/page.html",
+                "?x=1",
+                "#section",
+                "https://example.com",
+            ],
+        ),
+        (
+            "//cdn.test:80",
+            [
+                "https://cdn.test:80",
+                "https:",
+                "cdn.test:80",
+                "cdn.test",
+                "80",
+                "/",
+                "",
+                "",
+                "https://cdn.test:80",
+            ],
+        ),
+        (
+            "mailto:someone@example.com?subject=Hi",
+            [
+                "mailto:someone@example.com?subject=Hi",
+                "mailto:",
+                "",
+                "",
+                "",
+                "someone@example.com",
+                "?subject=Hi",
+                "",
+                "null",
+            ],
+        ),
+        (
+            "HTTP://[::1]:0080/",
+            [
+                "http://[::1]:0080/",
+                "http:",
+                "[::1]",
+                "[::1]",
+                "",
+                "/",
+                "",
+                "",
+                "http://[::1]",
+            ],
+        ),
+    ];
+    for (href, expected) in cases {
+        let doc = parse_document_with_url(&format!("<a href='{href}'>x</a>"), PAGE);
+        let a = HTMLAnchorElement::new(doc.links().item(&doc, 0).unwrap());
+        let actual = [
+            a.href(&doc).unwrap(),
+            a.protocol(&doc),
+            a.host(&doc),
+            a.hostname(&doc),
+            a.port(&doc),
+            a.pathname(&doc),
+            a.search(&doc),
+            a.hash(&doc),
+            a.origin(&doc),
+        ];
+        assert_eq!(actual, expected, "{href}");
+    }
+}
+
+#[test]
+fn unresolvable_href_gives_empty_components() {
+    let doc = parse_document("<a href='page.html?q#f'>x</a>");
+    let a = HTMLAnchorElement::new(doc.links().item(&doc, 0).unwrap());
+    assert_eq!(a.href(&doc).as_deref(), Some("page.html?q#f"));
+    assert_eq!(a.protocol(&doc), ":");
+    assert_eq!(a.host(&doc), "");
+    assert_eq!(a.pathname(&doc), "");
+    assert_eq!(a.search(&doc), "");
+    assert_eq!(a.hash(&doc), "");
+    assert_eq!(a.origin(&doc), "");
+}
+
+#[test]
+fn set_href_rel_target_and_text() {
+    let mut doc = parse_document_with_url("<a href=x rel='noopener'>old <b>text</b></a>", PAGE);
+    let a = HTMLAnchorElement::new(doc.links().item(&doc, 0).unwrap());
+    assert_eq!(a.text(&doc), "old text");
+    a.set_href(&mut doc, "/other?y");
+    assert_eq!(a.pathname(&doc), "/other");
+    assert_eq!(a.search(&doc), "?y");
+
+    let rel_list = a.rel_list();
+    assert!(rel_list.contains(&doc, "noopener"));
+    rel_list.add(&mut doc, &["noreferrer"]).unwrap();
+    assert_eq!(a.rel(&doc), "noopener noreferrer");
+    a.set_target(&mut doc, "_blank");
+    assert_eq!(a.target(&doc), "_blank");
+    a.set_text(&mut doc, "new");
+    assert_eq!(a.text(&doc), "new");
+}
+
+#[test]
+fn url_components_need_an_absolute_url() {
+    assert_eq!(UrlComponents::parse("/path"), Err(UrlError::NotAbsolute));
+    let url = UrlComponents::parse("ftp://files.test:21/pub").unwrap();
+    assert_eq!(
+        (url.port.as_str(), url.origin()),
+        ("", "ftp://files.test".to_string())
+    );
+}
@@ -0,0 +1,48 @@
+use brooster_web_parser::dom::arena::NodeArena;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeData;
+use brooster_web_parser::dom::parser::html_parser::{estimate_node_count, parse_document};
+
+#[test]
+fn with_capacity_reserves_node_storage() {
+    let mut arena = NodeArena::with_capacity(64, 0);
+    assert!(arena.is_empty());
+    assert!(arena.capacity() >= 64);
+    let capacity = arena.capacity();
+    for _ in 0..64 {
+        arena.new_node(NodeData::Comment(String::new()));
+    }
+    assert_eq!(arena.len(), 64);
+    assert_eq!(arena.capacity(), capacity);
+}
+
+#[test]
+fn with_capacity_reserves_text_nodes_between_elements() {
+    // fewer characters than gaps between elements: at most one text node per character
+    assert!(NodeArena::with_capacity(10, 3).capacity() >= 13);
+    // plenty of text: at most one text node before, between and after the elements
+    assert!(NodeArena::with_capacity(10, 1000).capacity() >= 21);
+}
+
+#[test]
+fn document_with_capacity_holds_the_document_node() {
+    let doc = Document::with_capacity(32, 0);
+    assert_eq!(doc.arena().len(), 1);
+    assert!(doc.arena().capacity() >= 32);
+}
+
+#[test]
+fn estimate_is_one_node_per_ten_bytes() {
+    assert_eq!(estimate_node_count(0), 0);
+    assert_eq!(estimate_node_count(9), 0);
+    assert_eq!(estimate_node_count(1000), 100);
+}
+
+#[test]
+fn parse_document_sizes_the_arena_from_the_input() {
+    let html = "<ul>".to_string() + &"<li>item</li>".repeat(200) + "</ul>";
+    let doc = parse_document(&html);
+    assert!(doc.arena().capacity() >= estimate_node_count(html.len()));
+    // 200 items, their text, and ul, html, head, body and the document
+    assert_eq!(doc.arena().len(), 405);
+}
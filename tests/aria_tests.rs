@@ -0,0 +1,39 @@
+use brooster_web_parser::dom::aria::AriaRole;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+#[test]
+fn aria_attributes_are_read_from_elements() {
+    let doc = parse_document(
+        r#"<div id=d role="presentation Tab button" aria-label="Close" aria-hidden=" TRUE " aria-describedby="a b"></div><span id=s role=unknown aria-hidden=maybe></span>"#,
+    );
+    let div = doc.element(doc.get_element_by_id("d").unwrap()).unwrap();
+    assert_eq!(div.aria_role(), Some(AriaRole::Tab));
+    assert_eq!(div.aria_label(), Some("Close"));
+    assert_eq!(div.aria_hidden(), Some(true));
+    assert_eq!(div.aria_describedby(), Some("a b"));
+
+    let span = doc.element(doc.get_element_by_id("s").unwrap()).unwrap();
+    assert_eq!(span.aria_role(), None);
+    assert_eq!(span.aria_label(), None);
+    assert_eq!(span.aria_hidden(), None);
+    assert_eq!(
+        AriaRole::from_token("MenuItemCheckbox").map(|r| r.as_str()),
+        Some("menuitemcheckbox")
+    );
+}
+
+#[test]
+fn aria_hidden_applies_to_the_whole_subtree() {
+    let doc = parse_document(
+        r#"<nav id=n aria-hidden=true><ul><li id=item aria-hidden=false>x</li></ul></nav><p id=p>y</p>"#,
+    );
+    let arena = doc.arena();
+    let hidden = |id: &str| {
+        arena
+            .get(doc.get_element_by_id(id).unwrap())
+            .is_aria_hidden_subtree(arena)
+    };
+    assert!(hidden("n"));
+    assert!(hidden("item"));
+    assert!(!hidden("p"));
+}
@@ -0,0 +1,228 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    tokenizer.tokens().to_vec()
+}
+
+fn start_tag(tag_name: &str, self_closing: bool, attributes: &[(&str, &str)]) -> Token {
+    Token::StartTag {
+        tag_name: tag_name.into(),
+        self_closing,
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| ((*name).into(), value.to_string()))
+            .collect(),
+        raw_attributes: Vec::new(),
+    }
+}
+
+#[test]
+fn double_quoted_value() {
+    assert_eq!(
+        tokenize(r#"<a href="/home">"#),
+        vec![start_tag("a", false, &[("href", "/home")]), Token::EOF]
+    );
+}
+
+#[test]
+fn single_quoted_value() {
+    assert_eq!(
+        tokenize("<a href='/home'>"),
+        vec![start_tag("a", false, &[("href", "/home")]), Token::EOF]
+    );
+}
+
+#[test]
+fn unquoted_value() {
+    assert_eq!(
+        tokenize("<a href=/home>"),
+        vec![start_tag("a", false, &[("href", "/home")]), Token::EOF]
+    );
+}
+
+#[test]
+fn mixed_quoting_styles_keep_source_order() {
+    assert_eq!(
+        tokenize(r#"<div id="main" class='wide' title=x>"#),
+        vec![
+            start_tag("div", false, &[("id", "main"), ("class", "wide"), ("title", "x")]),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn value_with_named_entity() {
+    assert_eq!(
+        tokenize(r#"<a title="Tom &amp; Jerry">"#),
+        vec![start_tag("a", false, &[("title", "Tom & Jerry")]), Token::EOF]
+    );
+}
+
+#[test]
+fn value_with_numeric_entities() {
+    assert_eq!(
+        tokenize(r#"<a title="&#65;&#x42;">"#),
+        vec![start_tag("a", false, &[("title", "AB")]), Token::EOF]
+    );
+}
+
+#[test]
+fn legacy_entity_followed_by_alphanumeric_is_not_decoded_in_attribute() {
+    assert_eq!(
+        tokenize(r#"<a href="?a=1&copy=2">"#),
+        vec![start_tag("a", false, &[("href", "?a=1&copy=2")]), Token::EOF]
+    );
+}
+
+#[test]
+fn boolean_attribute_without_value() {
+    assert_eq!(
+        tokenize("<input disabled>"),
+        vec![start_tag("input", false, &[("disabled", "")]), Token::EOF]
+    );
+}
+
+#[test]
+fn boolean_attribute_between_valued_attributes() {
+    assert_eq!(
+        tokenize(r#"<input type="checkbox" checked name=agree>"#),
+        vec![
+            start_tag(
+                "input",
+                false,
+                &[("type", "checkbox"), ("checked", ""), ("name", "agree")]
+            ),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn empty_quoted_value() {
+    assert_eq!(
+        tokenize(r#"<img alt="">"#),
+        vec![start_tag("img", false, &[("alt", "")]), Token::EOF]
+    );
+}
+
+#[test]
+fn duplicate_attribute_keeps_first_value() {
+    assert_eq!(
+        tokenize(r#"<p id="first" id="second">"#),
+        vec![start_tag("p", false, &[("id", "first")]), Token::EOF]
+    );
+}
+
+#[test]
+fn duplicate_boolean_attribute_is_dropped() {
+    assert_eq!(
+        tokenize("<input disabled disabled readonly>"),
+        vec![
+            start_tag("input", false, &[("disabled", ""), ("readonly", "")]),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn duplicate_detection_is_case_insensitive() {
+    assert_eq!(
+        tokenize(r#"<p ID="first" id="second">"#),
+        vec![start_tag("p", false, &[("id", "first")]), Token::EOF]
+    );
+}
+
+#[test]
+fn uppercase_names_are_lowercased_but_values_are_not() {
+    assert_eq!(
+        tokenize(r#"<DIV CLASS="Big" Data-X=Y>"#),
+        vec![start_tag("div", false, &[("class", "Big"), ("data-x", "Y")]), Token::EOF]
+    );
+}
+
+#[test]
+fn whitespace_inside_quoted_value_is_preserved() {
+    assert_eq!(
+        tokenize("<div class=\"  a\tb\nc  \">"),
+        vec![start_tag("div", false, &[("class", "  a\tb\nc  ")]), Token::EOF]
+    );
+}
+
+#[test]
+fn whitespace_around_equals_sign() {
+    assert_eq!(
+        tokenize("<div class = \"a\" >"),
+        vec![start_tag("div", false, &[("class", "a")]), Token::EOF]
+    );
+}
+
+#[test]
+fn special_characters_in_values() {
+    assert_eq!(
+        tokenize(r#"<a href="/search?q=a<b>c&amp;d='e'" data-json='{"k":1}'>"#),
+        vec![
+            start_tag(
+                "a",
+                false,
+                &[("href", "/search?q=a<b>c&d='e'"), ("data-json", r#"{"k":1}"#)]
+            ),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn special_characters_in_names() {
+    assert_eq!(
+        tokenize(r#"<div @click="go" :bind=x data-a_b.c="1">"#),
+        vec![
+            start_tag("div", false, &[("@click", "go"), (":bind", "x"), ("data-a_b.c", "1")]),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn self_closing_tag_with_quoted_attribute() {
+    assert_eq!(
+        tokenize(r#"<img src="a.png"/>"#),
+        vec![start_tag("img", true, &[("src", "a.png")]), Token::EOF]
+    );
+}
+
+#[test]
+fn self_closing_tag_with_boolean_attribute() {
+    assert_eq!(
+        tokenize("<input disabled />"),
+        vec![start_tag("input", true, &[("disabled", "")]), Token::EOF]
+    );
+}
+
+#[test]
+fn self_closing_tag_with_unquoted_attribute_needs_space() {
+    // the solidus belongs to the unquoted value when it directly follows it
+    assert_eq!(
+        tokenize("<br class=x/>"),
+        vec![start_tag("br", false, &[("class", "x/")]), Token::EOF]
+    );
+}
+
+#[test]
+fn attributes_do_not_leak_into_following_tags() {
+    assert_eq!(
+        tokenize(r#"<b id="1"><i>"#),
+        vec![
+            start_tag("b", false, &[("id", "1")]),
+            start_tag("i", false, &[]),
+            Token::EOF
+        ]
+    );
+}
+
+#[test]
+fn attribute_value_cut_by_eof_drops_the_tag() {
+    assert_eq!(tokenize(r#"<a href="unterminated"#), vec![Token::EOF]);
+}
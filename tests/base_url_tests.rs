@@ -0,0 +1,50 @@
+use brooster_web_parser::dom::elements::html_anchor_element::HTMLAnchorElement;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+
+#[test]
+fn first_base_with_href_wins() {
+    let doc = parse_document(
+        "<head><base target=_top><base href='https://one.example/a/'>\
+         <base href='https://two.example/'></head><body><base href='https://three.example/'>",
+    );
+    assert_eq!(doc.base_url(), Some("https://one.example/a/"));
+    assert_eq!(doc.get_elements_by_tag_name("base").len(&doc), 4);
+}
+
+#[test]
+fn base_href_resolves_against_the_document_url() {
+    let doc = parse_document_with_url(
+        "<base href='../static/'><base href='/ignored/'><a href='x.png'>a</a>",
+        "https://example.com/docs/page.html",
+    );
+    assert_eq!(doc.base_url(), Some("https://example.com/static/"));
+    let a = doc.get_elements_by_tag_name("a").item(&doc, 0).unwrap();
+    assert_eq!(
+        HTMLAnchorElement::new(a).href(&doc).as_deref(),
+        Some("https://example.com/static/x.png")
+    );
+}
+
+#[test]
+fn base_url_is_frozen_after_parsing() {
+    let mut doc = parse_document("<base href='https://example.com/'>");
+    let base = doc.get_elements_by_tag_name("base").item(&doc, 0).unwrap();
+    doc.set_attribute(base, "href", "https://other.example/");
+    assert_eq!(doc.base_url(), Some("https://example.com/"));
+}
+
+#[test]
+fn base_in_template_is_ignored() {
+    let doc = parse_document(
+        "<head><template><base href='https://template.example/'></template>\
+         <base href='https://example.com/'></head>",
+    );
+    assert_eq!(doc.base_url(), Some("https://example.com/"));
+}
+
+#[test]
+fn document_url_without_base() {
+    let doc = parse_document_with_url("<base target=_blank><p>x", "https://example.com/");
+    assert_eq!(doc.base_url(), Some("https://example.com/"));
+    assert_eq!(parse_document("<p>x").base_url(), None);
+}
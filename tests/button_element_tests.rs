@@ -0,0 +1,96 @@
+mod common;
+
+use brooster_web_parser::dom::elements::html_button_element::HTMLButtonElement;
+use brooster_web_parser::dom::elements::html_form_element::HTMLFormElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn type_is_normalized() {
+    let mut doc = parse_document(
+        "<button id=a></button><button id=b type=RESET></button>\
+         <button id=c type=Button></button><button id=d type=menu></button>",
+    );
+    assert_eq!(
+        HTMLButtonElement::new(by_id(&doc, "a")).r#type(&doc),
+        "submit"
+    );
+    assert_eq!(
+        HTMLButtonElement::new(by_id(&doc, "b")).r#type(&doc),
+        "reset"
+    );
+    assert_eq!(
+        HTMLButtonElement::new(by_id(&doc, "c")).r#type(&doc),
+        "button"
+    );
+    assert_eq!(
+        HTMLButtonElement::new(by_id(&doc, "d")).r#type(&doc),
+        "submit"
+    );
+
+    let a = HTMLButtonElement::new(by_id(&doc, "a"));
+    a.set_type(&mut doc, "reset");
+    assert_eq!(a.r#type(&doc), "reset");
+}
+
+#[test]
+fn disabled_fieldsets_disable_their_buttons() {
+    let doc = parse_document(
+        "<fieldset disabled><legend><button id=legend></button></legend>\
+         <button id=inside></button></fieldset>\
+         <fieldset><button id=enabled></button></fieldset>\
+         <fieldset disabled><legend></legend><legend><button id=second></button></legend>\
+         </fieldset>",
+    );
+    let inside = HTMLButtonElement::new(by_id(&doc, "inside"));
+    assert!(!inside.disabled(&doc));
+    assert!(inside.is_disabled(&doc));
+    assert!(!HTMLButtonElement::new(by_id(&doc, "legend")).is_disabled(&doc));
+    assert!(!HTMLButtonElement::new(by_id(&doc, "enabled")).is_disabled(&doc));
+    assert!(HTMLButtonElement::new(by_id(&doc, "second")).is_disabled(&doc));
+}
+
+#[test]
+fn reflected_attributes() {
+    let mut doc = parse_document("<button id=b name=go value=1></button>");
+    let b = HTMLButtonElement::new(by_id(&doc, "b"));
+    assert_eq!(
+        (b.name(&doc), b.value(&doc)),
+        ("go".to_string(), "1".to_string())
+    );
+    b.set_value(&mut doc, "2");
+    b.set_name(&mut doc, "stop");
+    b.set_disabled(&mut doc, true);
+    assert_eq!(doc.get_attribute(b.node(), "value"), Some("2"));
+    assert_eq!(doc.get_attribute(b.node(), "name"), Some("stop"));
+    assert!(b.disabled(&doc) && b.is_disabled(&doc));
+}
+
+#[test]
+fn only_enabled_submit_buttons_validate() {
+    let doc = parse_document(
+        "<button id=submit></button><button id=reset type=reset></button>\
+         <button id=plain type=button></button><button id=off disabled></button>",
+    );
+    assert!(HTMLButtonElement::new(by_id(&doc, "submit")).will_validate(&doc));
+    assert!(!HTMLButtonElement::new(by_id(&doc, "reset")).will_validate(&doc));
+    assert!(!HTMLButtonElement::new(by_id(&doc, "plain")).will_validate(&doc));
+    assert!(!HTMLButtonElement::new(by_id(&doc, "off")).will_validate(&doc));
+}
+
+#[test]
+fn buttons_are_listed_in_their_form() {
+    let doc = parse_document(
+        "<form id=f><input id=i><button id=inside></button></form>\
+         <button id=outside form=f></button>",
+    );
+    let form = HTMLFormElement::new(doc.get_element_by_id("f").unwrap());
+    let inside = HTMLButtonElement::new(by_id(&doc, "inside"));
+    assert_eq!(inside.form(&doc).map(|form| form.node()), Some(form.node()));
+    assert_eq!(form.length(&doc), 3);
+    assert_eq!(form.item(&doc, 1), Some(inside.node()));
+    assert_eq!(
+        form.named_item(&doc, "outside"),
+        Some(HTMLButtonElement::new(by_id(&doc, "outside")).node())
+    );
+}
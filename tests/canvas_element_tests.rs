@@ -0,0 +1,71 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_canvas_element::{ContextKind, HTMLCanvasElement};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use common::nth;
+
+fn canvas(doc: &Document, index: usize) -> HTMLCanvasElement {
+    HTMLCanvasElement::new(nth(doc, "canvas", index))
+}
+
+#[test]
+fn missing_or_invalid_dimensions_use_the_defaults() {
+    let doc = parse_document(
+        "<canvas></canvas><canvas width=\"abc\" height=\"-5\"></canvas>\
+         <canvas width=\" 640px\" height=\"+480\"></canvas>",
+    );
+    assert_eq!(
+        (canvas(&doc, 0).width(&doc), canvas(&doc, 0).height(&doc)),
+        (300, 150)
+    );
+    assert_eq!(
+        (canvas(&doc, 1).width(&doc), canvas(&doc, 1).height(&doc)),
+        (300, 150)
+    );
+    assert_eq!(
+        (canvas(&doc, 2).width(&doc), canvas(&doc, 2).height(&doc)),
+        (640, 480)
+    );
+}
+
+#[test]
+fn zero_is_a_valid_dimension() {
+    let doc = parse_document(
+        "<canvas width=\"0\" height=\"0\"></canvas><canvas width=\"-0\" height=\" -0\"></canvas>",
+    );
+    assert_eq!(canvas(&doc, 0).width(&doc), 0);
+    assert_eq!(canvas(&doc, 0).height(&doc), 0);
+    // negative zero is not negative
+    assert_eq!(canvas(&doc, 1).width(&doc), 0);
+    assert_eq!(canvas(&doc, 1).height(&doc), 0);
+}
+
+#[test]
+fn setters_rewrite_the_attributes() {
+    let mut doc = parse_document("<canvas width=\"abc\"></canvas>");
+    let element = canvas(&doc, 0);
+    element.set_width(&mut doc, 800);
+    element.set_height(&mut doc, 600);
+    assert_eq!(
+        serialize_node_to_string(&doc, element.node()),
+        "<canvas width=\"800\" height=\"600\"></canvas>"
+    );
+    element.set_width(&mut doc, u32::MAX);
+    assert_eq!(doc.get_attribute(element.node(), "width"), Some("300"));
+    assert_eq!(element.width(&doc), 300);
+}
+
+#[test]
+fn get_context_is_a_placeholder() {
+    let mut doc = parse_document("<canvas></canvas>");
+    let element = canvas(&doc, 0);
+    assert_eq!(ContextKind::from_id("2d"), Some(ContextKind::TwoD));
+    assert!(matches!(
+        element.get_context(&mut doc, "2d"),
+        Err(DomError::NotSupported)
+    ));
+    assert!(matches!(element.get_context(&mut doc, "2D"), Ok(None)));
+}
@@ -0,0 +1,63 @@
+use brooster_web_parser::dom::entities::longest_entity_match;
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+fn text(input: &str) -> String {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    tokenizer
+        .tokens()
+        .iter()
+        .filter_map(|token| match token {
+            Token::Character { data } => Some(*data),
+            _ => None,
+        })
+        .collect()
+}
+
+fn attribute_value(input: &str) -> String {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    match &tokenizer.tokens()[0] {
+        Token::StartTag { attributes, .. } => attributes[0].1.clone(),
+        token => panic!("expected a start tag, got {token:?}"),
+    }
+}
+
+#[test]
+fn longest_name_wins() {
+    assert_eq!(text("&notin;"), "\u{2209}");
+    assert_eq!(text("&sup2;&sup;"), "\u{B2}\u{2283}");
+    assert_eq!(text("&CounterClockwiseContourIntegral;"), "\u{2233}");
+}
+
+#[test]
+fn backs_off_to_a_shorter_historical_name() {
+    assert_eq!(text("&notit;"), "\u{AC}it;");
+    assert_eq!(text("&amp1"), "&1");
+    assert_eq!(text("&sup2x"), "\u{B2}x");
+}
+
+#[test]
+fn names_without_semicolon_match_only_historical_entities() {
+    // `sup` without semicolon is not historical, `sup1` is
+    assert_eq!(text("&sup x"), "&sup x");
+    assert_eq!(text("&sup1 x"), "\u{B9} x");
+    assert_eq!(text("&bogus;"), "&bogus;");
+}
+
+#[test]
+fn attributes_keep_ambiguous_references_literal() {
+    assert_eq!(attribute_value("<a href='?a&notx=1'>"), "?a&notx=1");
+    assert_eq!(attribute_value("<a href='?a&not=1'>"), "?a&not=1");
+    assert_eq!(attribute_value("<a title='&not;x'>"), "\u{AC}x");
+}
+
+#[test]
+fn match_reports_the_consumed_length() {
+    let (length, entity) = longest_entity_match(b"notin;rest").unwrap();
+    assert_eq!((length, entity.characters.as_str()), (6, "\u{2209}"));
+    let (length, _) = longest_entity_match(b"notinx").unwrap();
+    assert_eq!(length, 3);
+    assert!(longest_entity_match(b"xyz;").is_none());
+    assert!(longest_entity_match(b";").is_none());
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::node::Element;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_fragment};
+use brooster_web_parser::dom::serializer::serialize_children;
+use common::by_id;
+
+const LIST: &str = "<ul id=list><li id=a>a</li><li id=b>b</li><li id=c>c</li></ul>";
+
+#[test]
+fn outer_html_replaces_an_item_with_two() {
+    let mut doc = parse_document(LIST);
+    let b = by_id(&doc, "b");
+    doc.set_outer_html(b, "<li id=b1>b1</li><li id=b2>b2</li>")
+        .unwrap();
+    let list = by_id(&doc, "list");
+    assert_eq!(
+        serialize_children(&doc, list),
+        "<li id=\"a\">a</li><li id=\"b1\">b1</li><li id=\"b2\">b2</li><li id=\"c\">c</li>"
+    );
+    assert_eq!(doc.get_element_by_id("b"), None);
+    assert_eq!(doc.arena().parent(b), None);
+}
+
+#[test]
+fn outer_html_parses_in_the_parent_context() {
+    let mut doc = parse_document("<table><tbody><tr id=row><td>x</td></tr></tbody></table>");
+    let row = by_id(&doc, "row");
+    doc.set_outer_html(row, "<tr id=new><td>y</td></tr>")
+        .unwrap();
+    let new_row = by_id(&doc, "new");
+    assert_eq!(doc.element(new_row).unwrap().name, "tr");
+}
+
+#[test]
+fn outer_html_rejects_the_document_element_and_detached_nodes() {
+    let mut doc = parse_document(LIST);
+    let html = doc.document_element().unwrap();
+    assert_eq!(
+        doc.set_outer_html(html, "<p>x</p>"),
+        Err(DomError::NoModificationAllowed)
+    );
+    let detached = doc.create_element("p");
+    assert_eq!(
+        doc.set_outer_html(detached, "<p>x</p>"),
+        Err(DomError::NotFound)
+    );
+}
+
+#[test]
+fn inner_html_replaces_children() {
+    let mut doc = parse_document(LIST);
+    let list = by_id(&doc, "list");
+    doc.set_inner_html(list, "<li>only</li>").unwrap();
+    assert_eq!(serialize_children(&doc, list), "<li>only</li>");
+    assert_eq!(doc.get_element_by_id("a"), None);
+}
+
+#[test]
+fn fragment_context_selects_the_tokenizer_state() {
+    let fragment = parse_fragment("<b>&amp;</b>", &Element::new("textarea"));
+    let root = fragment.document_element().unwrap();
+    assert_eq!(
+        serialize_children(&fragment, root),
+        "&lt;b&gt;&amp;&lt;/b&gt;"
+    );
+}
+
+#[test]
+fn before_after_and_replace_with_keep_order() {
+    let mut doc = parse_document(LIST);
+    let [a, b, c] = ["a", "b", "c"].map(|id| by_id(&doc, id));
+    let list = by_id(&doc, "list");
+    let x = doc.create_element("li");
+    let y = doc.create_element("li");
+    doc.before(b, [x, c]).unwrap();
+    assert_eq!(doc.arena().children(list).collect::<Vec<_>>(), [a, x, c, b]);
+    doc.after(a, [b, y]).unwrap();
+    assert_eq!(
+        doc.arena().children(list).collect::<Vec<_>>(),
+        [a, b, y, x, c]
+    );
+    // replacing a node with a list containing itself keeps it in place
+    doc.replace_with(y, [c, y]).unwrap();
+    assert_eq!(
+        doc.arena().children(list).collect::<Vec<_>>(),
+        [a, b, c, y, x]
+    );
+    doc.replace_with(x, Vec::<NodeId>::new()).unwrap();
+    assert_eq!(doc.arena().children(list).collect::<Vec<_>>(), [a, b, c, y]);
+}
+
+#[test]
+fn remove_detaches_and_updates_id_lookup() {
+    let mut doc = parse_document(LIST);
+    let b = by_id(&doc, "b");
+    doc.remove(b);
+    assert_eq!(doc.arena().parent(b), None);
+    assert_eq!(doc.get_element_by_id("b"), None);
+    let list = by_id(&doc, "list");
+    assert_eq!(doc.arena().children(list).count(), 2);
+    // removing again is a no-op
+    doc.remove(b);
+}
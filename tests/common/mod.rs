@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+
+/// The element with the given id
+pub fn by_id(doc: &Document, id: &str) -> NodeId {
+    doc.get_element_by_id(id).unwrap()
+}
+
+/// The first element with the given local name in tree order
+pub fn first(doc: &Document, name: &str) -> NodeId {
+    nth(doc, name, 0)
+}
+
+/// The element with the given local name at `index` in tree order
+pub fn nth(doc: &Document, name: &str, index: usize) -> NodeId {
+    doc.get_elements_by_tag_name(name).item(doc, index).unwrap()
+}
@@ -0,0 +1,105 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use brooster_web_parser::dom::testing::tree_digest;
+
+fn generated_document() -> Document {
+    let mut html = String::new();
+    for section in 0..100 {
+        html.push_str(&format!(
+            "<section id=s{section}><h2>Title {section}</h2><ul>"
+        ));
+        for item in 0..5 {
+            html.push_str(&format!("<li class=i{item}>item <b>{item}</b></li>"));
+        }
+        html.push_str("</ul><!-- end --></section>");
+    }
+    html.push_str("<template><p>inside</p></template>");
+    parse_document(&html)
+}
+
+fn remove_odd_sections(doc: &mut Document) {
+    let sections: Vec<_> = doc.get_elements_by_tag_name("section").iter(doc).collect();
+    for section in sections.into_iter().skip(1).step_by(2) {
+        doc.remove(section);
+    }
+}
+
+#[test]
+fn compact_drops_removed_subtrees() {
+    let mut doc = generated_document();
+    let before = doc.node_count();
+    assert_eq!(doc.allocated_count(), before);
+
+    remove_odd_sections(&mut doc);
+    let after_removal = doc.node_count();
+    assert!(after_removal < before / 2 + 10);
+    assert_eq!(doc.allocated_count(), before);
+
+    let digest = tree_digest(&doc, doc.root());
+    let html = serialize_node_to_string(&doc, doc.root());
+    doc.compact();
+    assert_eq!(doc.allocated_count(), after_removal);
+    assert_eq!(doc.node_count(), after_removal);
+    assert_eq!(tree_digest(&doc, doc.root()), digest);
+    assert_eq!(serialize_node_to_string(&doc, doc.root()), html);
+}
+
+#[test]
+fn relationships_survive_compaction() {
+    let mut doc = generated_document();
+    remove_odd_sections(&mut doc);
+    let items: Vec<_> = doc.get_elements_by_tag_name("li").iter(&doc).collect();
+    let map = doc.compact();
+    let arena = doc.arena();
+    for old in items {
+        let item = map.get(old).unwrap();
+        let list = arena.parent(item).unwrap();
+        assert!(arena.get(list).is_element_named("ul"));
+        assert!(arena.children(list).any(|child| child == item));
+        if let Some(next) = arena.get(item).next_sibling() {
+            assert_eq!(arena.get(next).prev_sibling(), Some(item));
+        }
+        let bold = arena.get(item).last_child().unwrap();
+        assert!(arena.get(bold).is_element_named("b"));
+        assert_eq!(arena.parent(bold), Some(item));
+    }
+    assert_eq!(doc.get_elements_by_tag_name("section").len(&doc), 50);
+    assert!(doc.get_element_by_id("s98").is_some());
+    assert_eq!(doc.get_element_by_id("s99"), None);
+}
+
+#[test]
+fn template_contents_and_the_map() {
+    let mut doc = generated_document();
+    let removed = doc.get_element_by_id("s1").unwrap();
+    let kept = doc.get_element_by_id("s0").unwrap();
+    let detached = doc.create_element("div");
+    doc.remove(removed);
+    let map = doc.compact();
+    assert_eq!(map.get(removed), None);
+    assert_eq!(map.get(detached), None);
+    assert_eq!(map.get(kept), doc.get_element_by_id("s0"));
+    // ids from the new generation are not translated again
+    assert_eq!(map.get(map.get(kept).unwrap()), None);
+
+    let template = doc
+        .get_elements_by_tag_name("template")
+        .item(&doc, 0)
+        .unwrap();
+    let content = doc.template_content(template).unwrap();
+    assert_eq!(
+        serialize_node_to_string(&doc, template),
+        "<template><p>inside</p></template>"
+    );
+    assert_eq!(doc.arena().children(content).count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "compacted")]
+fn stale_ids_are_rejected() {
+    let mut doc = generated_document();
+    let section = doc.get_element_by_id("s0").unwrap();
+    doc.compact();
+    doc.arena().get(section);
+}
@@ -0,0 +1,54 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::dom::parser::tree_constructor::TreeConstructor;
+
+fn construct_until_eof(html: &str) -> TreeConstructor {
+    let mut tokenizer = Tokenizer::new(html.as_bytes());
+    let mut tree_constructor = TreeConstructor::new();
+    loop {
+        let token = tokenizer.next_token();
+        if token == Token::EOF {
+            return tree_constructor;
+        }
+        tree_constructor.process_token(token);
+    }
+}
+
+#[test]
+fn no_current_node_before_the_first_token() {
+    let tree_constructor = TreeConstructor::new();
+    let arena = tree_constructor.document().arena();
+    assert_eq!(tree_constructor.current_node(), None);
+    assert!(tree_constructor.current_element(arena).is_none());
+    assert_eq!(tree_constructor.current_node_tag_name(arena), None);
+}
+
+#[test]
+fn current_node_is_the_top_of_the_stack() {
+    let tree_constructor = construct_until_eof("<div><p>text<b>bold");
+    let arena = tree_constructor.document().arena();
+    let current = tree_constructor.current_node().unwrap();
+    assert_eq!(tree_constructor.current_node_tag_name(arena), Some("b"));
+    assert_eq!(tree_constructor.current_element(arena).unwrap().name, "b");
+    assert_eq!(
+        arena.get(current).as_element(),
+        tree_constructor.current_element(arena)
+    );
+    assert!(tree_constructor.dump_stack().ends_with("  b\n"));
+}
+
+#[test]
+fn end_tags_pop_the_current_node() {
+    let tree_constructor = construct_until_eof("<div><p>text</p>");
+    let arena = tree_constructor.document().arena();
+    assert_eq!(tree_constructor.current_node_tag_name(arena), Some("div"));
+}
+
+#[test]
+fn foreign_tag_names_keep_their_case() {
+    let tree_constructor = construct_until_eof("<svg><foreignObject>");
+    let arena = tree_constructor.document().arena();
+    assert_eq!(
+        tree_constructor.current_node_tag_name(arena),
+        Some("foreignObject")
+    );
+}
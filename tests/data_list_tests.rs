@@ -0,0 +1,66 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_data_list_element::HTMLDataListElement;
+use brooster_web_parser::dom::elements::html_input_element::HTMLInputElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn list_attribute_resolves_to_the_datalist() {
+    let doc = parse_document("<input id=i list=dl><datalist id=dl><option value=a></datalist>");
+    let list = HTMLInputElement::new(by_id(&doc, "i")).list(&doc).unwrap();
+    assert_eq!(list.node(), by_id(&doc, "dl"));
+}
+
+#[test]
+fn options_are_the_option_descendants() {
+    let mut doc = parse_document(
+        "<datalist id=dl><option id=a value=a>A<p>Pick one</p><span><option id=b></span>\
+         <select><option id=c></select></datalist>",
+    );
+    let list = input_with_list(&mut doc, "dl");
+    let options = list.options();
+    let found: Vec<NodeId> = options.iter(&doc).collect();
+    assert_eq!(
+        found,
+        [by_id(&doc, "a"), by_id(&doc, "b"), by_id(&doc, "c")]
+    );
+
+    let extra = doc.create_element("option");
+    doc.append_child(list.node(), extra).unwrap();
+    assert_eq!(options.len(&doc), 4);
+}
+
+fn input_with_list(doc: &mut Document, list: &str) -> HTMLDataListElement {
+    let input = doc.create_element("input");
+    doc.set_attribute(input, "list", list);
+    let body = doc.body().unwrap();
+    doc.append_child(body, input).unwrap();
+    HTMLInputElement::new(input).list(doc).unwrap()
+}
+
+#[test]
+fn dangling_or_wrong_list_references_give_none() {
+    let mut doc = parse_document(
+        "<input id=missing list=nothing><input id=div list=d><input id=none><div id=d></div>",
+    );
+    assert!(HTMLInputElement::new(by_id(&doc, "missing"))
+        .list(&doc)
+        .is_none());
+    assert!(HTMLInputElement::new(by_id(&doc, "div"))
+        .list(&doc)
+        .is_none());
+    assert!(HTMLInputElement::new(by_id(&doc, "none"))
+        .list(&doc)
+        .is_none());
+
+    let datalist = doc.create_element("datalist");
+    doc.set_attribute(datalist, "id", "nothing");
+    let body = doc.body().unwrap();
+    doc.append_child(body, datalist).unwrap();
+    assert!(HTMLInputElement::new(by_id(&doc, "missing"))
+        .list(&doc)
+        .is_some());
+}
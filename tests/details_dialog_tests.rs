@@ -0,0 +1,84 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_details_element::HTMLDetailsElement;
+use brooster_web_parser::dom::elements::html_dialog_element::HTMLDialogElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use common::first;
+use std::sync::{Arc, Mutex};
+
+fn count_events(doc: &mut Document, node: NodeId, type_: &str) -> Arc<Mutex<usize>> {
+    let count = Arc::new(Mutex::new(0));
+    let sink = Arc::clone(&count);
+    doc.add_event_listener(node, type_, false, move |_| *sink.lock().unwrap() += 1);
+    count
+}
+
+#[test]
+fn toggling_details_updates_the_attribute() {
+    let mut doc = parse_document("<details><summary>More</summary>text</details>");
+    let details = HTMLDetailsElement::new(first(&doc, "details"));
+    let toggles = count_events(&mut doc, details.node(), "toggle");
+    assert!(!details.open(&doc));
+
+    assert!(details.toggle(&mut doc));
+    assert_eq!(
+        serialize_node_to_string(&doc, details.node()),
+        "<details open=\"\"><summary>More</summary>text</details>"
+    );
+    assert!(!details.toggle(&mut doc));
+    assert_eq!(
+        serialize_node_to_string(&doc, details.node()),
+        "<details><summary>More</summary>text</details>"
+    );
+    details.set_open(&mut doc, false);
+    assert_eq!(*toggles.lock().unwrap(), 2);
+}
+
+#[test]
+fn show_modal_on_an_open_dialog_fails() {
+    let mut doc = parse_document("<dialog open>hi</dialog>");
+    let dialog = HTMLDialogElement::new(first(&doc, "dialog"));
+    assert_eq!(dialog.show_modal(&mut doc), Err(DomError::InvalidState));
+    dialog.show(&mut doc);
+    assert!(dialog.open(&doc));
+}
+
+#[test]
+fn show_modal_needs_a_connected_dialog() {
+    let mut doc = parse_document("");
+    let detached = HTMLDialogElement::new(doc.create_element("dialog"));
+    assert_eq!(detached.show_modal(&mut doc), Err(DomError::InvalidState));
+
+    let body = doc.body().unwrap();
+    doc.append_child(body, detached.node()).unwrap();
+    detached.show_modal(&mut doc).unwrap();
+    assert_eq!(doc.get_attribute(detached.node(), "open"), Some(""));
+}
+
+#[test]
+fn close_records_the_return_value() {
+    let mut doc = parse_document("<dialog>hi</dialog>");
+    let dialog = HTMLDialogElement::new(first(&doc, "dialog"));
+    let closes = count_events(&mut doc, dialog.node(), "close");
+    assert_eq!(dialog.return_value(&doc), "");
+
+    dialog.show(&mut doc);
+    dialog.close(&mut doc, Some("x"));
+    assert!(!dialog.open(&doc));
+    assert_eq!(dialog.return_value(&doc), "x");
+    assert_eq!(
+        serialize_node_to_string(&doc, dialog.node()),
+        "<dialog>hi</dialog>"
+    );
+
+    // closing again, or without a value, keeps the last value
+    dialog.close(&mut doc, Some("y"));
+    dialog.show(&mut doc);
+    dialog.close(&mut doc, None);
+    assert_eq!(dialog.return_value(&doc), "x");
+    assert_eq!(*closes.lock().unwrap(), 2);
+}
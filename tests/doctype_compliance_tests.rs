@@ -0,0 +1,76 @@
+use brooster_web_parser::dom::doctype::{check_doctype_compliance, DoctypeIssue};
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn issues(html: &str) -> Vec<DoctypeIssue> {
+    check_doctype_compliance(&parse_document(html))
+}
+
+#[test]
+fn html5_doctype_is_compliant() {
+    assert_eq!(issues("<!DOCTYPE html><p>x"), []);
+    assert_eq!(issues("<!doctype HTML><p>x"), []);
+}
+
+#[test]
+fn missing_doctype() {
+    assert_eq!(
+        issues("<p>x"),
+        [DoctypeIssue::MissingDoctype, DoctypeIssue::QuirksMode]
+    );
+    // a document built by hand is in no-quirks mode
+    assert_eq!(
+        check_doctype_compliance(&Document::new()),
+        [DoctypeIssue::MissingDoctype]
+    );
+}
+
+#[test]
+fn html4_doctypes_are_obsolete() {
+    assert_eq!(
+        issues(
+            r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd">"#
+        ),
+        [DoctypeIssue::ObsoleteDoctype]
+    );
+    assert_eq!(
+        issues(r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN">"#),
+        [DoctypeIssue::ObsoleteDoctype, DoctypeIssue::QuirksMode]
+    );
+}
+
+#[test]
+fn xhtml_doctypes_are_obsolete() {
+    assert_eq!(
+        issues(
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">"#
+        ),
+        [
+            DoctypeIssue::ObsoleteDoctype,
+            DoctypeIssue::LimitedQuirksMode
+        ]
+    );
+}
+
+#[test]
+fn unknown_public_identifier() {
+    assert_eq!(
+        issues(r#"<!DOCTYPE html PUBLIC "-//Example//DTD Custom//EN">"#),
+        [DoctypeIssue::UnknownPublicIdentifier(
+            "-//Example//DTD Custom//EN".to_string()
+        )]
+    );
+}
+
+#[test]
+fn system_identifier_present() {
+    assert_eq!(
+        issues(r#"<!DOCTYPE html SYSTEM "about:legacy-compat">"#),
+        [DoctypeIssue::SystemIdentifierPresent]
+    );
+}
+
+#[test]
+fn other_doctype_names_trigger_quirks_mode() {
+    assert_eq!(issues("<!DOCTYPE svg>"), [DoctypeIssue::QuirksMode]);
+}
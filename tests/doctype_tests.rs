@@ -0,0 +1,55 @@
+use brooster_web_parser::dom::node::DocumentType;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+#[test]
+fn parsed_doctype_is_the_first_child() {
+    let doc = parse_document("<!DOCTYPE html><p>x</p>");
+    let doctype = doc.doctype().unwrap();
+    assert_eq!(doc.arena().children(doc.root()).next(), Some(doctype));
+    assert_eq!(
+        doc.arena().get(doctype).as_doctype(),
+        Some(DocumentType {
+            name: "html",
+            public_id: "",
+            system_id: "",
+        })
+    );
+}
+
+#[test]
+fn document_without_doctype() {
+    let doc = parse_document("<p>x</p>");
+    assert_eq!(doc.doctype(), None);
+    let html = doc.document_element().unwrap();
+    assert_eq!(doc.arena().get(html).as_doctype(), None);
+}
+
+#[test]
+fn doctype_can_be_added_to_a_parsed_document() {
+    let mut doc = parse_document("<p>x</p>");
+    let doctype = doc.create_doctype("html", "", "");
+    let html = doc.document_element();
+    doc.insert_before(doc.root(), doctype, html).unwrap();
+    assert_eq!(doc.doctype(), Some(doctype));
+    assert!(serialize_children(&doc, doc.root()).starts_with("<!DOCTYPE html><html>"));
+}
+
+#[test]
+fn system_only_doctype_round_trips() {
+    let source = "<!DOCTYPE html SYSTEM \"about:legacy-compat\">";
+    let doc = parse_document(source);
+    let doctype = doc
+        .arena()
+        .get(doc.doctype().unwrap())
+        .as_doctype()
+        .unwrap();
+    assert_eq!(
+        (doctype.public_id, doctype.system_id),
+        ("", "about:legacy-compat")
+    );
+    let html = serialize_children(&doc, doc.root());
+    assert!(html.starts_with(source), "{html}");
+    let reparsed = parse_document(&html);
+    assert_eq!(serialize_children(&reparsed, reparsed.root()), html);
+}
@@ -0,0 +1,113 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+#[test]
+fn accessors_follow_the_parsed_structure() {
+    let doc = parse_document("<!DOCTYPE html><title>x</title><p>y");
+    let html = doc.document_element().unwrap();
+    assert!(doc.arena().get(html).is_element_named("html"));
+    assert!(doc
+        .arena()
+        .get(doc.head().unwrap())
+        .is_element_named("head"));
+    assert!(doc
+        .arena()
+        .get(doc.body().unwrap())
+        .is_element_named("body"));
+    assert_eq!(Document::new().document_element(), None);
+    assert_eq!(Document::new().body(), None);
+}
+
+#[test]
+fn title_is_the_collapsed_child_text_only() {
+    let mut doc = parse_document("<title>  Hello \n\t world  </title>");
+    assert_eq!(doc.title(), "Hello world");
+
+    let title = doc.get_elements_by_tag_name("title").item(&doc, 0).unwrap();
+    let bold = doc.create_element("b");
+    doc.append_child(title, bold).unwrap();
+    let nested = doc.create_text_node("ignored");
+    doc.append_child(bold, nested).unwrap();
+    let tail = doc.create_text_node(" again ");
+    doc.append_child(title, tail).unwrap();
+    assert_eq!(doc.title(), "Hello world again");
+
+    assert_eq!(parse_document("<p>no title").title(), "");
+}
+
+#[test]
+fn set_title_replaces_or_creates_the_title() {
+    let mut doc = parse_document("<title>old <b></b></title>");
+    doc.set_title("new");
+    assert_eq!(doc.title(), "new");
+    assert_eq!(
+        serialize_children(&doc, doc.head().unwrap()),
+        "<title>new</title>"
+    );
+
+    let mut doc = Document::new();
+    let root = doc.root();
+    let html = doc.create_element("html");
+    doc.append_child(root, html).unwrap();
+    let body = doc.create_element("body");
+    doc.append_child(html, body).unwrap();
+    doc.set_title("Created");
+    assert_eq!(
+        serialize_children(&doc, root),
+        "<html><head><title>Created</title></head><body></body></html>"
+    );
+    assert_eq!(doc.title(), "Created");
+}
+
+#[test]
+fn set_title_without_an_html_element_does_nothing() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let div = doc.create_element("div");
+    doc.append_child(root, div).unwrap();
+    doc.set_title("ignored");
+    assert_eq!(serialize_children(&doc, root), "<div></div>");
+}
+
+#[test]
+fn body_of_a_frameset_document_is_the_frameset() {
+    let doc = parse_document("<!DOCTYPE html><frameset><frame></frameset>");
+    assert!(doc
+        .arena()
+        .get(doc.body().unwrap())
+        .is_element_named("frameset"));
+}
+
+#[test]
+fn title_outside_the_head_is_ignored() {
+    let mut doc =
+        parse_document("<head></head><body><svg><title>svg</title></svg><title>late</title>");
+    assert_eq!(doc.title(), "");
+
+    doc.set_title("head title");
+    let titles = doc.get_elements_by_tag_name("title");
+    assert_eq!(titles.len(&doc), 3);
+    assert_eq!(doc.title(), "head title");
+    assert_eq!(
+        serialize_children(&doc, doc.head().unwrap()),
+        "<title>head title</title>"
+    );
+}
+
+#[test]
+fn set_title_reuses_the_existing_title_element() {
+    let mut doc = parse_document("<title>one</title>");
+    let title = doc.get_elements_by_tag_name("title").item(&doc, 0).unwrap();
+    doc.set_title("two");
+    assert_eq!(
+        doc.get_elements_by_tag_name("title").item(&doc, 0),
+        Some(title)
+    );
+    assert_eq!(doc.arena().children(title).count(), 1);
+    assert_eq!(doc.title(), "two");
+
+    doc.set_title("");
+    assert_eq!(doc.arena().children(title).count(), 0);
+    assert_eq!(doc.title(), "");
+}
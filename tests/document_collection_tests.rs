@@ -0,0 +1,57 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+const PAGE: &str = "<!DOCTYPE html>\
+    <head><script src=a.js></script></head>\
+    <body>\
+    <form id=search><input name=q></form>\
+    <form name=login></form>\
+    <img src=1.png><p><img src=2.png></p>\
+    <a href=/home>home</a><a name=anchor>no href</a>\
+    <map><area href=/x></map>\
+    <embed src=movie.swf>\
+    <svg><script></script><a href=#s></a></svg>\
+    <script>inline()</script>\
+    </body>";
+
+#[test]
+fn collections_follow_their_filters() {
+    let doc = parse_document(PAGE);
+    assert_eq!(doc.forms().len(&doc), 2);
+    assert_eq!(doc.images().len(&doc), 2);
+    assert_eq!(doc.links().len(&doc), 2);
+    assert_eq!(doc.scripts().len(&doc), 2);
+    assert_eq!(doc.embeds().len(&doc), 1);
+}
+
+#[test]
+fn links_exclude_anchors_without_href() {
+    let doc = parse_document(PAGE);
+    let hrefs: Vec<&str> = doc
+        .links()
+        .iter(&doc)
+        .map(|link| doc.get_attribute(link, "href").unwrap())
+        .collect();
+    assert_eq!(hrefs, ["/home", "/x"]);
+}
+
+#[test]
+fn forms_named_item_matches_id_or_name() {
+    let doc = parse_document(PAGE);
+    let forms = doc.forms();
+    assert_eq!(forms.named_item(&doc, "search"), forms.item(&doc, 0));
+    assert_eq!(forms.named_item(&doc, "login"), forms.item(&doc, 1));
+    assert_eq!(forms.named_item(&doc, "q"), None);
+}
+
+#[test]
+fn images_reflect_later_appends() {
+    let mut doc: Document = parse_document(PAGE);
+    let images = doc.images();
+    assert_eq!(images.len(&doc), 2);
+    let body = doc.body().unwrap();
+    let img = doc.create_element("img");
+    doc.append_child(body, img).unwrap();
+    assert_eq!(images.len(&doc), 3);
+    assert_eq!(images.item(&doc, 2), Some(img));
+}
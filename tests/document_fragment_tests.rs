@@ -0,0 +1,68 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+
+fn append_element(doc: &mut Document, parent: NodeId, name: &str) -> NodeId {
+    let element = doc.create_element(name);
+    doc.append_child(parent, element).unwrap();
+    element
+}
+
+fn children(doc: &Document, parent: NodeId) -> Vec<NodeId> {
+    doc.arena().children(parent).collect()
+}
+
+#[test]
+fn inserting_a_fragment_splices_its_children_in_order() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let list = append_element(&mut doc, root, "ul");
+    let first = append_element(&mut doc, list, "li");
+    let last = append_element(&mut doc, list, "li");
+
+    let fragment = doc.create_document_fragment();
+    let a = append_element(&mut doc, fragment, "li");
+    let b = doc.create_text_node("b");
+    doc.append_child(fragment, b).unwrap();
+    let c = append_element(&mut doc, fragment, "li");
+
+    assert_eq!(doc.insert_before(list, fragment, Some(last)), Ok(fragment));
+    assert_eq!(children(&doc, list), vec![first, a, b, c, last]);
+    assert!(children(&doc, fragment).is_empty());
+    assert_eq!(doc.arena().parent(fragment), None);
+    for node in [a, b, c] {
+        assert_eq!(doc.arena().parent(node), Some(list));
+    }
+}
+
+#[test]
+fn inserting_an_empty_fragment_is_a_no_op() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let div = append_element(&mut doc, root, "div");
+    let span = append_element(&mut doc, div, "span");
+    let fragment = doc.create_document_fragment();
+
+    doc.insert_before(div, fragment, Some(span)).unwrap();
+    doc.append_child(div, fragment).unwrap();
+    assert_eq!(children(&doc, div), vec![span]);
+    assert_eq!(doc.arena().parent(fragment), None);
+}
+
+#[test]
+fn replacing_with_a_fragment_moves_its_children_into_place() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let div = append_element(&mut doc, root, "div");
+    let before = append_element(&mut doc, div, "i");
+    let old = append_element(&mut doc, div, "b");
+    let after = append_element(&mut doc, div, "u");
+
+    let fragment = doc.create_document_fragment();
+    let x = append_element(&mut doc, fragment, "em");
+    let y = append_element(&mut doc, fragment, "strong");
+
+    assert_eq!(doc.replace_child(div, fragment, old), Ok(old));
+    assert_eq!(children(&doc, div), vec![before, x, y, after]);
+    assert!(children(&doc, fragment).is_empty());
+    assert_eq!(doc.arena().parent(old), None);
+}
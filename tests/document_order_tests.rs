@@ -0,0 +1,46 @@
+mod common;
+
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+use std::cmp::Ordering;
+
+#[test]
+fn compares_ancestors_siblings_and_cousins() {
+    let doc = parse_document(
+        "<div id=a><p id=b><span id=c></span></p><p id=d><span id=e></span></p></div>",
+    );
+    let arena = doc.arena();
+    let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|id| by_id(&doc, id));
+    assert_eq!(arena.document_order(a, c), Ordering::Less);
+    assert_eq!(arena.document_order(c, a), Ordering::Greater);
+    assert_eq!(arena.document_order(b, d), Ordering::Less);
+    assert_eq!(arena.document_order(c, d), Ordering::Less);
+    assert_eq!(arena.document_order(e, c), Ordering::Greater);
+    assert_eq!(arena.document_order(e, e), Ordering::Equal);
+}
+
+#[test]
+fn sorts_nodes_moved_after_creation() {
+    let mut doc = parse_document("<div id=a></div><div id=b></div>");
+    let (a, b) = (by_id(&doc, "a"), by_id(&doc, "b"));
+    // created last, inserted first: arena order differs from tree order
+    let first = doc.create_element("p");
+    doc.insert_before(doc.body().unwrap(), first, Some(a))
+        .unwrap();
+    let mut nodes = vec![b, a, first];
+    doc.arena().sort_by_document_order(&mut nodes);
+    assert_eq!(nodes, [first, a, b]);
+}
+
+#[test]
+fn disconnected_nodes_have_a_consistent_order() {
+    let mut doc = parse_document("<p id=a></p>");
+    let a = by_id(&doc, "a");
+    let detached = doc.create_element("div");
+    let arena = doc.arena();
+    assert_eq!(
+        arena.document_order(a, detached),
+        arena.document_order(detached, a).reverse()
+    );
+    assert_ne!(arena.document_order(a, detached), Ordering::Equal);
+}
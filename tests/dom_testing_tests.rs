@@ -0,0 +1,69 @@
+use brooster_web_parser::assert_dom_eq;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::testing::{compare_dom, tree_digest, DomCompareOptions};
+use std::panic;
+
+#[test]
+fn identical_trees_pass() {
+    let left = parse_document("<!DOCTYPE html><div id=a class=b>x<!--c--></div>");
+    let right = parse_document("<!DOCTYPE html><div class=b id=a>x<!--c--></div>");
+    assert_dom_eq!(left, right);
+    assert_eq!(
+        compare_dom(&left, &right, DomCompareOptions { ordered_attributes: true }),
+        Err("html > body > div: attribute order [id, class] != [class, id]".to_string())
+    );
+}
+
+#[test]
+fn attribute_difference_reports_the_path() {
+    let left = parse_document("<div></div><div class=a></div>");
+    let right = parse_document("<div></div><div class=b></div>");
+    let message = panic::catch_unwind(|| assert_dom_eq!(left, right))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(
+        message.starts_with("DOM trees differ at html > body > div[2]: attribute class \"a\" != \"b\""),
+        "{message}"
+    );
+}
+
+#[test]
+fn structural_differences_are_described() {
+    let options = DomCompareOptions::default();
+    let compare = |a: &str, b: &str| compare_dom(&parse_document(a), &parse_document(b), options);
+    assert_eq!(
+        compare("<p>a", "<p>b"),
+        Err("html > body > p > #text: text \"a\" != \"b\"".to_string())
+    );
+    assert_eq!(
+        compare("<p>a", "<p>a<b>"),
+        Err("html > body > p: extra child b on the right".to_string())
+    );
+    assert_eq!(
+        compare("<p>", "<span>"),
+        Err("html > body > p: name \"p\" != \"span\"".to_string())
+    );
+}
+
+#[test]
+fn tree_digest_uses_the_html5lib_format() {
+    let doc = parse_document(
+        "<!DOCTYPE html><p id=x class=y>Hi<!-- c --></p><svg viewBox='0 0 1 1' xlink:href=#a></svg>",
+    );
+    assert_eq!(
+        tree_digest(&doc, doc.root()),
+        "| <!DOCTYPE html>\n\
+         | <html>\n\
+         |   <head>\n\
+         |   <body>\n\
+         |     <p>\n\
+         |       class=\"y\"\n\
+         |       id=\"x\"\n\
+         |       \"Hi\"\n\
+         |       <!--  c  -->\n\
+         |     <svg svg>\n\
+         |       viewBox=\"0 0 1 1\"\n\
+         |       xlink href=\"#a\"\n"
+    );
+}
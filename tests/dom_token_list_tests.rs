@@ -0,0 +1,59 @@
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+#[test]
+fn class_list_reads_the_class_attribute() {
+    let doc = parse_document("<p id=p class='  one two\tone '></p>");
+    let list = doc.class_list(doc.get_element_by_id("p").unwrap());
+    assert_eq!(list.tokens(&doc), ["one", "two"]);
+    assert_eq!(list.len(&doc), 2);
+    assert_eq!(list.item(&doc, 1).as_deref(), Some("two"));
+    assert_eq!(list.item(&doc, 2), None);
+    assert_eq!(list.value(&doc), "  one two\tone ");
+}
+
+#[test]
+fn add_remove_and_serialize() {
+    let mut doc = parse_document("<p id=p class='a  b a'></p>");
+    let p = doc.get_element_by_id("p").unwrap();
+    let list = doc.class_list(p);
+    list.add(&mut doc, &["c", "a"]).unwrap();
+    assert_eq!(doc.get_attribute(p, "class"), Some("a b c"));
+    list.remove(&mut doc, &["a", "missing"]).unwrap();
+    assert_eq!(doc.get_attribute(p, "class"), Some("b c"));
+
+    assert_eq!(list.add(&mut doc, &["ok", ""]), Err(DomError::Syntax));
+    assert_eq!(
+        list.remove(&mut doc, &["a b"]),
+        Err(DomError::InvalidCharacter)
+    );
+    assert_eq!(doc.get_attribute(p, "class"), Some("b c"));
+}
+
+#[test]
+fn empty_updates_do_not_create_the_attribute() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = doc.get_element_by_id("p").unwrap();
+    let list = doc.class_list(p);
+    list.remove(&mut doc, &["x"]).unwrap();
+    assert_eq!(doc.get_attribute(p, "class"), None);
+    list.add(&mut doc, &[]).unwrap();
+    assert_eq!(doc.get_attribute(p, "class"), None);
+}
+
+#[test]
+fn toggle_and_replace() {
+    let mut doc = parse_document("<p id=p class='a b c'></p>");
+    let p = doc.get_element_by_id("p").unwrap();
+    let list = doc.class_list(p);
+    assert_eq!(list.toggle(&mut doc, "b", None), Ok(false));
+    assert_eq!(list.toggle(&mut doc, "d", None), Ok(true));
+    assert_eq!(list.toggle(&mut doc, "d", Some(true)), Ok(true));
+    assert_eq!(list.toggle(&mut doc, "e", Some(false)), Ok(false));
+    assert_eq!(doc.get_attribute(p, "class"), Some("a c d"));
+
+    assert_eq!(list.replace(&mut doc, "c", "a"), Ok(true));
+    assert_eq!(doc.get_attribute(p, "class"), Some("a d"));
+    assert_eq!(list.replace(&mut doc, "x", "y"), Ok(false));
+    assert_eq!(list.replace(&mut doc, "a", ""), Err(DomError::Syntax));
+}
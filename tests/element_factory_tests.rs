@@ -0,0 +1,54 @@
+mod common;
+
+use brooster_web_parser::dom::elements::element_factory::{html_element_for, ElementKind};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::first;
+
+#[test]
+fn parsed_elements_record_their_kind() {
+    let doc = parse_document(
+        "<select></select><x-foo></x-foo><blink></blink><div></div><h2></h2>\
+         <font-face></font-face><svg><title></title></svg>",
+    );
+    let kind = |name| first(&doc, name).element_kind(&doc);
+    assert_eq!(kind("select"), Some(ElementKind::Select));
+    assert_eq!(kind("x-foo"), Some(ElementKind::Custom));
+    assert_eq!(kind("blink"), Some(ElementKind::Unknown));
+    assert_eq!(kind("div"), Some(ElementKind::Generic));
+    assert_eq!(kind("h2"), Some(ElementKind::Heading));
+    assert_eq!(kind("font-face"), Some(ElementKind::Unknown));
+    assert_eq!(kind("svg"), Some(ElementKind::Foreign));
+    assert_eq!(doc.root().element_kind(&doc), None);
+}
+
+#[test]
+fn created_elements_record_their_kind() {
+    let mut doc = parse_document("");
+    let textarea = doc.create_element("TEXTAREA");
+    let custom = doc.create_element("my-widget");
+    assert_eq!(textarea.element_kind(&doc), Some(ElementKind::TextArea));
+    assert_eq!(custom.element_kind(&doc), Some(ElementKind::Custom));
+    assert_eq!(html_element_for("td"), ElementKind::TableCell);
+    assert_eq!(html_element_for("th"), ElementKind::TableCell);
+    assert_eq!(html_element_for("applet"), ElementKind::Unknown);
+}
+
+#[test]
+fn downcasts_return_the_typed_wrapper() {
+    let doc = parse_document("<select size=\"3\"></select><h3>x</h3><template></template>");
+    let select = first(&doc, "select").as_select(&doc).unwrap();
+    assert_eq!(select.size(&doc), 3);
+    assert_eq!(first(&doc, "h3").as_heading(&doc).unwrap().level(), 3);
+    assert!(first(&doc, "template").as_template(&doc).is_some());
+}
+
+#[test]
+fn downcast_mismatch_returns_none() {
+    let doc = parse_document("<select></select><p>text</p>");
+    let select = first(&doc, "select");
+    assert!(select.as_input(&doc).is_none());
+    assert!(select.as_heading(&doc).is_none());
+    assert!(first(&doc, "p").as_select(&doc).is_none());
+    let text = doc.arena().children(first(&doc, "p")).next().unwrap();
+    assert!(text.as_select(&doc).is_none());
+}
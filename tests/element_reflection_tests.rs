@@ -0,0 +1,39 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+#[test]
+fn set_id_updates_get_element_by_id() {
+    let mut doc = parse_document("<div id=old></div>");
+    let div = doc.get_element_by_id("old").unwrap();
+    assert_eq!(doc.element(div).unwrap().id(), "old");
+
+    doc.element_mut(div).unwrap().set_id("new");
+    assert_eq!(doc.get_element_by_id("old"), None);
+    assert_eq!(doc.get_element_by_id("new"), Some(div));
+}
+
+#[test]
+fn set_class_name_is_seen_by_class_queries() {
+    let mut doc = parse_document("<p>x</p>");
+    let p = doc.get_elements_by_tag_name("p").item(&doc, 0).unwrap();
+    assert_eq!(doc.element(p).unwrap().class_name(), "");
+
+    doc.element_mut(p).unwrap().set_class_name("a b");
+    assert_eq!(doc.element(p).unwrap().class_name(), "a b");
+    assert!(doc.element(p).unwrap().has_class("a"));
+    assert!(doc.element(p).unwrap().has_class("b"));
+    assert_eq!(doc.get_elements_by_class_name("a").item(&doc, 0), Some(p));
+    assert_eq!(doc.get_elements_by_class_name("b a").item(&doc, 0), Some(p));
+}
+
+#[test]
+fn clearing_id_or_class_name_removes_the_attribute() {
+    let mut doc = parse_document("<div id=x class=y></div>");
+    let div = doc.get_element_by_id("x").unwrap();
+    let element = doc.element_mut(div).unwrap();
+    element.set_id("");
+    element.set_class_name("");
+    assert!(!element.has_attribute("id"));
+    assert!(!element.has_attribute("class"));
+    assert_eq!(element.id(), "");
+    assert_eq!(doc.get_elements_by_class_name("y").len(&doc), 0);
+}
@@ -0,0 +1,75 @@
+use brooster_web_parser::dom::elements::html_form_element::HTMLFormElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+#[test]
+fn elements_sharing_a_name_in_tree_order() {
+    let doc = parse_document(
+        "<form><input name=color value=red><p><input name=color value=blue></p>\
+         <input name=Color><iframe name=color></iframe></form><meta name=color>\
+         <svg><rect name=color /></svg>",
+    );
+    let named = doc.get_elements_by_name("color");
+    let tags: Vec<_> = named
+        .iter(&doc)
+        .map(|id| doc.arena().get(id).as_element().unwrap().name.clone())
+        .collect();
+    assert_eq!(tags, ["input", "input", "iframe", "meta"]);
+    assert_eq!(
+        doc.get_attribute(named.item(&doc, 1).unwrap(), "value"),
+        Some("blue")
+    );
+    assert!(doc.get_elements_by_name("").is_empty(&doc));
+}
+
+#[test]
+fn renaming_updates_the_results() {
+    let mut doc = parse_document("<input name=a><input name=b>");
+    let by_a = doc.get_elements_by_name("a");
+    let second = doc.get_elements_by_name("b").item(&doc, 0).unwrap();
+    doc.set_attribute(second, "name", "a");
+    assert_eq!(by_a.len(&doc), 2);
+    assert_eq!(by_a.item(&doc, 1), Some(second));
+    assert!(doc.get_elements_by_name("b").is_empty(&doc));
+}
+
+#[test]
+fn results_follow_subtree_moves() {
+    let mut doc = parse_document(
+        "<div id=first><input name=x></div><div id=second><select name=x></select></div>",
+    );
+    let named = doc.get_elements_by_name("x");
+    let first = doc.get_element_by_id("first").unwrap();
+    let second = doc.get_element_by_id("second").unwrap();
+    let body = doc.body().unwrap();
+    doc.append_child(body, first).unwrap();
+    let order: Vec<_> = named.iter(&doc).map(|id| doc.arena().parent(id)).collect();
+    assert_eq!(order, [Some(second), Some(first)]);
+
+    doc.remove(first);
+    assert_eq!(named.len(&doc), 1);
+}
+
+#[test]
+fn form_named_item() {
+    let doc = parse_document(
+        "<form><input id=q name=query><img name=logo><input name=logo type=image>\
+         <output name=total></output></form>",
+    );
+    let form = HTMLFormElement::new(doc.forms().item(&doc, 0).unwrap());
+    let tag = |id| doc.arena().get(id).as_element().unwrap().name.clone();
+    assert_eq!(
+        form.named_item(&doc, "q").map(tag).as_deref(),
+        Some("input")
+    );
+    assert_eq!(form.named_item(&doc, "query"), form.named_item(&doc, "q"));
+    assert_eq!(
+        form.named_item(&doc, "total").map(tag).as_deref(),
+        Some("output")
+    );
+    // image buttons are not listed, the img is found instead
+    assert_eq!(
+        form.named_item(&doc, "logo").map(tag).as_deref(),
+        Some("img")
+    );
+    assert_eq!(form.named_item(&doc, "missing"), None);
+}
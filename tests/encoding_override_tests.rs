@@ -0,0 +1,68 @@
+use brooster_web_parser::dom::parser::encoding::{Confidence, Encoding, UnknownEncodingLabel};
+use brooster_web_parser::dom::parser::html_parser::{
+    parse_document_bytes_with, ParseResult, ParserOptions,
+};
+
+fn parse_with_override(bytes: &[u8], label: &str) -> ParseResult {
+    let options = ParserOptions::new().encoding_override(label).unwrap();
+    parse_document_bytes_with(bytes, &options)
+}
+
+fn paragraph_text(result: &ParseResult) -> String {
+    let doc = &result.document;
+    let p = doc.get_elements_by_tag_name("p").item(doc, 0).unwrap();
+    doc.text_content(p)
+}
+
+#[test]
+fn the_transport_encoding_beats_a_meta_in_the_document() {
+    let result = parse_with_override(b"<meta charset=utf-8><p>caf\xE9</p>", "ISO-8859-1");
+    assert_eq!(result.encoding, Encoding::Windows1252);
+    assert_eq!(result.confidence, Confidence::Certain);
+    assert_eq!(paragraph_text(&result), "caf\u{e9}");
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn a_late_meta_does_not_restart_an_overridden_parse() {
+    let mut bytes = b"<head><title>".to_vec();
+    bytes.extend(std::iter::repeat_n(b'x', 1100));
+    bytes.extend(b"</title><meta charset=windows-1252></head><p>\xC3\xA9</p>");
+    let result = parse_with_override(&bytes, "utf-8");
+    assert_eq!(result.encoding, Encoding::Utf8);
+    assert_eq!(result.confidence, Confidence::Certain);
+    assert_eq!(paragraph_text(&result), "\u{e9}");
+}
+
+#[test]
+fn unknown_labels_are_an_error() {
+    assert_eq!(
+        ParserOptions::new()
+            .encoding_override("klingon")
+            .unwrap_err(),
+        UnknownEncodingLabel("klingon".to_string())
+    );
+    assert!(ParserOptions::new().encoding_override("").is_err());
+    assert!(ParserOptions::new().encoding_override(" Latin1 ").is_ok());
+}
+
+#[test]
+fn a_byte_order_mark_wins_over_the_override() {
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend(b"<p>\xC3\xA9</p>");
+    let result = parse_with_override(&bytes, "windows-1252");
+    assert_eq!(result.encoding, Encoding::Utf8);
+    assert_eq!(result.confidence, Confidence::Certain);
+    assert_eq!(paragraph_text(&result), "\u{e9}");
+}
+
+#[test]
+fn default_options_sniff_as_parse_document_bytes_does() {
+    let result = parse_document_bytes_with(
+        b"<meta charset=windows-1252><p>\x93x\x94</p>",
+        &ParserOptions::default(),
+    );
+    assert_eq!(result.encoding, Encoding::Windows1252);
+    assert_eq!(result.confidence, Confidence::Certain);
+    assert_eq!(paragraph_text(&result), "\u{201c}x\u{201d}");
+}
@@ -0,0 +1,92 @@
+use brooster_web_parser::dom::parser::encoding::{prescan, Confidence, Encoding};
+use brooster_web_parser::dom::parser::html_parser::parse_document_bytes;
+
+#[test]
+fn charset_attribute_forms() {
+    assert_eq!(prescan(b"<meta charset='utf-8'>"), Some(Encoding::Utf8));
+    assert_eq!(prescan(b"<META CHARSET=UTF8>"), Some(Encoding::Utf8));
+    assert_eq!(
+        prescan(b"<meta/charset = \" utf-8 \"/>"),
+        Some(Encoding::Utf8)
+    );
+    assert_eq!(prescan(b"<meta charset=utf-8"), None);
+    assert_eq!(prescan(b"<meta charset=\"utf-8\""), None);
+    assert_eq!(prescan(b"<metacharset=utf-8>"), None);
+}
+
+#[test]
+fn declarations_in_comments_are_ignored() {
+    assert_eq!(prescan(b"<!-- <meta charset=utf-8> -->"), None);
+    assert_eq!(
+        prescan(b"<!--><meta charset=utf-8>"),
+        Some(Encoding::Utf8),
+        "`<!-->` is a complete comment"
+    );
+    assert_eq!(prescan(b"<!-- never closed <meta charset=utf-8>"), None);
+}
+
+#[test]
+fn only_the_first_1024_bytes_are_scanned() {
+    let mut late = b"<!DOCTYPE html><title>".to_vec();
+    late.resize(1024, b'x');
+    late.extend(b"<meta charset=utf-8>");
+    assert_eq!(prescan(&late), None);
+
+    let mut early = late.clone();
+    early.truncate(1000);
+    early.extend(b"<meta charset=utf-8>");
+    assert_eq!(prescan(&early[..1020]), Some(Encoding::Utf8));
+}
+
+#[test]
+fn http_equiv_content_type() {
+    assert_eq!(
+        prescan(b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=utf-8\">"),
+        Some(Encoding::Utf8)
+    );
+    assert_eq!(
+        prescan(b"<meta content='text/html; charset=\"utf-8\"' http-equiv=content-type>"),
+        Some(Encoding::Utf8)
+    );
+    // a content charset needs the pragma
+    assert_eq!(
+        prescan(b"<meta content=\"text/html; charset=utf-8\">"),
+        None
+    );
+    assert_eq!(
+        prescan(b"<meta http-equiv=refresh content=\"text/html; charset=utf-8\">"),
+        None
+    );
+}
+
+#[test]
+fn other_tags_and_labels() {
+    // attribute values of other tags are skipped, not scanned for metas
+    assert_eq!(prescan(b"<div title='<meta charset=utf-8>'>"), None);
+    assert_eq!(
+        prescan(b"<?xml version='1.0'?><meta charset=utf-8>"),
+        Some(Encoding::Utf8)
+    );
+    // UTF-16 in an ASCII-compatible stream means UTF-8
+    assert_eq!(prescan(b"<meta charset=utf-16le>"), Some(Encoding::Utf8));
+    // an unknown label fails this meta, the next one is looked at
+    assert_eq!(
+        prescan(b"<meta charset=bogus><meta charset=utf-8>"),
+        Some(Encoding::Utf8)
+    );
+    assert_eq!(
+        Encoding::for_label(" Unicode-1-1-UTF-8\n"),
+        Some(Encoding::Utf8)
+    );
+    assert_eq!(Encoding::for_label("utf-16"), Some(Encoding::Utf16Le));
+}
+
+#[test]
+fn parse_document_bytes_uses_the_prescanned_encoding() {
+    let result = parse_document_bytes(b"<meta charset=utf-16><p>\xC3\xA9</p>");
+    assert_eq!(result.encoding.name(), "UTF-8");
+    // the parsed meta confirms the prescanned encoding
+    assert_eq!(result.confidence, Confidence::Certain);
+    let body = result.document.body().unwrap();
+    assert_eq!(result.document.text_content(body), "\u{e9}");
+}
@@ -0,0 +1,98 @@
+use brooster_web_parser::dom::parser::encoding::{Confidence, Encoding};
+use brooster_web_parser::dom::parser::html_parser::parse_document_bytes;
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::dom::parser::tree_constructor::TreeConstructor;
+
+/// A document whose meta comes after the bytes the prescan looks at
+fn late_meta(charset: &str, body: &[u8]) -> Vec<u8> {
+    let mut bytes = b"<!DOCTYPE html><head><title>".to_vec();
+    bytes.extend(std::iter::repeat_n(b'x', 1100));
+    bytes.extend(format!("</title><meta charset=\"{charset}\"></head><body><p>").as_bytes());
+    bytes.extend(body);
+    bytes.extend(b"</p>");
+    bytes
+}
+
+fn paragraph_text(bytes: &[u8]) -> (String, Encoding, Confidence) {
+    let result = parse_document_bytes(bytes);
+    let doc = &result.document;
+    let p = doc.get_elements_by_tag_name("p").item(doc, 0).unwrap();
+    (doc.text_content(p), result.encoding, result.confidence)
+}
+
+#[test]
+fn a_late_conflicting_meta_restarts_with_its_encoding() {
+    let bytes = late_meta("windows-1252", b"\x93quoted\x94");
+    assert_eq!(
+        paragraph_text(&bytes),
+        (
+            "\u{201c}quoted\u{201d}".to_string(),
+            Encoding::Windows1252,
+            Confidence::Certain
+        )
+    );
+}
+
+#[test]
+fn a_late_matching_meta_confirms_the_encoding() {
+    let bytes = late_meta("utf-8", "caf\u{e9}".as_bytes());
+    assert_eq!(
+        paragraph_text(&bytes),
+        ("caf\u{e9}".to_string(), Encoding::Utf8, Confidence::Certain)
+    );
+
+    let mut tree_constructor = TreeConstructor::new();
+    tree_constructor.set_tentative_encoding(Encoding::Utf8);
+    let mut tokenizer = Tokenizer::new(&bytes);
+    loop {
+        let token = tokenizer.next_token();
+        let is_eof = token == Token::EOF;
+        tree_constructor.process_token(token);
+        if let Some(state) = tree_constructor.take_tokenizer_state() {
+            tokenizer.set_state(state);
+        }
+        if is_eof || tree_constructor.is_stopped() {
+            break;
+        }
+    }
+    assert_eq!(tree_constructor.encoding_change(), None);
+    assert_eq!(tree_constructor.tentative_encoding(), None);
+    // parsing went on to the end
+    let doc = tree_constructor.document();
+    assert_eq!(doc.get_elements_by_tag_name("p").len(doc), 1);
+}
+
+#[test]
+fn without_a_meta_the_encoding_stays_tentative() {
+    assert_eq!(
+        paragraph_text(b"<p>plain</p>"),
+        ("plain".to_string(), Encoding::Utf8, Confidence::Tentative)
+    );
+}
+
+#[test]
+fn a_certain_encoding_is_not_changed() {
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend(late_meta("windows-1252", "\u{201c}".as_bytes()));
+    assert_eq!(
+        paragraph_text(&bytes),
+        ("\u{201c}".to_string(), Encoding::Utf8, Confidence::Certain)
+    );
+}
+
+#[test]
+fn http_equiv_declarations_count_too() {
+    let mut bytes = b"<head><title>".to_vec();
+    bytes.extend(std::iter::repeat_n(b'x', 1100));
+    bytes.extend(
+        b"</title><meta http-equiv=Content-Type content=\"text/html; charset=latin1\"><p>\xE9</p>",
+    );
+    assert_eq!(
+        paragraph_text(&bytes),
+        (
+            "\u{e9}".to_string(),
+            Encoding::Windows1252,
+            Confidence::Certain
+        )
+    );
+}
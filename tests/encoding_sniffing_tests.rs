@@ -0,0 +1,65 @@
+use brooster_web_parser::dom::parser::encoding::{sniff_bom, Confidence, Encoding};
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_bytes};
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+
+const SOURCE: &str =
+    "<!DOCTYPE html><title>Caf\u{e9}</title><p class=\"x\">5 \u{20ac} \u{1f600}</p>";
+
+fn utf16(bom: [u8; 2], unit: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    let mut bytes = bom.to_vec();
+    bytes.extend(SOURCE.encode_utf16().flat_map(unit));
+    bytes
+}
+
+#[test]
+fn byte_order_marks_give_identical_trees() {
+    let expected = {
+        let doc = parse_document(SOURCE);
+        serialize_node_to_string(&doc, doc.root())
+    };
+    let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+    utf8.extend(SOURCE.as_bytes());
+    let inputs = [
+        (utf8, "UTF-8"),
+        (utf16([0xFF, 0xFE], u16::to_le_bytes), "UTF-16LE"),
+        (utf16([0xFE, 0xFF], u16::to_be_bytes), "UTF-16BE"),
+    ];
+    for (bytes, name) in inputs {
+        let result = parse_document_bytes(&bytes);
+        assert_eq!(result.encoding.name(), name);
+        assert_eq!(result.confidence, Confidence::Certain);
+        assert_eq!(
+            serialize_node_to_string(&result.document, result.document.root()),
+            expected,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn without_a_bom_utf8_is_tentative() {
+    let result = parse_document_bytes(SOURCE.as_bytes());
+    assert_eq!(result.encoding, Encoding::Utf8);
+    assert_eq!(result.confidence, Confidence::Tentative);
+    assert_eq!(result.document.title(), "Caf\u{e9}");
+}
+
+#[test]
+fn the_bom_is_not_part_of_the_text() {
+    let result = parse_document_bytes(b"\xEF\xBB\xBFhello");
+    let body = result.document.body().unwrap();
+    assert_eq!(result.document.text_content(body), "hello");
+}
+
+#[test]
+fn sniffing_and_malformed_input() {
+    assert_eq!(sniff_bom(b"\xFE\xFFrest"), Some((Encoding::Utf16Be, 2)));
+    assert_eq!(sniff_bom(b"\xEF\xBB"), None);
+    assert_eq!(sniff_bom(b""), None);
+    // an unpaired surrogate and a dangling byte
+    assert_eq!(
+        Encoding::Utf16Le.decode(&[0x41, 0x00, 0x00, 0xD8, 0x42]),
+        "A\u{fffd}\u{fffd}"
+    );
+    assert_eq!(Encoding::Utf8.decode(b"a\xFFb"), "a\u{fffd}b");
+}
@@ -0,0 +1,84 @@
+use brooster_web_parser::dom::entities::closest_entity_name;
+use brooster_web_parser::dom::parser::html_parser::{
+    parse_document_bytes_with, ParseResult, ParserOptions,
+};
+use brooster_web_parser::dom::parser::parse_error::{render_error, ErrorPolicy, ParseErrorCode};
+use brooster_web_parser::dom::parser::tokenizer::Tokenizer;
+
+fn parse(source: &str, suggest: bool) -> ParseResult {
+    parse_document_bytes_with(
+        source.as_bytes(),
+        &ParserOptions::new().suggest_entity_names(suggest),
+    )
+}
+
+fn suggestions(result: &ParseResult) -> Vec<Option<String>> {
+    result
+        .tokenizer_errors
+        .iter()
+        .filter(|error| error.code == ParseErrorCode::UnknownNamedCharacterReference)
+        .map(|error| error.suggestion.clone())
+        .collect()
+}
+
+#[test]
+fn typos_suggest_the_closest_name() {
+    let result = parse("a&nbps;b &mdahs; c", true);
+    assert_eq!(
+        suggestions(&result),
+        [Some("nbsp".to_string()), Some("mdash".to_string())]
+    );
+}
+
+#[test]
+fn names_far_from_every_entity_suggest_nothing() {
+    let result = parse("&zzzz;", true);
+    assert_eq!(suggestions(&result), [None]);
+    assert_eq!(closest_entity_name("zzzz"), None);
+}
+
+#[test]
+fn suggestions_are_off_by_default() {
+    let result = parse("&nbps;", false);
+    assert_eq!(suggestions(&result), [None]);
+
+    let mut tokenizer = Tokenizer::new(b"&nbps;");
+    tokenizer.run();
+    assert_eq!(tokenizer.errors()[0].suggestion, None);
+}
+
+#[test]
+fn suggestions_are_skipped_when_errors_are_not_kept() {
+    let result = parse_document_bytes_with(
+        b"&nbps;",
+        &ParserOptions::new()
+            .suggest_entity_names(true)
+            .error_policy(ErrorPolicy::CountOnly),
+    );
+    assert!(result.tokenizer_errors.is_empty());
+    assert_eq!(result.total_errors, 2);
+}
+
+#[test]
+fn only_names_with_the_same_first_letter_are_suggested() {
+    // `amp` is one deletion away but starts with another letter
+    assert_eq!(closest_entity_name("qamp"), None);
+    assert_eq!(closest_entity_name("ampp"), Some("amp"));
+    assert_eq!(closest_entity_name("hellip"), Some("hellip"));
+    assert_eq!(closest_entity_name(""), None);
+}
+
+#[test]
+fn the_rendering_helper_shows_the_suggestion() {
+    let source = "x &nbps; y";
+    let result = parse(source, true);
+    let error = &result.tokenizer_errors[0];
+    assert_eq!(
+        render_error(error, source),
+        "error[unknown-named-character-reference] at 1:8, on `nbps`\n  \
+         |\n\
+         1 | x &nbps; y\n  \
+         |        ^\n  \
+         = help: did you mean `&nbsp;`?"
+    );
+}
@@ -0,0 +1,85 @@
+use brooster_web_parser::dom::parser::html_parser::{
+    parse_document_bytes, parse_document_bytes_with, ParseResult, ParserOptions,
+};
+use brooster_web_parser::dom::parser::parse_error::{ErrorPolicy, ParseErrorCode};
+use brooster_web_parser::dom::testing::tree_digest;
+
+/// Input giving a missing-end-tag-name error every 3 bytes
+fn garbage(errors: usize) -> Vec<u8> {
+    b"</>".repeat(errors)
+}
+
+fn parse(bytes: &[u8], policy: ErrorPolicy) -> ParseResult {
+    parse_document_bytes_with(bytes, &ParserOptions::new().error_policy(policy))
+}
+
+fn stored(result: &ParseResult) -> usize {
+    result.tokenizer_errors.len() + result.tree_errors.len()
+}
+
+#[test]
+fn by_default_every_error_is_kept() {
+    let result = parse_document_bytes(&garbage(500));
+    assert!(result.total_errors >= 500);
+    assert_eq!(stored(&result), result.total_errors);
+    assert!(!result.truncated);
+    assert_eq!(
+        result
+            .tokenizer_errors
+            .iter()
+            .filter(|error| error.code == ParseErrorCode::MissingEndTagName)
+            .count(),
+        500
+    );
+}
+
+#[test]
+fn collect_stops_storing_at_max_but_keeps_counting() {
+    let bytes = garbage(500);
+    let all = parse_document_bytes(&bytes).total_errors;
+    let result = parse(&bytes, ErrorPolicy::Collect { max: 20 });
+    assert_eq!(stored(&result), 20);
+    assert_eq!(result.total_errors, all);
+    assert!(result.truncated);
+    // the first errors are kept
+    assert_eq!(result.tokenizer_errors[0].offset, 2);
+}
+
+#[test]
+fn collect_is_not_truncated_at_exactly_max() {
+    let bytes = garbage(5);
+    let all = parse_document_bytes(&bytes).total_errors;
+    let result = parse(&bytes, ErrorPolicy::Collect { max: all });
+    assert_eq!(stored(&result), all);
+    assert!(!result.truncated);
+}
+
+#[test]
+fn count_only_stores_nothing() {
+    let bytes = garbage(500);
+    let all = parse_document_bytes(&bytes).total_errors;
+    let result = parse(&bytes, ErrorPolicy::CountOnly);
+    assert_eq!(stored(&result), 0);
+    assert_eq!(result.total_errors, all);
+    assert!(!result.truncated);
+}
+
+#[test]
+fn ignore_does_not_even_count() {
+    let result = parse(&garbage(500), ErrorPolicy::Ignore);
+    assert_eq!(stored(&result), 0);
+    assert_eq!(result.total_errors, 0);
+    assert!(!result.truncated);
+}
+
+#[test]
+fn the_policy_does_not_change_the_document() {
+    let bytes = b"<table><td>a</td></tr><p>b\0</p>";
+    let kept = parse(bytes, ErrorPolicy::default());
+    let ignored = parse(bytes, ErrorPolicy::Ignore);
+    assert!(kept.total_errors > 0);
+    assert_eq!(
+        tree_digest(&kept.document, kept.document.root()),
+        tree_digest(&ignored.document, ignored.document.root())
+    );
+}
@@ -0,0 +1,101 @@
+use brooster_web_parser::dom::parser::parse_error::{render_error, ParseError, ParseErrorCode};
+use brooster_web_parser::dom::parser::tokenizer::Tokenizer;
+
+fn first_error(source: &str, code: ParseErrorCode) -> ParseError {
+    let mut tokenizer = Tokenizer::new(source.as_bytes());
+    tokenizer.run();
+    tokenizer
+        .take_errors()
+        .into_iter()
+        .find(|error| error.code == code)
+        .unwrap()
+}
+
+fn error_at(offset: usize) -> ParseError {
+    ParseError {
+        code: ParseErrorCode::UnexpectedNullCharacter,
+        offset,
+        context: None,
+        suggestion: None,
+    }
+}
+
+#[test]
+fn an_error_in_a_long_attribute_laden_line() {
+    let mut source = String::from("<!DOCTYPE html>\n<body>\n<div");
+    for index in 0..12 {
+        source.push_str(&format!(" data-item-{index}=\"value {index}\""));
+    }
+    source.push_str(" class=a class=b");
+    for index in 12..24 {
+        source.push_str(&format!(" data-item-{index}=\"value {index}\""));
+    }
+    source.push_str(">\n</body>\n");
+    let error = first_error(&source, ParseErrorCode::DuplicateAttribute);
+    assert_eq!(
+        render_error(&error, &source),
+        "error[duplicate-attribute] at 3:282, on `class`\n  \
+         |\n\
+         3 | ...lue 10\" data-item-11=\"value 11\" class=a class=b data-item-12=\"value 12\" \
+         data-ite...\n  \
+         |                                            ^"
+    );
+}
+
+#[test]
+fn an_eof_error_at_the_very_end() {
+    let source = "<p>text</p>\n<!-- never closed";
+    let error = first_error(source, ParseErrorCode::EofInComment);
+    assert_eq!(
+        render_error(&error, source),
+        "error[eof-in-comment] at 2:18\n  \
+         |\n\
+         2 | <!-- never closed\n  \
+         |                  ^"
+    );
+}
+
+#[test]
+fn display_renders_the_same() {
+    let source = "<a x=1 x=2>";
+    let error = first_error(source, ParseErrorCode::DuplicateAttribute);
+    assert_eq!(
+        error.display(source).to_string(),
+        "error[duplicate-attribute] at 1:8, on `x`\n  |\n1 | <a x=1 x=2>\n  |        ^"
+    );
+}
+
+#[test]
+fn tabs_are_expanded_before_the_caret() {
+    let source = "\t\t<p>\0";
+    assert_eq!(
+        render_error(&error_at(5), source),
+        "error[unexpected-null-character] at 1:6\n  |\n1 |         <p>\0\n  |            ^"
+    );
+}
+
+#[test]
+fn columns_count_characters_not_bytes() {
+    let source = "caf\u{e9} \u{1f600} \0";
+    let rendered = render_error(&error_at(source.len() - 1), source);
+    assert!(rendered.starts_with("error[unexpected-null-character] at 1:8\n"));
+    assert!(rendered.ends_with("\n  |        ^"));
+}
+
+#[test]
+fn the_line_number_widens_the_gutter() {
+    let source = format!("{}x\0", "\n".repeat(11));
+    assert_eq!(
+        render_error(&error_at(source.len() - 1), &source),
+        "error[unexpected-null-character] at 12:2\n   |\n12 | x\0\n   |  ^"
+    );
+}
+
+#[test]
+fn carriage_returns_are_not_shown() {
+    let source = "a\0\r\nb";
+    assert_eq!(
+        render_error(&error_at(1), source),
+        "error[unexpected-null-character] at 1:2\n  |\n1 | a\0\n  |  ^"
+    );
+}
@@ -0,0 +1,81 @@
+use brooster_web_parser::dom::parser::html_parser::{
+    parse_document_bytes, parse_document_bytes_with, ParserOptions,
+};
+use brooster_web_parser::dom::parser::parse_error::{ErrorPolicy, ParseErrorCode, StageError};
+
+#[test]
+fn each_stage_reports_to_its_own_stream() {
+    // `&` is a valid tag name character, the duplicate attribute gives the tokenizer error
+    let result = parse_document_bytes(b"<table><div a=1 a=2></tabl&>");
+    assert!(result
+        .tokenizer_errors
+        .iter()
+        .any(|error| error.code == ParseErrorCode::DuplicateAttribute));
+    assert!(result
+        .tree_errors
+        .iter()
+        .any(|error| error.code == ParseErrorCode::FosterParenting));
+    assert!(result
+        .tokenizer_errors
+        .iter()
+        .all(|error| error.code.is_tokenizer_error()));
+    assert!(result
+        .tree_errors
+        .iter()
+        .all(|error| !error.code.is_tokenizer_error()));
+}
+
+#[test]
+fn the_merged_errors_are_in_offset_order() {
+    let result = parse_document_bytes(b"<table><div a=1 a=2></tabl&>");
+    let errors: Vec<StageError> = result.errors().collect();
+    assert_eq!(
+        errors.len(),
+        result.tokenizer_errors.len() + result.tree_errors.len()
+    );
+    assert!(errors
+        .windows(2)
+        .all(|pair| pair[0].offset() <= pair[1].offset()));
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| (error.code(), error.offset()))
+            .collect::<Vec<_>>(),
+        [
+            (ParseErrorCode::MissingDoctype, 7),
+            (ParseErrorCode::DuplicateAttribute, 16),
+            (ParseErrorCode::FosterParenting, 20),
+            (ParseErrorCode::FosterParenting, 28),
+            (ParseErrorCode::UnexpectedEndTag, 28),
+        ]
+    );
+}
+
+#[test]
+fn tokenizer_errors_come_first_at_the_same_offset() {
+    let result = parse_document_bytes(b"<!DOCTYPE html><p>\0");
+    let errors: Vec<StageError> = result.errors().collect();
+    assert!(matches!(
+        errors.as_slice(),
+        [StageError::Tokenizer(first), StageError::Tree(second)]
+            if first.code == ParseErrorCode::UnexpectedNullCharacter
+                && second.code == ParseErrorCode::UnexpectedNullCharacter
+    ));
+}
+
+#[test]
+fn the_merged_errors_follow_the_error_policy() {
+    let result = parse_document_bytes_with(
+        b"<table><div a=1 a=2></tabl&>",
+        &ParserOptions::new().error_policy(ErrorPolicy::Collect { max: 2 }),
+    );
+    let codes: Vec<ParseErrorCode> = result.errors().map(|error| error.code()).collect();
+    assert_eq!(
+        codes,
+        [
+            ParseErrorCode::MissingDoctype,
+            ParseErrorCode::DuplicateAttribute
+        ]
+    );
+    assert!(result.truncated);
+}
@@ -0,0 +1,67 @@
+use brooster_web_parser::dom::escape::EscapePolicy;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::parser::tokenizer::Token;
+use brooster_web_parser::dom::serializer::{serialize_children_with, serialize_node_to_string};
+
+const INPUT: &str = "Say \"hi\"\u{A0}\u{1F600} & caf\u{E9}";
+
+#[test]
+fn text_escaping_per_policy() {
+    assert_eq!(
+        EscapePolicy::Minimal.escape_text(INPUT),
+        "Say \"hi\"&nbsp;\u{1F600} &amp; caf\u{E9}"
+    );
+    assert_eq!(
+        EscapePolicy::NamedEntities.escape_text(INPUT),
+        "Say \"hi\"&nbsp;&#x1F600; &amp; caf&eacute;"
+    );
+    assert_eq!(
+        EscapePolicy::NumericNonAscii.escape_text(INPUT),
+        "Say \"hi\"&nbsp;&#x1F600; &amp; caf&#xE9;"
+    );
+}
+
+#[test]
+fn attribute_escaping_per_policy() {
+    assert_eq!(
+        EscapePolicy::Minimal.escape_attribute_value(INPUT),
+        "Say &quot;hi&quot;&nbsp;\u{1F600} &amp; caf\u{E9}"
+    );
+    assert_eq!(
+        EscapePolicy::NamedEntities.escape_attribute_value(INPUT),
+        "Say &quot;hi&quot;&nbsp;&#x1F600; &amp; caf&eacute;"
+    );
+    assert_eq!(
+        EscapePolicy::NumericNonAscii.escape_attribute_value("<\u{E9}>"),
+        "<&#xE9;>"
+    );
+}
+
+#[test]
+fn serializer_applies_the_policy_once() {
+    let doc = parse_document("<p title='a&amp;b'>x &amp; y\u{E9}</p>");
+    let p = doc.get_elements_by_tag_name("p").item(&doc, 0).unwrap();
+    assert_eq!(
+        serialize_node_to_string(&doc, p),
+        "<p title=\"a&amp;b\">x &amp; y\u{E9}</p>"
+    );
+    assert_eq!(
+        serialize_children_with(&doc, p, EscapePolicy::NamedEntities),
+        "x &amp; y&eacute;"
+    );
+}
+
+#[test]
+fn tokens_display_as_markup() {
+    let tag = Token::StartTag {
+        tag_name: "a".into(),
+        self_closing: false,
+        attributes: vec![("title".into(), "\"&\"".to_string())],
+        raw_attributes: Vec::new(),
+    };
+    assert_eq!(tag.to_string(), "<a title=\"&quot;&amp;&quot;\">");
+    assert_eq!(Token::Character { data: '&' }.to_string(), "&amp;");
+    assert_eq!(Token::Character { data: '\u{A0}' }.to_string(), "&nbsp;");
+    assert_eq!(Token::Comment { data: "c".to_string() }.to_string(), "<!--c-->");
+    assert_eq!(Token::EOF.to_string(), "");
+}
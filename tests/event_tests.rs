@@ -0,0 +1,172 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::event::{Event, EventInit, EventPhase};
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+use std::sync::{Arc, Mutex};
+
+type Calls = Arc<Mutex<Vec<String>>>;
+
+fn record(doc: &mut Document, node: NodeId, capture: bool, label: &str, calls: &Calls) {
+    let calls = Arc::clone(calls);
+    let label = label.to_string();
+    doc.add_event_listener(node, "click", capture, move |event| {
+        calls
+            .lock()
+            .unwrap()
+            .push(format!("{label}:{:?}", event.event_phase()));
+    });
+}
+
+fn bubbling() -> Event {
+    Event::new(
+        "click",
+        EventInit {
+            bubbles: true,
+            cancelable: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn capture_then_target_then_bubble() {
+    let mut doc = parse_document("<div id=outer><p id=inner></p></div>");
+    let outer = by_id(&doc, "outer");
+    let inner = by_id(&doc, "inner");
+    let calls = Calls::default();
+    record(&mut doc, outer, false, "outer-bubble", &calls);
+    record(&mut doc, outer, true, "outer-capture", &calls);
+    record(&mut doc, inner, false, "inner-bubble", &calls);
+    record(&mut doc, inner, true, "inner-capture", &calls);
+
+    let mut event = bubbling();
+    assert!(doc.dispatch_event(inner, &mut event));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "outer-capture:Capturing",
+            "inner-capture:AtTarget",
+            "inner-bubble:AtTarget",
+            "outer-bubble:Bubbling",
+        ]
+    );
+    assert_eq!(event.target(), Some(inner));
+    assert_eq!(event.current_target(), None);
+    assert_eq!(event.event_phase(), EventPhase::None);
+}
+
+#[test]
+fn non_bubbling_events_stop_at_the_target() {
+    let mut doc = parse_document("<div id=outer><p id=inner></p></div>");
+    let outer = by_id(&doc, "outer");
+    let inner = by_id(&doc, "inner");
+    let calls = Calls::default();
+    record(&mut doc, outer, false, "outer", &calls);
+    record(&mut doc, inner, false, "inner", &calls);
+
+    let mut event = Event::new("click", EventInit::default());
+    doc.dispatch_event(inner, &mut event);
+    assert_eq!(*calls.lock().unwrap(), ["inner:AtTarget"]);
+}
+
+#[test]
+fn listeners_only_see_their_type() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = by_id(&doc, "p");
+    let calls = Calls::default();
+    record(&mut doc, p, false, "p", &calls);
+
+    doc.dispatch_event(p, &mut Event::new("input", EventInit::default()));
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn stop_propagation_finishes_the_current_node() {
+    let mut doc = parse_document("<div id=outer><p id=inner></p></div>");
+    let outer = by_id(&doc, "outer");
+    let inner = by_id(&doc, "inner");
+    let calls = Calls::default();
+    doc.add_event_listener(inner, "click", false, |event| event.stop_propagation());
+    record(&mut doc, inner, false, "inner", &calls);
+    record(&mut doc, outer, false, "outer", &calls);
+
+    doc.dispatch_event(inner, &mut bubbling());
+    assert_eq!(*calls.lock().unwrap(), ["inner:AtTarget"]);
+}
+
+#[test]
+fn stop_propagation_in_capture_skips_the_target() {
+    let mut doc = parse_document("<div id=outer><p id=inner></p></div>");
+    let outer = by_id(&doc, "outer");
+    let inner = by_id(&doc, "inner");
+    let calls = Calls::default();
+    doc.add_event_listener(outer, "click", true, |event| event.stop_propagation());
+    record(&mut doc, inner, false, "inner", &calls);
+
+    doc.dispatch_event(inner, &mut bubbling());
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn stop_immediate_propagation_skips_remaining_listeners() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = by_id(&doc, "p");
+    let calls = Calls::default();
+    doc.add_event_listener(p, "click", false, |event| {
+        event.stop_immediate_propagation()
+    });
+    record(&mut doc, p, false, "p", &calls);
+
+    doc.dispatch_event(p, &mut bubbling());
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn prevent_default_needs_a_cancelable_event() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = by_id(&doc, "p");
+    doc.add_event_listener(p, "click", false, |event| event.prevent_default());
+
+    let mut event = bubbling();
+    assert!(!doc.dispatch_event(p, &mut event));
+    assert!(event.default_prevented());
+
+    let mut event = Event::new("click", EventInit::default());
+    assert!(doc.dispatch_event(p, &mut event));
+    assert!(!event.default_prevented());
+}
+
+#[test]
+fn removed_listeners_are_not_called() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = by_id(&doc, "p");
+    let calls = Calls::default();
+    let sink = Arc::clone(&calls);
+    let id = doc.add_event_listener(p, "click", false, move |_| {
+        sink.lock().unwrap().push("p".to_string())
+    });
+    doc.remove_event_listener(id);
+
+    doc.dispatch_event(p, &mut bubbling());
+    assert!(calls.lock().unwrap().is_empty());
+}
+
+#[test]
+fn listeners_follow_their_node_through_compaction() {
+    let mut doc = parse_document("<div id=gone></div><p id=p></p>");
+    let gone = by_id(&doc, "gone");
+    let parent = doc.arena().parent(gone).unwrap();
+    doc.remove_child(parent, gone).unwrap();
+    let calls = Calls::default();
+    record(&mut doc, gone, false, "gone", &calls);
+    let p = by_id(&doc, "p");
+    record(&mut doc, p, false, "p", &calls);
+
+    let map = doc.compact();
+    let p = map.get(p).unwrap();
+    doc.dispatch_event(p, &mut bubbling());
+    assert_eq!(*calls.lock().unwrap(), ["p:AtTarget"]);
+}
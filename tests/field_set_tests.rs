@@ -0,0 +1,77 @@
+mod common;
+
+use brooster_web_parser::dom::elements::html_field_set_element::HTMLFieldSetElement;
+use brooster_web_parser::dom::elements::html_input_element::HTMLInputElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::elements::html_text_area_element::HTMLTextAreaElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn disabled_fieldset_spares_its_first_legend() {
+    let doc = parse_document(
+        "<fieldset id=fs disabled><legend><input id=in-legend></legend>\
+         <input id=inside><select id=s></select><textarea id=t></textarea>\
+         <legend><input id=in-second-legend></legend></fieldset>",
+    );
+    assert!(HTMLFieldSetElement::new(by_id(&doc, "fs")).disabled(&doc));
+    assert!(!HTMLInputElement::new(by_id(&doc, "in-legend")).is_disabled(&doc));
+    assert!(HTMLInputElement::new(by_id(&doc, "in-legend")).will_validate(&doc));
+
+    let inside = HTMLInputElement::new(by_id(&doc, "inside"));
+    assert!(!inside.disabled(&doc));
+    assert!(inside.is_disabled(&doc));
+    assert!(!inside.will_validate(&doc));
+    assert!(HTMLInputElement::new(by_id(&doc, "in-second-legend")).is_disabled(&doc));
+
+    let select = HTMLSelectElement::new(by_id(&doc, "s"));
+    assert!(select.is_disabled(&doc) && !select.will_validate(&doc));
+    let text_area = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    assert!(text_area.is_disabled(&doc) && !text_area.will_validate(&doc));
+}
+
+#[test]
+fn nested_fieldsets_compose() {
+    let mut doc = parse_document(
+        "<fieldset id=outer><legend><fieldset id=inner><input id=deep></fieldset></legend>\
+         <fieldset id=after><input id=late></fieldset></fieldset>",
+    );
+    let outer = HTMLFieldSetElement::new(by_id(&doc, "outer"));
+    let inner = HTMLFieldSetElement::new(by_id(&doc, "inner"));
+    assert!(!HTMLInputElement::new(by_id(&doc, "deep")).is_disabled(&doc));
+
+    outer.set_disabled(&mut doc, true);
+    assert!(!HTMLInputElement::new(by_id(&doc, "deep")).is_disabled(&doc));
+    assert!(HTMLInputElement::new(by_id(&doc, "late")).is_disabled(&doc));
+    assert!(HTMLFieldSetElement::new(by_id(&doc, "after")).is_disabled(&doc));
+
+    inner.set_disabled(&mut doc, true);
+    assert!(HTMLInputElement::new(by_id(&doc, "deep")).is_disabled(&doc));
+    outer.set_disabled(&mut doc, false);
+    assert!(HTMLInputElement::new(by_id(&doc, "deep")).is_disabled(&doc));
+    assert!(!HTMLInputElement::new(by_id(&doc, "late")).is_disabled(&doc));
+}
+
+#[test]
+fn elements_are_the_listed_descendants_in_tree_order() {
+    let doc = parse_document(
+        "<form id=f><fieldset id=fs name=group><legend>Who</legend><input id=a>\
+         <div><select id=b></select></div><input id=c type=image><p>text</p>\
+         <fieldset id=d><textarea id=e></textarea></fieldset><output id=o></output>\
+         </fieldset><input id=outside></form>",
+    );
+    let fieldset = HTMLFieldSetElement::new(by_id(&doc, "fs"));
+    let elements: Vec<NodeId> = fieldset.elements().iter(&doc).collect();
+    let expected: Vec<NodeId> = ["a", "b", "c", "d", "e", "o"]
+        .iter()
+        .map(|id| by_id(&doc, id))
+        .collect();
+    assert_eq!(elements, expected);
+    assert_eq!(fieldset.name(&doc), "group");
+    assert_eq!(fieldset.r#type(), "fieldset");
+    assert_eq!(
+        fieldset.form(&doc).map(|form| form.node()),
+        Some(by_id(&doc, "f"))
+    );
+}
@@ -0,0 +1,103 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_form_element::HTMLFormElement;
+use brooster_web_parser::dom::elements::html_input_element::HTMLInputElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+
+const FORM: &str = "<input id=before form=f>\
+    <form id=f name=signup>\
+      <input id=user name=user value=anon>\
+      <p>text</p>\
+      <select id=plan><option>free<option selected>pro</select>\
+      <input type=image id=go>\
+      <textarea name=bio></textarea>\
+    </form>\
+    <input id=outside>\
+    <button id=after form=f>ok</button>";
+
+fn form(doc: &Document) -> HTMLFormElement {
+    HTMLFormElement::new(doc.get_element_by_id("f").unwrap())
+}
+
+fn ids(doc: &Document, form: &HTMLFormElement) -> Vec<String> {
+    form.elements()
+        .iter(doc)
+        .map(|node| doc.get_attribute(node, "id").unwrap_or("-").to_string())
+        .collect()
+}
+
+#[test]
+fn elements_lists_owned_controls_in_tree_order() {
+    let doc = parse_document(FORM);
+    let form = form(&doc);
+    assert_eq!(ids(&doc, &form), ["before", "user", "plan", "-", "after"]);
+    assert_eq!(form.length(&doc), 5);
+    assert_eq!(form.item(&doc, 2), doc.get_element_by_id("plan"));
+    assert_eq!(form.item(&doc, 5), None);
+}
+
+#[test]
+fn named_access_matches_id_then_name() {
+    let doc = parse_document(FORM);
+    let form = form(&doc);
+    let user = doc.get_element_by_id("user");
+    assert_eq!(form.named_item(&doc, "user"), user);
+    assert_eq!(
+        form.named_item(&doc, "after"),
+        doc.get_element_by_id("after")
+    );
+    let bio = form.item(&doc, 3);
+    assert_eq!(form.named_item(&doc, "bio"), bio);
+    assert_eq!(form.named_item(&doc, "outside"), None);
+    assert_eq!(form.named_item(&doc, "go"), None);
+}
+
+#[test]
+fn reset_restores_inputs_and_selects() {
+    let mut doc = parse_document(FORM);
+    let form = form(&doc);
+    let user = HTMLInputElement::new(doc.get_element_by_id("user").unwrap());
+    let plan = HTMLSelectElement::new(doc.get_element_by_id("plan").unwrap());
+    let outside = HTMLInputElement::new(doc.get_element_by_id("outside").unwrap());
+
+    assert_eq!(user.value(&doc), "anon");
+    user.set_value(&mut doc, "alice");
+    plan.set_selected_index(&mut doc, 0);
+    outside.set_value(&mut doc, "kept");
+    assert_eq!(user.value(&doc), "alice");
+    assert_eq!(user.default_value(&doc), "anon");
+    assert_eq!(plan.value(&doc), "free");
+
+    form.reset(&mut doc);
+    assert_eq!(user.value(&doc), "anon");
+    assert_eq!(plan.value(&doc), "pro");
+    assert_eq!(outside.value(&doc), "kept");
+}
+
+#[test]
+fn attributes_are_reflected() {
+    let mut doc = parse_document_with_url(
+        "<form id=f method=POST action=submit></form>",
+        "https://example.com/dir/page",
+    );
+    let form = form(&doc);
+    assert_eq!(form.method(&doc), "post");
+    assert_eq!(form.action(&doc), "https://example.com/dir/submit");
+    assert_eq!(form.name(&doc), "");
+    assert!(!form.no_validate(&doc));
+
+    form.set_method(&mut doc, "Dialog");
+    assert_eq!(form.method(&doc), "dialog");
+    form.set_method(&mut doc, "put");
+    assert_eq!(form.method(&doc), "get");
+
+    form.set_action(&mut doc, "");
+    assert_eq!(form.action(&doc), "https://example.com/dir/page");
+
+    form.set_name(&mut doc, "login");
+    form.set_no_validate(&mut doc, true);
+    assert_eq!(doc.get_attribute(form.node(), "name"), Some("login"));
+    assert!(form.no_validate(&doc));
+    form.set_no_validate(&mut doc, false);
+    assert_eq!(doc.get_attribute(form.node(), "novalidate"), None);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_form_element::{form_owner, HTMLFormElement};
+use brooster_web_parser::dom::elements::html_input_element::HTMLInputElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+fn owner_of(doc: &Document, id: &str) -> Option<NodeId> {
+    form_owner(doc, by_id(doc, id))
+}
+
+#[test]
+fn form_attribute_points_outside_controls_at_their_form() {
+    let doc = parse_document(
+        "<form id=f1><input id=inside></form><input id=outside form=f1>\
+         <select id=choice form=f1></select>",
+    );
+    let f1 = by_id(&doc, "f1");
+    assert_eq!(owner_of(&doc, "outside"), Some(f1));
+    let select = HTMLSelectElement::new(by_id(&doc, "choice"));
+    assert_eq!(select.form(&doc).map(|form| form.node()), Some(f1));
+    let elements: Vec<NodeId> = HTMLFormElement::new(f1).elements().iter(&doc).collect();
+    assert_eq!(
+        elements,
+        [
+            by_id(&doc, "inside"),
+            by_id(&doc, "outside"),
+            by_id(&doc, "choice")
+        ]
+    );
+}
+
+#[test]
+fn invalid_form_attribute_means_no_owner() {
+    let doc = parse_document(
+        "<form id=f1><input id=missing form=missing><input id=empty form=''>\
+         <input id=div form=d></form><div id=d></div>",
+    );
+    assert_eq!(owner_of(&doc, "missing"), None);
+    assert_eq!(owner_of(&doc, "empty"), None);
+    assert_eq!(owner_of(&doc, "div"), None);
+    let f1 = HTMLFormElement::new(by_id(&doc, "f1"));
+    assert!(f1.elements().is_empty(&doc));
+}
+
+#[test]
+fn moving_a_control_between_forms_updates_the_owner() {
+    let mut doc = parse_document("<form id=a><input id=x></form><form id=b></form>");
+    let (a, b, x) = (by_id(&doc, "a"), by_id(&doc, "b"), by_id(&doc, "x"));
+    let input = HTMLInputElement::new(x);
+    assert_eq!(input.form(&doc).map(|form| form.node()), Some(a));
+
+    doc.append_child(b, x).unwrap();
+    assert_eq!(form_owner(&doc, x), Some(b));
+    assert_eq!(HTMLFormElement::new(a).length(&doc), 0);
+    assert_eq!(HTMLFormElement::new(b).item(&doc, 0), Some(x));
+
+    doc.set_attribute(x, "form", "a");
+    assert_eq!(form_owner(&doc, x), Some(a));
+    doc.remove_attribute(x, "form");
+    assert_eq!(form_owner(&doc, x), Some(b));
+
+    doc.remove(x);
+    assert_eq!(form_owner(&doc, x), None);
+}
+
+#[test]
+fn id_changes_retarget_form_attributes() {
+    let mut doc = parse_document("<form id=a></form><form id=b></form><input id=x form=a>");
+    let (a, b) = (by_id(&doc, "a"), by_id(&doc, "b"));
+    assert_eq!(owner_of(&doc, "x"), Some(a));
+    doc.set_attribute(a, "id", "old");
+    doc.set_attribute(b, "id", "a");
+    assert_eq!(owner_of(&doc, "x"), Some(b));
+}
+
+#[test]
+fn parser_associates_controls_with_the_form_element_pointer() {
+    // the form is closed by </div> but stays the form element pointer
+    let mut doc = parse_document("<div><form id=f></div><input id=late>");
+    let (f, late) = (by_id(&doc, "f"), by_id(&doc, "late"));
+    assert!(!doc.arena().ancestors(late).any(|node| node == f));
+    assert_eq!(form_owner(&doc, late), Some(f));
+    assert_eq!(HTMLFormElement::new(f).item(&doc, 0), Some(late));
+
+    // moving the control drops the association
+    let body = doc.body().unwrap();
+    doc.append_child(body, late).unwrap();
+    assert_eq!(form_owner(&doc, late), None);
+}
+
+#[test]
+fn parser_association_in_tables() {
+    let doc = parse_document("<table><form id=f><tr><td><select id=s></select></table>");
+    assert_eq!(owner_of(&doc, "s"), Some(by_id(&doc, "f")));
+}
+
+#[test]
+fn form_attribute_wins_over_the_parser_association() {
+    let doc = parse_document("<form id=other></form><div><form id=f></div><input id=x form=other>");
+    assert_eq!(owner_of(&doc, "x"), Some(by_id(&doc, "other")));
+}
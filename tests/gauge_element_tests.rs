@@ -0,0 +1,114 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_meter_element::HTMLMeterElement;
+use brooster_web_parser::dom::elements::html_progress_element::HTMLProgressElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn progress(attributes: &str) -> (Document, HTMLProgressElement) {
+    let doc = parse_document(&format!("<progress id=g {attributes}></progress>"));
+    let node = doc.get_element_by_id("g").unwrap();
+    (doc, HTMLProgressElement::new(node))
+}
+
+fn meter(attributes: &str) -> (Document, HTMLMeterElement) {
+    let doc = parse_document(&format!("<meter id=g {attributes}></meter>"));
+    let node = doc.get_element_by_id("g").unwrap();
+    (doc, HTMLMeterElement::new(node))
+}
+
+#[test]
+fn progress_values() {
+    // attributes, value, max, position
+    let cases = [
+        ("", 0.0, 1.0, -1.0),
+        ("value=0.25", 0.25, 1.0, 0.25),
+        ("value=.5", 0.5, 1.0, 0.5),
+        ("value=3 max=4", 3.0, 4.0, 0.75),
+        ("value=5 max=4", 4.0, 4.0, 1.0),
+        ("value=-2", 0.0, 1.0, 0.0),
+        ("value=abc max=10", 0.0, 10.0, 0.0),
+        ("value=1e max=2", 1.0, 2.0, 0.5),
+        ("value='  +2.5e1xyz' max=100", 25.0, 100.0, 0.25),
+        ("value=0.5 max=0", 0.5, 1.0, 0.5),
+        ("value=0.5 max=-3", 0.5, 1.0, 0.5),
+        ("value=0.5 max=1e999", 0.5, 1.0, 0.5),
+        ("max=5", 0.0, 5.0, -1.0),
+    ];
+    for (attributes, value, max, position) in cases {
+        let (doc, p) = progress(attributes);
+        assert_eq!(p.value(&doc), value, "value of {attributes:?}");
+        assert_eq!(p.max(&doc), max, "max of {attributes:?}");
+        assert_eq!(p.position(&doc), position, "position of {attributes:?}");
+    }
+}
+
+#[test]
+fn progress_setters() {
+    let (mut doc, p) = progress("");
+    assert!(p.is_indeterminate(&doc));
+    p.set_value(&mut doc, 0.5);
+    assert!(!p.is_indeterminate(&doc));
+    assert_eq!(doc.get_attribute(p.node(), "value"), Some("0.5"));
+    p.set_max(&mut doc, 2.0);
+    assert_eq!(doc.get_attribute(p.node(), "max"), Some("2"));
+    p.set_max(&mut doc, 0.0);
+    assert_eq!(p.max(&doc), 2.0);
+    assert_eq!(p.position(&doc), 0.25);
+}
+
+#[test]
+fn meter_values() {
+    // attributes, [min, max, value, low, high, optimum]
+    let cases = [
+        ("", [0.0, 1.0, 0.0, 0.0, 1.0, 0.5]),
+        ("value=0.7", [0.0, 1.0, 0.7, 0.0, 1.0, 0.5]),
+        (
+            "min=10 max=20 value=5",
+            [10.0, 20.0, 10.0, 10.0, 20.0, 15.0],
+        ),
+        (
+            "min=10 max=5 value=30",
+            [10.0, 10.0, 10.0, 10.0, 10.0, 10.0],
+        ),
+        (
+            "max=100 value=.5e2 low=-4 high=120",
+            [0.0, 100.0, 50.0, 0.0, 100.0, 50.0],
+        ),
+        (
+            "max=100 low=60 high=40",
+            [0.0, 100.0, 0.0, 60.0, 60.0, 50.0],
+        ),
+        (
+            "max=10 low=x high=7 optimum=11",
+            [0.0, 10.0, 0.0, 0.0, 7.0, 10.0],
+        ),
+        (
+            "min=-5 max=5 value=-1e optimum=-9",
+            [-5.0, 5.0, -1.0, -5.0, 5.0, -5.0],
+        ),
+    ];
+    for (attributes, expected) in cases {
+        let (doc, m) = meter(attributes);
+        let actual = [
+            m.min(&doc),
+            m.max(&doc),
+            m.value(&doc),
+            m.low(&doc),
+            m.high(&doc),
+            m.optimum(&doc),
+        ];
+        assert_eq!(actual, expected, "{attributes:?}");
+    }
+}
+
+#[test]
+fn meter_setters_write_the_attributes() {
+    let (mut doc, m) = meter("");
+    m.set_max(&mut doc, 10.0);
+    m.set_low(&mut doc, 2.5);
+    m.set_high(&mut doc, 7.0);
+    m.set_value(&mut doc, f64::NAN);
+    assert_eq!(doc.get_attribute(m.node(), "max"), Some("10"));
+    assert_eq!(doc.get_attribute(m.node(), "low"), Some("2.5"));
+    assert_eq!(doc.get_attribute(m.node(), "value"), None);
+    assert_eq!((m.low(&doc), m.high(&doc)), (2.5, 7.0));
+}
@@ -0,0 +1,84 @@
+use brooster_web_parser::dom::elements::html_heading_element::{HTMLHeadingElement, OutlineEntry};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn entry(level: u8, text: &str, children: Vec<OutlineEntry>) -> OutlineEntry {
+    OutlineEntry {
+        level,
+        text: text.to_string(),
+        id: None,
+        children,
+    }
+}
+
+#[test]
+fn level_comes_from_the_tag_name() {
+    let doc = parse_document("<h1>a</h1><H4>b</H4><p>c</p><svg><h2></h2></svg>");
+    let levels: Vec<Option<u8>> = doc
+        .arena()
+        .descendants(doc.root())
+        .filter(|&id| doc.element(id).is_some())
+        .map(|id| HTMLHeadingElement::new(&doc, id).map(|heading| heading.level()))
+        .collect();
+    // html, head, body, h1, h4, p, svg, then h2 breaks out of the svg
+    assert_eq!(
+        levels,
+        [None, None, None, Some(1), Some(4), None, None, Some(2)]
+    );
+}
+
+#[test]
+fn headings_nest_under_higher_ranks() {
+    let doc = parse_document(
+        "<h1>Title</h1><h2>One</h2><h3>One.a</h3><h3>One.b</h3><h2>Two</h2>\
+         <h4>Two.deep</h4><h1>Appendix</h1>",
+    );
+    assert_eq!(
+        doc.generate_outline(),
+        [
+            entry(
+                1,
+                "Title",
+                vec![
+                    entry(
+                        2,
+                        "One",
+                        vec![entry(3, "One.a", vec![]), entry(3, "One.b", vec![])]
+                    ),
+                    entry(2, "Two", vec![entry(4, "Two.deep", vec![])]),
+                ]
+            ),
+            entry(1, "Appendix", vec![]),
+        ]
+    );
+}
+
+#[test]
+fn leading_lower_ranks_start_the_outline() {
+    let doc = parse_document("<h3>Intro</h3><h2>Body</h2><h2>End</h2>");
+    assert_eq!(
+        doc.generate_outline(),
+        [
+            entry(3, "Intro", vec![]),
+            entry(2, "Body", vec![]),
+            entry(2, "End", vec![]),
+        ]
+    );
+}
+
+#[test]
+fn entries_carry_ids_and_collapsed_text() {
+    let doc = parse_document(
+        "<h1 id=top>  Hello\n <em>big</em>   world </h1><h2 id=\"\">x</h2>\
+         <template><h1>hidden</h1></template>",
+    );
+    let outline = doc.generate_outline();
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].text, "Hello big world");
+    assert_eq!(outline[0].id.as_deref(), Some("top"));
+    assert_eq!(outline[0].children, [entry(2, "x", vec![])]);
+}
+
+#[test]
+fn empty_outline_without_headings() {
+    assert!(parse_document("<p>x</p>").generate_outline().is_empty());
+}
@@ -0,0 +1,173 @@
+//! Runs html5lib tree construction fixtures (`.dat` files) through `parse_document` and
+//! compares the resulting trees. The fixtures vendored under `tests/fixtures/tree-construction`
+//! always run; point `HTML5LIB_TESTS_DIR` at a checkout of html5lib-tests to run the full
+//! suite as well.
+
+use brooster_web_parser::dom::node::{Element, Namespace};
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_fragment};
+use brooster_web_parser::dom::testing::tree_digest;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tests known to fail, as `file.dat:index` with the index counted from 0 within the file
+const KNOWN_FAILURES: &[&str] = &[];
+
+#[derive(Debug, Default)]
+struct TreeTest {
+    data: String,
+    fragment_context: Option<String>,
+    scripting: Option<bool>,
+    document: String,
+}
+
+fn parse_dat(content: &str) -> Vec<TreeTest> {
+    let mut tests = Vec::new();
+    let mut current: Option<(TreeTest, String, Vec<&str>)> = None;
+    let finish_section = |test: &mut TreeTest, section: &str, lines: &mut Vec<&str>| {
+        match section {
+            "#data" => test.data = lines.join("\n"),
+            "#document-fragment" => test.fragment_context = Some(lines.join("\n")),
+            "#document" => {
+                if lines.last() == Some(&"") {
+                    lines.pop();
+                }
+                test.document = lines.join("\n");
+            }
+            _ => {}
+        }
+        lines.clear();
+    };
+    for line in content.lines() {
+        let is_header = matches!(
+            line,
+            "#data"
+                | "#errors"
+                | "#new-errors"
+                | "#document-fragment"
+                | "#script-off"
+                | "#script-on"
+                | "#document"
+        );
+        if !is_header {
+            if let Some((_, _, lines)) = current.as_mut() {
+                lines.push(line);
+            }
+            continue;
+        }
+        if line == "#data" {
+            if let Some((mut test, section, mut lines)) = current.take() {
+                finish_section(&mut test, &section, &mut lines);
+                tests.push(test);
+            }
+            current = Some((TreeTest::default(), line.to_string(), Vec::new()));
+            continue;
+        }
+        if let Some((test, section, lines)) = current.as_mut() {
+            finish_section(test, section, lines);
+            match line {
+                "#script-on" => test.scripting = Some(true),
+                "#script-off" => test.scripting = Some(false),
+                _ => {}
+            }
+            *section = line.to_string();
+        }
+    }
+    if let Some((mut test, section, mut lines)) = current.take() {
+        finish_section(&mut test, &section, &mut lines);
+        tests.push(test);
+    }
+    tests
+}
+
+fn fixture_files() -> Vec<PathBuf> {
+    let mut directories =
+        vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tree-construction")];
+    if let Ok(dir) = std::env::var("HTML5LIB_TESTS_DIR") {
+        directories.push(Path::new(&dir).join("tree-construction"));
+    }
+    let mut files: Vec<PathBuf> = directories
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "dat"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn parse_dat_splits_sections() {
+    let tests = parse_dat(
+        "#data\n<p>a\nb\n#errors\n(1,1): x\n#document\n| <html>\n|   \"a\nb\"\n\n#data\nx\n#errors\n#document-fragment\ntd\n#script-on\n#document\n| \"x\"\n",
+    );
+    assert_eq!(tests.len(), 2);
+    assert_eq!(tests[0].data, "<p>a\nb");
+    assert_eq!(tests[0].document, "| <html>\n|   \"a\nb\"");
+    assert_eq!(tests[1].fragment_context.as_deref(), Some("td"));
+    assert_eq!(tests[1].scripting, Some(true));
+    assert_eq!(tests[1].document, "| \"x\"");
+}
+
+/// The context element of a `#document-fragment` section: `td`, `svg path` or `math mi`
+fn context_element(context: &str) -> Element {
+    let mut element = Element::new("");
+    match context.split_once(' ') {
+        Some(("svg", name)) => {
+            element.namespace = Namespace::Svg;
+            element.name = name.to_string();
+        }
+        Some(("math", name)) => {
+            element.namespace = Namespace::MathMl;
+            element.name = name.to_string();
+        }
+        _ => element.name = context.to_string(),
+    }
+    element
+}
+
+#[test]
+fn html5lib_tree_construction() {
+    let (mut passed, mut skipped, mut known) = (0, 0, 0);
+    let mut failures = Vec::new();
+    for file in fixture_files() {
+        let name = file.file_name().unwrap().to_string_lossy().to_string();
+        let content = fs::read_to_string(&file).unwrap();
+        for (index, test) in parse_dat(&content).into_iter().enumerate() {
+            let id = format!("{name}:{index}");
+            // scripting is never enabled
+            if test.scripting == Some(true) {
+                skipped += 1;
+                continue;
+            }
+            let actual = match &test.fragment_context {
+                Some(context) => {
+                    let doc = parse_fragment(&test.data, &context_element(context));
+                    tree_digest(&doc, doc.document_element().unwrap())
+                }
+                None => {
+                    let doc = parse_document(&test.data);
+                    tree_digest(&doc, doc.root())
+                }
+            };
+            let ok = actual.trim_end_matches('\n') == test.document;
+            match (ok, KNOWN_FAILURES.contains(&id.as_str())) {
+                (true, _) => passed += 1,
+                (false, true) => known += 1,
+                (false, false) => failures.push(format!(
+                    "{id}\n#data\n{}\n#expected\n{}\n#actual\n{actual}",
+                    test.data, test.document
+                )),
+            }
+        }
+    }
+    println!(
+        "html5lib tree construction: {passed} passed, {} failed, {known} known failures, {skipped} skipped",
+        failures.len()
+    );
+    assert!(
+        failures.is_empty(),
+        "unexpected failures:\n\n{}",
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,127 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_form_element::HTMLFormElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::node::NodeId;
+
+fn append_element(doc: &mut Document, parent: NodeId, name: &str) -> NodeId {
+    let element = doc.create_element(name);
+    doc.append_child(parent, element).unwrap();
+    element
+}
+
+#[test]
+fn tag_name_collection_reflects_appends_and_removals() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let body = append_element(&mut doc, root, "body");
+    let first = append_element(&mut doc, body, "p");
+
+    let paragraphs = doc.get_elements_by_tag_name("P");
+    assert_eq!(paragraphs.len(&doc), 1);
+    assert_eq!(paragraphs.item(&doc, 0), Some(first));
+
+    let div = append_element(&mut doc, body, "div");
+    let nested = append_element(&mut doc, div, "p");
+    assert_eq!(paragraphs.len(&doc), 2);
+    assert_eq!(paragraphs.item(&doc, 1), Some(nested));
+    assert_eq!(paragraphs.item(&doc, 2), None);
+
+    doc.remove_child(body, first).unwrap();
+    assert_eq!(paragraphs.iter(&doc).collect::<Vec<_>>(), vec![nested]);
+}
+
+#[test]
+fn star_matches_every_element_in_tree_order() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let html = append_element(&mut doc, root, "html");
+    let head = append_element(&mut doc, html, "head");
+    let body = append_element(&mut doc, html, "body");
+    let text = doc.create_text_node("not an element");
+    doc.append_child(body, text).unwrap();
+
+    let all = doc.get_elements_by_tag_name("*");
+    assert_eq!(all.iter(&doc).collect::<Vec<_>>(), vec![html, head, body]);
+}
+
+#[test]
+fn class_name_collection_tracks_attribute_changes() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let a = append_element(&mut doc, root, "div");
+    let b = append_element(&mut doc, a, "span");
+    doc.set_attribute(a, "class", "card wide");
+    doc.set_attribute(b, "class", "card");
+
+    let wide_cards = doc.get_elements_by_class_name(" card  wide ");
+    assert_eq!(wide_cards.iter(&doc).collect::<Vec<_>>(), vec![a]);
+
+    doc.set_attribute(b, "class", "wide card");
+    assert_eq!(wide_cards.iter(&doc).collect::<Vec<_>>(), vec![a, b]);
+
+    doc.remove_attribute(a, "class");
+    assert_eq!(wide_cards.iter(&doc).collect::<Vec<_>>(), vec![b]);
+
+    assert!(doc.get_elements_by_class_name("   ").is_empty(&doc));
+}
+
+#[test]
+fn named_item_prefers_id_over_name() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let form = append_element(&mut doc, root, "form");
+    let by_name = append_element(&mut doc, form, "input");
+    doc.set_attribute(by_name, "name", "user");
+
+    let inputs = doc.get_elements_by_tag_name("input");
+    assert_eq!(inputs.named_item(&doc, "user"), Some(by_name));
+
+    let by_id = append_element(&mut doc, form, "input");
+    doc.set_attribute(by_id, "id", "user");
+    assert_eq!(inputs.named_item(&doc, "user"), Some(by_id));
+    assert_eq!(inputs.named_item(&doc, ""), None);
+    assert_eq!(inputs.named_item(&doc, "missing"), None);
+}
+
+#[test]
+fn select_selected_options_is_live() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let select_node = append_element(&mut doc, root, "select");
+    let one = append_element(&mut doc, select_node, "option");
+    let two = append_element(&mut doc, select_node, "option");
+    let select = HTMLSelectElement::new(select_node);
+
+    let selected = select.selected_options();
+    assert!(selected.is_empty(&doc));
+
+    doc.set_attribute(two, "selected", "");
+    assert_eq!(selected.iter(&doc).collect::<Vec<_>>(), vec![two]);
+
+    doc.set_attribute(one, "selected", "");
+    assert_eq!(selected.iter(&doc).collect::<Vec<_>>(), vec![one, two]);
+}
+
+#[test]
+fn form_elements_lists_controls_except_image_buttons() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let form_node = append_element(&mut doc, root, "form");
+    let text = append_element(&mut doc, form_node, "input");
+    let image = append_element(&mut doc, form_node, "input");
+    doc.set_attribute(image, "type", "IMAGE");
+    append_element(&mut doc, form_node, "label");
+    let fieldset = append_element(&mut doc, form_node, "fieldset");
+    let textarea = append_element(&mut doc, fieldset, "textarea");
+    let form = HTMLFormElement::new(form_node);
+
+    let elements = form.elements();
+    assert_eq!(
+        elements.iter(&doc).collect::<Vec<_>>(),
+        vec![text, fieldset, textarea]
+    );
+
+    let button = append_element(&mut doc, form_node, "button");
+    assert_eq!(elements.len(&doc), 4);
+    assert_eq!(elements.item(&doc, 3), Some(button));
+}
@@ -0,0 +1,41 @@
+use brooster_web_parser::dom::canonicalizer::canonicalize;
+use brooster_web_parser::dom::html_diff::html_diff;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+#[test]
+fn identical_trees_produce_no_diff() {
+    let before = "<!DOCTYPE html><div id=a class=x>Hello   world</div>";
+    let after = "<!DOCTYPE html>\n<html><body>\n<div class=\"x\" id='a'>Hello world</div>";
+    assert_eq!(html_diff(before, after), "");
+}
+
+#[test]
+fn changed_attribute_is_reported_as_removed_and_added_line() {
+    let diff = html_diff(
+        "<!DOCTYPE html><div class=\"old\">text</div>",
+        "<!DOCTYPE html><div class=\"new\">text</div>",
+    );
+    let lines: Vec<&str> = diff.lines().collect();
+    assert!(lines[0].starts_with("@@ "));
+    assert!(lines.contains(&"-     <div class=\"old\">"));
+    assert!(lines.contains(&"+     <div class=\"new\">"));
+    assert!(lines.contains(&"        text"));
+}
+
+#[test]
+fn inserted_element_only_adds_lines() {
+    let diff = html_diff("<ul><li>a<li>c</ul>", "<ul><li>a<li>b<li>c</ul>");
+    assert!(diff.starts_with("@@ -7,6 +7,9 @@\n"));
+    assert_eq!(diff.lines().filter(|l| l.starts_with('+')).count(), 3);
+    assert_eq!(diff.lines().filter(|l| l.starts_with('-')).count(), 0);
+    assert!(diff.contains("+         b\n"));
+}
+
+#[test]
+fn canonical_form_is_one_node_per_line() {
+    let doc = parse_document("<!DOCTYPE html><title>T</title><p b=2 a=1>x<br>y");
+    assert_eq!(
+        canonicalize(&doc),
+        "<!DOCTYPE html>\n<html>\n  <head>\n    <title>\n      T\n    </title>\n  </head>\n  <body>\n    <p a=\"1\" b=\"2\">\n      x\n      <br>\n      y\n    </p>\n  </body>\n</html>\n"
+    );
+}
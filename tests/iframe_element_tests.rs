@@ -0,0 +1,83 @@
+use brooster_web_parser::dom::document::{Document, QuirksMode};
+use brooster_web_parser::dom::elements::html_iframe_element::HTMLIFrameElement;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_srcdoc_document};
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn iframe(doc: &Document) -> HTMLIFrameElement {
+    HTMLIFrameElement::new(doc.get_elements_by_tag_name("iframe").item(doc, 0).unwrap())
+}
+
+#[test]
+fn sandbox_is_a_token_list() {
+    let mut doc = parse_document("<iframe sandbox=' allow-scripts\tallow-forms allow-scripts'>");
+    let sandbox = iframe(&doc).sandbox();
+    assert_eq!(sandbox.tokens(&doc), ["allow-scripts", "allow-forms"]);
+    assert!(sandbox.contains(&doc, "allow-forms"));
+    assert!(!sandbox.contains(&doc, "allow-popups"));
+    sandbox.toggle(&mut doc, "allow-forms", None).unwrap();
+    assert_eq!(sandbox.value(&doc), "allow-scripts");
+}
+
+#[test]
+fn srcdoc_entities_and_nested_iframe_are_markup() {
+    let doc = parse_document(
+        "<iframe srcdoc='<p>Tom &amp;amp; Jerry</p><iframe srcdoc=&quot;x&quot;></iframe>'>\
+         </iframe>",
+    );
+    let frame = iframe(&doc);
+    assert_eq!(
+        frame.srcdoc(&doc),
+        "<p>Tom &amp; Jerry</p><iframe srcdoc=\"x\"></iframe>"
+    );
+    // the iframe content in the outer document is text, not elements
+    assert_eq!(doc.get_elements_by_tag_name("iframe").len(&doc), 1);
+
+    let inner = frame.parse_srcdoc(&doc);
+    let body = inner.body().unwrap();
+    assert_eq!(
+        serialize_children(&inner, body),
+        "<p>Tom &amp; Jerry</p><iframe srcdoc=\"x\"></iframe>"
+    );
+    let nested = iframe(&inner);
+    assert_eq!(nested.srcdoc(&inner), "x");
+}
+
+#[test]
+fn srcdoc_documents_are_never_quirky() {
+    let doc = parse_srcdoc_document("<p>no doctype</p>");
+    assert_eq!(doc.quirks_mode(), QuirksMode::NoQuirks);
+    assert!(doc.doctype().is_none());
+
+    let legacy = "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 3.2 Final//EN\"><p>x";
+    assert_eq!(
+        parse_srcdoc_document(legacy).quirks_mode(),
+        QuirksMode::NoQuirks
+    );
+    assert_eq!(parse_document(legacy).quirks_mode(), QuirksMode::Quirks);
+
+    let doc = parse_document("<iframe></iframe>");
+    let empty = iframe(&doc).parse_srcdoc(&doc);
+    assert_eq!(empty.quirks_mode(), QuirksMode::NoQuirks);
+}
+
+#[test]
+fn reflected_attributes() {
+    let mut doc = parse_document(
+        "<iframe src=/embed name=frame allow=fullscreen width=100% height=300 loading=LAZY>",
+    );
+    doc.set_url("https://example.com/page");
+    let frame = iframe(&doc);
+    assert_eq!(
+        frame.src(&doc).as_deref(),
+        Some("https://example.com/embed")
+    );
+    assert_eq!(frame.name(&doc), "frame");
+    assert_eq!(frame.allow(&doc), "fullscreen");
+    assert_eq!(
+        (frame.width(&doc), frame.height(&doc)),
+        ("100%".to_string(), "300".to_string())
+    );
+    assert_eq!(frame.loading(&doc), "lazy");
+    frame.set_loading(&mut doc, "auto");
+    assert_eq!(frame.loading(&doc), "eager");
+}
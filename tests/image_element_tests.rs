@@ -0,0 +1,158 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_image_element::{
+    parse_srcset, CandidateDescriptor, HTMLImageElement, ImageCandidate,
+};
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+use CandidateDescriptor::{Density, Width};
+
+fn image(doc: &Document) -> HTMLImageElement {
+    HTMLImageElement::new(doc.images().item(doc, 0).unwrap())
+}
+
+fn candidate(url: &str, descriptor: Option<CandidateDescriptor>) -> ImageCandidate {
+    ImageCandidate {
+        url: url.to_string(),
+        descriptor,
+    }
+}
+
+#[test]
+fn srcset_examples() {
+    let cases = [
+        (
+            "a.png 1x, b.png 2x",
+            vec![
+                candidate("a.png", Some(Density(1.0))),
+                candidate("b.png", Some(Density(2.0))),
+            ],
+        ),
+        (
+            "small.jpg 480w,large.jpg 1080w",
+            vec![
+                candidate("small.jpg", Some(Width(480))),
+                candidate("large.jpg", Some(Width(1080))),
+            ],
+        ),
+        ("a.png", vec![candidate("a.png", None)]),
+        (
+            "a.png,, b.png,,,",
+            vec![candidate("a.png", None), candidate("b.png", None)],
+        ),
+        (
+            "  ,\ta.png  1.5x  ,  b.png\n",
+            vec![
+                candidate("a.png", Some(Density(1.5))),
+                candidate("b.png", None),
+            ],
+        ),
+        (
+            "data:image/png;base64,iVBO 1x, b.png 2x",
+            vec![
+                candidate("data:image/png;base64,iVBO", Some(Density(1.0))),
+                candidate("b.png", Some(Density(2.0))),
+            ],
+        ),
+        ("a,b.png 2x", vec![candidate("a,b.png", Some(Density(2.0)))]),
+        (
+            "a.png 100w 50h, b.png 2x",
+            vec![
+                candidate("a.png", Some(Width(100))),
+                candidate("b.png", Some(Density(2.0))),
+            ],
+        ),
+        ("", vec![]),
+        (" , , ", vec![]),
+    ];
+    for (srcset, expected) in cases {
+        assert_eq!(parse_srcset(srcset), expected, "{srcset:?}");
+    }
+}
+
+#[test]
+fn srcset_drops_invalid_candidates() {
+    let cases = [
+        "a.png 2w 1x",
+        "a.png 0w",
+        "a.png -1x",
+        "a.png +1x",
+        "a.png 1.x",
+        "a.png 1e",
+        "a.png 50h",
+        "a.png 1x 1x",
+        "a.png 10q",
+        "a.png 2x 50h",
+        "a.png foo(1, 2)",
+    ];
+    for srcset in cases {
+        assert_eq!(
+            parse_srcset(&format!("{srcset}, ok.png")),
+            [candidate("ok.png", None)],
+            "{srcset:?}"
+        );
+    }
+}
+
+#[test]
+fn srcset_edge_cases_around_commas() {
+    // a comma in parentheses does not end the descriptors
+    assert_eq!(
+        parse_srcset("a.png foo(1, 2) 2x, b.png"),
+        [candidate("b.png", None)]
+    );
+    // a comma right after the descriptors ends the candidate
+    assert_eq!(
+        parse_srcset("a.png 2x,b.png 3x"),
+        [
+            candidate("a.png", Some(Density(2.0))),
+            candidate("b.png", Some(Density(3.0)))
+        ]
+    );
+    assert_eq!(
+        parse_srcset("a.png 0.5e1x"),
+        [candidate("a.png", Some(Density(5.0)))]
+    );
+}
+
+#[test]
+fn width_and_height() {
+    let mut doc = parse_document("<img width=abc height=-5>");
+    let img = image(&doc);
+    assert_eq!((img.width(&doc), img.height(&doc)), (0, 0));
+    doc.set_attribute(img.node(), "width", " 40px");
+    assert_eq!(img.width(&doc), 40);
+    img.set_height(&mut doc, 30);
+    assert_eq!(img.height(&doc), 30);
+    img.set_width(&mut doc, u32::MAX);
+    assert_eq!(doc.get_attribute(img.node(), "width"), Some("0"));
+}
+
+#[test]
+fn enumerated_attributes() {
+    let doc = parse_document("<img loading=LAZY decoding=Async>");
+    assert_eq!(image(&doc).loading(&doc), "lazy");
+    assert_eq!(image(&doc).decoding(&doc), "async");
+    let doc = parse_document("<img loading=soon decoding=fast>");
+    assert_eq!(image(&doc).loading(&doc), "eager");
+    assert_eq!(image(&doc).decoding(&doc), "auto");
+}
+
+#[test]
+fn src_alt_and_current_src() {
+    let base = "https://example.com/gallery/";
+    let doc = parse_document_with_url("<img src=a.png alt='A cat'>", base);
+    let img = image(&doc);
+    assert_eq!(
+        img.src(&doc).as_deref(),
+        Some("https://example.com/gallery/a.png")
+    );
+    assert_eq!(img.alt(&doc), "A cat");
+    assert_eq!(img.current_src(&doc), "https://example.com/gallery/a.png");
+
+    let doc = parse_document_with_url("<img src=a.png srcset='big.png 2x, huge.png 3x'>", base);
+    assert_eq!(
+        image(&doc).current_src(&doc),
+        "https://example.com/gallery/big.png"
+    );
+    let doc = parse_document("<img>");
+    assert_eq!(image(&doc).current_src(&doc), "");
+}
@@ -0,0 +1,109 @@
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::{AdjacentPosition, Document};
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn setup() -> (Document, NodeId) {
+    let doc = parse_document("<div id=target><i>child</i></div>");
+    let target = doc.get_element_by_id("target").unwrap();
+    (doc, target)
+}
+
+fn body_html(doc: &Document) -> String {
+    serialize_children(doc, doc.body().unwrap())
+}
+
+#[test]
+fn html_at_every_position() {
+    let (mut doc, target) = setup();
+    for (position, html) in [
+        (AdjacentPosition::BeforeBegin, "<p>1</p>"),
+        (AdjacentPosition::AfterBegin, "<b>2</b>"),
+        (AdjacentPosition::BeforeEnd, "<u>3</u>"),
+        (AdjacentPosition::AfterEnd, "4<hr>"),
+    ] {
+        doc.insert_adjacent_html(target, position, html).unwrap();
+    }
+    assert_eq!(
+        body_html(&doc),
+        "<p>1</p><div id=\"target\"><b>2</b><i>child</i><u>3</u></div>4<hr>"
+    );
+}
+
+#[test]
+fn html_is_parsed_in_the_right_context() {
+    let mut doc = parse_document("<table><tbody><tr id=row><td>a</td></tr></tbody></table>");
+    let row = doc.get_element_by_id("row").unwrap();
+    // inner positions parse in the tr, outer positions in the tbody
+    doc.insert_adjacent_html(row, AdjacentPosition::BeforeEnd, "<td>b")
+        .unwrap();
+    doc.insert_adjacent_html(row, AdjacentPosition::AfterEnd, "<tr><td>c")
+        .unwrap();
+    assert_eq!(
+        body_html(&doc),
+        "<table><tbody><tr id=\"row\"><td>a</td><td>b</td></tr><tr><td>c</td></tr></tbody></table>"
+    );
+
+    let mut doc = parse_document("<title>t</title>");
+    let title = doc.get_elements_by_tag_name("title").item(&doc, 0).unwrap();
+    doc.insert_adjacent_html(title, AdjacentPosition::BeforeEnd, "<b>&amp;</b>")
+        .unwrap();
+    assert_eq!(serialize_children(&doc, title), "t&lt;b&gt;&amp;&lt;/b&gt;");
+}
+
+#[test]
+fn html_outside_needs_an_element_parent() {
+    let (mut doc, target) = setup();
+    let detached = doc.create_element("span");
+    assert_eq!(
+        doc.insert_adjacent_html(detached, AdjacentPosition::AfterEnd, "<b>x</b>"),
+        Err(DomError::NoModificationAllowed)
+    );
+    let html = doc.document_element().unwrap();
+    assert_eq!(
+        doc.insert_adjacent_html(html, AdjacentPosition::BeforeBegin, "<b>x</b>"),
+        Err(DomError::NoModificationAllowed)
+    );
+    // inside an html element the markup is parsed as body content
+    doc.insert_adjacent_html(html, AdjacentPosition::BeforeEnd, "<b>x</b>")
+        .unwrap();
+    let last = doc.arena().get(html).last_child().unwrap();
+    assert!(doc.arena().get(last).is_element_named("b"));
+    assert_eq!(doc.arena().parent(target), doc.body());
+}
+
+#[test]
+fn element_and_text() {
+    let (mut doc, target) = setup();
+    let em = doc.create_element("em");
+    assert_eq!(
+        doc.insert_adjacent_element(target, AdjacentPosition::AfterBegin, em),
+        Ok(Some(em))
+    );
+    doc.insert_adjacent_text(target, AdjacentPosition::AfterEnd, "tail")
+        .unwrap();
+    assert_eq!(
+        body_html(&doc),
+        "<div id=\"target\"><em></em><i>child</i></div>tail"
+    );
+
+    // moving an existing element
+    let child = doc.get_elements_by_tag_name("i").item(&doc, 0).unwrap();
+    doc.insert_adjacent_element(target, AdjacentPosition::BeforeBegin, child)
+        .unwrap();
+    assert_eq!(
+        body_html(&doc),
+        "<i>child</i><div id=\"target\"><em></em></div>tail"
+    );
+
+    let detached = doc.create_element("span");
+    assert_eq!(
+        doc.insert_adjacent_element(detached, AdjacentPosition::BeforeBegin, em),
+        Ok(None)
+    );
+    assert_eq!(
+        doc.insert_adjacent_element(em, AdjacentPosition::BeforeEnd, target),
+        Err(DomError::HierarchyRequest)
+    );
+}
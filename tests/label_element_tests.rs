@@ -0,0 +1,50 @@
+mod common;
+
+use brooster_web_parser::dom::elements::html_label_element::HTMLLabelElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn for_attribute_names_the_control() {
+    let mut doc =
+        parse_document("<form id=f><select id=s></select></form><label id=l for=s>Pick</label>");
+    let l = HTMLLabelElement::new(by_id(&doc, "l"));
+    assert_eq!(l.html_for(&doc), "s");
+    assert_eq!(l.control(&doc), Some(by_id(&doc, "s")));
+    assert_eq!(l.form(&doc).map(|form| form.node()), Some(by_id(&doc, "f")));
+
+    l.set_html_for(&mut doc, "nothing");
+    assert_eq!(l.control(&doc), None);
+    assert!(l.form(&doc).is_none());
+}
+
+#[test]
+fn wrapping_label_labels_its_first_labelable_descendant() {
+    let doc = parse_document(
+        "<label id=l><input type=hidden><span><input id=text></span><textarea></textarea></label>",
+    );
+    let l = HTMLLabelElement::new(by_id(&doc, "l"));
+    assert_eq!(l.html_for(&doc), "");
+    assert_eq!(l.control(&doc), Some(by_id(&doc, "text")));
+    assert!(l.form(&doc).is_none());
+}
+
+#[test]
+fn for_attribute_pointing_at_a_non_labelable_element() {
+    let doc = parse_document("<div id=d></div><label id=l for=d><input></label>");
+    assert_eq!(HTMLLabelElement::new(by_id(&doc, "l")).control(&doc), None);
+}
+
+#[test]
+fn select_labels_are_the_labels_controlling_it() {
+    let doc = parse_document(
+        "<label id=wrap>Outer <select id=s></select></label>\
+         <label id=for for=s></label><label id=first><input><select></select></label>\
+         <label id=other for=missing><select></select></label>",
+    );
+    let select = HTMLSelectElement::new(by_id(&doc, "s"));
+    let labels: Vec<NodeId> = select.labels(&doc).iter(&doc).collect();
+    assert_eq!(labels, [by_id(&doc, "wrap"), by_id(&doc, "for")]);
+}
@@ -0,0 +1,66 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use std::ops::Range;
+
+const INPUTS: &[&str] = &[
+    r#"<a href="/home" CLASS='x y' data-n=1 checked>"#,
+    "<img src=a.png alt='caf&eacute; &amp; &#x41;'/>",
+    "<p title=\"a&notit;b\" id=x\0y =eq></p>",
+    "<input value=\"\u{1F600}\" value=dup disabled=>",
+    "<div a=1b\"c d>",
+];
+
+fn tokenize(input: &str, lazy: bool) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.set_lazy_attributes(lazy);
+    let mut tokens = Vec::new();
+    loop {
+        let mut token = tokenizer.next_token();
+        tokenizer.decode_attributes(&mut token);
+        if token == Token::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+#[test]
+fn decoded_lazy_attributes_match_eager_tokenization() {
+    for input in INPUTS {
+        assert_eq!(tokenize(input, true), tokenize(input, false), "{input}");
+    }
+}
+
+#[test]
+fn lazy_start_tags_only_carry_ranges() {
+    let input = r#"<a href="/x" title=t>"#;
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.set_lazy_attributes(true);
+    let token = tokenizer.next_token();
+    let Token::StartTag {
+        attributes,
+        raw_attributes,
+        ..
+    } = &token
+    else {
+        panic!("expected a start tag, got {token:?}");
+    };
+    assert!(attributes.is_empty());
+    let expected: Vec<(Range<usize>, Range<usize>)> = vec![(3..7, 9..11), (13..18, 19..20)];
+    assert_eq!(raw_attributes, &expected);
+    assert_eq!(&input[9..11], "/x");
+}
+
+#[test]
+fn attribute_value_decodes_a_single_attribute() {
+    let input = "<a TITLE='x &lt; y' href=/a>";
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.set_lazy_attributes(true);
+    let token = tokenizer.next_token();
+    assert_eq!(tokenizer.attribute_value(&token, "title").as_deref(), Some("x < y"));
+    assert_eq!(tokenizer.attribute_value(&token, "href").as_deref(), Some("/a"));
+    assert_eq!(tokenizer.attribute_value(&token, "missing"), None);
+
+    let mut eager = Tokenizer::new(input.as_bytes());
+    let token = eager.next_token();
+    assert_eq!(eager.attribute_value(&token, "title").as_deref(), Some("x < y"));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_link_element::HTMLLinkElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+use common::by_id;
+
+#[test]
+fn rel_classification() {
+    let doc = parse_document(
+        "<link id=alt rel='Stylesheet alternate' href=a.css>\
+         <link id=icon rel='shortcut ICON' href=i.ico>\
+         <link id=preload rel=preload as=font href=f.woff2>\
+         <link id=stylesheets rel=stylesheets>",
+    );
+    let alt = HTMLLinkElement::new(by_id(&doc, "alt"));
+    assert!(alt.is_stylesheet(&doc) && !alt.is_icon(&doc) && !alt.is_preload(&doc));
+    assert_eq!(alt.rel_list().tokens(&doc), ["Stylesheet", "alternate"]);
+    assert!(HTMLLinkElement::new(by_id(&doc, "icon")).is_icon(&doc));
+    let preload = HTMLLinkElement::new(by_id(&doc, "preload"));
+    assert!(preload.is_preload(&doc));
+    assert_eq!(preload.as_(&doc), "font");
+    assert!(!HTMLLinkElement::new(by_id(&doc, "stylesheets")).is_stylesheet(&doc));
+}
+
+#[test]
+fn sizes_token_list() {
+    let mut doc = parse_document("<link id=l rel=icon sizes='32x32 64x64'>");
+    let sizes = HTMLLinkElement::new(by_id(&doc, "l")).sizes();
+    assert_eq!(sizes.tokens(&doc), ["32x32", "64x64"]);
+    assert!(sizes.contains(&doc, "64x64"));
+    sizes.add(&mut doc, &["any"]).unwrap();
+    assert_eq!(sizes.len(&doc), 3);
+}
+
+#[test]
+fn protocol_relative_href() {
+    let doc = parse_document_with_url(
+        "<link id=l rel=stylesheet href=//cdn.example.net/site.css>",
+        "https://example.com/docs/",
+    );
+    assert_eq!(
+        HTMLLinkElement::new(by_id(&doc, "l")).href(&doc).as_deref(),
+        Some("https://cdn.example.net/site.css")
+    );
+}
+
+#[test]
+fn stylesheet_links_in_tree_order() {
+    let mut doc = parse_document(
+        "<link id=a rel=stylesheet><link id=icon rel=icon><style></style>\
+         <body><link id=b rel='alternate STYLESHEET'>",
+    );
+    let ids = |doc: &Document| -> Vec<NodeId> { doc.stylesheet_links().iter(doc).collect() };
+    let (a, b) = (
+        doc.get_element_by_id("a").unwrap(),
+        doc.get_element_by_id("b").unwrap(),
+    );
+    assert_eq!(ids(&doc), [a, b]);
+    HTMLLinkElement::new(by_id(&doc, "icon")).set_rel(&mut doc, "stylesheet");
+    assert_eq!(ids(&doc).len(), 3);
+}
+
+#[test]
+fn reflected_attributes() {
+    let mut doc = parse_document("<link id=l type=text/css media=print hreflang=fr>");
+    let l = HTMLLinkElement::new(by_id(&doc, "l"));
+    assert_eq!(l.r#type(&doc), "text/css");
+    assert_eq!(l.media(&doc), "print");
+    assert_eq!(l.hreflang(&doc), "fr");
+    l.set_media(&mut doc, "screen");
+    l.set_as(&mut doc, "style");
+    assert_eq!(
+        (l.media(&doc), l.as_(&doc)),
+        ("screen".to_string(), "style".to_string())
+    );
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_li_element::HTMLLIElement;
+use brooster_web_parser::dom::elements::html_o_list_element::HTMLOListElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::nth;
+
+fn list(doc: &Document, index: usize) -> HTMLOListElement {
+    HTMLOListElement::new(nth(doc, "ol", index))
+}
+
+#[test]
+fn items_count_up_from_start() {
+    let doc = parse_document("<ol><li>a<li>b<li>c</ol><ol start=\"-2\"><li>a<li>b<li>c<li>d</ol>");
+    assert_eq!(list(&doc, 0).start(&doc), 1);
+    assert_eq!(list(&doc, 0).item_numbers(&doc), vec![1, 2, 3]);
+    assert_eq!(list(&doc, 1).start(&doc), -2);
+    assert_eq!(list(&doc, 1).item_numbers(&doc), vec![-2, -1, 0, 1]);
+}
+
+#[test]
+fn reversed_lists_count_down() {
+    let doc = parse_document(
+        "<ol reversed><li>a<li>b<li>c</ol><ol reversed start=\"10\"><li>a<li>b</ol>",
+    );
+    assert!(list(&doc, 0).reversed(&doc));
+    assert_eq!(list(&doc, 0).item_numbers(&doc), vec![3, 2, 1]);
+    assert_eq!(list(&doc, 1).item_numbers(&doc), vec![10, 9]);
+}
+
+#[test]
+fn a_value_override_restarts_the_sequence() {
+    let doc = parse_document(
+        "<ol><li>a<li value=\"10\">b<li>c<li value=\"x\">d</ol>\
+         <ol reversed><li>a<li value=\"7\">b<li>c</ol>",
+    );
+    assert_eq!(list(&doc, 0).item_numbers(&doc), vec![1, 10, 11, 12]);
+    assert_eq!(list(&doc, 1).item_numbers(&doc), vec![3, 7, 6]);
+}
+
+#[test]
+fn nested_lists_keep_their_own_numbering() {
+    let doc = parse_document(
+        "<ol><li>a<ol start=\"5\"><li>a.1<li>a.2</ol><li>b<ul><li>b.1</ul><li>c</ol>",
+    );
+    let outer = list(&doc, 0);
+    assert_eq!(outer.items(&doc).len(), 3);
+    assert_eq!(outer.item_numbers(&doc), vec![1, 2, 3]);
+    assert_eq!(list(&doc, 1).item_numbers(&doc), vec![5, 6]);
+}
+
+#[test]
+fn integer_attributes_follow_the_parsing_rules() {
+    let mut doc = parse_document(
+        "<ol start=\" +4th\"></ol><ol start=\"abc\"></ol><ol start=\"99999999999\"></ol>\
+         <ol><li value=\"\t-3 \">a<li value=\"-\">b</ol>",
+    );
+    assert_eq!(list(&doc, 0).start(&doc), 4);
+    assert_eq!(list(&doc, 1).start(&doc), 1);
+    assert_eq!(list(&doc, 2).start(&doc), 1);
+
+    let items = list(&doc, 3).items(&doc);
+    assert_eq!(HTMLLIElement::new(items[0]).value(&doc), -3);
+    assert_eq!(HTMLLIElement::new(items[1]).value(&doc), 0);
+
+    HTMLLIElement::new(items[1]).set_value(&mut doc, 8);
+    assert_eq!(list(&doc, 3).item_numbers(&doc), vec![-3, 8]);
+
+    let ol = list(&doc, 1);
+    ol.set_start(&mut doc, 0);
+    ol.set_reversed(&mut doc, true);
+    ol.set_type(&mut doc, "i");
+    assert_eq!(doc.get_attribute(ol.node(), "start"), Some("0"));
+    assert_eq!(doc.get_attribute(ol.node(), "reversed"), Some(""));
+    assert_eq!(ol.r#type(&doc), "i");
+}
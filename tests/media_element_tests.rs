@@ -0,0 +1,94 @@
+mod common;
+
+use brooster_web_parser::dom::elements::html_audio_element::HTMLAudioElement;
+use brooster_web_parser::dom::elements::html_media_element::is_playable_type;
+use brooster_web_parser::dom::elements::html_video_element::HTMLVideoElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::nth;
+
+#[test]
+fn the_first_acceptable_source_is_selected() {
+    let doc = parse_document(
+        "<base href=\"https://example.com/media/\">\
+         <video>\
+         <source src=\"clip.mkv\" type=\"application/x-matroska\">\
+         <source src=\"wide.webm\" type=\"video/webm\" media=\"(min-width: 800px)\">\
+         <source type=\"video/mp4\">\
+         <source src=\"clip.mp4\" type=\"video/mp4; codecs=avc1\">\
+         <source src=\"clip.ogv\">\
+         </video>\
+         <audio><source src=\"a.ogg\" type=\"application/ogg\"><source src=\"a.mp3\"></audio>\
+         <audio><source src=\"a.txt\" type=\"text/plain\"></audio>",
+    );
+    let video = HTMLVideoElement::new(nth(&doc, "video", 0));
+    assert_eq!(video.src(&doc), None);
+    assert_eq!(
+        video.current_src(&doc),
+        "https://example.com/media/clip.mp4"
+    );
+
+    let audio = HTMLAudioElement::new(nth(&doc, "audio", 0));
+    assert_eq!(audio.current_src(&doc), "https://example.com/media/a.ogg");
+    let unplayable = HTMLAudioElement::new(nth(&doc, "audio", 1));
+    assert_eq!(unplayable.current_src(&doc), "");
+}
+
+#[test]
+fn the_src_attribute_overrides_sources() {
+    let mut doc = parse_document(
+        "<base href=\"https://example.com/\">\
+         <audio src=\"song.mp3\"><source src=\"other.ogg\"></audio>",
+    );
+    let audio = HTMLAudioElement::new(nth(&doc, "audio", 0));
+    assert_eq!(
+        audio.src(&doc).as_deref(),
+        Some("https://example.com/song.mp3")
+    );
+    assert_eq!(audio.current_src(&doc), "https://example.com/song.mp3");
+
+    // an empty src fails selection instead of falling back to the sources
+    audio.set_src(&mut doc, "");
+    assert_eq!(audio.current_src(&doc), "");
+}
+
+#[test]
+fn preload_is_normalized() {
+    let doc = parse_document(
+        "<video preload=\"unknown\"></video><video></video><video preload></video>\
+         <video preload=\"NONE\"></video><video preload=\"auto\"></video>",
+    );
+    let preloads: Vec<_> = (0..5)
+        .map(|index| HTMLVideoElement::new(nth(&doc, "video", index)).preload(&doc))
+        .collect();
+    assert_eq!(preloads, ["metadata", "metadata", "auto", "none", "auto"]);
+}
+
+#[test]
+fn flags_and_video_attributes_reflect() {
+    let mut doc = parse_document(
+        "<video controls loop width=\"640\" height=\"x\" poster=\"p.jpg\" crossorigin></video>",
+    );
+    let video = HTMLVideoElement::new(nth(&doc, "video", 0));
+    assert!(video.controls(&doc) && video.r#loop(&doc));
+    assert!(!video.autoplay(&doc) && !video.muted(&doc));
+    assert_eq!((video.width(&doc), video.height(&doc)), (640, 0));
+    assert_eq!(video.poster(&doc).as_deref(), Some("p.jpg"));
+    assert_eq!(video.cross_origin(&doc), Some("anonymous"));
+
+    video.set_muted(&mut doc, true);
+    video.set_controls(&mut doc, false);
+    video.set_height(&mut doc, 360);
+    assert_eq!(doc.get_attribute(video.node(), "muted"), Some(""));
+    assert_eq!(doc.get_attribute(video.node(), "controls"), None);
+    assert_eq!(video.height(&doc), 360);
+}
+
+#[test]
+fn playable_types() {
+    assert!(is_playable_type(""));
+    assert!(is_playable_type("Video/MP4"));
+    assert!(is_playable_type("audio/ogg; codecs=opus"));
+    assert!(!is_playable_type("video/"));
+    assert!(!is_playable_type("image/png"));
+    assert!(!is_playable_type("mp4"));
+}
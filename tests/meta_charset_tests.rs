@@ -0,0 +1,97 @@
+use brooster_web_parser::dom::elements::html_meta_element::{
+    extract_charset_from_meta, HTMLMetaElement,
+};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn meta_charset(html: &str) -> Option<String> {
+    parse_document(html).meta_charset()
+}
+
+#[test]
+fn charset_attribute() {
+    assert_eq!(
+        meta_charset("<meta charset=utf-8>").as_deref(),
+        Some("utf-8")
+    );
+    assert_eq!(
+        meta_charset("<meta charset=' UTF-8 '>").as_deref(),
+        Some("UTF-8")
+    );
+    assert_eq!(
+        meta_charset("<meta charset=''><meta charset=koi8-r>").as_deref(),
+        Some("koi8-r")
+    );
+    assert_eq!(meta_charset("<title>x</title>"), None);
+}
+
+#[test]
+fn content_type_pragma() {
+    let html = "<meta http-equiv=Content-Type content=\"text/html; charset=ISO-8859-1\">";
+    assert_eq!(meta_charset(html).as_deref(), Some("ISO-8859-1"));
+    // without http-equiv the content is not a declaration
+    assert_eq!(
+        meta_charset("<meta content='text/html; charset=big5'>"),
+        None
+    );
+    assert_eq!(
+        meta_charset("<meta http-equiv=refresh content='5; charset=big5'>"),
+        None
+    );
+}
+
+#[test]
+fn first_declaration_in_the_head_wins() {
+    let both = "<meta http-equiv=content-type content='text/html;charset=windows-1252'>\
+                <meta charset=utf-8>";
+    assert_eq!(meta_charset(both).as_deref(), Some("windows-1252"));
+    let reversed = "<meta charset=utf-8>\
+                    <meta http-equiv=content-type content='text/html;charset=windows-1252'>";
+    assert_eq!(meta_charset(reversed).as_deref(), Some("utf-8"));
+    assert_eq!(meta_charset("<body><meta charset=utf-8>"), None);
+}
+
+#[test]
+fn noscript_declarations_are_skipped() {
+    let html = "<head><noscript><meta charset=shift_jis></noscript><meta charset=utf-8>";
+    assert_eq!(meta_charset(html).as_deref(), Some("utf-8"));
+}
+
+#[test]
+fn extracting_from_content() {
+    let cases = [
+        ("text/html; charset=utf-8", Some("utf-8")),
+        ("text/html;CHARSET = \"euc-jp\" ; x", Some("euc-jp")),
+        ("charset='gbk'", Some("gbk")),
+        ("charset=\"unterminated", None),
+        ("charsetx; charset=latin1;", Some("latin1")),
+        ("text/html; charset=", None),
+        ("text/html", None),
+        ("charset=a b", Some("a")),
+    ];
+    for (content, expected) in cases {
+        assert_eq!(
+            extract_charset_from_meta(content).as_deref(),
+            expected,
+            "{content:?}"
+        );
+    }
+}
+
+#[test]
+fn reflected_attributes() {
+    let mut doc = parse_document(
+        "<meta name=viewport content='width=device-width' media='(min-width: 1px)'>",
+    );
+    let meta = HTMLMetaElement::new(doc.get_elements_by_tag_name("meta").item(&doc, 0).unwrap());
+    assert_eq!(meta.name(&doc), "viewport");
+    assert_eq!(meta.content(&doc), "width=device-width");
+    assert_eq!(meta.media(&doc), "(min-width: 1px)");
+    assert_eq!(meta.http_equiv(&doc), "");
+    meta.set_charset(&mut doc, "utf-8");
+    assert_eq!(meta.charset(&doc), "utf-8");
+    meta.set_http_equiv(&mut doc, "refresh");
+    assert_eq!(
+        doc.get_attribute(meta.node(), "http-equiv"),
+        Some("refresh")
+    );
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::mutation_observer::{
+    MutationObserver, MutationObserverId, MutationObserverInit, MutationRecord, MutationType,
+};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+use std::sync::{Arc, Mutex};
+
+type Deliveries = Arc<Mutex<Vec<Vec<MutationRecord>>>>;
+
+fn observer(doc: &mut Document) -> (MutationObserverId, Deliveries) {
+    let deliveries = Deliveries::default();
+    let sink = Arc::clone(&deliveries);
+    let observer = MutationObserver::new(move |records| sink.lock().unwrap().push(records));
+    (doc.arena_mut().add_mutation_observer(observer), deliveries)
+}
+
+#[test]
+fn child_list_records_are_delivered_in_a_batch() {
+    let mut doc = parse_document("<div id=list><p id=old></p></div>");
+    let (id, deliveries) = observer(&mut doc);
+    let list = by_id(&doc, "list");
+    let old = by_id(&doc, "old");
+    let options = MutationObserverInit {
+        child_list: true,
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(list, options);
+
+    let new = doc.create_element("span");
+    doc.append_child(list, new).unwrap();
+    doc.remove_child(list, old).unwrap();
+    assert!(deliveries.lock().unwrap().is_empty());
+
+    doc.arena_mut().deliver_mutation_records();
+    let deliveries = deliveries.lock().unwrap();
+    assert_eq!(deliveries.len(), 1);
+    let records = &deliveries[0];
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].type_, MutationType::ChildList);
+    assert_eq!(records[0].target, list);
+    assert_eq!(records[0].added_nodes, [new]);
+    assert_eq!(records[1].removed_nodes, [old]);
+    assert!(records[1].added_nodes.is_empty());
+}
+
+#[test]
+fn attributes_with_old_values_and_filter() {
+    let mut doc = parse_document("<div id=box class=a title=t></div>");
+    let (id, deliveries) = observer(&mut doc);
+    let target = by_id(&doc, "box");
+    let options = MutationObserverInit {
+        attribute_old_value: true,
+        attribute_filter: Some(vec!["class".to_string(), "hidden".to_string()]),
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(target, options);
+
+    doc.set_attribute(target, "class", "b");
+    doc.set_attribute(target, "title", "ignored");
+    doc.set_attribute(target, "hidden", "");
+    doc.remove_attribute(target, "class");
+    doc.remove_attribute(target, "missing");
+    let records = doc.arena_mut().mutation_observer_mut(id).take_records();
+    let summary: Vec<_> = records
+        .iter()
+        .map(|r| (r.attribute_name.as_deref(), r.old_value.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (Some("class"), Some("a")),
+            (Some("hidden"), None),
+            (Some("class"), Some("b")),
+        ]
+    );
+    // taken records are not delivered any more
+    doc.arena_mut().deliver_mutation_records();
+    assert!(deliveries.lock().unwrap().is_empty());
+}
+
+#[test]
+fn mixed_case_attribute_names_are_lowercased_on_html_elements() {
+    let mut doc = parse_document("<div id=box class=a></div>");
+    let (id, _deliveries) = observer(&mut doc);
+    let target = by_id(&doc, "box");
+    let options = MutationObserverInit {
+        attribute_old_value: true,
+        attribute_filter: Some(vec!["class".to_string()]),
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(target, options);
+
+    doc.set_attribute(target, "CLASS", "b");
+    doc.remove_attribute(target, "Class");
+    let records = doc.arena_mut().mutation_observer_mut(id).take_records();
+    let summary: Vec<_> = records
+        .iter()
+        .map(|r| (r.attribute_name.as_deref(), r.old_value.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        [(Some("class"), Some("a")), (Some("class"), Some("b"))]
+    );
+    assert_eq!(doc.get_attribute(target, "class"), None);
+}
+
+#[test]
+fn subtree_observation() {
+    let mut doc = parse_document("<section id=root><div id=inner></div></section><p id=outside>");
+    let (id, _) = observer(&mut doc);
+    let root = by_id(&doc, "root");
+    let inner = by_id(&doc, "inner");
+    let outside = by_id(&doc, "outside");
+
+    let shallow = MutationObserverInit {
+        attributes: true,
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(root, shallow);
+    doc.set_attribute(inner, "class", "x");
+    assert!(doc
+        .arena()
+        .mutation_observer(id)
+        .clone()
+        .take_records()
+        .is_empty());
+
+    // observing again replaces the options of the registration
+    let deep = MutationObserverInit {
+        attributes: true,
+        child_list: true,
+        subtree: true,
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(root, deep);
+    doc.set_attribute(inner, "class", "y");
+    doc.set_attribute(outside, "class", "y");
+    let text = doc.create_text_node("t");
+    doc.append_child(inner, text).unwrap();
+    let records = doc.arena_mut().mutation_observer_mut(id).take_records();
+    let targets: Vec<_> = records.iter().map(|r| (r.type_, r.target)).collect();
+    assert_eq!(
+        targets,
+        [
+            (MutationType::Attributes, inner),
+            (MutationType::ChildList, inner)
+        ]
+    );
+    // the observer did not ask for old values
+    assert_eq!(records[0].old_value, None);
+}
+
+#[test]
+fn disconnect_stops_observation_and_drops_pending_records() {
+    let mut doc = parse_document("<div id=box></div>");
+    let (id, deliveries) = observer(&mut doc);
+    let target = by_id(&doc, "box");
+    let options = MutationObserverInit {
+        attributes: true,
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(target, options.clone());
+    doc.set_attribute(target, "a", "1");
+    doc.arena_mut().mutation_observer_mut(id).disconnect();
+    doc.set_attribute(target, "a", "2");
+    doc.arena_mut().deliver_mutation_records();
+    assert!(deliveries.lock().unwrap().is_empty());
+
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(target, options);
+    doc.set_attribute(target, "a", "3");
+    doc.arena_mut().deliver_mutation_records();
+    assert_eq!(deliveries.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn registrations_follow_compaction() {
+    let mut doc = parse_document("<div id=keep></div><div id=drop></div>");
+    let (id, _) = observer(&mut doc);
+    let keep = by_id(&doc, "keep");
+    let dropped = by_id(&doc, "drop");
+    let options = MutationObserverInit {
+        attributes: true,
+        ..Default::default()
+    };
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(keep, options.clone());
+    doc.arena_mut()
+        .mutation_observer_mut(id)
+        .observe(dropped, options);
+    doc.remove(dropped);
+    doc.compact();
+    let keep = by_id(&doc, "keep");
+    doc.set_attribute(keep, "class", "x");
+    let records = doc.arena_mut().mutation_observer_mut(id).take_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].target, keep);
+}
@@ -0,0 +1,73 @@
+use brooster_web_parser::dom::node::Namespace;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+#[test]
+fn foreign_content_keeps_namespaced_attributes() {
+    let doc = parse_document(
+        r##"<!DOCTYPE html><svg xlink:href="#icon" viewbox="0 0 10 10"><use xlink:href="#a"/></svg>"##,
+    );
+    let svgs = doc.get_elements_by_tag_name("svg");
+    assert_eq!(svgs.len(&doc), 1);
+    let svg = doc.element(svgs.item(&doc, 0).unwrap()).unwrap();
+    assert_eq!(svg.namespace(), Namespace::Svg);
+    assert_eq!(svg.local_name(), "svg");
+
+    let href = &svg.attributes[0];
+    assert_eq!(href.prefix.as_deref(), Some("xlink"));
+    assert_eq!(href.local_name, "href");
+    assert_eq!(href.namespace, Some(Namespace::XLink));
+    assert_eq!(href.value, "#icon");
+    assert_eq!(svg.get_attribute("xlink:href"), Some("#icon"));
+    assert_eq!(
+        svg.get_attribute_ns(Some(Namespace::XLink), "href"),
+        Some("#icon")
+    );
+    assert_eq!(svg.get_attribute("viewBox"), Some("0 0 10 10"));
+}
+
+#[test]
+fn serialization_reproduces_prefixes() {
+    let doc = parse_document(
+        r##"<svg xlink:href="#icon" xml:lang="en"><foreignobject><p>x</p></foreignobject></svg>"##,
+    );
+    assert_eq!(
+        serialize_children(&doc, doc.root()),
+        r##"<html><head></head><body><svg xlink:href="#icon" xml:lang="en"><foreignObject><p>x</p></foreignObject></svg></body></html>"##
+    );
+}
+
+#[test]
+fn tag_name_matching_is_namespace_aware() {
+    let doc = parse_document(
+        "<svg><title>t</title><clippath/></svg><title>x</title><math><mi>1</mi></math>",
+    );
+    let titles = doc.get_elements_by_tag_name("title");
+    let namespaces: Vec<Namespace> = titles
+        .iter(&doc)
+        .map(|node| doc.element(node).unwrap().namespace())
+        .collect();
+    assert_eq!(namespaces, vec![Namespace::Svg, Namespace::Html]);
+    assert_eq!(doc.get_elements_by_tag_name("clipPath").len(&doc), 1);
+    assert_eq!(doc.get_elements_by_tag_name("CLIPPATH").len(&doc), 0);
+    assert_eq!(doc.get_elements_by_tag_name("MI").len(&doc), 0);
+    assert_eq!(doc.get_elements_by_tag_name("mi").len(&doc), 1);
+}
+
+#[test]
+fn html_start_tags_break_out_of_foreign_content() {
+    let doc = parse_document("<svg><circle><p>text</p></svg>");
+    assert_eq!(
+        serialize_children(&doc, doc.root()),
+        "<html><head></head><body><svg><circle></circle></svg><p>text</p></body></html>"
+    );
+}
+
+#[test]
+fn cdata_is_text_in_foreign_content_and_a_comment_in_html() {
+    let doc = parse_document("<svg><![CDATA[a<b]]></svg><![CDATA[x]]>");
+    assert_eq!(
+        serialize_children(&doc, doc.root()),
+        "<html><head></head><body><svg>a&lt;b</svg><!--[CDATA[x]]--></body></html>"
+    );
+}
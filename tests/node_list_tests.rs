@@ -0,0 +1,90 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::elements::node_list::NodeList;
+use brooster_web_parser::dom::node::NodeId;
+
+fn append_element(doc: &mut Document, parent: NodeId, name: &str) -> NodeId {
+    let element = doc.create_element(name);
+    doc.append_child(parent, element).unwrap();
+    element
+}
+
+#[test]
+fn item_out_of_bounds_is_none() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let div = append_element(&mut doc, root, "div");
+
+    let children = doc.child_nodes(root);
+    assert_eq!(children.item(&doc, 0), Some(div));
+    assert_eq!(children.item(&doc, 1), None);
+    assert_eq!(doc.child_nodes(div).item(&doc, 0), None);
+
+    let snapshot = NodeList::from_nodes(vec![div]);
+    assert_eq!(snapshot.item(&doc, 1), None);
+    assert_eq!(snapshot.item(&doc, usize::MAX), None);
+}
+
+#[test]
+fn iteration_is_in_tree_order() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let body = append_element(&mut doc, root, "body");
+    let a = append_element(&mut doc, body, "a");
+    let text = doc.create_text_node("text");
+    doc.append_child(body, text).unwrap();
+    let c = doc.create_element("c");
+    doc.insert_before(body, c, Some(a)).unwrap();
+
+    let children = doc.child_nodes(body);
+    let iter = children.iter(&doc);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.collect::<Vec<_>>(), vec![c, a, text]);
+
+    let mut seen = Vec::new();
+    for node in children.view(&doc) {
+        seen.push(node);
+    }
+    assert_eq!(seen, vec![c, a, text]);
+}
+
+#[test]
+fn live_child_nodes_observes_later_appends() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let ul = append_element(&mut doc, root, "ul");
+    let first = append_element(&mut doc, ul, "li");
+
+    let children = doc.child_nodes(ul);
+    let snapshot = NodeList::from_nodes(children.iter(&doc).collect());
+    assert!(children.is_live());
+    assert!(!snapshot.is_live());
+    assert_eq!(children.len(&doc), 1);
+
+    let second = append_element(&mut doc, ul, "li");
+    assert_eq!(children.len(&doc), 2);
+    assert_eq!(children.item(&doc, 1), Some(second));
+    assert_eq!(children.view(&doc).iter().collect::<Vec<_>>(), vec![first, second]);
+    assert_eq!(snapshot.len(&doc), 1);
+}
+
+#[test]
+fn select_labels_is_a_static_snapshot() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let wrapping = append_element(&mut doc, root, "label");
+    let select_node = append_element(&mut doc, wrapping, "select");
+    doc.set_attribute(select_node, "id", "pick");
+    let by_for = append_element(&mut doc, root, "label");
+    doc.set_attribute(by_for, "for", "pick");
+    let other = append_element(&mut doc, root, "label");
+    doc.set_attribute(other, "for", "elsewhere");
+    let select = HTMLSelectElement::new(select_node);
+
+    let labels = select.labels(&doc);
+    assert_eq!(labels.iter(&doc).collect::<Vec<_>>(), vec![wrapping, by_for]);
+
+    doc.set_attribute(other, "for", "pick");
+    assert_eq!(labels.len(&doc), 2);
+    assert_eq!(select.labels(&doc).len(&doc), 3);
+}
@@ -0,0 +1,59 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+/// Tokens of `input`, the attributes decoded in lazy mode too
+fn tokens(input: &str, lazy: bool) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.set_lazy_attributes(lazy);
+    let mut tokens = Vec::new();
+    loop {
+        let mut token = tokenizer.next_token();
+        tokenizer.decode_attributes(&mut token);
+        if token == Token::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+fn data_x(input: &str) -> String {
+    let doc = parse_document(input);
+    let div = doc.get_elements_by_tag_name("div").item(&doc, 0).unwrap();
+    doc.get_attribute(div, "data-x").unwrap().to_string()
+}
+
+#[test]
+fn null_character_references_in_quoted_values_become_replacement_characters() {
+    assert_eq!(data_x("<div data-x=\"&#x0;\">"), "\u{FFFD}");
+    assert_eq!(data_x("<div data-x='&#x0;'>"), "\u{FFFD}");
+    assert_eq!(data_x("<div data-x=a&#0;b>"), "a\u{FFFD}b");
+}
+
+#[test]
+fn literal_nulls_in_values_become_replacement_characters() {
+    assert_eq!(data_x("<div data-x=\"a\0b\">"), "a\u{FFFD}b");
+    assert_eq!(data_x("<div data-x='a\0b'>"), "a\u{FFFD}b");
+    assert_eq!(data_x("<div data-x=a\0b>"), "a\u{FFFD}b");
+}
+
+#[test]
+fn nulls_in_values_emit_no_character_tokens() {
+    for input in [
+        "<div data-x=\"&#x0;\">",
+        "<div data-x='&#x0;'>",
+        "<div data-x=\"\0\">",
+        "<div data-x='\0'>",
+    ] {
+        for lazy in [false, true] {
+            let tokens = tokens(input, lazy);
+            assert_eq!(tokens.len(), 1, "{input:?}, lazy: {lazy}");
+            let Token::StartTag { attributes, .. } = &tokens[0] else {
+                panic!("expected a start tag for {input:?}");
+            };
+            assert_eq!(
+                attributes,
+                &[("data-x".into(), "\u{FFFD}".to_string())]
+            );
+        }
+    }
+}
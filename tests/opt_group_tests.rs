@@ -0,0 +1,84 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_opt_group_element::HTMLOptGroupElement;
+use brooster_web_parser::dom::elements::html_option_element::HTMLOptionElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn parse_select(html: &str) -> (Document, HTMLSelectElement) {
+    let doc = parse_document(html);
+    let node = doc
+        .get_elements_by_tag_name("select")
+        .item(&doc, 0)
+        .unwrap();
+    (doc, HTMLSelectElement::new(node))
+}
+
+fn first_opt_group(doc: &Document) -> HTMLOptGroupElement {
+    HTMLOptGroupElement::new(
+        doc.get_elements_by_tag_name("optgroup")
+            .item(doc, 0)
+            .unwrap(),
+    )
+}
+
+#[test]
+fn label_and_disabled_are_reflected() {
+    let (mut doc, _) = parse_select("<select><optgroup label=Fruit><option>apple</select>");
+    let group = first_opt_group(&doc);
+    assert_eq!(group.label(&doc), "Fruit");
+    assert!(!group.disabled(&doc));
+
+    group.set_label(&mut doc, "Vegetables");
+    group.set_disabled(&mut doc, true);
+    assert_eq!(doc.get_attribute(group.node(), "label"), Some("Vegetables"));
+    assert_eq!(doc.get_attribute(group.node(), "disabled"), Some(""));
+    assert!(group.disabled(&doc));
+
+    group.set_disabled(&mut doc, false);
+    assert_eq!(doc.get_attribute(group.node(), "disabled"), None);
+
+    let (doc, _) = parse_select("<select><optgroup><option>a</select>");
+    assert_eq!(first_opt_group(&doc).label(&doc), "");
+}
+
+#[test]
+fn default_selection_skips_disabled_groups() {
+    let (doc, select) = parse_select(
+        "<select><optgroup disabled><option>a<option>b</optgroup>\
+         <option>c<option>d</select>",
+    );
+    assert_eq!(select.length(&doc), 4);
+    assert_eq!(select.selected_index(&doc), 2);
+    assert_eq!(select.value(&doc), "c");
+}
+
+#[test]
+fn disabling_a_group_disables_its_options() {
+    let (mut doc, select) =
+        parse_select("<select><optgroup><option>a</optgroup><option>b</select>");
+    let group = first_opt_group(&doc);
+    let first = HTMLOptionElement::new(select.item(&doc, 0).unwrap());
+    assert!(!first.disabled(&doc));
+    group.set_disabled(&mut doc, true);
+    assert!(first.disabled(&doc));
+}
+
+#[test]
+fn options_in_groups_are_in_tree_order() {
+    let (doc, select) = parse_select(
+        "<select><option>a<optgroup label=g><option>b<option>c</optgroup>\
+         <option>d</select>",
+    );
+    let values: Vec<String> = select
+        .options()
+        .iter(&doc)
+        .map(|option| HTMLOptionElement::new(option).value(&doc))
+        .collect();
+    assert_eq!(values, ["a", "b", "c", "d"]);
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<option>a</option><optgroup label=\"g\"><option>b</option><option>c</option>\
+         </optgroup><option>d</option>"
+    );
+}
@@ -0,0 +1,107 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_option_element::HTMLOptionElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn parse_options(html: &str) -> (Document, HTMLSelectElement, Vec<HTMLOptionElement>) {
+    let doc = parse_document(html);
+    let node = doc
+        .get_elements_by_tag_name("select")
+        .item(&doc, 0)
+        .unwrap();
+    let select = HTMLSelectElement::new(node);
+    let options = select
+        .options()
+        .iter(&doc)
+        .map(HTMLOptionElement::new)
+        .collect();
+    (doc, select, options)
+}
+
+const SELECT: &str = "<select>\
+    <option value=first label=First>  one\n <b>1</b> </option>\
+    <option>two<script>ignored()</script></option>\
+    <optgroup disabled><option selected value=\"\">three</option></optgroup>\
+    <option disabled label=\"\">four</option>\
+    </select>";
+
+#[test]
+fn text_value_and_label() {
+    let (doc, _, options) = parse_options(SELECT);
+    assert_eq!(options[0].text(&doc), "one 1");
+    assert_eq!(options[0].value(&doc), "first");
+    assert_eq!(options[0].label(&doc), "First");
+
+    assert_eq!(options[1].text(&doc), "two");
+    assert_eq!(options[1].value(&doc), "two");
+    assert_eq!(options[1].label(&doc), "two");
+
+    // present but empty attributes are used as they are
+    assert_eq!(options[2].value(&doc), "");
+    assert_eq!(options[3].label(&doc), "");
+}
+
+#[test]
+fn selected_and_default_selected() {
+    let (mut doc, select, options) = parse_options(SELECT);
+    assert!(options[2].selected(&doc));
+    assert!(options[2].default_selected(&doc));
+    assert!(!options[1].selected(&doc));
+
+    options[1].set_selected(&mut doc, true);
+    assert!(options[1].selected(&doc));
+    assert!(!options[2].selected(&doc));
+    assert!(options[2].default_selected(&doc));
+    assert!(!options[1].default_selected(&doc));
+    assert_eq!(select.selected_index(&doc), 1);
+
+    options[0].set_default_selected(&mut doc, true);
+    assert_eq!(doc.get_attribute(options[0].node(), "selected"), Some(""));
+    options[0].set_default_selected(&mut doc, false);
+    assert!(!options[0].default_selected(&doc));
+}
+
+#[test]
+fn deselecting_in_a_single_select_resets_to_the_first_enabled_option() {
+    let (mut doc, select, options) = parse_options(SELECT);
+    options[2].set_selected(&mut doc, false);
+    assert_eq!(select.selected_index(&doc), 0);
+}
+
+#[test]
+fn multiple_selects_keep_other_selections() {
+    let (mut doc, select, options) =
+        parse_options("<select multiple><option>a<option>b<option>c</select>");
+    options[0].set_selected(&mut doc, true);
+    options[2].set_selected(&mut doc, true);
+    assert_eq!(select.selected_options().len(&doc), 2);
+    options[0].set_selected(&mut doc, false);
+    assert_eq!(select.selected_index(&doc), 2);
+}
+
+#[test]
+fn disabled_includes_the_optgroup() {
+    let (doc, _, options) = parse_options(SELECT);
+    let disabled: Vec<bool> = options.iter().map(|option| option.disabled(&doc)).collect();
+    assert_eq!(disabled, [false, false, true, true]);
+}
+
+#[test]
+fn index_in_the_owning_select() {
+    let (mut doc, _, options) = parse_options(SELECT);
+    let indices: Vec<i32> = options.iter().map(|option| option.index(&doc)).collect();
+    assert_eq!(indices, [0, 1, 2, 3]);
+
+    let detached = HTMLOptionElement::new(doc.create_element("option"));
+    assert_eq!(detached.index(&doc), 0);
+}
+
+#[test]
+fn value_and_label_setters_write_attributes() {
+    let (mut doc, _, options) = parse_options(SELECT);
+    options[1].set_value(&mut doc, "2");
+    options[1].set_label(&mut doc, "Two");
+    assert_eq!(options[1].value(&doc), "2");
+    assert_eq!(options[1].label(&doc), "Two");
+    assert_eq!(options[1].text(&doc), "two");
+}
@@ -0,0 +1,106 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_options_collection::HTMLOptionsCollection;
+use brooster_web_parser::dom::elements::html_select_element::{AddBefore, HTMLSelectElement};
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn setup(html: &str) -> (Document, HTMLSelectElement, HTMLOptionsCollection) {
+    let doc = parse_document(html);
+    let select = HTMLSelectElement::new(
+        doc.get_elements_by_tag_name("select")
+            .item(&doc, 0)
+            .unwrap(),
+    );
+    let options = select.options();
+    (doc, select, options)
+}
+
+fn option(doc: &mut Document, value: &str) -> NodeId {
+    let option = doc.create_element("option");
+    doc.set_attribute(option, "value", value);
+    option
+}
+
+#[test]
+fn options_is_the_options_collection_of_the_select() {
+    let (doc, select, options) = setup("<select><option>a<option>b</select>");
+    assert_eq!(options.select(), select.node());
+    assert_eq!(options.length(&doc), 2);
+    assert_eq!(options, HTMLOptionsCollection::new(select.node()));
+}
+
+#[test]
+fn setting_past_the_end_pads_with_blank_options() {
+    let (mut doc, select, options) = setup("<select><option>a<option>b</select>");
+    let new = option(&mut doc, "f");
+    options.set(&mut doc, 5, Some(new)).unwrap();
+    assert_eq!(options.length(&doc), 6);
+    assert_eq!(options.item(&doc, 5), Some(new));
+    assert_eq!(select.length(&doc), 6);
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<option>a</option><option>b</option><option></option><option></option>\
+         <option></option><option value=\"f\"></option>"
+    );
+}
+
+#[test]
+fn setting_an_existing_index_replaces_the_option() {
+    let (mut doc, select, options) = setup("<select><option>a<option>b</select>");
+    let new = option(&mut doc, "x");
+    options.set(&mut doc, 0, Some(new)).unwrap();
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<option value=\"x\"></option><option>b</option>"
+    );
+
+    options.set(&mut doc, 0, None).unwrap();
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<option>b</option>"
+    );
+}
+
+#[test]
+fn removal_reindexes_the_options() {
+    let (mut doc, select, options) = setup("<select><option>a<option>b<option>c</select>");
+    let c = options.item(&doc, 2).unwrap();
+    options.remove(&mut doc, 0);
+    assert_eq!(options.length(&doc), 2);
+    assert_eq!(options.item(&doc, 1), Some(c));
+    assert_eq!(select.item(&doc, 1), Some(c));
+
+    options.remove(&mut doc, 7);
+    options.remove(&mut doc, -1);
+    assert_eq!(options.length(&doc), 2);
+}
+
+#[test]
+fn set_length_truncates_and_pads() {
+    let (mut doc, select, options) = setup("<select><option>a<option>b<option>c</select>");
+    options.set_length(&mut doc, 1);
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<option>a</option>"
+    );
+    options.set_length(&mut doc, 3);
+    assert_eq!(select.length(&doc), 3);
+}
+
+#[test]
+fn add_and_selected_index() {
+    let (mut doc, select, options) = setup("<select><option>a<option>b</select>");
+    let first = option(&mut doc, "first");
+    options
+        .add(&mut doc, first, Some(AddBefore::Index(0)))
+        .unwrap();
+    assert_eq!(options.item(&doc, 0), Some(first));
+
+    // the parser selected a, now at index 1
+    assert_eq!(options.selected_index(&doc), 1);
+    options.set_selected_index(&mut doc, 2);
+    assert_eq!(options.selected_index(&doc), 2);
+    assert_eq!(select.selected_index(&doc), 2);
+    assert_eq!(select.value(&doc), "b");
+}
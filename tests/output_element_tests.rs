@@ -0,0 +1,67 @@
+mod common;
+
+use brooster_web_parser::dom::elements::html_form_element::HTMLFormElement;
+use brooster_web_parser::dom::elements::html_output_element::HTMLOutputElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn parsed_value_is_the_text() {
+    let doc = parse_document("<output id=o name=sum for='a b'>4<b>2</b></output>");
+    let o = HTMLOutputElement::new(by_id(&doc, "o"));
+    assert_eq!(o.value(&doc), "42");
+    assert_eq!(o.default_value(&doc), "42");
+    assert_eq!(o.name(&doc), "sum");
+    assert_eq!(o.r#type(), "output");
+}
+
+#[test]
+fn set_value_rewrites_the_text_and_keeps_the_default() {
+    let mut doc = parse_document("<output id=o>4<b>2</b></output>");
+    let o = HTMLOutputElement::new(by_id(&doc, "o"));
+    o.set_value(&mut doc, "7");
+    let children: Vec<_> = doc.arena().children(o.node()).collect();
+    assert_eq!(children.len(), 1);
+    assert_eq!(doc.arena().get(children[0]).as_text(), Some("7"));
+    assert_eq!(o.value(&doc), "7");
+    assert_eq!(o.default_value(&doc), "42");
+
+    o.set_default_value(&mut doc, "0");
+    assert_eq!(o.value(&doc), "7");
+    assert_eq!(o.default_value(&doc), "0");
+}
+
+#[test]
+fn set_default_value_before_any_value_replaces_the_text() {
+    let mut doc = parse_document("<output id=o>1</output>");
+    let o = HTMLOutputElement::new(by_id(&doc, "o"));
+    o.set_default_value(&mut doc, "2");
+    assert_eq!(o.value(&doc), "2");
+}
+
+#[test]
+fn form_reset_restores_the_default() {
+    let mut doc = parse_document("<form id=f><output id=o>initial</output></form>");
+    let o = HTMLOutputElement::new(by_id(&doc, "o"));
+    o.set_value(&mut doc, "changed");
+    HTMLFormElement::new(doc.get_element_by_id("f").unwrap()).reset(&mut doc);
+    assert_eq!(o.value(&doc), "initial");
+
+    // the default follows the text again after a reset
+    doc.set_text_content(o.node(), "later");
+    assert_eq!(o.default_value(&doc), "later");
+}
+
+#[test]
+fn html_for_is_a_token_list() {
+    let mut doc = parse_document("<output id=o for='a b a'></output>");
+    let o = HTMLOutputElement::new(by_id(&doc, "o"));
+    let html_for = o.html_for();
+    assert_eq!(html_for.tokens(&doc), ["a", "b"]);
+    assert!(!html_for.contains(&doc, "c"));
+
+    html_for.set_value(&mut doc, "x y");
+    assert!(html_for.contains(&doc, "y"));
+    html_for.add(&mut doc, &["z"]).unwrap();
+    assert_eq!(doc.get_attribute(o.node(), "for"), Some("x y z"));
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::{AdjacentPosition, NodeOrText};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::{serialize_children, serialize_node_to_string};
+use common::by_id;
+
+#[test]
+fn append_mixes_nodes_and_strings() {
+    let mut doc = parse_document("<div id=d><i>x</i></div>");
+    let div = by_id(&doc, "d");
+    let b = doc.create_element("b");
+    doc.append(
+        div,
+        [
+            NodeOrText::from("one "),
+            NodeOrText::from(b),
+            NodeOrText::from(String::from(" two")),
+        ],
+    )
+    .unwrap();
+    assert_eq!(serialize_children(&doc, div), "<i>x</i>one <b></b> two");
+}
+
+#[test]
+fn prepend_to_an_empty_element() {
+    let mut doc = parse_document("<p id=p></p>");
+    let p = by_id(&doc, "p");
+    let span = doc.create_element("span");
+    doc.prepend(p, [NodeOrText::from(span), NodeOrText::from("!")])
+        .unwrap();
+    assert_eq!(serialize_children(&doc, p), "<span></span>!");
+    doc.prepend(p, ["first"]).unwrap();
+    assert_eq!(serialize_children(&doc, p), "first<span></span>!");
+}
+
+#[test]
+fn append_moves_a_node_already_in_the_tree() {
+    let mut doc = parse_document("<div id=a><b id=moved></b><i></i></div><div id=b></div>");
+    let (a, b, moved) = (by_id(&doc, "a"), by_id(&doc, "b"), by_id(&doc, "moved"));
+    doc.append(b, [moved]).unwrap();
+    assert_eq!(serialize_children(&doc, a), "<i></i>");
+    assert_eq!(serialize_children(&doc, b), "<b id=\"moved\"></b>");
+    assert_eq!(doc.arena().parent(moved), Some(b));
+}
+
+#[test]
+fn inserting_an_ancestor_fails_without_moving_anything() {
+    let mut doc = parse_document("<div id=outer><p id=inner></p></div>");
+    let (outer, inner) = (by_id(&doc, "outer"), by_id(&doc, "inner"));
+    let before = serialize_node_to_string(&doc, doc.body().unwrap());
+    let text = NodeOrText::from("x");
+    assert_eq!(
+        doc.append(inner, [text, NodeOrText::from(outer)]),
+        Err(DomError::HierarchyRequest)
+    );
+    assert_eq!(doc.append(inner, [inner]), Err(DomError::HierarchyRequest));
+    assert_eq!(serialize_node_to_string(&doc, doc.body().unwrap()), before);
+}
+
+#[test]
+fn insert_adjacent_text_at_each_position() {
+    let mut doc = parse_document("<div id=w><p id=p>x</p></div>");
+    let (w, p) = (by_id(&doc, "w"), by_id(&doc, "p"));
+    doc.insert_adjacent_text(p, AdjacentPosition::BeforeBegin, "1")
+        .unwrap();
+    doc.insert_adjacent_text(p, AdjacentPosition::AfterBegin, "2")
+        .unwrap();
+    doc.insert_adjacent_text(p, AdjacentPosition::BeforeEnd, "3")
+        .unwrap();
+    doc.insert_adjacent_text(p, AdjacentPosition::AfterEnd, "4")
+        .unwrap();
+    assert_eq!(serialize_children(&doc, w), "1<p id=\"p\">2x3</p>4");
+
+    let detached = doc.create_element("p");
+    doc.insert_adjacent_text(detached, AdjacentPosition::AfterEnd, "lost")
+        .unwrap();
+    assert_eq!(doc.arena().get(detached).next_sibling(), None);
+}
@@ -0,0 +1,144 @@
+use brooster_web_parser::dom::parser::parse_error::{ParseErrorCode, UnknownParseErrorCode};
+use brooster_web_parser::dom::parser::tokenizer::Tokenizer;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The error codes used by the `errors` lists of the html5lib tokenizer tests
+const HTML5LIB_TOKENIZER_ERRORS: &[&str] = &[
+    "abrupt-closing-of-empty-comment",
+    "abrupt-doctype-public-identifier",
+    "abrupt-doctype-system-identifier",
+    "absence-of-digits-in-numeric-character-reference",
+    "cdata-in-html-content",
+    "character-reference-outside-unicode-range",
+    "control-character-in-input-stream",
+    "control-character-reference",
+    "duplicate-attribute",
+    "end-tag-with-attributes",
+    "end-tag-with-trailing-solidus",
+    "eof-before-tag-name",
+    "eof-in-cdata",
+    "eof-in-comment",
+    "eof-in-doctype",
+    "eof-in-script-html-comment-like-text",
+    "eof-in-tag",
+    "incorrectly-closed-comment",
+    "incorrectly-opened-comment",
+    "invalid-character-sequence-after-doctype-name",
+    "invalid-first-character-of-tag-name",
+    "missing-attribute-value",
+    "missing-doctype-name",
+    "missing-doctype-public-identifier",
+    "missing-doctype-system-identifier",
+    "missing-end-tag-name",
+    "missing-quote-before-doctype-public-identifier",
+    "missing-quote-before-doctype-system-identifier",
+    "missing-semicolon-after-character-reference",
+    "missing-whitespace-after-doctype-public-keyword",
+    "missing-whitespace-after-doctype-system-keyword",
+    "missing-whitespace-before-doctype-name",
+    "missing-whitespace-between-attributes",
+    "missing-whitespace-between-doctype-public-and-system-identifiers",
+    "nested-comment",
+    "noncharacter-character-reference",
+    "noncharacter-in-input-stream",
+    "non-void-html-element-start-tag-with-trailing-solidus",
+    "null-character-reference",
+    "surrogate-character-reference",
+    "surrogate-in-input-stream",
+    "unexpected-character-after-doctype-system-identifier",
+    "unexpected-character-in-attribute-name",
+    "unexpected-character-in-unquoted-attribute-value",
+    "unexpected-equals-sign-before-attribute-name",
+    "unexpected-null-character",
+    "unexpected-question-mark-instead-of-tag-name",
+    "unexpected-solidus-in-tag",
+    "unknown-named-character-reference",
+];
+
+/// The codes named in the `errors` lists of the `.test` files of an html5lib-tests checkout
+fn checkout_error_codes(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir.join("tokenizer")) else {
+        return Vec::new();
+    };
+    let mut codes = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.extension().is_none_or(|ext| ext != "test") {
+            continue;
+        }
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        let tests = json["tests"].as_array().into_iter().flatten();
+        let errors = tests.flat_map(|test| test["errors"].as_array().into_iter().flatten());
+        codes.extend(errors.filter_map(|error| error["code"].as_str().map(str::to_string)));
+    }
+    codes
+}
+
+#[test]
+fn every_html5lib_tokenizer_error_is_a_variant() {
+    let mut names: Vec<String> = HTML5LIB_TOKENIZER_ERRORS
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    if let Ok(dir) = std::env::var("HTML5LIB_TESTS_DIR") {
+        names.extend(checkout_error_codes(Path::new(&dir)));
+    }
+    for name in names {
+        let code = ParseErrorCode::from_str(&name)
+            .unwrap_or_else(|_| panic!("{name} has no ParseErrorCode"));
+        assert_eq!(code.as_str(), name);
+        assert!(code.is_tokenizer_error(), "{name}");
+    }
+}
+
+#[test]
+fn names_round_trip_and_are_unique() {
+    for (index, code) in ParseErrorCode::ALL.iter().enumerate() {
+        assert_eq!(code.as_str().parse::<ParseErrorCode>(), Ok(*code));
+        assert!(!ParseErrorCode::ALL[..index]
+            .iter()
+            .any(|other| other.as_str() == code.as_str()));
+    }
+    let tokenizer_errors = ParseErrorCode::ALL
+        .iter()
+        .filter(|code| code.is_tokenizer_error())
+        .count();
+    assert_eq!(tokenizer_errors, HTML5LIB_TOKENIZER_ERRORS.len());
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    assert_eq!(
+        "Parse error: EOF in tag".parse::<ParseErrorCode>(),
+        Err(UnknownParseErrorCode("Parse error: EOF in tag".to_string()))
+    );
+    assert!(" eof-before-tag-name".parse::<ParseErrorCode>().is_err());
+    assert!("Duplicate-Attribute".parse::<ParseErrorCode>().is_err());
+}
+
+#[test]
+fn the_tokenizer_reports_canonical_codes() {
+    let codes = |input: &str| -> Vec<ParseErrorCode> {
+        let mut tokenizer = Tokenizer::new(input.as_bytes());
+        tokenizer.run();
+        tokenizer.errors().iter().map(|error| error.code).collect()
+    };
+    assert_eq!(codes("<"), [ParseErrorCode::EofBeforeTagName]);
+    assert_eq!(codes("<div"), [ParseErrorCode::EofInTag]);
+    assert_eq!(codes("&#0;"), [ParseErrorCode::NullCharacterReference]);
+    assert_eq!(
+        codes("&#x110000;"),
+        [ParseErrorCode::CharacterReferenceOutsideUnicodeRange]
+    );
+    assert_eq!(
+        codes("&#xD800;"),
+        [ParseErrorCode::SurrogateCharacterReference]
+    );
+    assert_eq!(
+        codes("&#xFFFF;"),
+        [ParseErrorCode::NoncharacterCharacterReference]
+    );
+    assert_eq!(codes("&#x01;"), [ParseErrorCode::ControlCharacterReference]);
+}
@@ -0,0 +1,103 @@
+use brooster_web_parser::dom::parser::parse_error::{ParseError, ParseErrorCode, TreeError};
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::dom::parser::tree_constructor::TreeConstructor;
+
+fn tokenizer_errors(input: &str) -> Vec<ParseError> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    tokenizer.take_errors()
+}
+
+fn tree_errors(input: &str) -> Vec<TreeError> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    let mut tree_constructor = TreeConstructor::new();
+    loop {
+        let token = tokenizer.next_token();
+        let is_eof = token == Token::EOF;
+        tree_constructor.set_token_offset(tokenizer.offset());
+        tree_constructor.process_token(token);
+        if let Some(state) = tree_constructor.take_tokenizer_state() {
+            tokenizer.set_state(state);
+        }
+        if is_eof {
+            break;
+        }
+    }
+    tree_constructor.take_errors()
+}
+
+fn find<'a>(errors: &'a [ParseError], code: &str) -> &'a ParseError {
+    errors
+        .iter()
+        .find(|error| error.code.as_str() == code)
+        .unwrap_or_else(|| panic!("no {code} in {errors:?}"))
+}
+
+#[test]
+fn duplicate_attributes_carry_the_attribute_name() {
+    let errors = tokenizer_errors("<a x=1 x=2>");
+    let error = find(&errors, "duplicate-attribute");
+    assert_eq!(error.context.as_deref(), Some("x"));
+}
+
+#[test]
+fn lazy_attributes_report_duplicates_too() {
+    let mut tokenizer = Tokenizer::new(b"<a X=1 x=2>");
+    tokenizer.set_lazy_attributes(true);
+    tokenizer.run();
+    let errors = tokenizer.take_errors();
+    let error = find(&errors, "duplicate-attribute");
+    assert_eq!(error.context.as_deref(), Some("x"));
+}
+
+#[test]
+fn unknown_character_references_carry_the_entity_text() {
+    let errors = tokenizer_errors("a &nbp; b");
+    let error = find(&errors, "unknown-named-character-reference");
+    assert_eq!(error.context.as_deref(), Some("nbp"));
+    // the offset is that of the semicolon
+    assert_eq!(error.offset, 6);
+}
+
+#[test]
+fn missing_semicolons_carry_the_entity_text() {
+    let errors = tokenizer_errors("&amp x");
+    let error = find(&errors, "missing-semicolon-after-character-reference");
+    assert_eq!(error.context.as_deref(), Some("amp"));
+}
+
+#[test]
+fn tag_errors_carry_the_tag_name() {
+    let errors = tokenizer_errors("<p/ x>");
+    let error = find(&errors, "unexpected-solidus-in-tag");
+    assert_eq!(error.context.as_deref(), Some("p"));
+
+    let errors = tokenizer_errors("<span class=x");
+    let error = find(&errors, "eof-in-tag");
+    assert_eq!(error.context.as_deref(), Some("span"));
+}
+
+#[test]
+fn doctype_errors_carry_the_doctype_name() {
+    let errors = tokenizer_errors("<!DOCTYPE html PUBLIC");
+    let error = find(&errors, "eof-in-doctype");
+    assert_eq!(error.context.as_deref(), Some("html"));
+}
+
+#[test]
+fn errors_without_a_subject_have_no_context() {
+    let errors = tokenizer_errors("<!-- x");
+    let error = find(&errors, "eof-in-comment");
+    assert_eq!(error.context, None);
+}
+
+#[test]
+fn tree_errors_carry_the_tag_name_of_the_token() {
+    let errors = tree_errors("<!DOCTYPE html><p></span>");
+    let error = errors
+        .iter()
+        .find(|error| error.code == ParseErrorCode::UnexpectedEndTag)
+        .unwrap();
+    assert_eq!(error.context.as_deref(), Some("span"));
+    assert_eq!(error.offset, "<!DOCTYPE html><p></span>".len());
+}
@@ -0,0 +1,69 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer, TokenizerState};
+
+fn characters(text: &str) -> Vec<Token> {
+    text.chars().map(|data| Token::Character { data }).collect()
+}
+
+fn start_tag(tokenizer: &mut Tokenizer, name: &str) {
+    match tokenizer.next_token() {
+        Token::StartTag { tag_name, .. } => assert_eq!(tag_name, name),
+        token => panic!("expected <{name}>, got {token:?}"),
+    }
+}
+
+fn child_text(doc: &Document, element: NodeId) -> String {
+    let arena = doc.arena();
+    arena
+        .children(element)
+        .filter_map(|child| arena.get(child).as_text())
+        .collect()
+}
+
+#[test]
+fn plaintext_state_emits_the_rest_of_the_input_as_characters() {
+    let mut tokenizer = Tokenizer::new(b"<body><plaintext><b>bold</b></plaintext>");
+    start_tag(&mut tokenizer, "body");
+    start_tag(&mut tokenizer, "plaintext");
+    // what the tree constructor does on a plaintext start tag
+    tokenizer.set_state(TokenizerState::PLAINTEXT);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.next_token();
+        if token == Token::EOF {
+            break;
+        }
+        tokens.push(token);
+    }
+    assert_eq!(tokens, characters("<b>bold</b></plaintext>"));
+    assert_eq!(tokenizer.current_state(), &TokenizerState::PLAINTEXT);
+}
+
+#[test]
+fn plaintext_state_replaces_nul_and_keeps_references() {
+    let mut tokenizer = Tokenizer::new("a\0&amp;<!-- é -->".as_bytes());
+    tokenizer.set_state(TokenizerState::PLAINTEXT);
+    let mut tokens = Vec::new();
+    while let token @ Token::Character { .. } = tokenizer.next_token() {
+        tokens.push(token);
+    }
+    assert_eq!(tokens, characters("a\u{FFFD}&amp;<!-- é -->"));
+}
+
+#[test]
+fn plaintext_element_swallows_the_rest_of_the_document() {
+    let doc = parse_document("<body><plaintext><b>bold</b></plaintext><p>after");
+    let plaintext = doc
+        .get_elements_by_tag_name("plaintext")
+        .item(&doc, 0)
+        .unwrap();
+    assert_eq!(
+        child_text(&doc, plaintext),
+        "<b>bold</b></plaintext><p>after"
+    );
+    assert_eq!(doc.get_elements_by_tag_name("b").len(&doc), 0);
+    assert_eq!(doc.get_elements_by_tag_name("p").len(&doc), 0);
+}
@@ -0,0 +1,72 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::{Element, NodeId};
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_fragment};
+use brooster_web_parser::dom::parser::tree_constructor::{
+    is_escapable_raw_text_element, is_raw_text_element,
+};
+use brooster_web_parser::dom::serializer::serialize_children;
+
+#[test]
+fn element_lists() {
+    for name in ["style", "script", "xmp", "iframe", "noembed", "noframes"] {
+        assert!(is_raw_text_element(name), "{name}");
+        assert!(!is_escapable_raw_text_element(name), "{name}");
+    }
+    for name in ["textarea", "title"] {
+        assert!(is_escapable_raw_text_element(name), "{name}");
+        assert!(!is_raw_text_element(name), "{name}");
+    }
+    for name in ["noscript", "plaintext", "div", "STYLE"] {
+        assert!(!is_raw_text_element(name) && !is_escapable_raw_text_element(name));
+    }
+}
+
+fn child_text(doc: &Document, element: NodeId) -> String {
+    let arena = doc.arena();
+    arena
+        .children(element)
+        .filter_map(|child| arena.get(child).as_text())
+        .collect()
+}
+
+fn text_of(html: &str, tag: &str) -> String {
+    let doc = parse_document(html);
+    let element = doc.get_elements_by_tag_name(tag).item(&doc, 0).unwrap();
+    child_text(&doc, element)
+}
+
+#[test]
+fn raw_text_contents_keep_markup_and_references() {
+    for tag in ["style", "script", "xmp", "iframe", "noembed", "noframes"] {
+        let html = format!("<body><{tag}><b>&amp;</b></{tag}>");
+        assert_eq!(text_of(&html, tag), "<b>&amp;</b>", "{tag}");
+    }
+}
+
+#[test]
+fn escapable_raw_text_contents_decode_references() {
+    assert_eq!(text_of("<title><b>&amp;</b></title>", "title"), "<b>&</b>");
+    assert_eq!(
+        text_of("<textarea>\n<b>&lt;</b></textarea>", "textarea"),
+        "<b><</b>"
+    );
+}
+
+#[test]
+fn noscript_is_parsed_as_markup() {
+    let doc = parse_document("<body><noscript><b>x</b></noscript>");
+    assert_eq!(doc.get_elements_by_tag_name("b").len(&doc), 1);
+}
+
+#[test]
+fn fragment_contexts_use_the_same_lists() {
+    let doc = parse_fragment("<b>&amp;</b>", &Element::new("xmp"));
+    let root = doc.document_element().unwrap();
+    assert_eq!(
+        serialize_children(&doc, root),
+        "&lt;b&gt;&amp;amp;&lt;/b&gt;"
+    );
+    let doc = parse_fragment("<b>&amp;</b>", &Element::new("title"));
+    let root = doc.document_element().unwrap();
+    assert_eq!(child_text(&doc, root), "<b>&</b>");
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::elements::html_button_element::HTMLButtonElement;
+use brooster_web_parser::dom::elements::html_image_element::HTMLImageElement;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::elements::html_text_area_element::HTMLTextAreaElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::first;
+
+#[test]
+fn string_reflection() {
+    let mut doc = parse_document("<button value=\"go\"></button><select name=\"s\"></select>");
+    let button = HTMLButtonElement::new(first(&doc, "button"));
+    let select = HTMLSelectElement::new(first(&doc, "select"));
+    assert_eq!(button.value(&doc), "go");
+    assert_eq!(button.name(&doc), "");
+    assert_eq!(select.name(&doc), "s");
+
+    select.set_name(&mut doc, "country");
+    select.set_autocomplete(&mut doc, "off");
+    assert_eq!(doc.get_attribute(select.node(), "name"), Some("country"));
+    assert_eq!(select.autocomplete(&doc), "off");
+}
+
+#[test]
+fn bool_reflection() {
+    let mut doc = parse_document("<select multiple=\"false\"></select>");
+    let select = HTMLSelectElement::new(first(&doc, "select"));
+    // presence is what counts, not the value
+    assert!(select.multiple(&doc));
+    assert!(!select.required(&doc));
+
+    select.set_required(&mut doc, true);
+    select.set_multiple(&mut doc, false);
+    assert_eq!(doc.get_attribute(select.node(), "required"), Some(""));
+    assert_eq!(doc.get_attribute(select.node(), "multiple"), None);
+    assert!(select.required(&doc));
+}
+
+#[test]
+fn u32_reflection() {
+    let mut doc =
+        parse_document("<img width=\" +40px\" height=\"-3\"><select size=\"x\"></select>");
+    let img = HTMLImageElement::new(first(&doc, "img"));
+    let select = HTMLSelectElement::new(first(&doc, "select"));
+    assert_eq!(img.width(&doc), 40);
+    assert_eq!(img.height(&doc), 0);
+    assert_eq!(select.size(&doc), 0);
+
+    img.set_height(&mut doc, 25);
+    select.set_size(&mut doc, 3_000_000_000);
+    assert_eq!(doc.get_attribute(img.node(), "height"), Some("25"));
+    assert_eq!(doc.get_attribute(select.node(), "size"), Some("0"));
+}
+
+#[test]
+fn enum_reflection() {
+    let mut doc =
+        parse_document("<button type=\"RESET\"></button><img loading=\"Lazy\" decoding=\"later\">");
+    let button = HTMLButtonElement::new(first(&doc, "button"));
+    let img = HTMLImageElement::new(first(&doc, "img"));
+    assert_eq!(button.r#type(&doc), "reset");
+    assert_eq!(img.loading(&doc), "lazy");
+    assert_eq!(img.decoding(&doc), "auto");
+
+    button.set_type(&mut doc, "menu");
+    assert_eq!(button.r#type(&doc), "submit");
+    assert_eq!(doc.get_attribute(button.node(), "type"), Some("menu"));
+}
+
+#[test]
+fn url_reflection() {
+    let mut doc = parse_document("<base href=\"https://example.com/a/\"><img>");
+    let img = HTMLImageElement::new(first(&doc, "img"));
+    assert_eq!(img.src(&doc), None);
+    img.set_src(&mut doc, "../b.png");
+    assert_eq!(doc.get_attribute(img.node(), "src"), Some("../b.png"));
+    assert_eq!(img.src(&doc).as_deref(), Some("https://example.com/b.png"));
+}
+
+#[test]
+fn positive_u32_reflection() {
+    let mut doc = parse_document("<textarea rows=\"0\" cols=\"3000000000\"></textarea>");
+    let textarea = HTMLTextAreaElement::new(first(&doc, "textarea"));
+    assert_eq!(textarea.rows(&doc), 2);
+    assert_eq!(textarea.cols(&doc), 20);
+
+    assert_eq!(textarea.set_rows(&mut doc, 0), Err(DomError::IndexSize));
+    assert_eq!(textarea.set_cols(&mut doc, 3_000_000_000), Ok(()));
+    assert_eq!(doc.get_attribute(textarea.node(), "rows"), Some("0"));
+    assert_eq!(doc.get_attribute(textarea.node(), "cols"), Some("20"));
+}
+
+#[test]
+fn non_negative_i32_reflection() {
+    let mut doc = parse_document("<textarea maxlength=\"-5\"></textarea>");
+    let textarea = HTMLTextAreaElement::new(first(&doc, "textarea"));
+    assert_eq!(textarea.max_length(&doc), -1);
+
+    assert_eq!(
+        textarea.set_max_length(&mut doc, -1),
+        Err(DomError::IndexSize)
+    );
+    assert_eq!(textarea.set_max_length(&mut doc, 12), Ok(()));
+    assert_eq!(textarea.max_length(&doc), 12);
+}
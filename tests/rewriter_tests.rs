@@ -0,0 +1,88 @@
+use brooster_web_parser::dom::rewriter::{
+    Action, CommentMut, EndTagMut, HtmlRewriter, RewriterHandler, StartTagMut, TextMut,
+};
+
+struct Identity;
+
+impl RewriterHandler for Identity {}
+
+fn rewrite<H: RewriterHandler>(handler: H, input: &str) -> String {
+    String::from_utf8(HtmlRewriter::new(handler).process(input.as_bytes())).unwrap()
+}
+
+#[test]
+fn default_handler_round_trips_normalized_markup() {
+    let input = "<!DOCTYPE html><p class=\"a\">caf\u{e9} &amp; \u{1F600}</p><!--note--><br/>";
+    assert_eq!(rewrite(Identity, input), input);
+}
+
+#[test]
+fn script_and_style_text_is_not_escaped() {
+    let input = "<script>if (a < b && c) {}</script><style>a > b {}</style><title>x &amp; y</title>";
+    assert_eq!(rewrite(Identity, input), input);
+}
+
+struct LinkRewriter;
+
+impl RewriterHandler for LinkRewriter {
+    fn on_start_tag(&mut self, tag: &mut StartTagMut) -> Action {
+        if tag.name() == "a" {
+            if let Some(href) = tag.get_attribute("href").map(str::to_string) {
+                tag.set_attribute("href", &href.replace("http:", "https:"));
+            }
+            tag.set_attribute("rel", "noopener");
+            tag.remove_attribute("onclick");
+        }
+        Action::Keep
+    }
+}
+
+#[test]
+fn start_tag_attributes_can_be_modified() {
+    assert_eq!(
+        rewrite(LinkRewriter, "<a href='http://x.test' onclick=\"go()\">x</a>"),
+        "<a href=\"https://x.test\" rel=\"noopener\">x</a>"
+    );
+}
+
+struct Scrubber;
+
+impl RewriterHandler for Scrubber {
+    fn on_start_tag(&mut self, tag: &mut StartTagMut) -> Action {
+        match tag.name() {
+            "b" => {
+                tag.set_name("strong");
+                Action::Keep
+            }
+            "img" => Action::Replace(b"[image]".to_vec()),
+            _ => Action::Keep,
+        }
+    }
+
+    fn on_end_tag(&mut self, tag: &mut EndTagMut) -> Action {
+        if tag.name() == "b" {
+            tag.set_name("strong");
+        }
+        Action::Keep
+    }
+
+    fn on_text(&mut self, text: &mut TextMut) -> Action {
+        if !text.is_raw() {
+            let upper = text.text().to_uppercase();
+            text.set_text(&upper);
+        }
+        Action::Keep
+    }
+
+    fn on_comment(&mut self, _comment: &mut CommentMut) -> Action {
+        Action::Remove
+    }
+}
+
+#[test]
+fn handlers_can_rename_replace_and_remove_tokens() {
+    assert_eq!(
+        rewrite(Scrubber, "<b>hi</b><!-- secret --><img src=x.png><script>x</script>"),
+        "<strong>HI</strong>[image]<script>x</script>"
+    );
+}
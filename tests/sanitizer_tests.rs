@@ -0,0 +1,71 @@
+use brooster_web_parser::dom::sanitizer::Sanitizer;
+
+#[test]
+fn default_allowlist_keeps_safe_formatting() {
+    let html = "<p>Some <b>bold</b>, <em>em</em> and <code>code</code></p><ul><li>item</li></ul>";
+    assert_eq!(Sanitizer::default().clean(html), html);
+}
+
+#[test]
+fn disallowed_tags_are_unwrapped_and_scripts_dropped() {
+    let clean = Sanitizer::default().clean(
+        "<div><span>kept</span></div><script>alert(1)</script><style>p{}</style><!-- c -->",
+    );
+    assert_eq!(clean, "kept");
+}
+
+#[test]
+fn disallowed_attributes_are_removed() {
+    let clean = Sanitizer::default().clean(
+        "<a href=\"https://example.com\" onclick=\"steal()\" style=\"x\">link</a>\
+         <img src=\"/a.png\" alt=\"A\" onerror=\"x()\">",
+    );
+    assert_eq!(
+        clean,
+        "<a href=\"https://example.com\">link</a><img src=\"/a.png\" alt=\"A\">"
+    );
+}
+
+#[test]
+fn urls_with_disallowed_schemes_are_removed() {
+    let sanitizer = Sanitizer::default();
+    for href in [
+        "javascript:alert(1)",
+        "JaVaScRiPt:alert(1)",
+        " java\tscript:alert(1)",
+        "javascript&#58;alert(1)",
+        "data:text/html,x",
+    ] {
+        assert_eq!(
+            sanitizer.clean(&format!("<a href=\"{href}\">x</a>")),
+            "<a>x</a>",
+            "{href}"
+        );
+    }
+    assert!(sanitizer.is_url_allowed("mailto:a@b.c"));
+    assert!(sanitizer.is_url_allowed("/relative/path:with-colon"));
+    assert!(sanitizer.is_url_allowed("page?q=a:b"));
+}
+
+#[test]
+fn builder_sets_custom_allowlists() {
+    let sanitizer = Sanitizer::builder()
+        .allow_tag("DIV")
+        .allow_tag("a")
+        .allow_attribute("div", "class")
+        .allow_attribute("a", "href")
+        .allow_protocol("ftp")
+        .build();
+    assert_eq!(
+        sanitizer.clean("<div class=x id=y><p>t</p><a href=\"ftp://h/f\">f</a><a href=\"http://h\">h</a></div>"),
+        "<div class=\"x\">t<a href=\"ftp://h/f\">f</a><a>h</a></div>"
+    );
+}
+
+#[test]
+fn nested_templates_are_dropped_entirely() {
+    assert_eq!(
+        Sanitizer::default().clean("<template><template>a</template>b</template><b>c</b>"),
+        "<b>c</b>"
+    );
+}
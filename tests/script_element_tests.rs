@@ -0,0 +1,92 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_script_element::{HTMLScriptElement, ScriptType};
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+
+fn script(doc: &Document, index: usize) -> HTMLScriptElement {
+    HTMLScriptElement::new(doc.scripts().item(doc, index).unwrap())
+}
+
+#[test]
+fn inline_text_round_trips() {
+    let source = "if (a < b && c > d) { s = '<!-- <script> -->'; }\n/* &amp; </scrip */";
+    let mut doc = parse_document(&format!("<script>{source}</script>"));
+    let s = script(&doc, 0);
+    assert_eq!(s.text(&doc), source);
+
+    s.set_text(&mut doc, "x = 1 &lt; 2");
+    assert_eq!(s.text(&doc), "x = 1 &lt; 2");
+    assert_eq!(doc.arena().children(s.node()).count(), 1);
+}
+
+#[test]
+fn async_and_defer_reflect_their_attributes() {
+    let mut doc = parse_document(
+        "<script src=a.js></script><script async defer src=b.js></script><script async=false>\
+         </script>",
+    );
+    let (plain, both, written) = (script(&doc, 0), script(&doc, 1), script(&doc, 2));
+    assert!(!plain.r#async(&doc) && !plain.defer(&doc));
+    assert!(both.r#async(&doc) && both.defer(&doc));
+    assert!(written.r#async(&doc));
+
+    plain.set_async(&mut doc, true);
+    both.set_defer(&mut doc, false);
+    assert_eq!(doc.get_attribute(plain.node(), "async"), Some(""));
+    assert!(!both.defer(&doc));
+}
+
+#[test]
+fn empty_and_missing_type_differ_with_a_language() {
+    let mut doc = parse_document(
+        "<script type='' language=vbscript></script><script language=vbscript></script>\
+         <script></script>",
+    );
+    let (empty, missing, bare) = (script(&doc, 0), script(&doc, 1), script(&doc, 2));
+    assert_eq!(
+        (empty.r#type(&doc), missing.r#type(&doc)),
+        (String::new(), String::new())
+    );
+    assert_eq!(empty.script_type(&doc), ScriptType::Classic);
+    assert_eq!(
+        missing.script_type(&doc),
+        ScriptType::Unknown("text/vbscript".to_string())
+    );
+    assert_eq!(doc.get_attribute(bare.node(), "type"), None);
+
+    bare.set_type(&mut doc, "module");
+    assert_eq!(bare.r#type(&doc), "module");
+    assert_eq!(bare.script_type(&doc), ScriptType::Module);
+}
+
+#[test]
+fn src_and_no_module() {
+    let doc = parse_document_with_url(
+        "<script src=js/app.js nomodule></script><script></script>",
+        "https://example.com/page/",
+    );
+    assert_eq!(
+        script(&doc, 0).src(&doc).as_deref(),
+        Some("https://example.com/page/js/app.js")
+    );
+    assert!(script(&doc, 0).no_module(&doc));
+    assert_eq!(script(&doc, 1).src(&doc), None);
+    assert!(!script(&doc, 1).no_module(&doc));
+}
+
+#[test]
+fn enumerated_attributes() {
+    let mut doc = parse_document(
+        "<script crossorigin referrerpolicy=NO-REFERRER></script>\
+         <script crossorigin=USE-CREDENTIALS referrerpolicy=nope></script>\
+         <script crossorigin=bogus></script><script></script>",
+    );
+    assert_eq!(script(&doc, 0).cross_origin(&doc), Some("anonymous"));
+    assert_eq!(script(&doc, 0).referrer_policy(&doc), "no-referrer");
+    assert_eq!(script(&doc, 1).cross_origin(&doc), Some("use-credentials"));
+    assert_eq!(script(&doc, 1).referrer_policy(&doc), "");
+    assert_eq!(script(&doc, 2).cross_origin(&doc), Some("anonymous"));
+    assert_eq!(script(&doc, 3).cross_origin(&doc), None);
+
+    script(&doc, 0).set_cross_origin(&mut doc, None);
+    assert_eq!(script(&doc, 0).cross_origin(&doc), None);
+}
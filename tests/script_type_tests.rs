@@ -0,0 +1,60 @@
+use brooster_web_parser::dom::elements::html_script_element::{
+    is_javascript_mime_type, HTMLScriptElement, ScriptType,
+};
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn script_types(html: &str) -> Vec<ScriptType> {
+    let doc = parse_document(html);
+    let scripts = doc.scripts();
+    (0..scripts.len(&doc))
+        .map(|index| HTMLScriptElement::new(scripts.item(&doc, index).unwrap()).script_type(&doc))
+        .collect()
+}
+
+#[test]
+fn classic_scripts() {
+    assert_eq!(
+        script_types(
+            "<script></script><script type></script><script type=' TEXT/JavaScript '></script>\
+             <script type=application/x-ecmascript></script><script language=javascript1.5></script>\
+             <script language=''></script>"
+        ),
+        vec![ScriptType::Classic; 6]
+    );
+}
+
+#[test]
+fn modules_and_import_maps() {
+    assert_eq!(
+        script_types("<script type=module></script><script type=ImportMap></script>"),
+        [ScriptType::Module, ScriptType::ImportMap]
+    );
+}
+
+#[test]
+fn data_blocks() {
+    assert_eq!(
+        script_types(
+            "<script type=application/ld+json></script><script type=text/json></script>\
+             <script type='text/javascript; charset=utf-8'></script><script language=vbscript></script>"
+        ),
+        [
+            ScriptType::JsonModule("application/ld+json".to_string()),
+            ScriptType::JsonModule("text/json".to_string()),
+            ScriptType::Unknown("text/javascript; charset=utf-8".to_string()),
+            ScriptType::Unknown("text/vbscript".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn javascript_mime_types_ignore_parameters_and_case() {
+    assert!(is_javascript_mime_type("text/javascript"));
+    assert!(is_javascript_mime_type(
+        "Application/JavaScript; charset=utf-8"
+    ));
+    assert!(is_javascript_mime_type(" text/jscript "));
+    assert!(!is_javascript_mime_type("module"));
+    assert!(!is_javascript_mime_type("application/json"));
+    assert!(!is_javascript_mime_type(""));
+}
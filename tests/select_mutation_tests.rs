@@ -0,0 +1,116 @@
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::{AddBefore, HTMLSelectElement};
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn setup() -> (Document, HTMLSelectElement) {
+    let doc = parse_document("<select><option>a<optgroup><option>b</optgroup><option>c</select>");
+    let select = HTMLSelectElement::new(
+        doc.get_elements_by_tag_name("select")
+            .item(&doc, 0)
+            .unwrap(),
+    );
+    (doc, select)
+}
+
+fn option(doc: &mut Document, label: &str) -> NodeId {
+    let option = doc.create_element("option");
+    let text = doc.create_text_node(label);
+    doc.append_child(option, text).unwrap();
+    option
+}
+
+fn html(doc: &Document, select: &HTMLSelectElement) -> String {
+    serialize_children(doc, select.node())
+        .replace("<option>", "(")
+        .replace("</option>", ")")
+}
+
+#[test]
+fn add_appends_or_inserts_before() {
+    let (mut doc, select) = setup();
+    let d = option(&mut doc, "d");
+    select.add(&mut doc, d, None).unwrap();
+    assert_eq!(html(&doc, &select), "(a)<optgroup>(b)</optgroup>(c)(d)");
+
+    // before an index goes into the parent of that option
+    let e = option(&mut doc, "e");
+    select.add(&mut doc, e, Some(AddBefore::Index(1))).unwrap();
+    assert_eq!(html(&doc, &select), "(a)<optgroup>(e)(b)</optgroup>(c)(d)");
+
+    let f = option(&mut doc, "f");
+    let first = select.item(&doc, 0).unwrap();
+    select
+        .add(&mut doc, f, Some(AddBefore::Element(first)))
+        .unwrap();
+    let g = option(&mut doc, "g");
+    select.add(&mut doc, g, Some(AddBefore::Index(99))).unwrap();
+    assert_eq!(
+        html(&doc, &select),
+        "(f)(a)<optgroup>(e)(b)</optgroup>(c)(d)(g)"
+    );
+    assert_eq!(select.length(&doc), 7);
+}
+
+#[test]
+fn add_errors() {
+    let (mut doc, select) = setup();
+    let body = doc.body().unwrap();
+    assert_eq!(
+        select.add(&mut doc, body, None),
+        Err(DomError::HierarchyRequest)
+    );
+    let outside = doc.create_element("option");
+    let new = option(&mut doc, "x");
+    assert_eq!(
+        select.add(&mut doc, new, Some(AddBefore::Element(outside))),
+        Err(DomError::NotFound)
+    );
+    assert_eq!(
+        select.add(&mut doc, new, Some(AddBefore::Element(select.node()))),
+        Err(DomError::NotFound)
+    );
+    let first = select.item(&doc, 0).unwrap();
+    select
+        .add(&mut doc, first, Some(AddBefore::Element(first)))
+        .unwrap();
+    assert_eq!(html(&doc, &select), "(a)<optgroup>(b)</optgroup>(c)");
+}
+
+#[test]
+fn remove_at_and_remove() {
+    let (mut doc, select) = setup();
+    select.remove_at(&mut doc, 1);
+    assert_eq!(html(&doc, &select), "(a)<optgroup></optgroup>(c)");
+    select.remove_at(&mut doc, -1);
+    select.remove_at(&mut doc, 5);
+    assert_eq!(select.length(&doc), 2);
+
+    // without an index the select itself goes away
+    select.remove(&mut doc);
+    assert_eq!(doc.arena().parent(select.node()), None);
+    assert!(doc.get_elements_by_tag_name("select").is_empty(&doc));
+}
+
+#[test]
+fn set_at_replaces_removes_and_pads() {
+    let (mut doc, select) = setup();
+    let x = option(&mut doc, "x");
+    select.set_at(&mut doc, 1, Some(x)).unwrap();
+    assert_eq!(html(&doc, &select), "(a)<optgroup>(x)</optgroup>(c)");
+
+    select.set_at(&mut doc, 0, None).unwrap();
+    assert_eq!(html(&doc, &select), "<optgroup>(x)</optgroup>(c)");
+
+    let y = option(&mut doc, "y");
+    select.set_at(&mut doc, 4, Some(y)).unwrap();
+    assert_eq!(html(&doc, &select), "<optgroup>(x)</optgroup>(c)()()(y)");
+    assert_eq!(select.item(&doc, 4), Some(y));
+
+    let z = option(&mut doc, "z");
+    select.set_at(&mut doc, 5, Some(z)).unwrap();
+    assert_eq!(select.item(&doc, 5), Some(z));
+    assert_eq!(select.length(&doc), 6);
+}
@@ -0,0 +1,81 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn select_of(doc: &Document) -> HTMLSelectElement {
+    HTMLSelectElement::new(doc.get_elements_by_tag_name("select").item(doc, 0).unwrap())
+}
+
+fn text(doc: &Document, node: NodeId) -> String {
+    let arena = doc.arena();
+    arena
+        .children(node)
+        .filter_map(|child| arena.get(child).as_text())
+        .collect()
+}
+
+#[test]
+fn options_inside_optgroups() {
+    let doc = parse_document("<select><optgroup><option id=a>1<option>2</optgroup></select>");
+    let select = select_of(&doc);
+    assert_eq!(select.length(&doc), 2);
+    assert_eq!(text(&doc, select.item(&doc, 1).unwrap()), "2");
+    assert_eq!(select.item(&doc, 2), None);
+    assert_eq!(select.named_item(&doc, "a"), select.item(&doc, 0));
+    assert_eq!(select.named_item(&doc, "b"), None);
+}
+
+#[test]
+fn set_length_truncates_and_grows() {
+    let mut doc = parse_document("<select><optgroup><option id=a>1<option>2</optgroup></select>");
+    let select = select_of(&doc);
+    let second = select.item(&doc, 1).unwrap();
+    select.set_length(&mut doc, 1);
+    assert_eq!(select.length(&doc), 1);
+    assert_eq!(doc.arena().parent(second), None);
+
+    select.set_length(&mut doc, 3);
+    assert_eq!(select.length(&doc), 3);
+    assert_eq!(
+        serialize_children(&doc, select.node()),
+        "<optgroup><option id=\"a\">1</option></optgroup><option></option><option></option>"
+    );
+
+    select.set_length(&mut doc, 100_001);
+    assert_eq!(select.length(&doc), 3);
+    select.set_length(&mut doc, 0);
+    assert!(select.options().is_empty(&doc));
+}
+
+#[test]
+fn options_only_come_from_children_and_optgroup_children() {
+    let mut doc = Document::new();
+    let root = doc.root();
+    let select_node = doc.create_element("select");
+    doc.append_child(root, select_node).unwrap();
+    let direct = doc.create_element("option");
+    let div = doc.create_element("div");
+    let nested = doc.create_element("option");
+    let group = doc.create_element("optgroup");
+    let grouped = doc.create_element("option");
+    let inner_group = doc.create_element("optgroup");
+    let too_deep = doc.create_element("option");
+    doc.append_child(select_node, direct).unwrap();
+    doc.append_child(select_node, div).unwrap();
+    doc.append_child(div, nested).unwrap();
+    doc.append_child(select_node, group).unwrap();
+    doc.append_child(group, grouped).unwrap();
+    doc.append_child(group, inner_group).unwrap();
+    doc.append_child(inner_group, too_deep).unwrap();
+
+    let select = HTMLSelectElement::new(select_node);
+    let options = select.options();
+    assert_eq!(options.iter(&doc).collect::<Vec<_>>(), [direct, grouped]);
+
+    // the collection is live
+    doc.append_child(select_node, nested).unwrap();
+    assert_eq!(options.item(&doc, 2), Some(nested));
+    assert_eq!(select.length(&doc), 3);
+}
@@ -0,0 +1,98 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn parse_select(html: &str) -> (Document, HTMLSelectElement) {
+    let doc = parse_document(html);
+    let node = doc
+        .get_elements_by_tag_name("select")
+        .item(&doc, 0)
+        .unwrap();
+    (doc, HTMLSelectElement::new(node))
+}
+
+#[test]
+fn first_option_is_selected_by_default() {
+    let (doc, select) = parse_select("<select><option>a<option>b</select>");
+    assert_eq!(select.selected_index(&doc), 0);
+    assert_eq!(select.value(&doc), "a");
+}
+
+#[test]
+fn selected_attribute_wins() {
+    let (doc, select) =
+        parse_select("<select><option>a<option>b<option selected value=third>c</select>");
+    assert_eq!(select.selected_index(&doc), 2);
+    assert_eq!(select.value(&doc), "third");
+
+    // without multiple only the last selected option stays selected
+    let (doc, select) =
+        parse_select("<select><option selected>a<option selected>b<option>c</select>");
+    assert_eq!(select.selected_index(&doc), 1);
+    let first = select.item(&doc, 0).unwrap();
+    assert!(!doc.option_selectedness(first));
+}
+
+#[test]
+fn default_skips_disabled_options() {
+    let (doc, select) = parse_select(
+        "<select><option disabled>a<optgroup disabled><option>b</optgroup><option>c</select>",
+    );
+    assert_eq!(select.selected_index(&doc), 2);
+}
+
+#[test]
+fn list_boxes_and_multiple_selects_have_no_default() {
+    let (doc, select) = parse_select("<select size=3><option>a<option>b</select>");
+    assert_eq!(select.selected_index(&doc), -1);
+    assert_eq!(select.value(&doc), "");
+
+    let (doc, select) =
+        parse_select("<select multiple><option selected>a<option selected>b</select>");
+    assert_eq!(select.selected_index(&doc), 0);
+    assert_eq!(select.selected_options().len(&doc), 2);
+
+    // an invalid size is the default display size
+    let (doc, select) = parse_select("<select size=0><option>a</select>");
+    assert_eq!(select.selected_index(&doc), 0);
+}
+
+#[test]
+fn set_value_selects_the_first_match() {
+    let (mut doc, select) = parse_select(
+        "<select><option>  spaced \n text </option><option value=x>b<option value=x>c</select>",
+    );
+    assert_eq!(select.value(&doc), "spaced text");
+    select.set_value(&mut doc, "x");
+    assert_eq!(select.selected_index(&doc), 1);
+    assert_eq!(select.value(&doc), "x");
+
+    select.set_value(&mut doc, "missing");
+    assert_eq!(select.selected_index(&doc), -1);
+    assert_eq!(select.value(&doc), "");
+}
+
+#[test]
+fn set_selected_index_updates_selectedness() {
+    let (mut doc, select) = parse_select("<select><option>a<option>b<option>c</select>");
+    select.set_selected_index(&mut doc, 2);
+    assert_eq!(select.selected_index(&doc), 2);
+    assert_eq!(select.value(&doc), "c");
+    let options: Vec<_> = select.options().iter(&doc).collect();
+    let selectedness: Vec<_> = options
+        .iter()
+        .map(|&option| doc.option_selectedness(option))
+        .collect();
+    assert_eq!(selectedness, [false, false, true]);
+
+    select.set_selected_index(&mut doc, 7);
+    assert_eq!(select.selected_index(&doc), -1);
+    select.set_selected_index(&mut doc, -1);
+    assert_eq!(select.value(&doc), "");
+}
+
+#[test]
+fn option_text_skips_scripts() {
+    let (doc, select) = parse_select("<select><option>a<script>x</script> b</select>");
+    assert_eq!(select.value(&doc), "a b");
+}
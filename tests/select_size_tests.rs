@@ -0,0 +1,68 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn parse_select(html: &str) -> (Document, HTMLSelectElement) {
+    let doc = parse_document(html);
+    let node = doc
+        .get_elements_by_tag_name("select")
+        .item(&doc, 0)
+        .unwrap();
+    (doc, HTMLSelectElement::new(node))
+}
+
+#[test]
+fn default_size_depends_on_multiple() {
+    let (mut doc, select) = parse_select("<select></select>");
+    assert_eq!(select.size(&doc), 0);
+    assert_eq!(select.effective_size(&doc), 1);
+
+    select.set_multiple(&mut doc, true);
+    assert_eq!(select.size(&doc), 0);
+    assert_eq!(select.effective_size(&doc), 4);
+}
+
+#[test]
+fn size_attribute_is_reflected() {
+    for (attribute, size) in [
+        ("3", 3),
+        (" +7", 7),
+        ("5rows", 5),
+        ("0", 0),
+        ("-0", 0),
+        (" -00", 0),
+        ("-2", 0),
+        ("x", 0),
+        ("", 0),
+        ("2147483648", 0),
+    ] {
+        let (doc, select) = parse_select(&format!("<select size='{attribute}'></select>"));
+        assert_eq!(select.size(&doc), size, "size={attribute:?}");
+        let effective = if size == 0 { 1 } else { size };
+        assert_eq!(select.effective_size(&doc), effective, "size={attribute:?}");
+    }
+}
+
+#[test]
+fn set_size_writes_the_attribute() {
+    let (mut doc, select) = parse_select("<select multiple></select>");
+    select.set_size(&mut doc, 6);
+    assert_eq!(doc.get_attribute(select.node(), "size"), Some("6"));
+    assert_eq!(select.effective_size(&doc), 6);
+
+    select.set_size(&mut doc, 0);
+    assert_eq!(doc.get_attribute(select.node(), "size"), Some("0"));
+    assert_eq!(select.size(&doc), 0);
+    assert_eq!(select.effective_size(&doc), 4);
+
+    select.set_size(&mut doc, u32::MAX);
+    assert_eq!(doc.get_attribute(select.node(), "size"), Some("0"));
+}
+
+#[test]
+fn effective_size_decides_the_default_selection() {
+    let (doc, select) = parse_select("<select size=1><option>a</select>");
+    assert_eq!(select.selected_index(&doc), 0);
+    let (doc, select) = parse_select("<select size=2><option>a</select>");
+    assert_eq!(select.selected_index(&doc), -1);
+}
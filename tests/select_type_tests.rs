@@ -0,0 +1,84 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_select_element::HTMLSelectElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn parse_select(html: &str) -> (Document, HTMLSelectElement) {
+    let doc = parse_document(html);
+    let node = doc
+        .get_elements_by_tag_name("select")
+        .item(&doc, 0)
+        .unwrap();
+    (doc, HTMLSelectElement::new(node))
+}
+
+#[test]
+fn multiple_attribute_flips_the_type() {
+    let (mut doc, select) = parse_select("<select><option>a</select>");
+    assert!(!select.multiple(&doc));
+    assert_eq!(select.r#type(&doc), "select-one");
+
+    select.set_multiple(&mut doc, true);
+    assert_eq!(doc.get_attribute(select.node(), "multiple"), Some(""));
+    assert_eq!(select.r#type(&doc), "select-multiple");
+
+    doc.remove_attribute(select.node(), "multiple");
+    assert_eq!(select.r#type(&doc), "select-one");
+
+    doc.set_attribute(select.node(), "multiple", "multiple");
+    assert!(select.multiple(&doc));
+    select.set_multiple(&mut doc, false);
+    assert_eq!(select.r#type(&doc), "select-one");
+}
+
+#[test]
+fn selected_options_are_in_tree_order() {
+    let (doc, select) = parse_select(
+        "<select multiple><option>a<optgroup><option selected>b</optgroup>\
+         <option>c<option selected>d</select>",
+    );
+    let texts: Vec<String> = select
+        .selected_options()
+        .iter(&doc)
+        .map(|option| select_text(&doc, option))
+        .collect();
+    assert_eq!(texts, ["b", "d"]);
+}
+
+#[test]
+fn selected_options_follow_selectedness_not_the_attribute() {
+    let (mut doc, select) =
+        parse_select("<select><option selected>a<option selected>b<option>c</select>");
+    let options: Vec<_> = select.options().iter(&doc).collect();
+    // only the last selected option stays selected in a single select
+    assert_eq!(
+        select.selected_options().iter(&doc).collect::<Vec<_>>(),
+        [options[1]]
+    );
+
+    select.set_selected_index(&mut doc, 2);
+    assert_eq!(
+        select.selected_options().iter(&doc).collect::<Vec<_>>(),
+        [options[2]]
+    );
+    assert!(doc.get_attribute(options[0], "selected").is_some());
+}
+
+#[test]
+fn selected_options_skip_options_outside_the_list() {
+    let (mut doc, select) = parse_select("<select multiple><option selected>a</select>");
+    let div = doc.create_element("div");
+    doc.append_child(select.node(), div).unwrap();
+    let nested = doc.create_element("option");
+    doc.append_child(div, nested).unwrap();
+    doc.set_attribute(nested, "selected", "");
+    assert!(doc.option_selectedness(nested));
+    assert_eq!(select.selected_options().len(&doc), 1);
+}
+
+fn select_text(doc: &Document, option: NodeId) -> String {
+    doc.arena()
+        .children(option)
+        .filter_map(|child| doc.arena().get(child).as_text().map(str::to_string))
+        .collect()
+}
@@ -0,0 +1,153 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeData;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::{
+    serialize_children, serialize_children_opts, SerializeError, SerializeOpts,
+};
+
+fn comment_document(data: &str) -> Document {
+    let mut doc = parse_document("<p>before</p><p>after</p>");
+    let body = doc.body().unwrap();
+    let comment = doc.create_comment(data);
+    let after = doc.arena().children(body).nth(1);
+    doc.insert_before(body, comment, after).unwrap();
+    doc
+}
+
+fn comments(doc: &Document) -> Vec<String> {
+    doc.arena()
+        .descendants(doc.root())
+        .filter_map(|node| match &doc.arena().get(node).data {
+            NodeData::Comment(data) => Some(data.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn unserializable_comments_reparse_to_an_equivalent_tree() {
+    for data in ["a-->b", ">x", "->x", "a--!>b", "x<!-"] {
+        let doc = comment_document(data);
+        let html = serialize_children(&doc, doc.root());
+        let reparsed = parse_document(&html);
+        let body = reparsed.body().unwrap();
+        assert_eq!(reparsed.arena().children(body).count(), 3, "{data}: {html}");
+        assert_eq!(comments(&reparsed).len(), 1, "{data}: {html}");
+        assert_eq!(
+            serialize_children(&reparsed, reparsed.root()),
+            html,
+            "{data} is rewritten once"
+        );
+    }
+    let doc = comment_document("a-->b");
+    assert!(serialize_children(&doc, doc.root()).contains("<!--a-- >b-->"));
+}
+
+#[test]
+fn serializable_comments_are_kept_as_is() {
+    for data in ["a-b", "-", "a--", "<!-- nested", "x->"] {
+        let doc = comment_document(data);
+        let html = serialize_children(&doc, doc.root());
+        assert_eq!(comments(&parse_document(&html)), [data], "{html}");
+    }
+}
+
+#[test]
+fn strict_mode_rejects_unserializable_comments() {
+    let doc = comment_document("a-->b");
+    let comment = doc
+        .arena()
+        .descendants(doc.root())
+        .find(|&node| matches!(doc.arena().get(node).data, NodeData::Comment(_)))
+        .unwrap();
+    let strict = SerializeOpts {
+        strict: true,
+        ..SerializeOpts::default()
+    };
+    assert_eq!(
+        serialize_children_opts(&doc, doc.root(), &strict),
+        Err(SerializeError::Comment(comment))
+    );
+    let lenient = serialize_children_opts(&doc, doc.root(), &SerializeOpts::default());
+    assert_eq!(
+        lenient.as_deref().ok(),
+        Some(serialize_children(&doc, doc.root()).as_str())
+    );
+}
+
+#[test]
+fn doctype_identifiers_are_quoted() {
+    let doc = parse_document(
+        "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\" \"http://www.w3.org/TR/html4/strict.dtd\">",
+    );
+    assert!(serialize_children(&doc, doc.root()).starts_with(
+        "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\" \"http://www.w3.org/TR/html4/strict.dtd\">"
+    ));
+
+    let doc = parse_document("<!DOCTYPE html SYSTEM 'say \"hi\"'>");
+    let html = serialize_children(&doc, doc.root());
+    assert!(
+        html.starts_with("<!DOCTYPE html SYSTEM 'say \"hi\"'>"),
+        "{html}"
+    );
+    assert_eq!(serialize_children(&parse_document(&html), doc.root()), html);
+
+    let doc = parse_document("<!DOCTYPE html>");
+    assert!(serialize_children(&doc, doc.root()).starts_with("<!DOCTYPE html><html>"));
+}
+
+fn body_html(doc: &Document, opts: &SerializeOpts) -> String {
+    serialize_children_opts(doc, doc.body().unwrap(), opts).unwrap()
+}
+
+#[test]
+fn void_elements_have_no_end_tag_nor_children() {
+    let mut doc = parse_document("<br><img src=a.png>");
+    let br = doc.get_elements_by_tag_name("br").item(&doc, 0).unwrap();
+    // a child added by hand is not serialized
+    let text = doc.create_text_node("lost");
+    doc.append_child(br, text).unwrap();
+    assert_eq!(
+        body_html(&doc, &SerializeOpts::default()),
+        "<br><img src=\"a.png\">"
+    );
+}
+
+#[test]
+fn boolean_attributes_are_minimized_on_request() {
+    let doc = parse_document("<input disabled checked=checked value=\"\"><p hidden=no></p>");
+    assert_eq!(
+        body_html(&doc, &SerializeOpts::default()),
+        "<input disabled=\"\" checked=\"checked\" value=\"\"><p hidden=\"no\"></p>"
+    );
+    let minimized = SerializeOpts {
+        minimize_boolean_attributes: true,
+        ..SerializeOpts::default()
+    };
+    assert_eq!(
+        body_html(&doc, &minimized),
+        "<input disabled checked value=\"\"><p hidden=\"no\"></p>"
+    );
+}
+
+#[test]
+fn leading_newline_of_pre_survives_a_round_trip() {
+    // the newline right after <pre> is dropped by the parser, the second one is text
+    let doc = parse_document("<pre>\n\nfoo</pre><textarea>\n\nbar</textarea><pre>\nbaz</pre>");
+    let html = body_html(&doc, &SerializeOpts::default());
+    assert_eq!(
+        html,
+        "<pre>\n\nfoo</pre><textarea>\n\nbar</textarea><pre>baz</pre>"
+    );
+    let reparsed = parse_document(&html);
+    assert_eq!(body_html(&reparsed, &SerializeOpts::default()), html);
+
+    let without = SerializeOpts {
+        preserve_leading_newline: false,
+        ..SerializeOpts::default()
+    };
+    assert_eq!(
+        body_html(&doc, &without),
+        "<pre>\nfoo</pre><textarea>\nbar</textarea><pre>baz</pre>"
+    );
+}
@@ -0,0 +1,46 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::{serialize_children, serialize_node_to_string};
+
+fn body_markup(html: &str) -> String {
+    let doc = parse_document(html);
+    let html_element = doc.arena().children(doc.root()).last().unwrap();
+    let body = doc.arena().children(html_element).last().unwrap();
+    serialize_children(&doc, body)
+}
+
+#[test]
+fn text_and_attribute_values_are_escaped() {
+    assert_eq!(
+        body_markup("<p title='a \"b\" &amp; c'>1 &lt; 2 &amp;&nbsp;3 &gt; 0</p>"),
+        "<p title=\"a &quot;b&quot; &amp; c\">1 &lt; 2 &amp;&nbsp;3 &gt; 0</p>"
+    );
+}
+
+#[test]
+fn void_elements_have_no_end_tag() {
+    assert_eq!(
+        body_markup("<br><img src=a><input type=text></input><hr/>"),
+        "<br><img src=\"a\"><input type=\"text\"><hr>"
+    );
+}
+
+#[test]
+fn comments_and_doctypes_round_trip() {
+    let doc = parse_document("<!DOCTYPE html><!-- top --><p>x<!--inner-->");
+    let markup = serialize_children(&doc, doc.root());
+    assert!(markup.starts_with("<!DOCTYPE html><!-- top --><html>"));
+    assert!(markup.contains("<p>x<!--inner--></p>"));
+}
+
+#[test]
+fn serialize_node_to_string_includes_the_node() {
+    let doc = parse_document("<div id=d><span>a</span>b</div>");
+    let html_element = doc.arena().children(doc.root()).last().unwrap();
+    let body = doc.arena().children(html_element).last().unwrap();
+    let div = doc.arena().children(body).next().unwrap();
+    assert_eq!(
+        serialize_node_to_string(&doc, div),
+        "<div id=\"d\"><span>a</span>b</div>"
+    );
+    assert_eq!(serialize_children(&doc, div), "<span>a</span>b");
+}
@@ -0,0 +1,56 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::helper::small_str::{SmallStr, INLINE_CAPACITY};
+
+#[test]
+fn short_strings_stay_inline() {
+    let name = SmallStr::from("class");
+    assert!(name.is_inline());
+    assert_eq!(name, "class");
+    assert_eq!(name.len(), 5);
+
+    let longest = "x".repeat(INLINE_CAPACITY);
+    assert!(SmallStr::from(longest.as_str()).is_inline());
+    assert!(!SmallStr::from(format!("{longest}x")).is_inline());
+}
+
+#[test]
+fn pushing_past_the_inline_capacity_moves_to_the_heap() {
+    let mut name = SmallStr::new();
+    for _ in 0..INLINE_CAPACITY {
+        name.push('a');
+    }
+    assert!(name.is_inline());
+    name.push('é');
+    assert!(!name.is_inline());
+    assert_eq!(name, format!("{}é", "a".repeat(INLINE_CAPACITY)));
+
+    name.clear();
+    assert!(name.is_inline());
+    assert!(name.is_empty());
+}
+
+#[test]
+fn multi_byte_characters_are_not_split() {
+    let mut name = SmallStr::from("x".repeat(INLINE_CAPACITY - 1).as_str());
+    name.push('\u{FFFD}');
+    assert_eq!(name.chars().last(), Some('\u{FFFD}'));
+    assert_eq!(String::from(name.clone()), name.as_str());
+}
+
+#[test]
+fn tokenizer_names_are_inline() {
+    let mut tokenizer = Tokenizer::new(b"<div data-role=main>");
+    match tokenizer.next_token() {
+        Token::StartTag {
+            tag_name,
+            attributes,
+            ..
+        } => {
+            assert!(tag_name.is_inline());
+            assert_eq!(tag_name, "div");
+            assert!(attributes[0].0.is_inline());
+            assert_eq!(attributes[0].0, "data-role");
+        }
+        token => panic!("expected a start tag, got {token:?}"),
+    }
+}
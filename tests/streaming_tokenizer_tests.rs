@@ -0,0 +1,97 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    tokenizer.tokens().to_vec()
+}
+
+fn tokenize_in_chunks(input: &str, chunk_size: usize) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::streaming();
+    let mut tokens = Vec::new();
+    for chunk in input.as_bytes().chunks(chunk_size) {
+        tokenizer.feed(chunk);
+        while let Some(token) = tokenizer.next_available_token() {
+            tokens.push(token);
+        }
+    }
+    tokenizer.end_input();
+    while let Some(token) = tokenizer.next_available_token() {
+        let is_eof = token == Token::EOF;
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    tokens
+}
+
+const DOCUMENT: &str = "<!DOCTYPE html><html><head><title>t</title></head>\
+    <body><p class=\"a b\" id=x>fish &amp; chips &notin; &notit; &#x41;</p>\
+    <!-- a comment --><br/><script>if (a < b) {}</script></body></html>";
+
+#[test]
+fn chunked_input_gives_the_same_tokens_as_the_whole_input() {
+    for chunk_size in [1, 2, 7, 64, 8192] {
+        assert_eq!(tokenize_in_chunks(DOCUMENT, chunk_size), tokenize(DOCUMENT));
+    }
+}
+
+#[test]
+fn streaming_tokenizer_waits_for_more_input_before_end_input() {
+    let mut tokenizer = Tokenizer::streaming();
+    tokenizer.feed(b"ab");
+    assert_eq!(tokenizer.next_available_token(), None);
+
+    tokenizer.end_input();
+    assert_eq!(
+        tokenizer.next_available_token(),
+        Some(Token::Character { data: 'a' })
+    );
+    assert_eq!(
+        tokenizer.next_available_token(),
+        Some(Token::Character { data: 'b' })
+    );
+    assert_eq!(tokenizer.next_available_token(), Some(Token::EOF));
+    assert_eq!(tokenizer.next_available_token(), Some(Token::EOF));
+}
+
+#[test]
+fn character_reference_split_across_chunks_is_decoded() {
+    assert_eq!(tokenize_in_chunks("&no", 3), tokenize("&no"));
+    assert_eq!(tokenize_in_chunks("x&notin;y", 3), tokenize("x&notin;y"));
+}
+
+#[cfg(feature = "async")]
+mod tokenize_async {
+    use super::{tokenize, DOCUMENT};
+    use brooster_web_parser::dom::parser::async_tokenizer::tokenize_async;
+    use brooster_web_parser::dom::parser::tokenizer::Token;
+    use tokio::io::AsyncWriteExt;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn tokens_match_the_synchronous_tokenizer() {
+        let tokens: Vec<Token> = tokenize_async(DOCUMENT.as_bytes())
+            .await
+            .map(|token| token.unwrap())
+            .collect()
+            .await;
+        assert_eq!(tokens, tokenize(DOCUMENT));
+    }
+
+    #[tokio::test]
+    async fn tokens_are_yielded_before_the_input_ends() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        let mut tokens = Box::pin(tokenize_async(reader).await);
+        writer.write_all(&[b'x'; 100]).await.unwrap();
+        assert_eq!(
+            tokens.next().await.unwrap().unwrap(),
+            Token::Character { data: 'x' }
+        );
+        drop(writer);
+        let rest: Vec<Token> = tokens.map(|token| token.unwrap()).collect().await;
+        assert_eq!(rest.len(), 100);
+        assert_eq!(rest.last(), Some(&Token::EOF));
+    }
+}
@@ -0,0 +1,66 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_style_element::HTMLStyleElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+
+fn style(doc: &Document) -> HTMLStyleElement {
+    HTMLStyleElement::new(doc.get_elements_by_tag_name("style").item(doc, 0).unwrap())
+}
+
+#[test]
+fn text_round_trips_up_to_the_real_end_tag() {
+    let css = "a::after { content: '</sty' } /* <!-- <b> --> */ p > q { color: red }";
+    let doc = parse_document(&format!("<style>{css}</style><p>after"));
+    let s = style(&doc);
+    assert_eq!(s.text(&doc), css);
+    assert_eq!(
+        serialize_node_to_string(&doc, s.node()),
+        format!("<style>{css}</style>")
+    );
+}
+
+#[test]
+fn only_text_css_is_css() {
+    let doc = parse_document(
+        "<style></style><style type=''></style><style type=TEXT/CSS></style>\
+         <style type=text/less></style>",
+    );
+    let styles = doc.get_elements_by_tag_name("style");
+    let is_css: Vec<bool> = styles
+        .iter(&doc)
+        .map(|node| HTMLStyleElement::new(node).is_css(&doc))
+        .collect();
+    assert_eq!(is_css, [true, true, true, false]);
+}
+
+#[test]
+fn set_text_replaces_the_content_unescaped() {
+    let mut doc = parse_document("<style>old {}</style>");
+    let s = style(&doc);
+    s.set_text(&mut doc, "q::before { content: \"<&>\" }");
+    assert_eq!(
+        serialize_node_to_string(&doc, s.node()),
+        "<style>q::before { content: \"<&>\" }</style>"
+    );
+    assert_eq!(doc.arena().children(s.node()).count(), 1);
+}
+
+#[test]
+fn disabled_is_not_an_attribute() {
+    let mut doc = parse_document("<style media=print type=text/css>p {}</style>");
+    let s = style(&doc);
+    assert_eq!(
+        (s.media(&doc), s.r#type(&doc)),
+        ("print".to_string(), "text/css".to_string())
+    );
+    assert!(!s.disabled(&doc));
+    s.set_disabled(&mut doc, true);
+    assert!(s.disabled(&doc));
+    assert_eq!(doc.get_attribute(s.node(), "disabled"), None);
+
+    let map = doc.compact();
+    let s = HTMLStyleElement::new(map.get(s.node()).unwrap());
+    assert!(s.disabled(&doc));
+    s.set_disabled(&mut doc, false);
+    assert!(!s.disabled(&doc));
+}
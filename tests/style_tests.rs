@@ -0,0 +1,75 @@
+use brooster_web_parser::dom::node::Element;
+use brooster_web_parser::dom::style::CssStyleDeclaration;
+
+fn element_with_style(style: &str) -> Element {
+    let mut element = Element::new("div");
+    element.set_attribute("style", style);
+    element
+}
+
+#[test]
+fn declarations_are_parsed_in_order() {
+    let style = element_with_style("color: red; WIDTH:10px;margin : 0 auto").style();
+    let names: Vec<&str> = style.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["color", "width", "margin"]);
+    assert_eq!(style.get_property("width"), Some("10px"));
+    assert_eq!(style.get_property("Margin"), Some("0 auto"));
+    assert_eq!(style.get_property("height"), None);
+}
+
+#[test]
+fn semicolons_inside_urls_and_strings_do_not_split() {
+    let style = CssStyleDeclaration::parse(
+        "background: url(data:image/png;base64,AAA;) no-repeat; content: \"a;b\"; font-family: 'x:y'",
+    );
+    assert_eq!(style.len(), 3);
+    assert_eq!(
+        style.get_property("background"),
+        Some("url(data:image/png;base64,AAA;) no-repeat")
+    );
+    assert_eq!(style.get_property("content"), Some("\"a;b\""));
+    assert_eq!(style.get_property("font-family"), Some("'x:y'"));
+}
+
+#[test]
+fn important_comments_and_malformed_declarations() {
+    let style = CssStyleDeclaration::parse(
+        "color: red ! IMPORTANT; /* note; */ width: 1px; junk; : nothing; --Brand: Blue",
+    );
+    assert_eq!(style.get_property("color"), Some("red"));
+    assert!(style.is_important("color"));
+    assert!(!style.is_important("width"));
+    assert_eq!(style.len(), 3);
+    assert_eq!(style.get_property("--Brand"), Some("Blue"));
+    assert_eq!(style.get_property("--brand"), None);
+}
+
+#[test]
+fn duplicates_are_kept_until_the_property_is_set() {
+    let mut element = element_with_style("color: red; width: 1px; color: blue");
+    assert_eq!(element.style().get_property("color"), Some("blue"));
+    assert_eq!(element.style().css_text(), "color: red; width: 1px; color: blue");
+
+    element.style_mut().set_property("color", "green", false);
+    assert_eq!(
+        element.get_attribute("style"),
+        Some("width: 1px; color: green")
+    );
+}
+
+#[test]
+fn mutations_round_trip_through_the_attribute() {
+    let mut element = element_with_style("color:red;");
+    {
+        let mut style = element.style_mut();
+        style.set_property("Width", "10px", true);
+        assert_eq!(style.get_property("width"), Some("10px"));
+        assert_eq!(style.remove_property("color").as_deref(), Some("red"));
+    }
+    assert_eq!(element.get_attribute("style"), Some("width: 10px !important"));
+    let reparsed = CssStyleDeclaration::parse(element.get_attribute("style").unwrap());
+    assert_eq!(reparsed, element.style());
+
+    element.style_mut().remove_property("width");
+    assert!(!element.has_attribute("style"));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_table_element::HTMLTableElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use common::by_id;
+
+fn table(doc: &Document) -> HTMLTableElement {
+    HTMLTableElement::new(doc.get_elements_by_tag_name("table").item(doc, 0).unwrap())
+}
+
+fn row_ids(doc: &Document, table: &HTMLTableElement) -> Vec<String> {
+    table
+        .rows()
+        .iter(doc)
+        .map(|row| doc.get_attribute(row, "id").unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn insert_row_in_an_empty_table_creates_a_tbody() {
+    let mut doc = parse_document("<table></table>");
+    let t = table(&doc);
+    let row = t.insert_row(&mut doc, -1).unwrap();
+    let t_body = doc.arena().parent(row).unwrap();
+    assert!(doc.element(t_body).unwrap().is_html_named("tbody"));
+    assert_eq!(doc.arena().parent(t_body), Some(t.node()));
+    assert_eq!(t.t_bodies().len(&doc), 1);
+    assert_eq!(t.rows().item(&doc, 0), Some(row));
+}
+
+#[test]
+fn rows_put_the_head_first_and_the_foot_last() {
+    let doc = parse_document(
+        "<table><tbody><tr id=b1></tbody><tfoot><tr id=f1></tfoot><thead><tr id=h1></thead>\
+         <tbody><tr id=b2><tr id=b3></tbody><thead><tr id=h2></thead></table>",
+    );
+    let t = table(&doc);
+    assert_eq!(row_ids(&doc, &t), ["h1", "h2", "b1", "b2", "b3", "f1"]);
+}
+
+#[test]
+fn rows_ignore_nested_tables() {
+    let doc = parse_document(
+        "<table><tr id=a><td><table><tr id=inner></table></td></tr><tr id=b></table>",
+    );
+    assert_eq!(row_ids(&doc, &table(&doc)), ["a", "b"]);
+}
+
+#[test]
+fn insert_row_positions() {
+    let mut doc = parse_document("<table><thead><tr id=h></thead><tbody><tr id=b></tbody></table>");
+    let t = table(&doc);
+    let first = t.insert_row(&mut doc, 0).unwrap();
+    doc.set_attribute(first, "id", "first");
+    let last = t.insert_row(&mut doc, 3).unwrap();
+    doc.set_attribute(last, "id", "last");
+    let middle = t.insert_row(&mut doc, 2).unwrap();
+    doc.set_attribute(middle, "id", "middle");
+    assert_eq!(row_ids(&doc, &t), ["first", "h", "middle", "b", "last"]);
+    assert_eq!(
+        doc.arena().parent(first),
+        doc.arena().parent(by_id(&doc, "h"))
+    );
+    assert_eq!(
+        doc.arena().parent(last),
+        doc.arena().parent(by_id(&doc, "b"))
+    );
+
+    assert_eq!(t.insert_row(&mut doc, 6), Err(DomError::IndexSize));
+    assert_eq!(t.insert_row(&mut doc, -2), Err(DomError::IndexSize));
+}
+
+#[test]
+fn delete_row_reindexes() {
+    let mut doc = parse_document("<table><tr id=a><tr id=b><tr id=c><tr id=d></table>");
+    let t = table(&doc);
+    t.delete_row(&mut doc, 1).unwrap();
+    assert_eq!(row_ids(&doc, &t), ["a", "c", "d"]);
+    t.delete_row(&mut doc, 1).unwrap();
+    assert_eq!(row_ids(&doc, &t), ["a", "d"]);
+    t.delete_row(&mut doc, -1).unwrap();
+    assert_eq!(row_ids(&doc, &t), ["a"]);
+    assert_eq!(t.delete_row(&mut doc, 1), Err(DomError::IndexSize));
+    assert_eq!(t.delete_row(&mut doc, -3), Err(DomError::IndexSize));
+    t.delete_row(&mut doc, 0).unwrap();
+    t.delete_row(&mut doc, -1).unwrap();
+    assert!(t.rows().is_empty(&doc));
+}
+
+#[test]
+fn sections_are_created_in_place() {
+    let mut doc = parse_document("<table><colgroup></colgroup><tbody></tbody></table>");
+    let t = table(&doc);
+    let t_foot = t.create_t_foot(&mut doc);
+    let t_head = t.create_t_head(&mut doc);
+    let caption = t.create_caption(&mut doc);
+    let t_body = t.create_t_body(&mut doc);
+    assert_eq!(t.create_t_head(&mut doc), t_head);
+    assert_eq!(t.create_caption(&mut doc), caption);
+    assert_eq!(t.t_foot(&doc), Some(t_foot));
+    assert_eq!(t.caption(&doc), Some(caption));
+    assert_eq!(
+        serialize_node_to_string(&doc, t.node()),
+        "<table><caption></caption><colgroup></colgroup><thead></thead><tbody></tbody>\
+         <tbody></tbody><tfoot></tfoot></table>"
+    );
+    assert_eq!(t.t_bodies().item(&doc, 1), Some(t_body));
+
+    t.delete_t_head(&mut doc);
+    t.delete_t_foot(&mut doc);
+    t.delete_caption(&mut doc);
+    assert_eq!(
+        (t.t_head(&doc), t.t_foot(&doc), t.caption(&doc)),
+        (None, None, None)
+    );
+}
+
+#[test]
+fn create_t_head_skips_whitespace_around_the_caption() {
+    let mut doc =
+        parse_document("<table id=t>\n <caption>c</caption>\n <tr><td>x</td></tr></table>");
+    let t = HTMLTableElement::new(by_id(&doc, "t"));
+    t.create_t_head(&mut doc);
+    assert_eq!(
+        serialize_node_to_string(&doc, t.node()),
+        "<table id=\"t\">\n <caption>c</caption>\n <thead></thead><tbody><tr><td>x</td></tr></tbody></table>"
+    );
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::elements::html_table_cell_element::HTMLTableCellElement;
+use brooster_web_parser::dom::elements::html_table_row_element::HTMLTableRowElement;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use common::by_id;
+
+#[test]
+fn building_a_row_with_insert_cell() {
+    let mut doc = parse_document("<table><tr id=r></table>");
+    let r = HTMLTableRowElement::new(by_id(&doc, "r"));
+    let b = r.insert_cell(&mut doc, -1).unwrap();
+    doc.set_attribute(b, "id", "b");
+    let a = r.insert_cell(&mut doc, 0).unwrap();
+    doc.set_attribute(a, "id", "a");
+    let c = r.insert_cell(&mut doc, 2).unwrap();
+    doc.set_attribute(c, "id", "c");
+    assert_eq!(
+        serialize_node_to_string(&doc, r.node()),
+        "<tr id=\"r\"><td id=\"a\"></td><td id=\"b\"></td><td id=\"c\"></td></tr>"
+    );
+    assert_eq!(r.insert_cell(&mut doc, 4), Err(DomError::IndexSize));
+    assert_eq!(r.insert_cell(&mut doc, -2), Err(DomError::IndexSize));
+
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "c")).cell_index(&doc),
+        2
+    );
+    r.delete_cell(&mut doc, 0).unwrap();
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "c")).cell_index(&doc),
+        1
+    );
+    r.delete_cell(&mut doc, -1).unwrap();
+    assert_eq!(r.cells().len(&doc), 1);
+    assert_eq!(r.delete_cell(&mut doc, 1), Err(DomError::IndexSize));
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "b")).cell_index(&doc),
+        0
+    );
+    assert_eq!(HTMLTableCellElement::new(c).cell_index(&doc), -1);
+}
+
+#[test]
+fn cells_include_th_and_skip_other_children() {
+    let doc = parse_document("<table><tr id=r><th id=h><td id=d></tr></table>");
+    let cells: Vec<NodeId> = HTMLTableRowElement::new(by_id(&doc, "r"))
+        .cells()
+        .iter(&doc)
+        .collect();
+    assert_eq!(cells, [by_id(&doc, "h"), by_id(&doc, "d")]);
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "d")).cell_index(&doc),
+        1
+    );
+}
+
+#[test]
+fn row_index_across_sections() {
+    let doc = parse_document(
+        "<table><tfoot><tr id=f></tfoot><tbody><tr id=b1><tr id=b2></tbody>\
+         <thead><tr id=h></thead></table>",
+    );
+    let indexes: Vec<(i32, i32)> = ["h", "b1", "b2", "f"]
+        .iter()
+        .map(|id| {
+            (
+                HTMLTableRowElement::new(by_id(&doc, id)).row_index(&doc),
+                HTMLTableRowElement::new(by_id(&doc, id)).section_row_index(&doc),
+            )
+        })
+        .collect();
+    assert_eq!(indexes, [(0, 0), (1, 0), (2, 1), (3, 0)]);
+}
+
+#[test]
+fn rows_outside_tables_have_no_index() {
+    let mut doc = parse_document("");
+    let tr = doc.create_element("tr");
+    let body = doc.body().unwrap();
+    doc.append_child(body, tr).unwrap();
+    let r = HTMLTableRowElement::new(tr);
+    assert_eq!((r.row_index(&doc), r.section_row_index(&doc)), (-1, -1));
+}
+
+#[test]
+fn spans_are_clamped() {
+    let doc = parse_document(
+        "<table><tr><td id=zero colspan=0 rowspan=0><td id=big colspan=99999 rowspan=99999>\
+         <td id=huge colspan=99999999999><td id=bad colspan=abc rowspan=-2><td id=none>\
+         <td id=ok colspan=' 3 ' rowspan=+4></table>",
+    );
+    let spans = |id| {
+        (
+            HTMLTableCellElement::new(by_id(&doc, id)).col_span(&doc),
+            HTMLTableCellElement::new(by_id(&doc, id)).row_span(&doc),
+        )
+    };
+    assert_eq!(spans("zero"), (1, 0));
+    assert_eq!(spans("big"), (1000, 65534));
+    assert_eq!(spans("huge"), (1000, 1));
+    assert_eq!(spans("bad"), (1, 1));
+    assert_eq!(spans("none"), (1, 1));
+    assert_eq!(spans("ok"), (3, 4));
+}
+
+#[test]
+fn headers_scope_and_abbr() {
+    let mut doc = parse_document(
+        "<table><tr><th id=h scope=COLGROUP abbr=T><th id=x scope=sideways><td id=d headers='h x'>\
+         </table>",
+    );
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "h")).scope(&doc),
+        "colgroup"
+    );
+    assert_eq!(HTMLTableCellElement::new(by_id(&doc, "h")).abbr(&doc), "T");
+    assert_eq!(HTMLTableCellElement::new(by_id(&doc, "x")).scope(&doc), "");
+    assert_eq!(HTMLTableCellElement::new(by_id(&doc, "d")).scope(&doc), "");
+    let headers = HTMLTableCellElement::new(by_id(&doc, "d")).headers();
+    assert_eq!(headers.tokens(&doc), ["h", "x"]);
+    headers.remove(&mut doc, &["x"]).unwrap();
+    assert_eq!(doc.get_attribute(by_id(&doc, "d"), "headers"), Some("h"));
+    HTMLTableCellElement::new(by_id(&doc, "d")).set_col_span(&mut doc, 2);
+    assert_eq!(
+        HTMLTableCellElement::new(by_id(&doc, "d")).col_span(&doc),
+        2
+    );
+}
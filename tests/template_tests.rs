@@ -0,0 +1,50 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::elements::html_template_element::HTMLTemplateElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+use brooster_web_parser::dom::testing::tree_digest;
+
+#[test]
+fn template_children_go_to_its_content() {
+    let doc = parse_document("<template><p>x</p></template>");
+    let template = doc
+        .get_elements_by_tag_name("template")
+        .item(&doc, 0)
+        .unwrap();
+    let template = HTMLTemplateElement::new(&doc, template).unwrap();
+    assert_eq!(doc.arena().children(template.node()).count(), 0);
+    let p = doc.arena().children(template.content()).next().unwrap();
+    assert_eq!(doc.element(p).unwrap().name, "p");
+}
+
+#[test]
+fn template_content_is_not_in_the_document_tree() {
+    let doc = parse_document("<template><p>x</p></template><p>y</p>");
+    assert_eq!(doc.get_elements_by_tag_name("p").len(&doc), 1);
+}
+
+#[test]
+fn template_serializes_its_content() {
+    let doc = parse_document("<body><template><p>x</p></template></body>");
+    let body = doc.body().unwrap();
+    assert_eq!(
+        serialize_children(&doc, body),
+        "<template><p>x</p></template>"
+    );
+}
+
+#[test]
+fn template_digest_shows_content() {
+    let doc = parse_document("<template><b>x</b></template>");
+    let digest = tree_digest(&doc, doc.root());
+    assert!(digest.contains("|     <template>\n|       content\n|         <b>\n"));
+}
+
+#[test]
+fn created_template_has_content() {
+    let mut doc = Document::new();
+    let template = doc.create_element("template");
+    assert!(HTMLTemplateElement::new(&doc, template).is_some());
+    let div = doc.create_element("div");
+    assert!(HTMLTemplateElement::new(&doc, div).is_none());
+}
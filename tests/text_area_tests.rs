@@ -0,0 +1,89 @@
+mod common;
+
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::elements::html_text_area_element::HTMLTextAreaElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use common::by_id;
+
+#[test]
+fn parsed_text_is_default_value_and_value() {
+    let doc = parse_document("<textarea id=t>\nHello\n  world</textarea>");
+    let t = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    assert_eq!(t.default_value(&doc), "Hello\n  world");
+    assert_eq!(t.value(&doc), "Hello\n  world");
+    assert_eq!(t.text_length(&doc), 13);
+}
+
+#[test]
+fn value_follows_the_default_until_set() {
+    let mut doc = parse_document("<textarea id=t>one</textarea>");
+    let t = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    t.set_default_value(&mut doc, "two");
+    assert_eq!(t.value(&doc), "two");
+
+    t.set_value(&mut doc, "a\r\nb\rc");
+    assert_eq!(t.value(&doc), "a\nb\nc");
+    t.set_default_value(&mut doc, "three");
+    assert_eq!(t.value(&doc), "a\nb\nc");
+    assert_eq!(t.default_value(&doc), "three");
+}
+
+#[test]
+fn form_reset_restores_the_default() {
+    let mut doc = parse_document("<form id=f><textarea id=t>initial</textarea></form>");
+    let t = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    t.set_value(&mut doc, "edited");
+    t.form(&doc).unwrap().reset(&mut doc);
+    assert_eq!(t.value(&doc), "initial");
+}
+
+#[test]
+fn rows_and_cols_fall_back_to_their_defaults() {
+    let mut doc = parse_document(
+        "<textarea id=a rows=abc cols=0></textarea><textarea id=b rows=' 5x' cols=+40></textarea>",
+    );
+    let (a, b) = (
+        HTMLTextAreaElement::new(by_id(&doc, "a")),
+        HTMLTextAreaElement::new(by_id(&doc, "b")),
+    );
+    assert_eq!((a.rows(&doc), a.cols(&doc)), (2, 20));
+    assert_eq!((b.rows(&doc), b.cols(&doc)), (5, 40));
+
+    assert_eq!(a.set_rows(&mut doc, 0), Err(DomError::IndexSize));
+    a.set_cols(&mut doc, u32::MAX).unwrap();
+    assert_eq!(doc.get_attribute(a.node(), "cols"), Some("20"));
+    a.set_rows(&mut doc, 7).unwrap();
+    assert_eq!(a.rows(&doc), 7);
+}
+
+#[test]
+fn reflected_attributes() {
+    let mut doc = parse_document("<textarea id=t wrap=hard maxlength=10 readonly></textarea>");
+    let t = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    assert_eq!(t.wrap(&doc), "hard");
+    assert_eq!(t.max_length(&doc), 10);
+    assert!(t.read_only(&doc));
+    assert!(!t.required(&doc));
+
+    doc.set_attribute(t.node(), "maxlength", "-3");
+    assert_eq!(t.max_length(&doc), -1);
+    assert_eq!(t.set_max_length(&mut doc, -1), Err(DomError::IndexSize));
+    t.set_max_length(&mut doc, 4).unwrap();
+    assert_eq!(t.max_length(&doc), 4);
+    t.set_read_only(&mut doc, false);
+    t.set_required(&mut doc, true);
+    assert_eq!(doc.get_attribute(t.node(), "required"), Some(""));
+    assert_eq!(doc.get_attribute(t.node(), "readonly"), None);
+}
+
+#[test]
+fn value_missing_for_empty_required_text_areas() {
+    let mut doc = parse_document("<textarea id=t required></textarea>");
+    let t = HTMLTextAreaElement::new(by_id(&doc, "t"));
+    assert!(t.value_missing(&doc));
+    t.set_value(&mut doc, "x");
+    assert!(!t.value_missing(&doc));
+    t.set_value(&mut doc, "");
+    t.set_read_only(&mut doc, true);
+    assert!(!t.value_missing(&doc));
+}
@@ -0,0 +1,63 @@
+use brooster_web_parser::dom::arena::DomError;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+
+fn paragraph(doc: &Document) -> (NodeId, NodeId) {
+    let p = doc.get_elements_by_tag_name("p").item(doc, 0).unwrap();
+    (p, doc.arena().children(p).next().unwrap())
+}
+
+fn texts(doc: &Document, parent: NodeId) -> Vec<String> {
+    doc.arena()
+        .children(parent)
+        .map(|child| doc.arena().get(child).as_text().unwrap_or("<>").to_string())
+        .collect()
+}
+
+#[test]
+fn splits_ascii_text_into_siblings() {
+    let mut doc = parse_document("<p>hello world</p>");
+    let (p, text) = paragraph(&doc);
+    let rest = doc.split_text(text, 5).unwrap();
+    assert_eq!(texts(&doc, p), ["hello", " world"]);
+    assert_eq!(doc.arena().get(text).next_sibling(), Some(rest));
+    assert_eq!(doc.arena().parent(rest), Some(p));
+}
+
+#[test]
+fn splits_before_a_multi_byte_character() {
+    let mut doc = parse_document("<p>caf\u{E9}!</p>");
+    let (p, text) = paragraph(&doc);
+    assert_eq!(doc.split_text(text, 4), Err(DomError::IndexSize));
+    doc.split_text(text, 3).unwrap();
+    assert_eq!(texts(&doc, p), ["caf", "\u{E9}!"]);
+}
+
+#[test]
+fn rejects_out_of_range_offsets_and_other_nodes() {
+    let mut doc = parse_document("<p>abc</p>");
+    let (p, text) = paragraph(&doc);
+    assert_eq!(doc.split_text(text, 4), Err(DomError::IndexSize));
+    assert_eq!(doc.split_text(p, 0), Err(DomError::InvalidNodeType));
+    // splitting at the end leaves an empty sibling
+    doc.split_text(text, 3).unwrap();
+    assert_eq!(texts(&doc, p), ["abc", ""]);
+}
+
+#[test]
+fn whole_text_spans_the_pieces_and_normalize_merges_them() {
+    let mut doc = parse_document("<p>one two three<b>x</b>tail</p>");
+    let (p, text) = paragraph(&doc);
+    let second = doc.split_text(text, 4).unwrap();
+    let third = doc.split_text(second, 4).unwrap();
+    assert_eq!(texts(&doc, p), ["one ", "two ", "three", "<>", "tail"]);
+    assert_eq!(doc.whole_text(third).as_deref(), Some("one two three"));
+    assert_eq!(doc.whole_text(p), None);
+
+    let empty = doc.create_text_node("");
+    doc.append_child(p, empty).unwrap();
+    doc.normalize(p);
+    assert_eq!(texts(&doc, p), ["one two three", "<>", "tail"]);
+    assert_eq!(doc.arena().parent(third), None);
+}
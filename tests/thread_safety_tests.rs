@@ -0,0 +1,37 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_node_to_string;
+use std::sync::Arc;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn documents_and_handles_are_send_and_sync() {
+    assert_send_sync::<Document>();
+    assert_send_sync::<NodeId>();
+}
+
+#[test]
+fn parse_on_one_thread_query_on_another() {
+    let doc = thread::spawn(|| parse_document("<ul><li class=a>one<li>two<li class=a>three</ul>"))
+        .join()
+        .unwrap();
+    let doc = Arc::new(doc);
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let doc = Arc::clone(&doc);
+            thread::spawn(move || {
+                let items = doc.get_elements_by_class_name("a");
+                let first = items.item(&doc, 0).unwrap();
+                (items.len(&doc), serialize_node_to_string(&doc, first))
+            })
+        })
+        .collect();
+    for handle in handles {
+        let (count, html) = handle.join().unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(html, "<li class=\"a\">one</li>");
+    }
+}
@@ -0,0 +1,189 @@
+use brooster_web_parser::dom::datetime::{parse_date_time_value, Date, DateTimeValue, Time};
+use brooster_web_parser::dom::elements::html_time_element::HTMLTimeElement;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use std::time::Duration;
+
+fn date(year: u32, month: u32, day: u32) -> Date {
+    Date { year, month, day }
+}
+
+fn time(hour: u32, minute: u32, second: u32, millisecond: u32) -> Time {
+    Time {
+        hour,
+        minute,
+        second,
+        millisecond,
+    }
+}
+
+#[test]
+fn valid_strings_parse_to_their_microsyntax() {
+    let cases = [
+        (
+            "2024-05",
+            DateTimeValue::Month {
+                year: 2024,
+                month: 5,
+            },
+        ),
+        ("2024-05-17", DateTimeValue::Date(date(2024, 5, 17))),
+        ("2024-02-29", DateTimeValue::Date(date(2024, 2, 29))),
+        ("2000-02-29", DateTimeValue::Date(date(2000, 2, 29))),
+        ("12024-01-01", DateTimeValue::Date(date(12024, 1, 1))),
+        ("05-17", DateTimeValue::YearlessDate { month: 5, day: 17 }),
+        ("--02-29", DateTimeValue::YearlessDate { month: 2, day: 29 }),
+        ("14:54", DateTimeValue::Time(time(14, 54, 0, 0))),
+        ("14:54:39", DateTimeValue::Time(time(14, 54, 39, 0))),
+        ("00:00:00.5", DateTimeValue::Time(time(0, 0, 0, 500))),
+        ("23:59:59.929", DateTimeValue::Time(time(23, 59, 59, 929))),
+        (
+            "2024-05-17T14:54",
+            DateTimeValue::LocalDateTime(date(2024, 5, 17), time(14, 54, 0, 0)),
+        ),
+        (
+            "2024-05-17 14:54:39.07",
+            DateTimeValue::LocalDateTime(date(2024, 5, 17), time(14, 54, 39, 70)),
+        ),
+        ("Z", DateTimeValue::TimeZoneOffset(0)),
+        ("+05:30", DateTimeValue::TimeZoneOffset(330)),
+        ("-0800", DateTimeValue::TimeZoneOffset(-480)),
+        (
+            "2024-05-17T14:54Z",
+            DateTimeValue::GlobalDateTime(date(2024, 5, 17), time(14, 54, 0, 0), 0),
+        ),
+        (
+            "2024-05-17 14:54:39-04:00",
+            DateTimeValue::GlobalDateTime(date(2024, 5, 17), time(14, 54, 39, 0), -240),
+        ),
+        (
+            "2024-W20",
+            DateTimeValue::Week {
+                year: 2024,
+                week: 20,
+            },
+        ),
+        (
+            "2020-W53",
+            DateTimeValue::Week {
+                year: 2020,
+                week: 53,
+            },
+        ),
+        (
+            "2015-W53",
+            DateTimeValue::Week {
+                year: 2015,
+                week: 53,
+            },
+        ),
+        ("2024", DateTimeValue::Year(2024)),
+        ("0001", DateTimeValue::Year(1)),
+        (
+            "PT4H18M3S",
+            DateTimeValue::Duration(Duration::from_secs(4 * 3600 + 18 * 60 + 3)),
+        ),
+        (
+            "P2D",
+            DateTimeValue::Duration(Duration::from_secs(2 * 86_400)),
+        ),
+        (
+            "P1DT0.25S",
+            DateTimeValue::Duration(Duration::from_millis(86_400_250)),
+        ),
+        (
+            "4h 18m 3s",
+            DateTimeValue::Duration(Duration::from_secs(4 * 3600 + 18 * 60 + 3)),
+        ),
+        (
+            "1W 2d",
+            DateTimeValue::Duration(Duration::from_secs(9 * 86_400)),
+        ),
+        ("1.5s", DateTimeValue::Duration(Duration::from_millis(1500))),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(parse_date_time_value(input), Some(expected), "{input}");
+    }
+}
+
+#[test]
+fn invalid_strings_parse_to_none() {
+    let cases = [
+        "",
+        "2024-13-01",
+        "2024-00-10",
+        "2023-02-29",
+        "1900-02-29",
+        "2024-04-31",
+        "24-05-17",
+        "2024-5-17",
+        "0000",
+        "024",
+        "---05-17",
+        "02-30",
+        "24:00:01",
+        "12:60",
+        "12:00:60",
+        "12:00:00.1234",
+        "12:00:00.",
+        "2024-05-17T",
+        "2024-05-17t14:54",
+        "2024-05-17T14:54+24:00",
+        "+5:30",
+        "2024-W00",
+        "2024-W53",
+        "2024-w20",
+        "P",
+        "PT",
+        "P5",
+        "P1H",
+        "PT1D",
+        "4h 4h",
+        "1.5h",
+        "4x",
+        "h",
+        " 2024-05-17",
+    ];
+    for input in cases {
+        assert_eq!(parse_date_time_value(input), None, "{input}");
+    }
+}
+
+#[test]
+fn date_time_falls_back_to_the_text_content() {
+    let doc = parse_document(
+        "<time datetime=\"2024-05-17\">Friday</time><time>14:<b>54</b></time><time>soon</time>",
+    );
+    let times = doc.get_elements_by_tag_name("time");
+    let with_attribute = HTMLTimeElement::new(times.item(&doc, 0).unwrap());
+    assert_eq!(with_attribute.date_time(&doc), "2024-05-17");
+    assert_eq!(
+        with_attribute.parsed(&doc),
+        Some(DateTimeValue::Date(date(2024, 5, 17)))
+    );
+
+    let from_text = HTMLTimeElement::new(times.item(&doc, 1).unwrap());
+    assert_eq!(from_text.date_time(&doc), "14:54");
+    assert_eq!(
+        from_text.parsed(&doc),
+        Some(DateTimeValue::Time(time(14, 54, 0, 0)))
+    );
+
+    let unparsable = HTMLTimeElement::new(times.item(&doc, 2).unwrap());
+    assert_eq!(unparsable.parsed(&doc), None);
+}
+
+#[test]
+fn set_date_time_writes_the_attribute() {
+    let mut doc = parse_document("<time>noon</time>");
+    let time_element =
+        HTMLTimeElement::new(doc.get_elements_by_tag_name("time").item(&doc, 0).unwrap());
+    time_element.set_date_time(&mut doc, "12:00");
+    assert_eq!(
+        doc.get_attribute(time_element.node(), "datetime"),
+        Some("12:00")
+    );
+    assert_eq!(
+        time_element.parsed(&doc),
+        Some(DateTimeValue::Time(time(12, 0, 0, 0)))
+    );
+}
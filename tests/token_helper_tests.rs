@@ -0,0 +1,25 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+fn first_token(input: &str) -> Token {
+    Tokenizer::new(input.as_bytes()).next_token()
+}
+
+#[test]
+fn tag_name_is_only_set_for_tags() {
+    assert_eq!(first_token("<div class=x>").tag_name(), Some("div"));
+    assert_eq!(first_token("</DIV>").tag_name(), Some("div"));
+    assert_eq!(first_token("<!--div-->").tag_name(), None);
+    assert_eq!(first_token("div").tag_name(), None);
+    assert_eq!(Token::EOF.tag_name(), None);
+}
+
+#[test]
+fn start_and_end_tag_checks_compare_kind_and_name() {
+    let start = first_token("<p>");
+    let end = first_token("</p>");
+    assert!(start.is_start_tag("p"));
+    assert!(!start.is_end_tag("p"));
+    assert!(!start.is_start_tag("pre"));
+    assert!(end.is_end_tag("p"));
+    assert!(!end.is_start_tag("p"));
+}
@@ -0,0 +1,32 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new(input.as_bytes());
+    tokenizer.run();
+    tokenizer.tokens().to_vec()
+}
+
+#[test]
+fn reset_tokenizer_behaves_like_a_fresh_one() {
+    let first = String::from("<title>unfinished <b class=\"x");
+    let mut tokenizer = Tokenizer::new(first.as_bytes());
+    for _ in 0..3 {
+        tokenizer.next_token();
+    }
+
+    let second = String::from("<p id=a>x&amp;y</p><!--c-->");
+    let mut tokenizer = tokenizer.reset(second.as_bytes());
+    drop(first);
+    tokenizer.run();
+    assert_eq!(tokenizer.tokens(), tokenize(&second).as_slice());
+}
+
+#[test]
+fn next_token_starts_from_the_new_input_after_reset() {
+    let mut tokenizer = Tokenizer::new(b"<a>");
+    tokenizer.run();
+
+    let mut tokenizer = tokenizer.reset(b"z");
+    assert_eq!(tokenizer.next_token(), Token::Character { data: 'z' });
+    assert_eq!(tokenizer.next_token(), Token::EOF);
+}
@@ -0,0 +1,50 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer, TokenizerState};
+
+fn tokens(tokenizer: &mut Tokenizer) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.next_token();
+        if token == Token::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+fn characters(text: &str) -> Vec<Token> {
+    text.chars().map(|data| Token::Character { data }).collect()
+}
+
+#[test]
+fn starts_in_the_data_state() {
+    let tokenizer = Tokenizer::new(b"<p>");
+    assert_eq!(tokenizer.current_state(), &TokenizerState::Data);
+}
+
+#[test]
+fn set_state_switches_to_rcdata() {
+    let mut tokenizer = Tokenizer::new(b"<b>&amp;");
+    tokenizer.set_state(TokenizerState::RCDATA);
+    assert_eq!(tokenizer.current_state(), &TokenizerState::RCDATA);
+    assert_eq!(tokens(&mut tokenizer), characters("<b>&"));
+}
+
+#[test]
+fn end_tag_needs_a_matching_last_start_tag() {
+    let mut tokenizer = Tokenizer::new(b"a</textarea>");
+    tokenizer.set_state(TokenizerState::RCDATA);
+    assert_eq!(tokens(&mut tokenizer), characters("a</textarea>"));
+
+    let mut tokenizer = Tokenizer::new(b"a</textarea>b");
+    tokenizer.set_state(TokenizerState::RCDATA);
+    tokenizer.set_last_start_tag("textarea".to_string());
+    let mut expected = characters("a");
+    expected.push(Token::EndTag {
+        tag_name: "textarea".into(),
+        self_closing: false,
+        attributes: Vec::new(),
+    });
+    expected.extend(characters("b"));
+    assert_eq!(tokens(&mut tokenizer), expected);
+    assert_eq!(tokenizer.current_state(), &TokenizerState::Data);
+}
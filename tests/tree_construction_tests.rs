@@ -0,0 +1,99 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::serialize_children;
+
+fn parse(html: &str) -> String {
+    let doc = parse_document(html);
+    serialize_children(&doc, doc.root())
+}
+
+#[test]
+fn implied_html_head_and_body() {
+    assert_eq!(
+        parse("<!DOCTYPE html><title>T</title><p>x"),
+        "<!DOCTYPE html><html><head><title>T</title></head><body><p>x</p></body></html>"
+    );
+    assert_eq!(parse(""), "<html><head></head><body></body></html>");
+}
+
+#[test]
+fn implied_end_tags_close_paragraphs_and_list_items() {
+    assert_eq!(
+        parse("<p>a<p>b<ul><li>c<li>d</ul>"),
+        "<html><head></head><body><p>a</p><p>b</p><ul><li>c</li><li>d</li></ul></body></html>"
+    );
+    assert_eq!(
+        parse("<p>a<div>b</div>"),
+        "<html><head></head><body><p>a</p><div>b</div></body></html>"
+    );
+}
+
+#[test]
+fn adoption_agency_splits_misnested_formatting() {
+    assert_eq!(
+        parse("<b>1<p>2</b>3</p>"),
+        "<html><head></head><body><b>1</b><p><b>2</b>3</p></body></html>"
+    );
+    assert_eq!(
+        parse("<a href=x>1<a href=y>2</a>"),
+        "<html><head></head><body><a href=\"x\">1</a><a href=\"y\">2</a></body></html>"
+    );
+}
+
+#[test]
+fn active_formatting_elements_are_reconstructed() {
+    assert_eq!(
+        parse("<p><i>a<p>b"),
+        "<html><head></head><body><p><i>a</i></p><p><i>b</i></p></body></html>"
+    );
+}
+
+#[test]
+fn foster_parenting_moves_content_before_the_table() {
+    assert_eq!(
+        parse("<table>x<tr><td>y</td></tr></table>"),
+        "<html><head></head><body>x<table><tbody><tr><td>y</td></tr></tbody></table></body></html>"
+    );
+    assert_eq!(
+        parse("<table><b>bold</b><tr><td>c</table>"),
+        "<html><head></head><body><b>bold</b><table><tbody><tr><td>c</td></tr></tbody></table>\
+         </body></html>"
+    );
+}
+
+#[test]
+fn table_sections_are_implied() {
+    assert_eq!(
+        parse("<table><td>a<td>b<tr><td>c</table>"),
+        "<html><head></head><body><table><tbody><tr><td>a</td><td>b</td></tr><tr><td>c</td>\
+         </tr></tbody></table></body></html>"
+    );
+}
+
+#[test]
+fn template_contents_keep_their_own_insertion_modes() {
+    assert_eq!(
+        parse("<template><tr><td>a</td></tr></template>"),
+        "<html><head><template><tr><td>a</td></tr></template></head><body></body></html>"
+    );
+    assert_eq!(
+        parse("<body><template><p>x</template><p>y"),
+        "<html><head></head><body><template><p>x</p></template><p>y</p></body></html>"
+    );
+}
+
+#[test]
+fn self_closing_svg_and_math_are_closed() {
+    assert_eq!(
+        parse("<svg/><p>a</p><math/>b"),
+        "<html><head></head><body><svg></svg><p>a</p><math></math>b</body></html>"
+    );
+}
+
+#[test]
+fn raw_text_elements_keep_markup_as_text() {
+    assert_eq!(
+        parse("<title>a<b>c</title><script>if (a<b) {}</script>"),
+        "<html><head><title>a&lt;b&gt;c</title><script>if (a<b) {}</script></head><body>\
+         </body></html>"
+    );
+}
@@ -0,0 +1,25 @@
+use brooster_web_parser::dom::parser::tokenizer::{Token, Tokenizer};
+use brooster_web_parser::dom::parser::tree_constructor::TreeConstructor;
+
+fn construct_until_eof(html: &str) -> TreeConstructor {
+    let mut tokenizer = Tokenizer::new(html.as_bytes());
+    let mut tree_constructor = TreeConstructor::new();
+    loop {
+        let token = tokenizer.next_token();
+        if token == Token::EOF {
+            return tree_constructor;
+        }
+        tree_constructor.process_token(token);
+    }
+}
+
+#[test]
+fn dump_tree_shows_nodes_and_open_elements() {
+    let tree_constructor = construct_until_eof("<!DOCTYPE html><p class=\"x\">hello<!--c-->");
+    let arena = tree_constructor.document().arena();
+    assert_eq!(
+        tree_constructor.dump_tree(arena),
+        "Document\n  <!DOCTYPE html>\n  html\n    head\n    body\n      p class=\"x\"\n        \"hello\"\n        <!-- c -->\nStack of open elements:\n  html\n  body\n  p class=\"x\"\n"
+    );
+    assert_eq!(tree_constructor.dump_stack(), "  html\n  body\n  p class=\"x\"\n");
+}
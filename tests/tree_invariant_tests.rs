@@ -0,0 +1,115 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::testing::check_tree_invariants;
+
+/// Markup pieces that stress the tree builder: misnested formatting, tables, foreign
+/// content, templates, stray html/body/head tags and raw text elements
+const PIECES: &[&str] = &[
+    "<html>",
+    "</html>",
+    "<head>",
+    "</head>",
+    "<body>",
+    "</body>",
+    "<b>",
+    "</b>",
+    "<i>",
+    "</i>",
+    "<a>",
+    "</a>",
+    "<p>",
+    "</p>",
+    "<div>",
+    "</div>",
+    "<table>",
+    "</table>",
+    "<tr>",
+    "<td>",
+    "</td>",
+    "<caption>",
+    "<colgroup>",
+    "<select>",
+    "<option>",
+    "<optgroup>",
+    "</select>",
+    "<template>",
+    "</template>",
+    "<svg>",
+    "</svg>",
+    "<math>",
+    "<mi>",
+    "<foreignObject>",
+    "<title>",
+    "</title>",
+    "<script>",
+    "</script>",
+    "<style>",
+    "<textarea>",
+    "<frameset>",
+    "<frame>",
+    "<li>",
+    "<ul>",
+    "<dd>",
+    "<h1>",
+    "<h2>",
+    "<form>",
+    "</form>",
+    "<button>",
+    "<nobr>",
+    "<marquee>",
+    "<br/>",
+    "</br>",
+    "<img>",
+    "<!--c-->",
+    "<!DOCTYPE html>",
+    "text",
+    " ",
+    "&amp;",
+    "\0",
+    "<",
+    "</",
+    "<plaintext>",
+    "<noscript>",
+    "<iframe>",
+    "<base href=x>",
+];
+
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+#[test]
+fn generated_tag_soup_keeps_the_tree_consistent() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..3000 {
+        let html: String = (0..rng.below(40))
+            .map(|_| PIECES[rng.below(PIECES.len())])
+            .collect();
+        let document = parse_document(&html);
+        if let Err(error) = check_tree_invariants(&document) {
+            panic!("{error}\ninput: {html:?}");
+        }
+    }
+}
+
+#[test]
+fn well_formed_documents_pass() {
+    for html in [
+        "",
+        "<!DOCTYPE html><html><head><title>t</title></head><body><p>x</p></body></html>",
+        "<html><html lang=en><body><html>",
+        "<svg><html></html></svg>",
+    ] {
+        assert_eq!(
+            check_tree_invariants(&parse_document(html)),
+            Ok(()),
+            "{html}"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::node::NodeId;
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::tree_walker::{FilterResult, TreeWalker, WhatToShow};
+
+const FIXTURE: &str = "<body><div id=main><h1>Title</h1><!-- note -->\
+    <script>track()</script><p>one <b>two</b></p></div><p>three</p></body>";
+
+fn names(doc: &Document, nodes: &[NodeId]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(|&node| match doc.element(node) {
+            Some(element) => element.name.clone(),
+            None => format!("{:?}", doc.arena().get(node).data),
+        })
+        .collect()
+}
+
+fn walk_forward(doc: &Document, walker: &mut TreeWalker) -> Vec<NodeId> {
+    let mut nodes = Vec::new();
+    while let Some(node) = walker.next_node(doc) {
+        nodes.push(node);
+    }
+    nodes
+}
+
+#[test]
+fn shows_only_elements() {
+    let doc = parse_document(FIXTURE);
+    let mut walker = TreeWalker::new(doc.body().unwrap(), WhatToShow::ELEMENT);
+    let nodes = walk_forward(&doc, &mut walker);
+    assert_eq!(names(&doc, &nodes), ["div", "h1", "script", "p", "b", "p"]);
+}
+
+#[test]
+fn shows_combined_node_types() {
+    let doc = parse_document(FIXTURE);
+    let mut walker = TreeWalker::new(doc.body().unwrap(), WhatToShow::TEXT | WhatToShow::COMMENT);
+    let nodes = walk_forward(&doc, &mut walker);
+    assert_eq!(
+        names(&doc, &nodes),
+        [
+            "Text(\"Title\")",
+            "Comment(\" note \")",
+            "Text(\"track()\")",
+            "Text(\"one \")",
+            "Text(\"two\")",
+            "Text(\"three\")"
+        ]
+    );
+}
+
+#[test]
+fn reject_prunes_the_subtree_and_skip_only_the_node() {
+    let doc = parse_document(FIXTURE);
+    let filter = |doc: &Document, node: NodeId| match doc.element(node) {
+        Some(element) if element.name == "script" => FilterResult::Reject,
+        Some(element) if element.name == "div" => FilterResult::Skip,
+        _ => FilterResult::Accept,
+    };
+    let mut walker = TreeWalker::with_filter(doc.body().unwrap(), WhatToShow::ALL, filter);
+    let nodes = walk_forward(&doc, &mut walker);
+    assert!(!names(&doc, &nodes)
+        .iter()
+        .any(|name| name.contains("track")));
+    assert!(!names(&doc, &nodes).contains(&"div".to_string()));
+    assert!(names(&doc, &nodes).contains(&"h1".to_string()));
+}
+
+#[test]
+fn previous_node_retraces_next_node() {
+    let doc = parse_document(FIXTURE);
+    let filter = |doc: &Document, node: NodeId| match doc.element(node) {
+        Some(element) if element.name == "script" => FilterResult::Reject,
+        Some(element) if element.name == "p" => FilterResult::Skip,
+        _ => FilterResult::Accept,
+    };
+    let root = doc.body().unwrap();
+    let mut walker = TreeWalker::with_filter(root, WhatToShow::ALL, filter);
+    let forward = walk_forward(&doc, &mut walker);
+    let mut backward = Vec::new();
+    while let Some(node) = walker.previous_node(&doc) {
+        backward.push(node);
+    }
+    assert_eq!(backward.pop(), Some(root));
+    backward.reverse();
+    backward.push(*forward.last().unwrap());
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn children_siblings_and_parent() {
+    let doc = parse_document(FIXTURE);
+    let skip_div = |doc: &Document, node: NodeId| match doc.element(node) {
+        Some(element) if element.name == "div" => FilterResult::Skip,
+        _ => FilterResult::Accept,
+    };
+    let body = doc.body().unwrap();
+    let mut walker = TreeWalker::with_filter(body, WhatToShow::ELEMENT, skip_div);
+    let h1 = walker.first_child(&doc).unwrap();
+    assert_eq!(doc.element(h1).unwrap().name, "h1");
+    let script = walker.next_sibling(&doc).unwrap();
+    assert_eq!(doc.element(script).unwrap().name, "script");
+    walker.next_sibling(&doc).unwrap();
+    // past the skipped div, its following sibling
+    let last = walker.next_sibling(&doc).unwrap();
+    assert_eq!(doc.arena().parent(last), Some(body));
+    assert_eq!(walker.next_sibling(&doc), None);
+    assert_eq!(walker.parent_node(&doc), Some(body));
+    assert_eq!(walker.parent_node(&doc), None);
+    assert_eq!(walker.last_child(&doc), Some(last));
+    assert_eq!(
+        walker
+            .previous_sibling(&doc)
+            .map(|p| doc.element(p).unwrap().name.clone()),
+        Some("p".to_string())
+    );
+}
@@ -0,0 +1,118 @@
+use brooster_web_parser::dom::elements::html_anchor_element::HTMLAnchorElement;
+use brooster_web_parser::dom::document::Document;
+use brooster_web_parser::dom::parser::html_parser::{parse_document, parse_document_with_url};
+use brooster_web_parser::dom::url::{resolve_url, UrlError};
+
+const BASE: &str = "http://a/b/c/d;p?q";
+
+#[test]
+fn rfc_3986_normal_examples() {
+    let cases = [
+        ("g:h", "g:h"),
+        ("g", "http://a/b/c/g"),
+        ("./g", "http://a/b/c/g"),
+        ("g/", "http://a/b/c/g/"),
+        ("/g", "http://a/g"),
+        ("//g", "http://g"),
+        ("?y", "http://a/b/c/d;p?y"),
+        ("g?y", "http://a/b/c/g?y"),
+        ("#s", "http://a/b/c/d;p?q#s"),
+        ("g#s", "http://a/b/c/g#s"),
+        ("g?y#s", "http://a/b/c/g?y#s"),
+        (";x", "http://a/b/c/;x"),
+        ("", "http://a/b/c/d;p?q"),
+        (".", "http://a/b/c/"),
+        ("./", "http://a/b/c/"),
+        ("..", "http://a/b/"),
+        ("../", "http://a/b/"),
+        ("../g", "http://a/b/g"),
+        ("../..", "http://a/"),
+        ("../../g", "http://a/g"),
+    ];
+    for (relative, expected) in cases {
+        assert_eq!(resolve_url(BASE, relative).as_deref(), Ok(expected), "{relative}");
+    }
+}
+
+#[test]
+fn rfc_3986_abnormal_examples() {
+    let cases = [
+        ("../../../g", "http://a/g"),
+        ("/./g", "http://a/g"),
+        ("/../g", "http://a/g"),
+        ("g.", "http://a/b/c/g."),
+        ("..g", "http://a/b/c/..g"),
+        ("./../g", "http://a/b/g"),
+        ("g/./h", "http://a/b/c/g/h"),
+        ("g/../h", "http://a/b/c/h"),
+        ("g;x=1/../y", "http://a/b/c/y"),
+        ("g?y/./x", "http://a/b/c/g?y/./x"),
+        ("g#s/../x", "http://a/b/c/g#s/../x"),
+        ("http:g", "http:g"),
+    ];
+    for (relative, expected) in cases {
+        assert_eq!(resolve_url(BASE, relative).as_deref(), Ok(expected), "{relative}");
+    }
+}
+
+#[test]
+fn relative_base_is_an_error() {
+    assert_eq!(resolve_url("/docs/", "a.html"), Err(UrlError::RelativeBase));
+    assert_eq!(resolve_url("1http://x/", "a"), Err(UrlError::InvalidScheme));
+}
+
+#[test]
+fn anchor_href_resolves_against_the_base_element() {
+    let doc = parse_document(
+        "<base target=_blank><base href='https://example.com/docs/'><base href='https://ignored/'>\
+         <a href='../img/a.png'>a</a><a href='#top'>b</a><a>c</a>",
+    );
+    assert_eq!(doc.base_url(), Some("https://example.com/docs/"));
+    let anchors = doc.get_elements_by_tag_name("a");
+    let href = |i| HTMLAnchorElement::new(anchors.item(&doc, i).unwrap()).href(&doc);
+    assert_eq!(href(0).as_deref(), Some("https://example.com/img/a.png"));
+    assert_eq!(href(1).as_deref(), Some("https://example.com/docs/#top"));
+    assert_eq!(href(2), None);
+
+    let without_base = parse_document("<a href='page.html'>x</a>");
+    let a = without_base.get_elements_by_tag_name("a").item(&without_base, 0).unwrap();
+    assert_eq!(without_base.base_url(), None);
+    assert_eq!(HTMLAnchorElement::new(a).href(&without_base).as_deref(), Some("page.html"));
+}
+
+#[test]
+fn document_url_is_the_base_without_a_base_element() {
+    let doc = parse_document_with_url(
+        "<a href='g?y#s'>a</a><img src='../i.png'><a href='//cdn/x'>b</a>",
+        "http://a/b/c/d;p?q",
+    );
+    assert_eq!(doc.url(), Some("http://a/b/c/d;p?q"));
+    assert_eq!(doc.base_url(), Some("http://a/b/c/d;p?q"));
+    let a = doc.get_elements_by_tag_name("a");
+    let img = doc.get_elements_by_tag_name("img").item(&doc, 0).unwrap();
+    assert_eq!(
+        HTMLAnchorElement::new(a.item(&doc, 0).unwrap()).href(&doc).as_deref(),
+        Some("http://a/b/c/g?y#s")
+    );
+    assert_eq!(doc.resolved_url(img, "src").as_deref(), Some("http://a/b/i.png"));
+    assert_eq!(
+        doc.resolved_url(a.item(&doc, 1).unwrap(), "href").as_deref(),
+        Some("http://cdn/x")
+    );
+    assert_eq!(doc.resolved_url(img, "missing"), None);
+}
+
+#[test]
+fn base_element_overrides_the_document_url() {
+    let doc = parse_document_with_url(
+        "<head><base href='/static/'></head><a href='?q=1'>a</a>",
+        "https://example.com/page/index.html",
+    );
+    assert_eq!(doc.base_url(), Some("https://example.com/static/"));
+    let a = doc.get_elements_by_tag_name("a").item(&doc, 0).unwrap();
+    assert_eq!(
+        HTMLAnchorElement::new(a).href(&doc).as_deref(),
+        Some("https://example.com/static/?q=1")
+    );
+    assert_eq!(Document::with_url("https://x/").base_url(), Some("https://x/"));
+}
@@ -0,0 +1,42 @@
+use brooster_web_parser::dom::parser::html_parser::parse_document;
+use brooster_web_parser::dom::serializer::{
+    is_void_element, serialize_children, serialize_node_to_string, VOID_ELEMENTS,
+};
+
+#[test]
+fn void_element_list() {
+    for name in VOID_ELEMENTS {
+        assert!(is_void_element(name), "{name}");
+    }
+    for name in ["p", "div", "template", "BR", "image", "menuitem"] {
+        assert!(!is_void_element(name), "{name}");
+    }
+}
+
+#[test]
+fn parsed_void_elements_have_no_children() {
+    let doc = parse_document(
+        "<table><col>x</table><p><area>a<br>b<embed>c<hr>d<img>e<input>f<keygen>g<wbr>h\
+         <video><source>i<track>j</video><object><param>k</object>",
+    );
+    for name in VOID_ELEMENTS {
+        for element in doc.get_elements_by_tag_name(name).iter(&doc) {
+            assert_eq!(doc.arena().get(element).first_child(), None, "{name}");
+        }
+    }
+    let body = doc.body().unwrap();
+    let html = serialize_children(&doc, body);
+    assert!(html.contains("a<br>b<embed>c"), "{html}");
+    for name in VOID_ELEMENTS {
+        assert!(!html.contains(&format!("</{name}>")), "{html}");
+    }
+}
+
+#[test]
+fn children_of_void_elements_are_not_serialized() {
+    let mut doc = parse_document("<p><img src=a.png></p>");
+    let img = doc.get_elements_by_tag_name("img").item(&doc, 0).unwrap();
+    let text = doc.create_text_node("alt");
+    doc.append_child(img, text).unwrap();
+    assert_eq!(serialize_node_to_string(&doc, img), "<img src=\"a.png\">");
+}
@@ -0,0 +1,76 @@
+use brooster_web_parser::dom::parser::encoding::{
+    declared_encoding, prescan, Confidence, Encoding,
+};
+use brooster_web_parser::dom::parser::html_parser::parse_document_bytes;
+
+#[test]
+fn curly_quotes_decode_to_unicode() {
+    let result =
+        parse_document_bytes(b"<meta charset=windows-1252><p>\x93quoted\x94 \x80 caf\xE9</p>");
+    assert_eq!(result.encoding, Encoding::Windows1252);
+    assert_eq!(result.confidence, Confidence::Certain);
+    assert!(result.warnings.is_empty());
+    let body = result.document.body().unwrap();
+    assert_eq!(
+        result.document.text_content(body),
+        "\u{201c}quoted\u{201d} \u{20ac} caf\u{e9}"
+    );
+}
+
+#[test]
+fn every_byte_decodes_to_one_character() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let text = Encoding::Windows1252.decode(&bytes);
+    assert_eq!(text.chars().count(), 256);
+    assert_eq!(text.chars().nth(0x41), Some('A'));
+    assert_eq!(text.chars().nth(0x81), Some('\u{81}'));
+    assert_eq!(text.chars().nth(0x9F), Some('\u{178}'));
+    assert_eq!(text.chars().nth(0xFF), Some('\u{ff}'));
+}
+
+#[test]
+fn latin1_labels_resolve_to_windows_1252() {
+    for label in [
+        "latin1",
+        "ISO8859-1",
+        "iso-8859-1",
+        " US-ASCII ",
+        "cp1252",
+        "l1",
+    ] {
+        assert_eq!(
+            Encoding::for_label(label),
+            Some(Encoding::Windows1252),
+            "{label}"
+        );
+    }
+    assert_eq!(Encoding::Windows1252.name(), "windows-1252");
+    assert_eq!(
+        declared_encoding("x-user-defined"),
+        Some(Encoding::Windows1252)
+    );
+    assert_eq!(
+        prescan(b"<meta charset=x-user-defined>"),
+        Some(Encoding::Windows1252)
+    );
+    assert_eq!(Encoding::for_label("latin-1"), None);
+}
+
+#[test]
+fn unknown_labels_fall_back_with_a_warning() {
+    let result = parse_document_bytes(b"<meta charset=\"shift_jis\"><p>\x93x\x94</p>");
+    assert_eq!(result.encoding, Encoding::Windows1252);
+    assert_eq!(result.warnings.len(), 1);
+    assert!(result.warnings[0].contains("shift_jis"));
+    let body = result.document.body().unwrap();
+    assert_eq!(result.document.text_content(body), "\u{201c}x\u{201d}");
+}
+
+#[test]
+fn a_later_known_label_wins_over_an_unknown_one() {
+    let result = parse_document_bytes(b"<meta charset=bogus><meta charset=utf-8><p>\xC3\xA9</p>");
+    assert_eq!(result.encoding, Encoding::Utf8);
+    assert!(result.warnings.is_empty());
+    let body = result.document.body().unwrap();
+    assert_eq!(result.document.text_content(body), "\u{e9}");
+}